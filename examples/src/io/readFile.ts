@@ -1,3 +1,6 @@
+/**
+ * @allow fs
+ */
 import fs from "node:fs/promises";
 import { FileNotFoundError } from "../errors/FileNotFoundError.ts";
 
@@ -9,6 +12,9 @@ export async function readFile(path: string): Promise<string> {
   try {
     return await fs.readFile(path, "utf-8");
   } catch (error) {
-    throw new FileNotFoundError(path);
+    if (Error.isError(error)) {
+      throw new FileNotFoundError(path);
+    }
+    throw error;
   }
 }
@@ -3,6 +3,7 @@ import process from "node:process";
 
 /**
  * @allow console
+ * @allow throws
  */
 async function main(): Promise<void> {
   console.log("Application started");
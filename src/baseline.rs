@@ -0,0 +1,294 @@
+// Baseline of known diagnostics, for adopting rules on existing code
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::LintError;
+
+/// A diagnostic identified by its rule and the code it points at, so that it
+/// still matches after unrelated edits move it around
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fingerprint {
+    pub rule: String,
+    pub hash: String,
+}
+
+impl Fingerprint {
+    /// Hash the first line of the diagnostic's span with whitespace collapsed
+    pub fn new(error: &LintError, source_text: &str) -> Self {
+        let start = (error.span.start as usize).min(source_text.len());
+        let end = (error.span.end as usize).clamp(start, source_text.len());
+        let code = source_text
+            .get(start..end)
+            .unwrap_or_default()
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Self {
+            rule: error.rule.clone(),
+            hash: format!("{:016x}", xxh3_64(code.as_bytes())),
+        }
+    }
+}
+
+/// One baselined diagnostic (or several identical ones) in a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// Path relative to the directory containing the baseline file
+    pub file: String,
+    pub rule: String,
+    pub fingerprint: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BaselineFile {
+    entries: Vec<BaselineEntry>,
+}
+
+/// Diagnostics recorded with `--write-baseline` and suppressed with `--baseline`
+#[derive(Debug)]
+pub struct Baseline {
+    path: PathBuf,
+    dir: PathBuf,
+    files: HashMap<String, BTreeMap<Fingerprint, usize>>,
+}
+
+impl Baseline {
+    /// An empty baseline to be written to `path`
+    pub fn new(path: &Path) -> Self {
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        Self {
+            path: path.to_path_buf(),
+            dir: dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf()),
+            files: HashMap::new(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline {}", path.display()))?;
+        let file: BaselineFile = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse baseline {}", path.display()))?;
+
+        let mut baseline = Self::new(path);
+        for entry in file.entries {
+            let fingerprint = Fingerprint {
+                rule: entry.rule,
+                hash: entry.fingerprint,
+            };
+            *baseline
+                .files
+                .entry(entry.file)
+                .or_default()
+                .entry(fingerprint)
+                .or_default() += entry.count;
+        }
+        Ok(baseline)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Total number of baselined diagnostics
+    pub fn len(&self) -> usize {
+        self.files.values().flat_map(|counts| counts.values()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Key of a linted file: its path relative to the baseline directory
+    fn file_key(&self, file: &Path) -> String {
+        let file = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+        let relative = file.strip_prefix(&self.dir).unwrap_or(&file);
+        relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    pub fn insert(&mut self, file: &Path, fingerprints: &[Fingerprint]) {
+        if fingerprints.is_empty() {
+            return;
+        }
+        let counts = self.files.entry(self.file_key(file)).or_default();
+        for fingerprint in fingerprints {
+            *counts.entry(fingerprint.clone()).or_default() += 1;
+        }
+    }
+
+    /// Remove the baselined diagnostics of `file` from `errors` and return
+    /// the entries that no longer occur in it
+    pub fn suppress(
+        &self,
+        file: &Path,
+        source_text: &str,
+        errors: &mut Vec<LintError>,
+    ) -> Vec<BaselineEntry> {
+        let key = self.file_key(file);
+        let Some(counts) = self.files.get(&key) else {
+            return Vec::new();
+        };
+
+        let mut remaining = counts.clone();
+        errors.retain(
+            |error| match remaining.get_mut(&Fingerprint::new(error, source_text)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            },
+        );
+
+        remaining
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(fingerprint, count)| entry(&key, fingerprint, count))
+            .collect()
+    }
+
+    /// Entries for files that no longer exist
+    pub fn missing_files(&self) -> Vec<BaselineEntry> {
+        let mut entries: Vec<BaselineEntry> = self
+            .files
+            .iter()
+            .filter(|(file, _)| !self.dir.join(file).exists())
+            .flat_map(|(file, counts)| {
+                counts
+                    .iter()
+                    .map(|(fingerprint, count)| entry(file, fingerprint.clone(), *count))
+            })
+            .collect();
+        entries.sort_by(|a, b| (&a.file, &a.rule).cmp(&(&b.file, &b.rule)));
+        entries
+    }
+
+    /// Write the baseline sorted by file, rule and fingerprint so it diffs well
+    pub fn save(&self) -> Result<()> {
+        let mut entries: Vec<BaselineEntry> = self
+            .files
+            .iter()
+            .flat_map(|(file, counts)| {
+                counts
+                    .iter()
+                    .map(|(fingerprint, count)| entry(file, fingerprint.clone(), *count))
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            (&a.file, &a.rule, &a.fingerprint).cmp(&(&b.file, &b.rule, &b.fingerprint))
+        });
+
+        let content = serde_json::to_string_pretty(&BaselineFile { entries })?;
+        fs::write(&self.path, content + "\n")
+            .with_context(|| format!("Failed to write baseline {}", self.path.display()))
+    }
+}
+
+fn entry(file: &str, fingerprint: Fingerprint, count: usize) -> BaselineEntry {
+    BaselineEntry {
+        file: file.to_string(),
+        rule: fingerprint.rule,
+        fingerprint: fingerprint.hash,
+        count,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule_registry::Severity;
+    use oxc::span::Span;
+    use tempfile::TempDir;
+
+    fn error(rule: &str, source_text: &str, code: &str) -> LintError {
+        let start = source_text.find(code).unwrap() as u32;
+        LintError {
+            rule: rule.to_string(),
+            message: String::new(),
+            span: Span::new(start, start + code.len() as u32),
+            severity: Severity::Error,
+            fix: None,
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_position_and_whitespace() {
+        let before = "throw new Error(\"x\");\n";
+        let after = "const a = 1;\n\n    throw   new Error(\"x\");\n";
+        let fingerprint =
+            |source: &str, code: &str| Fingerprint::new(&error("no-throw", source, code), source);
+
+        assert_eq!(
+            fingerprint(before, "throw new Error(\"x\");"),
+            fingerprint(after, "throw   new Error(\"x\");")
+        );
+        assert_ne!(
+            fingerprint(before, "throw new Error(\"x\");"),
+            fingerprint("throw new Error(\"y\");", "throw new Error(\"y\");")
+        );
+    }
+
+    #[test]
+    fn test_suppress_and_stale_entries() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("add.ts");
+        let old = "throw a;\nthrow b;\nthrow b;\n";
+        fs::write(&file, old).unwrap();
+
+        let baseline_path = dir.path().join("purets-baseline.json");
+        let mut baseline = Baseline::new(&baseline_path);
+        let fingerprints: Vec<Fingerprint> = ["throw a", "throw b", "throw b"]
+            .iter()
+            .map(|code| Fingerprint::new(&error("no-throw", old, code), old))
+            .collect();
+        baseline.insert(&file, &fingerprints);
+        baseline.save().unwrap();
+
+        let baseline = Baseline::load(&baseline_path).unwrap();
+        assert_eq!(baseline.len(), 3);
+
+        // `throw a` was fixed, one `throw b` moved and `throw c` is new
+        let new = "\nthrow b;\nthrow c;\n";
+        let mut errors = vec![
+            error("no-throw", new, "throw b"),
+            error("no-throw", new, "throw c"),
+        ];
+        let stale = baseline.suppress(&file, new, &mut errors);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].span.start, new.find("throw c").unwrap() as u32);
+        let mut stale_counts: Vec<usize> = stale.iter().map(|entry| entry.count).collect();
+        stale_counts.sort();
+        assert_eq!(stale_counts, vec![1, 1]);
+        assert!(stale.iter().all(|entry| entry.file == "add.ts"));
+    }
+
+    #[test]
+    fn test_missing_files() {
+        let dir = TempDir::new().unwrap();
+        let source = "throw a;\n";
+        let mut baseline = Baseline::new(&dir.path().join("purets-baseline.json"));
+        let fingerprint = Fingerprint::new(&error("no-throw", source, "throw a"), source);
+        baseline.insert(&dir.path().join("removed.ts"), &[fingerprint]);
+
+        let missing = baseline.missing_files();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].file, "removed.ts");
+    }
+}
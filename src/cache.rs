@@ -0,0 +1,309 @@
+// Persistent per-file lint results, keyed by a hash of everything that affects them
+
+use anyhow::{Context, Result};
+use oxc::span::Span;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::rule_registry::Severity;
+use crate::{LintError, SuppressedError};
+
+/// Default cache directory, relative to the project root
+pub const DEFAULT_CACHE_DIR: &str = "node_modules/.cache/purets";
+
+const CACHE_FILE: &str = "lint-cache.json";
+
+/// A diagnostic as stored in the cache
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedDiagnostic {
+    pub rule: String,
+    pub message: String,
+    pub start: u32,
+    pub end: u32,
+    pub severity: Severity,
+    /// Reason given by the disable directive, for suppressed diagnostics
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl From<&LintError> for CachedDiagnostic {
+    fn from(error: &LintError) -> Self {
+        Self {
+            rule: error.rule.clone(),
+            message: error.message.clone(),
+            start: error.span.start,
+            end: error.span.end,
+            severity: error.severity,
+            reason: None,
+        }
+    }
+}
+
+impl From<&SuppressedError> for CachedDiagnostic {
+    fn from(suppressed: &SuppressedError) -> Self {
+        Self {
+            reason: suppressed.reason.clone(),
+            ..Self::from(&suppressed.error)
+        }
+    }
+}
+
+impl From<&CachedDiagnostic> for SuppressedError {
+    fn from(diagnostic: &CachedDiagnostic) -> Self {
+        Self {
+            error: LintError::from(diagnostic),
+            reason: diagnostic.reason.clone(),
+        }
+    }
+}
+
+impl From<&CachedDiagnostic> for LintError {
+    fn from(diagnostic: &CachedDiagnostic) -> Self {
+        Self {
+            rule: diagnostic.rule.clone(),
+            message: diagnostic.message.clone(),
+            span: Span::new(diagnostic.start, diagnostic.end),
+            severity: diagnostic.severity,
+            fix: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub key: String,
+    pub diagnostics: Vec<CachedDiagnostic>,
+    /// Diagnostics silenced by disable directives
+    #[serde(default)]
+    pub suppressed: Vec<CachedDiagnostic>,
+    /// Other files the diagnostics depend on (e.g. imported `types/` unions),
+    /// with the hash of their contents when the file was linted
+    #[serde(default)]
+    pub dependencies: BTreeMap<PathBuf, Option<String>>,
+}
+
+impl CacheEntry {
+    /// Record the current contents of the files the diagnostics depend on
+    pub fn with_dependencies(mut self, files: &BTreeSet<PathBuf>) -> Self {
+        self.dependencies = files
+            .iter()
+            .map(|file| (file.clone(), content_hash(file)))
+            .collect();
+        self
+    }
+
+    /// Whether every dependency still has the contents it was linted against
+    fn dependencies_unchanged(&self) -> bool {
+        self.dependencies
+            .iter()
+            .all(|(file, hash)| content_hash(file) == *hash)
+    }
+}
+
+/// Hash of a file's contents, or `None` when it cannot be read
+fn content_hash(path: &Path) -> Option<String> {
+    let content = fs::read(path).ok()?;
+    let mut hasher = Xxh3::new();
+    hasher.update(&content);
+    Some(format!("{:032x}", hasher.digest128()))
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    version: String,
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+/// Lint results from previous runs, stored as JSON in the cache directory
+#[derive(Debug)]
+pub struct LintCache {
+    path: PathBuf,
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+impl LintCache {
+    /// Load the cache from `dir`. A missing, unreadable or outdated cache
+    /// starts empty
+    pub fn load(dir: &Path) -> Self {
+        let path = dir.join(CACHE_FILE);
+        let entries = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheFile>(&content).ok())
+            .filter(|file| file.version == env!("CARGO_PKG_VERSION"))
+            .map(|file| file.entries)
+            .unwrap_or_default();
+
+        Self { path, entries }
+    }
+
+    /// Hash of the file contents, the purets version and the settings
+    /// (rule configuration, test runner, entry flags) a file is linted with
+    pub fn key(source_text: &str, settings: &str) -> String {
+        let mut hasher = Xxh3::new();
+        for part in [env!("CARGO_PKG_VERSION"), settings, source_text] {
+            hasher.update(&(part.len() as u64).to_le_bytes());
+            hasher.update(part.as_bytes());
+        }
+        format!("{:032x}", hasher.digest128())
+    }
+
+    /// Cached result for a file, if it was linted with the same key and the
+    /// files it depends on are unchanged
+    pub fn get(&self, path: &Path, key: &str) -> Option<&CacheEntry> {
+        self.entries
+            .get(path)
+            .filter(|entry| entry.key == key && entry.dependencies_unchanged())
+    }
+
+    pub fn insert(&mut self, path: PathBuf, entry: CacheEntry) {
+        self.entries.insert(path, entry);
+    }
+
+    /// Drop entries for files that are no longer linted
+    pub fn retain_paths(&mut self, paths: &[PathBuf]) {
+        let paths: HashSet<&PathBuf> = paths.iter().collect();
+        self.entries.retain(|path, _| paths.contains(path));
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let file = CacheFile {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            entries: self.entries.clone(),
+        };
+        fs::write(&self.path, serde_json::to_string(&file)?)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(key: &str) -> CacheEntry {
+        CacheEntry {
+            key: key.to_string(),
+            diagnostics: vec![CachedDiagnostic {
+                rule: "no-throw".to_string(),
+                message: "Throw statements are not allowed".to_string(),
+                start: 4,
+                end: 20,
+                severity: Severity::Error,
+                reason: None,
+            }],
+            suppressed: Vec::new(),
+            dependencies: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_key_depends_on_content_and_settings() {
+        let key = LintCache::key("const a = 1;", "test-runner=vitest");
+        assert_eq!(key, LintCache::key("const a = 1;", "test-runner=vitest"));
+        assert_ne!(key, LintCache::key("const a = 2;", "test-runner=vitest"));
+        assert_ne!(key, LintCache::key("const a = 1;", "test-runner=deno-test"));
+        // Parts are length-prefixed, so moving text between them changes the key
+        assert_ne!(LintCache::key("ab", "c"), LintCache::key("b", "ca"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = PathBuf::from("src/pure/add.ts");
+
+        let mut cache = LintCache::load(dir.path());
+        assert!(cache.get(&path, "k1").is_none());
+        cache.insert(path.clone(), entry("k1"));
+        cache.save().unwrap();
+
+        let cache = LintCache::load(dir.path());
+        assert_eq!(
+            cache.get(&path, "k1").map(|entry| &entry.diagnostics),
+            Some(&entry("k1").diagnostics)
+        );
+        assert!(cache.get(&path, "k2").is_none());
+    }
+
+    #[test]
+    fn test_corrupt_or_outdated_cache_is_ignored() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(CACHE_FILE), "{ not json").unwrap();
+        assert!(LintCache::load(dir.path()).entries.is_empty());
+
+        let outdated = r#"{ "version": "0.0.0-old", "entries": { "a.ts": { "key": "k", "diagnostics": [] } } }"#;
+        fs::write(dir.path().join(CACHE_FILE), outdated).unwrap();
+        assert!(LintCache::load(dir.path()).entries.is_empty());
+    }
+
+    #[test]
+    fn test_retain_paths() {
+        let mut cache = LintCache::load(Path::new("/nonexistent"));
+        cache.insert(PathBuf::from("a.ts"), entry("k"));
+        cache.insert(PathBuf::from("b.ts"), entry("k"));
+        cache.retain_paths(&[PathBuf::from("b.ts")]);
+        assert!(cache.get(Path::new("a.ts"), "k").is_none());
+        assert!(cache.get(Path::new("b.ts"), "k").is_some());
+    }
+
+    #[test]
+    fn test_changed_types_union_invalidates_entry() {
+        let dir = TempDir::new().unwrap();
+        let types_dir = dir.path().join("src/types");
+        let pure_dir = dir.path().join("src/pure");
+        fs::create_dir_all(&types_dir).unwrap();
+        fs::create_dir_all(&pure_dir).unwrap();
+        let union = types_dir.join("Shape.ts");
+        fs::write(
+            &union,
+            "export type Shape = { readonly kind: 'circle' } | { readonly kind: 'square' };\n",
+        )
+        .unwrap();
+        let file = pure_dir.join("area.ts");
+        let source = r#"import type { Shape } from "../types/Shape.ts";
+
+export function area(shape: Shape): number {
+  switch (shape.kind) {
+    case 'circle': {
+      return 1;
+    }
+    case 'square': {
+      return 2;
+    }
+    default: {
+      return assertNever(shape);
+    }
+  }
+}
+"#;
+        fs::write(&file, source).unwrap();
+
+        let allocator = oxc::allocator::Allocator::default();
+        let source_type = oxc::span::SourceType::from_path(&file).unwrap();
+        let ret = oxc::parser::Parser::new(&allocator, source, source_type).parse();
+        let mut linter = crate::Linter::new(&file, source, false);
+        crate::rules::check_switch_exhaustive(&mut linter, &ret.program);
+        assert!(linter.dependencies.contains(&union));
+
+        let mut cache = LintCache::load(dir.path());
+        cache.insert(
+            file.clone(),
+            entry("k").with_dependencies(&linter.dependencies),
+        );
+        assert!(cache.get(&file, "k").is_some());
+
+        // A new union member makes the switch non-exhaustive
+        fs::write(
+            &union,
+            "export type Shape = { readonly kind: 'circle' } | { readonly kind: 'square' } | { readonly kind: 'line' };\n",
+        )
+        .unwrap();
+        assert!(cache.get(&file, "k").is_none());
+    }
+}
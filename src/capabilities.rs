@@ -0,0 +1,779 @@
+// Capabilities granted to a file with `@allow` directives
+
+use anyhow::{bail, Context, Result};
+use glob::{MatchOptions, Pattern};
+use oxc::ast::ast::*;
+use oxc::ast::Comment;
+use oxc::span::{GetSpan, Span};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+
+use crate::config::relative_path;
+use crate::directories::in_directory;
+
+/// Ambient access a file has to ask for with `@allow <name>`, and the code
+/// that counts as using it
+#[derive(Debug)]
+pub struct Capability {
+    pub name: String,
+    pub description: String,
+    /// Global identifiers, e.g. `document`
+    pub globals: Vec<String>,
+    /// Callee paths of calls, e.g. `Date.now` or `console.*`
+    pub calls: Vec<Pattern>,
+    /// Member paths accessed in any way, e.g. `process.env`
+    pub members: Vec<Pattern>,
+    /// Callee paths of `new` expressions, e.g. `Date`
+    pub constructors: Vec<Pattern>,
+    /// Type names, e.g. `HTMLElement`
+    pub types: Vec<String>,
+    /// Module specifiers without the `node:` prefix, e.g. `fs/*`
+    pub modules: Vec<Pattern>,
+}
+
+/// A piece of code that may require a capability
+#[derive(Debug, Clone, Copy)]
+pub enum Usage<'a> {
+    Global(&'a str),
+    Call(&'a str),
+    Member(&'a str),
+    New(&'a str),
+    Type(&'a str),
+    Import(&'a str),
+}
+
+impl fmt::Display for Usage<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Usage::Global(name) | Usage::Member(name) => write!(f, "Access to '{}'", name),
+            Usage::Call(path) => write!(f, "Use of '{}'", path),
+            Usage::New(path) => write!(f, "Use of 'new {}()'", path),
+            Usage::Type(name) => write!(f, "Type '{}'", name),
+            Usage::Import(source) => write!(f, "Import of '{}'", source),
+        }
+    }
+}
+
+impl Capability {
+    pub fn matches(&self, usage: Usage) -> bool {
+        let any = |patterns: &[Pattern], text: &str| patterns.iter().any(|p| p.matches(text));
+        match usage {
+            Usage::Global(name) => self.globals.iter().any(|global| global == name),
+            Usage::Call(path) => any(&self.calls, path),
+            Usage::Member(path) => any(&self.members, path),
+            Usage::New(path) => any(&self.constructors, path),
+            Usage::Type(name) => self.types.iter().any(|ty| ty == name),
+            Usage::Import(source) => any(&self.modules, module_name(source)),
+        }
+    }
+}
+
+/// `node:fs` and `fs` name the same module
+fn module_name(source: &str) -> &str {
+    source.strip_prefix("node:").unwrap_or(source)
+}
+
+/// The most a set of files may use: `@allow` directives for anything else
+/// are reported even though they are well-formed
+#[derive(Debug)]
+pub struct CapabilityPolicy {
+    pub root: PathBuf,
+    /// Globs relative to the config directory
+    pub files: Vec<Pattern>,
+    pub allow: Vec<String>,
+}
+
+/// The capability ceiling of a file and where it comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ceiling<'a> {
+    pub allow: &'a [String],
+    /// Glob of the policy entry, or `None` for the built-in `pure/` policy
+    pub files: Option<&'a str>,
+}
+
+impl Ceiling<'_> {
+    pub fn permits(&self, name: &str) -> bool {
+        self.allow.iter().any(|allowed| allowed == name)
+    }
+}
+
+/// Policy globs match path separators only literally
+const POLICY_MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// The built-in capabilities followed by those declared in `purets.json`,
+/// with the directory policy
+#[derive(Debug, Default)]
+pub struct Capabilities {
+    list: Vec<Capability>,
+    builtin_count: usize,
+    policy: Vec<CapabilityPolicy>,
+}
+
+static BUILTIN: LazyLock<Arc<Capabilities>> = LazyLock::new(|| {
+    let list: Vec<Capability> = BUILTIN_CAPABILITIES.iter().map(builtin).collect();
+    Arc::new(Capabilities {
+        builtin_count: list.len(),
+        list,
+        policy: Vec::new(),
+    })
+});
+
+impl Capabilities {
+    pub fn builtin() -> Arc<Self> {
+        Arc::clone(&BUILTIN)
+    }
+
+    /// Built-in capabilities extended with custom ones, whose names must be
+    /// new
+    pub fn with_custom(custom: Vec<Capability>) -> Result<Self> {
+        let mut list: Vec<Capability> = BUILTIN_CAPABILITIES.iter().map(builtin).collect();
+        let builtin_count = list.len();
+        for capability in custom {
+            if list.iter().any(|known| known.name == capability.name) {
+                bail!(
+                    "Capability '{}' conflicts with a built-in capability",
+                    capability.name
+                );
+            }
+            list.push(capability);
+        }
+        Ok(Self {
+            list,
+            builtin_count,
+            policy: Vec::new(),
+        })
+    }
+
+    /// Limit capabilities by path; policy entries may only name known
+    /// capabilities
+    pub fn with_policy(mut self, policy: Vec<CapabilityPolicy>) -> Result<Self> {
+        for entry in &policy {
+            if let Some(unknown) = entry.allow.iter().find(|name| self.get(name).is_none()) {
+                bail!("Unknown capability '{}' in capability policy", unknown);
+            }
+        }
+        self.policy = policy;
+        Ok(self)
+    }
+
+    pub fn policy(&self) -> &[CapabilityPolicy] {
+        &self.policy
+    }
+
+    /// The last policy entry matching a file applies. Without one, files
+    /// under `pure/` may not use any capability
+    pub fn ceiling(&self, path: &Path) -> Option<Ceiling<'_>> {
+        let entry = self.policy.iter().rev().find(|entry| {
+            let relative = relative_path(&entry.root, path);
+            // `*` stays within one directory, so `src/cli/*` does not also
+            // cover `src/cli/deep/x.ts`
+            entry
+                .files
+                .iter()
+                .any(|glob| glob.matches_with(&relative, POLICY_MATCH_OPTIONS))
+        });
+        if let Some(entry) = entry {
+            return Some(Ceiling {
+                allow: &entry.allow,
+                files: entry.files.first().map(Pattern::as_str),
+            });
+        }
+
+        in_directory(path, "pure").then_some(Ceiling {
+            allow: &[],
+            files: None,
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Capability> {
+        self.list.iter()
+    }
+
+    /// Capabilities declared in `purets.json`
+    pub fn custom(&self) -> &[Capability] {
+        &self.list[self.builtin_count..]
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Capability> {
+        self.list.iter().find(|capability| capability.name == name)
+    }
+
+    /// Names of the capabilities that allow a usage
+    pub fn required_by(&self, usage: Usage) -> Vec<&str> {
+        self.list
+            .iter()
+            .filter(|capability| capability.matches(usage))
+            .map(|capability| capability.name.as_str())
+            .collect()
+    }
+}
+
+/// A custom capability as written in the configuration file
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CapabilityConfig {
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub globals: Vec<String>,
+    #[serde(default)]
+    pub calls: Vec<String>,
+    #[serde(default)]
+    pub members: Vec<String>,
+    #[serde(default)]
+    pub constructors: Vec<String>,
+    #[serde(default)]
+    pub types: Vec<String>,
+    #[serde(default)]
+    pub modules: Vec<String>,
+}
+
+impl CapabilityConfig {
+    /// Validate the capability and compile its globs
+    pub fn compile(&self, name: &str) -> Result<Capability> {
+        let valid_name = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        if !valid_name {
+            bail!(
+                "Capability name '{}' must be lowercase letters, digits, dashes and underscores",
+                name
+            );
+        }
+        let is_empty = self.globals.is_empty()
+            && self.calls.is_empty()
+            && self.members.is_empty()
+            && self.constructors.is_empty()
+            && self.types.is_empty()
+            && self.modules.is_empty();
+        if is_empty {
+            bail!("Capability '{}' does not match any code", name);
+        }
+
+        let compile = |globs: &[String]| -> Result<Vec<Pattern>> {
+            globs
+                .iter()
+                .map(|glob| {
+                    Pattern::new(glob).with_context(|| {
+                        format!("Invalid glob '{}' in capability '{}'", glob, name)
+                    })
+                })
+                .collect()
+        };
+
+        Ok(Capability {
+            name: name.to_string(),
+            description: self.description.clone(),
+            globals: self.globals.clone(),
+            calls: compile(&self.calls)?,
+            members: compile(&self.members)?,
+            constructors: compile(&self.constructors)?,
+            types: self.types.clone(),
+            modules: compile(
+                &self
+                    .modules
+                    .iter()
+                    .map(|module| module_name(module).to_string())
+                    .collect::<Vec<_>>(),
+            )?,
+        })
+    }
+}
+
+/// A capability policy entry as written in the configuration file
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CapabilityPolicyConfig {
+    pub files: Vec<String>,
+    #[serde(default)]
+    pub allow: Vec<String>,
+}
+
+impl CapabilityPolicyConfig {
+    /// Compile the entry's globs, relative to the config directory `root`
+    pub fn compile(&self, root: &Path) -> Result<CapabilityPolicy> {
+        if self.files.is_empty() {
+            bail!("Capability policy entries must list files");
+        }
+        let files = self
+            .files
+            .iter()
+            .map(|glob| {
+                Pattern::new(glob)
+                    .with_context(|| format!("Invalid glob '{}' in capability policy", glob))
+            })
+            .collect::<Result<_>>()?;
+        Ok(CapabilityPolicy {
+            root: root.to_path_buf(),
+            files,
+            allow: self.allow.clone(),
+        })
+    }
+}
+
+/// Compile the custom capabilities of a configuration file
+pub fn compile_custom(configs: &BTreeMap<String, CapabilityConfig>) -> Result<Vec<Capability>> {
+    configs
+        .iter()
+        .map(|(name, config)| config.compile(name))
+        .collect()
+}
+
+struct BuiltinCapability {
+    name: &'static str,
+    description: &'static str,
+    globals: &'static [&'static str],
+    calls: &'static [&'static str],
+    members: &'static [&'static str],
+    constructors: &'static [&'static str],
+    types: &'static [&'static str],
+    modules: &'static [&'static str],
+}
+
+const NONE: BuiltinCapability = BuiltinCapability {
+    name: "",
+    description: "",
+    globals: &[],
+    calls: &[],
+    members: &[],
+    constructors: &[],
+    types: &[],
+    modules: &[],
+};
+
+fn builtin(capability: &BuiltinCapability) -> Capability {
+    let compile = |globs: &[&str]| -> Vec<Pattern> {
+        globs
+            .iter()
+            .map(|glob| Pattern::new(glob).expect("valid built-in glob"))
+            .collect()
+    };
+    let strings = |names: &[&str]| -> Vec<String> { names.iter().map(|s| s.to_string()).collect() };
+
+    Capability {
+        name: capability.name.to_string(),
+        description: capability.description.to_string(),
+        globals: strings(capability.globals),
+        calls: compile(capability.calls),
+        members: compile(capability.members),
+        constructors: compile(capability.constructors),
+        types: strings(capability.types),
+        modules: compile(capability.modules),
+    }
+}
+
+const BUILTIN_CAPABILITIES: &[BuiltinCapability] = &[
+    BuiltinCapability {
+        name: "dom",
+        description: "Browser document, window and UI",
+        globals: &[
+            "document",
+            "window",
+            "navigator",
+            "location",
+            "history",
+            "screen",
+            "alert",
+            "confirm",
+            "prompt",
+        ],
+        types: &[
+            "HTMLElement",
+            "HTMLDivElement",
+            "HTMLInputElement",
+            "Document",
+            "Window",
+            "Navigator",
+            "Location",
+            "Element",
+            "Node",
+            "Event",
+            "MouseEvent",
+            "KeyboardEvent",
+            "DOMParser",
+            "XMLSerializer",
+        ],
+        ..NONE
+    },
+    BuiltinCapability {
+        name: "net",
+        description: "Network requests and sockets",
+        globals: &[
+            "fetch",
+            "XMLHttpRequest",
+            "WebSocket",
+            "EventSource",
+            "ServiceWorker",
+        ],
+        types: &[
+            "Response",
+            "Request",
+            "Headers",
+            "RequestInit",
+            "XMLHttpRequest",
+            "WebSocket",
+            "EventSource",
+            "ServiceWorker",
+            "ServiceWorkerRegistration",
+        ],
+        modules: &[
+            "http", "https", "http2", "net", "tls", "dgram", "dns", "dns/*",
+        ],
+        ..NONE
+    },
+    BuiltinCapability {
+        name: "timers",
+        description: "Timers and animation frames",
+        calls: &[
+            "setTimeout",
+            "setInterval",
+            "setImmediate",
+            "requestAnimationFrame",
+            "requestIdleCallback",
+            "clearTimeout",
+            "clearInterval",
+            "clearImmediate",
+            "cancelAnimationFrame",
+            "cancelIdleCallback",
+        ],
+        modules: &["timers", "timers/*"],
+        ..NONE
+    },
+    BuiltinCapability {
+        name: "console",
+        description: "Console output",
+        calls: &["console.*"],
+        modules: &["console"],
+        ..NONE
+    },
+    BuiltinCapability {
+        name: "throws",
+        description: "throw statements and try/catch",
+        ..NONE
+    },
+    BuiltinCapability {
+        name: "fs",
+        description: "File system access",
+        calls: &[
+            "Deno.readFile*",
+            "Deno.readTextFile*",
+            "Deno.writeFile*",
+            "Deno.writeTextFile*",
+            "Deno.readDir*",
+            "Deno.mkdir*",
+            "Deno.remove*",
+            "Deno.stat*",
+            "Deno.open*",
+            "Bun.file",
+            "Bun.write",
+        ],
+        modules: &["fs", "fs/*"],
+        ..NONE
+    },
+    BuiltinCapability {
+        name: "env",
+        description: "Environment variables",
+        members: &["process.env", "Deno.env"],
+        ..NONE
+    },
+    BuiltinCapability {
+        name: "random",
+        description: "Random numbers and UUIDs",
+        calls: &["Math.random", "crypto.getRandomValues", "crypto.randomUUID"],
+        ..NONE
+    },
+    BuiltinCapability {
+        name: "time",
+        description: "The current date and time",
+        calls: &["Date.now", "performance.now"],
+        constructors: &["Date"],
+        ..NONE
+    },
+    BuiltinCapability {
+        name: "child_process",
+        description: "Spawning processes",
+        calls: &["Bun.spawn", "Bun.spawnSync"],
+        constructors: &["Deno.Command"],
+        modules: &["child_process"],
+        ..NONE
+    },
+    BuiltinCapability {
+        name: "storage",
+        description: "Browser storage",
+        globals: &["localStorage", "sessionStorage", "indexedDB"],
+        types: &["Storage", "IDBDatabase"],
+        ..NONE
+    },
+    BuiltinCapability {
+        name: "worker",
+        description: "Workers and threads",
+        globals: &["Worker", "SharedWorker"],
+        modules: &["worker_threads"],
+        ..NONE
+    },
+];
+
+/// Capability names listed with `@allow` in a JSDoc comment
+#[derive(Debug, Clone, Default)]
+pub struct AllowedCapabilities {
+    names: Vec<String>,
+}
+
+impl AllowedCapabilities {
+    /// Read `@allow <name>` lines of a comment; anything after the name is a
+    /// comment
+    pub fn parse(jsdoc: &str) -> Self {
+        let mut allowed = Self::default();
+
+        for line in jsdoc.lines() {
+            let line = line
+                .trim()
+                .trim_start_matches('/')
+                .trim_start_matches('*')
+                .trim_start();
+            let Some(rest) = line.strip_prefix("@allow ") else {
+                continue;
+            };
+            if let Some(name) = rest.split_whitespace().next() {
+                if !allowed.allows(name) {
+                    allowed.names.push(name.to_string());
+                }
+            }
+        }
+
+        allowed
+    }
+
+    pub fn allows(&self, name: &str) -> bool {
+        self.names.iter().any(|allowed| allowed == name)
+    }
+
+    /// Names in the order they were written
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+}
+
+/// A top-level function or const, with the `@allow` directives of its JSDoc
+#[derive(Debug, Clone)]
+pub struct FunctionScope {
+    pub name: String,
+    pub span: Span,
+    pub allowed: AllowedCapabilities,
+    /// The JSDoc comment holding the directives
+    pub jsdoc: Option<Span>,
+}
+
+/// The `@allow` directives of a file. A leading JSDoc comment that does not
+/// document a declaration grants capabilities to the whole file; the JSDoc
+/// of a top-level function or const only to that declaration
+#[derive(Debug, Clone, Default)]
+pub struct AllowScopes {
+    pub file: AllowedCapabilities,
+    pub functions: Vec<FunctionScope>,
+}
+
+impl AllowScopes {
+    pub fn from_program(source_text: &str, program: &Program) -> Self {
+        let jsdocs: Vec<&Comment> = program
+            .comments
+            .iter()
+            .filter(|comment| comment.is_jsdoc())
+            .collect();
+        let mut scopes = Self::default();
+
+        for statement in &program.body {
+            let Some(name) = declared_name(statement) else {
+                continue;
+            };
+            let span = statement.span();
+            // The closest JSDoc comment before the declaration documents it
+            let jsdoc = jsdocs
+                .iter()
+                .rev()
+                .find(|comment| comment.attached_to == span.start)
+                .map(|comment| comment.span);
+            let allowed = jsdoc.map_or_else(AllowedCapabilities::default, |jsdoc| {
+                AllowedCapabilities::parse(jsdoc.source_text(source_text))
+            });
+            scopes.functions.push(FunctionScope {
+                name,
+                span,
+                allowed,
+                jsdoc,
+            });
+        }
+
+        // The first JSDoc comment is the file header unless it documents a
+        // function or const
+        let header = jsdocs.first().filter(|comment| {
+            !scopes
+                .functions
+                .iter()
+                .any(|function| function.jsdoc == Some(comment.span))
+        });
+        if let Some(header) = header {
+            scopes.file = AllowedCapabilities::parse(header.span.source_text(source_text));
+        }
+
+        scopes
+    }
+
+    /// Index of the top-level function or const containing an offset
+    pub fn function_at(&self, offset: u32) -> Option<usize> {
+        self.functions
+            .iter()
+            .position(|function| function.span.start <= offset && offset < function.span.end)
+    }
+
+    /// Whether a capability is allowed at an offset, by the file or by the
+    /// enclosing function
+    pub fn allows(&self, name: &str, offset: u32) -> bool {
+        self.file.allows(name)
+            || self
+                .function_at(offset)
+                .is_some_and(|index| self.functions[index].allowed.allows(name))
+    }
+}
+
+/// Name of a top-level function or const declaration, exported or not
+fn declared_name(statement: &Statement) -> Option<String> {
+    match statement {
+        Statement::FunctionDeclaration(function) => function_name(function),
+        Statement::VariableDeclaration(declaration) => const_name(declaration),
+        Statement::ExportNamedDeclaration(export) => match &export.declaration {
+            Some(Declaration::FunctionDeclaration(function)) => function_name(function),
+            Some(Declaration::VariableDeclaration(declaration)) => const_name(declaration),
+            _ => None,
+        },
+        Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+            ExportDefaultDeclarationKind::FunctionDeclaration(function) => {
+                Some(function_name(function).unwrap_or_else(|| "default".to_string()))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn function_name(function: &Function) -> Option<String> {
+    function.id.as_ref().map(|id| id.name.to_string())
+}
+
+fn const_name(declaration: &VariableDeclaration) -> Option<String> {
+    if declaration.kind != VariableDeclarationKind::Const {
+        return None;
+    }
+    declaration
+        .declarations
+        .first()
+        .and_then(|declarator| declarator.id.get_binding_identifier())
+        .map(|id| id.name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    #[test]
+    fn test_builtin_usages() {
+        let capabilities = Capabilities::builtin();
+        assert_eq!(
+            capabilities.required_by(Usage::Import("node:fs/promises")),
+            vec!["fs"]
+        );
+        assert_eq!(capabilities.required_by(Usage::Import("fs")), vec!["fs"]);
+        assert_eq!(
+            capabilities.required_by(Usage::Member("process.env")),
+            vec!["env"]
+        );
+        assert_eq!(
+            capabilities.required_by(Usage::Call("console.log")),
+            vec!["console"]
+        );
+        assert_eq!(capabilities.required_by(Usage::New("Date")), vec!["time"]);
+        assert_eq!(
+            capabilities.required_by(Usage::Global("localStorage")),
+            vec!["storage"]
+        );
+        assert!(capabilities
+            .required_by(Usage::Import("node:path"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_custom_capabilities() {
+        let configs: BTreeMap<String, CapabilityConfig> =
+            serde_json::from_str(r#"{ "db": { "modules": ["pg", "node:sqlite"] } }"#).unwrap();
+        let capabilities = Capabilities::with_custom(compile_custom(&configs).unwrap()).unwrap();
+        assert_eq!(capabilities.custom().len(), 1);
+        assert_eq!(capabilities.required_by(Usage::Import("pg")), vec!["db"]);
+        assert_eq!(
+            capabilities.required_by(Usage::Import("node:sqlite")),
+            vec!["db"]
+        );
+
+        let empty = CapabilityConfig::default();
+        assert!(empty.compile("db").is_err());
+        let builtin = CapabilityConfig {
+            modules: vec!["pg".to_string()],
+            ..CapabilityConfig::default()
+        };
+        assert!(Capabilities::with_custom(vec![builtin.compile("fs").unwrap()]).is_err());
+        assert!(builtin.compile("DB").is_err());
+    }
+
+    #[test]
+    fn test_parse_allowed() {
+        let allowed = AllowedCapabilities::parse(
+            "/**\n * @allow fs\n * @allow env needed for config\n *@allow fs\n */",
+        );
+        assert_eq!(allowed.names().collect::<Vec<_>>(), vec!["fs", "env"]);
+        assert!(!allowed.allows("net"));
+
+        let allowed = AllowedCapabilities::parse("/** @allow time */");
+        assert_eq!(allowed.names().collect::<Vec<_>>(), vec!["time"]);
+    }
+
+    #[test]
+    fn test_allow_scopes() {
+        let source = r#"/**
+ * @allow fs
+ */
+import fs from "node:fs";
+
+/**
+ * @allow console
+ */
+export function debug(message: string): void {
+  console.log(message);
+}
+
+const quiet = (): void => {};
+"#;
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+        let scopes = AllowScopes::from_program(source, &ret.program);
+
+        assert!(scopes.file.allows("fs"));
+        let names: Vec<&str> = scopes.functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["debug", "quiet"]);
+
+        let inside = source.find("console.log").unwrap() as u32;
+        let outside = source.find("const quiet").unwrap() as u32;
+        assert!(scopes.allows("console", inside));
+        assert!(!scopes.allows("console", outside));
+        assert!(scopes.allows("fs", outside));
+    }
+}
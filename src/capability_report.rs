@@ -0,0 +1,264 @@
+// Capability manifest: which capabilities each file declares and uses
+
+use anyhow::{bail, Result};
+use colored::Colorize;
+use oxc::allocator::Allocator;
+use oxc::parser::Parser;
+use oxc::span::SourceType;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::capabilities::{AllowScopes, Capabilities};
+use crate::rules::check_allow_directives;
+use crate::Linter;
+
+/// Capabilities a file declares with `@allow` (at file or function level)
+/// and capabilities its code uses, whether declared or not
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileCapabilities {
+    pub file: String,
+    pub package: String,
+    pub declared: BTreeSet<String>,
+    pub used: BTreeSet<String>,
+}
+
+impl FileCapabilities {
+    /// Scan a file; `file` and `package` are left for the caller to fill in
+    pub fn scan(path: &Path, source_text: &str, capabilities: Arc<Capabilities>) -> Result<Self> {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path(path).unwrap_or_default();
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        if !ret.errors.is_empty() {
+            bail!("Failed to parse {}", path.display());
+        }
+
+        let scopes = AllowScopes::from_program(source_text, &ret.program);
+        let declared = scopes
+            .file
+            .names()
+            .chain(scopes.functions.iter().flat_map(|f| f.allowed.names()))
+            .map(str::to_string)
+            .collect();
+
+        let mut linter = Linter::new(path, source_text, false).with_capabilities(capabilities);
+        check_allow_directives(&mut linter, &ret.program);
+
+        Ok(Self {
+            file: path.display().to_string(),
+            package: String::new(),
+            declared,
+            used: linter.used_capabilities,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.declared.is_empty() && self.used.is_empty()
+    }
+}
+
+/// Capabilities declared and used across the files of a workspace package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PackageCapabilities {
+    pub name: String,
+    pub files: usize,
+    pub declared: BTreeSet<String>,
+    pub used: BTreeSet<String>,
+}
+
+/// The `purets capabilities` report, sorted so that it diffs well
+#[derive(Debug, Serialize)]
+pub struct CapabilityReport {
+    pub packages: Vec<PackageCapabilities>,
+    pub files: Vec<FileCapabilities>,
+}
+
+impl CapabilityReport {
+    /// Files that neither declare nor use a capability are left out
+    pub fn new(files: Vec<FileCapabilities>) -> Self {
+        let mut files: Vec<FileCapabilities> =
+            files.into_iter().filter(|file| !file.is_empty()).collect();
+        files.sort_by(|a, b| a.file.cmp(&b.file));
+
+        let mut packages: BTreeMap<&str, PackageCapabilities> = BTreeMap::new();
+        for file in &files {
+            let package = packages
+                .entry(&file.package)
+                .or_insert_with(|| PackageCapabilities {
+                    name: file.package.clone(),
+                    files: 0,
+                    declared: BTreeSet::new(),
+                    used: BTreeSet::new(),
+                });
+            package.files += 1;
+            package.declared.extend(file.declared.iter().cloned());
+            package.used.extend(file.used.iter().cloned());
+        }
+        let packages = packages.into_values().collect();
+
+        Self { packages, files }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)? + "\n")
+    }
+
+    /// Package and file tables; used capabilities without a directive are
+    /// marked with `*`
+    pub fn format_table(&self) -> String {
+        if self.files.is_empty() {
+            return "No capabilities declared or used\n".to_string();
+        }
+
+        let package_width = column_width("PACKAGE", self.packages.iter().map(|p| &p.name));
+        let file_width = column_width("FILE", self.files.iter().map(|f| &f.file));
+        let declared_width = column_width(
+            "DECLARED",
+            self.files
+                .iter()
+                .map(|f| join(&f.declared))
+                .chain(self.packages.iter().map(|p| join(&p.declared))),
+        );
+
+        let mut out = format!(
+            "{:<package_width$} {:<5} {:<declared_width$} {}\n",
+            "PACKAGE".bold(),
+            "FILES".bold(),
+            "DECLARED".bold(),
+            "USED".bold()
+        );
+        for package in &self.packages {
+            out.push_str(&format!(
+                "{:<package_width$} {:<5} {:<declared_width$} {}\n",
+                package.name.cyan(),
+                package.files,
+                join(&package.declared),
+                used_list(&package.used, &package.declared)
+            ));
+        }
+
+        out.push_str(&format!(
+            "\n{:<file_width$} {:<declared_width$} {}\n",
+            "FILE".bold(),
+            "DECLARED".bold(),
+            "USED".bold()
+        ));
+        for file in &self.files {
+            out.push_str(&format!(
+                "{:<file_width$} {:<declared_width$} {}\n",
+                file.file.cyan(),
+                join(&file.declared),
+                used_list(&file.used, &file.declared)
+            ));
+        }
+
+        if self
+            .files
+            .iter()
+            .any(|file| !file.used.is_subset(&file.declared))
+        {
+            out.push_str("\n* used without an @allow directive\n");
+        }
+        out
+    }
+}
+
+fn column_width<S: AsRef<str>>(header: &str, values: impl Iterator<Item = S>) -> usize {
+    values
+        .map(|value| value.as_ref().len())
+        .chain([header.len()])
+        .max()
+        .unwrap_or_default()
+}
+
+fn join(names: &BTreeSet<String>) -> String {
+    if names.is_empty() {
+        return "-".to_string();
+    }
+    names.iter().cloned().collect::<Vec<_>>().join(", ")
+}
+
+fn used_list(used: &BTreeSet<String>, declared: &BTreeSet<String>) -> String {
+    if used.is_empty() {
+        return "-".to_string();
+    }
+    used.iter()
+        .map(|name| {
+            if declared.contains(name) {
+                name.clone()
+            } else {
+                format!("{}*", name).yellow().to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(file: &str, package: &str, source: &str) -> FileCapabilities {
+        FileCapabilities {
+            file: file.to_string(),
+            package: package.to_string(),
+            ..FileCapabilities::scan(Path::new(file), source, Capabilities::builtin()).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_scan_declared_and_used() {
+        let file = scan(
+            "src/io/load.ts",
+            ".",
+            r#"/**
+ * @allow net
+ * @allow console
+ */
+import fs from "node:fs";
+
+/**
+ * @allow timers
+ */
+export async function load(): Promise<string> {
+  setTimeout(() => {}, 10);
+  const response = await fetch("/api");
+  return response.text();
+}
+"#,
+        );
+        let names = |set: &BTreeSet<String>| set.iter().cloned().collect::<Vec<_>>();
+        assert_eq!(names(&file.declared), vec!["console", "net", "timers"]);
+        assert_eq!(names(&file.used), vec!["fs", "net", "timers"]);
+    }
+
+    #[test]
+    fn test_report_groups_packages() {
+        let report = CapabilityReport::new(vec![
+            scan("packages/b/src/b.ts", "packages/b", "fetch('/b');\n"),
+            scan("packages/a/src/add.ts", "packages/a", "const a = 1;\n"),
+            scan(
+                "packages/a/src/log.ts",
+                "packages/a",
+                "/**\n * @allow console\n */\nconsole.log(1);\n",
+            ),
+        ]);
+
+        let files: Vec<&str> = report.files.iter().map(|f| f.file.as_str()).collect();
+        assert_eq!(files, vec!["packages/a/src/log.ts", "packages/b/src/b.ts"]);
+        let packages: Vec<(&str, usize)> = report
+            .packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.files))
+            .collect();
+        assert_eq!(packages, vec![("packages/a", 1), ("packages/b", 1)]);
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["files"][1]["used"][0], "net");
+        assert_eq!(json["files"][1]["declared"], serde_json::json!([]));
+        assert!(report
+            .format_table()
+            .contains("* used without an @allow directive"));
+    }
+}
@@ -0,0 +1,445 @@
+use anyhow::{bail, Context, Result};
+use glob::Pattern;
+use oxc::ast::AstType;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::capabilities::{self, Capabilities, CapabilityConfig, CapabilityPolicyConfig};
+use crate::rule_registry::{self, Severity};
+use crate::rules::{custom_rule, ImperativeLoopsOptions};
+
+/// Name of the project configuration file
+pub const CONFIG_FILE: &str = "purets.json";
+
+/// Project configuration read from `purets.json`
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PuretsConfig {
+    /// Directory containing the configuration file
+    #[serde(skip)]
+    pub root: PathBuf,
+    #[serde(default)]
+    pub custom_rules: Vec<CustomRuleConfig>,
+    /// Require `-- reason` on every `purets-disable-*` comment
+    #[serde(default)]
+    pub require_disable_reason: bool,
+    /// Custom capabilities for `@allow` directives, by name
+    #[serde(default)]
+    pub capabilities: BTreeMap<String, CapabilityConfig>,
+    /// The capabilities files may use, by path
+    #[serde(default)]
+    pub capability_policy: Vec<CapabilityPolicyConfig>,
+    /// Rule and diagnostic ids that are not reported, in source and config files
+    #[serde(default)]
+    pub disabled_rules: Vec<String>,
+    /// Where no-imperative-loops applies and whether it allows `for...of`
+    /// over readonly inputs
+    #[serde(default)]
+    pub imperative_loops: ImperativeLoopsOptions,
+}
+
+/// A user-defined rule as written in the configuration file
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CustomRuleConfig {
+    pub id: String,
+    pub message: String,
+    #[serde(default = "default_severity")]
+    pub severity: Severity,
+    pub pattern: PatternConfig,
+    /// Globs of files the rule applies to, relative to the config directory
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// Globs of files excluded from the rule, relative to the config directory
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+fn default_severity() -> Severity {
+    Severity::Error
+}
+
+/// Structural pattern matched by a custom rule
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", deny_unknown_fields)]
+pub enum PatternConfig {
+    /// Calls whose callee path (e.g. `db.query`) matches a glob. With
+    /// `awaited`, only calls that are (or are not) awaited match
+    Call {
+        callee: String,
+        awaited: Option<bool>,
+    },
+    /// `new` expressions whose callee path matches a glob
+    New { callee: String },
+    /// Imports, re-exports, dynamic imports and `require()` of a module
+    /// whose source matches a glob
+    Import { source: String },
+    /// Any AST node of the given kind (e.g. `TryStatement`)
+    Node { kind: String },
+}
+
+/// A validated custom rule, ready to be run by the linter
+#[derive(Debug)]
+pub struct CustomRule {
+    pub id: String,
+    pub message: String,
+    pub severity: Severity,
+    pub pattern: CustomPattern,
+    pub root: PathBuf,
+    pub files: Vec<Pattern>,
+    pub exclude: Vec<Pattern>,
+}
+
+#[derive(Debug)]
+pub enum CustomPattern {
+    Call {
+        callee: Pattern,
+        awaited: Option<bool>,
+    },
+    New {
+        callee: Pattern,
+    },
+    Import {
+        source: Pattern,
+    },
+    Node {
+        kind: AstType,
+    },
+}
+
+impl CustomRule {
+    /// Whether the rule applies to a file, matching globs against the path
+    /// relative to the config directory
+    pub fn applies_to(&self, path: &Path) -> bool {
+        let relative = relative_path(&self.root, path);
+
+        (self.files.is_empty() || self.files.iter().any(|glob| glob.matches(&relative)))
+            && !self.exclude.iter().any(|glob| glob.matches(&relative))
+    }
+}
+
+/// Path of a file relative to the config directory, with `/` separators,
+/// for matching globs from the config
+pub(crate) fn relative_path(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    relative
+        .strip_prefix("./")
+        .map(str::to_string)
+        .unwrap_or(relative)
+}
+
+fn compile_glob(rule_id: &str, glob: &str) -> Result<Pattern> {
+    Pattern::new(glob)
+        .with_context(|| format!("Invalid glob '{}' in custom rule '{}'", glob, rule_id))
+}
+
+impl PuretsConfig {
+    /// Load `purets.json` from the given file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut config: PuretsConfig = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid {}", path.display()))?;
+        config.root = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        Ok(config)
+    }
+
+    /// Find `purets.json` in `start` or its closest ancestor
+    pub fn find(start: &Path) -> Result<Option<Self>> {
+        for dir in start.ancestors() {
+            let candidate = dir.join(CONFIG_FILE);
+            if candidate.is_file() {
+                return Self::load(&candidate).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Validate custom rules and compile their globs
+    pub fn custom_rules(&self) -> Result<Vec<Arc<CustomRule>>> {
+        let mut seen = HashSet::new();
+        let mut rules = Vec::new();
+
+        for rule in &self.custom_rules {
+            let id = rule.id.as_str();
+            let valid_id = !id.is_empty()
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+            if !valid_id {
+                bail!(
+                    "Custom rule id '{}' must be lowercase letters, digits and dashes",
+                    id
+                );
+            }
+            if rule_registry::is_known(id) {
+                bail!("Custom rule id '{}' conflicts with a built-in rule", id);
+            }
+            if !seen.insert(id) {
+                bail!("Custom rule id '{}' is declared more than once", id);
+            }
+            if rule.message.trim().is_empty() {
+                bail!("Custom rule '{}' must have a message", id);
+            }
+
+            let pattern = match &rule.pattern {
+                PatternConfig::Call { callee, awaited } => CustomPattern::Call {
+                    callee: compile_glob(id, callee)?,
+                    awaited: *awaited,
+                },
+                PatternConfig::New { callee } => CustomPattern::New {
+                    callee: compile_glob(id, callee)?,
+                },
+                PatternConfig::Import { source } => CustomPattern::Import {
+                    source: compile_glob(id, source)?,
+                },
+                PatternConfig::Node { kind } => {
+                    let Some(kind) = custom_rule::node_kind(kind) else {
+                        let names: Vec<&str> = custom_rule::NODE_KINDS
+                            .iter()
+                            .map(|(name, _)| *name)
+                            .collect();
+                        bail!(
+                            "Unknown node kind '{}' in custom rule '{}'; expected one of: {}",
+                            kind,
+                            id,
+                            names.join(", ")
+                        );
+                    };
+                    CustomPattern::Node { kind }
+                }
+            };
+
+            rules.push(Arc::new(CustomRule {
+                id: rule.id.clone(),
+                message: rule.message.clone(),
+                severity: rule.severity,
+                pattern,
+                root: self.root.clone(),
+                files: rule
+                    .files
+                    .iter()
+                    .map(|glob| compile_glob(id, glob))
+                    .collect::<Result<_>>()?,
+                exclude: rule
+                    .exclude
+                    .iter()
+                    .map(|glob| compile_glob(id, glob))
+                    .collect::<Result<_>>()?,
+            }));
+        }
+
+        Ok(rules)
+    }
+
+    /// Rules switched off with `disabledRules`, which must name built-in
+    /// rules or diagnostics, or custom rules
+    pub fn disabled_rules(&self) -> Result<HashSet<String>> {
+        for id in &self.disabled_rules {
+            if !rule_registry::is_known(id) && !self.custom_rules.iter().any(|rule| rule.id == *id)
+            {
+                bail!("Unknown rule '{}' in disabledRules", id);
+            }
+        }
+        Ok(self.disabled_rules.iter().cloned().collect())
+    }
+
+    /// Options for no-imperative-loops, whose directories must be plain
+    /// directory names or paths like `src/domain`
+    pub fn imperative_loops(&self) -> Result<ImperativeLoopsOptions> {
+        for dir in &self.imperative_loops.directories {
+            if dir.is_empty() || dir.starts_with('/') || dir.ends_with('/') {
+                bail!("Invalid directory '{}' in imperativeLoops", dir);
+            }
+        }
+        Ok(self.imperative_loops.clone())
+    }
+
+    /// Built-in capabilities plus the custom ones declared in the config,
+    /// limited by the capability policy
+    pub fn capabilities(&self) -> Result<Arc<Capabilities>> {
+        if self.capabilities.is_empty() && self.capability_policy.is_empty() {
+            return Ok(Capabilities::builtin());
+        }
+        let custom = capabilities::compile_custom(&self.capabilities)?;
+        let policy = self
+            .capability_policy
+            .iter()
+            .map(|entry| entry.compile(&self.root))
+            .collect::<Result<_>>()?;
+        Ok(Arc::new(
+            Capabilities::with_custom(custom)?.with_policy(policy)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> PuretsConfig {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_parse_custom_rules() {
+        let config = parse(
+            r#"{
+                "customRules": [
+                    {
+                        "id": "no-date-outside-io",
+                        "message": "Create dates in io/",
+                        "severity": "warning",
+                        "pattern": { "type": "new", "callee": "Date" },
+                        "exclude": ["src/io/**"]
+                    },
+                    {
+                        "id": "await-db-query",
+                        "message": "db.query must be awaited",
+                        "pattern": { "type": "call", "callee": "db.query", "awaited": false }
+                    }
+                ]
+            }"#,
+        );
+        let rules = config.custom_rules().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].severity, Severity::Warning);
+        assert_eq!(rules[1].severity, Severity::Error);
+        assert!(matches!(
+            rules[1].pattern,
+            CustomPattern::Call {
+                awaited: Some(false),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_applies_to() {
+        let config = parse(
+            r#"{
+                "customRules": [{
+                    "id": "no-date-outside-io",
+                    "message": "Create dates in io/",
+                    "pattern": { "type": "new", "callee": "Date" },
+                    "files": ["src/**"],
+                    "exclude": ["src/io/**"]
+                }]
+            }"#,
+        );
+        let rule = &config.custom_rules().unwrap()[0];
+        assert!(rule.applies_to(Path::new("src/pure/age.ts")));
+        assert!(rule.applies_to(Path::new("./src/pure/age.ts")));
+        assert!(!rule.applies_to(Path::new("src/io/now.ts")));
+        assert!(!rule.applies_to(Path::new("scripts/build.ts")));
+    }
+
+    #[test]
+    fn test_rejects_invalid_rules() {
+        let builtin = parse(
+            r#"{ "customRules": [
+                { "id": "no-throw", "message": "x", "pattern": { "type": "node", "kind": "ThrowStatement" } }
+            ] }"#,
+        );
+        assert!(builtin.custom_rules().is_err());
+
+        let duplicate = parse(
+            r#"{ "customRules": [
+                { "id": "no-legacy", "message": "x", "pattern": { "type": "import", "source": "legacy" } },
+                { "id": "no-legacy", "message": "y", "pattern": { "type": "import", "source": "old" } }
+            ] }"#,
+        );
+        assert!(duplicate.custom_rules().is_err());
+
+        let bad_glob = parse(
+            r#"{ "customRules": [
+                { "id": "no-legacy", "message": "x", "pattern": { "type": "import", "source": "[" } }
+            ] }"#,
+        );
+        assert!(bad_glob.custom_rules().is_err());
+    }
+
+    #[test]
+    fn test_rejects_unknown_node_kind() {
+        let config = parse(
+            r#"{ "customRules": [
+                { "id": "no-calls", "message": "x", "pattern": { "type": "node", "kind": "CallExpresion" } }
+            ] }"#,
+        );
+        let message = config.custom_rules().unwrap_err().to_string();
+        assert!(message.starts_with("Unknown node kind 'CallExpresion' in custom rule 'no-calls'"));
+        assert!(message.contains("CallExpression, NewExpression"));
+    }
+
+    #[test]
+    fn test_custom_capabilities() {
+        let config = parse(
+            r#"{ "capabilities": { "db": { "description": "Database", "modules": ["pg"] } } }"#,
+        );
+        let capabilities = config.capabilities().unwrap();
+        assert_eq!(capabilities.custom()[0].name, "db");
+
+        let builtin = parse(r#"{ "capabilities": { "fs": { "modules": ["fs-extra"] } } }"#);
+        assert!(builtin.capabilities().is_err());
+
+        let policy = parse(
+            r#"{ "capabilities": { "db": { "modules": ["pg"] } },
+                 "capabilityPolicy": [{ "files": ["src/io/db/**"], "allow": ["db", "env"] }] }"#,
+        );
+        assert_eq!(policy.capabilities().unwrap().policy().len(), 1);
+        let unknown =
+            parse(r#"{ "capabilityPolicy": [{ "files": ["src/**"], "allow": ["db"] }] }"#);
+        assert!(unknown.capabilities().is_err());
+    }
+
+    #[test]
+    fn test_rejects_unknown_pattern_type() {
+        let result: Result<PuretsConfig, _> = serde_json::from_str(
+            r#"{ "customRules": [
+                { "id": "x", "message": "x", "pattern": { "type": "regex", "source": "." } }
+            ] }"#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_disabled_rules() {
+        let config = parse(
+            r#"{ "disabledRules": ["tsconfig-recommended", "no-try-catch", "no-legacy"],
+                 "customRules": [{ "id": "no-legacy", "message": "x",
+                                   "pattern": { "type": "import", "source": "legacy" } }] }"#,
+        );
+        assert_eq!(config.disabled_rules().unwrap().len(), 3);
+        assert!(parse(r#"{ "disabledRules": ["no-consle"] }"#)
+            .disabled_rules()
+            .is_err());
+    }
+
+    #[test]
+    fn test_imperative_loops() {
+        assert_eq!(
+            parse("{}").imperative_loops().unwrap(),
+            ImperativeLoopsOptions::default()
+        );
+
+        let config = parse(r#"{ "imperativeLoops": { "directories": ["domain"] } }"#);
+        let options = config.imperative_loops().unwrap();
+        assert_eq!(options.directories, vec!["domain"]);
+        assert!(options.allow_readonly_for_of);
+
+        let config = parse(r#"{ "imperativeLoops": { "allowReadonlyForOf": false } }"#);
+        let options = config.imperative_loops().unwrap();
+        assert_eq!(options.directories, vec!["pure"]);
+        assert!(!options.allow_readonly_for_of);
+
+        assert!(
+            parse(r#"{ "imperativeLoops": { "directories": ["pure/"] } }"#)
+                .imperative_loops()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,716 @@
+// deno.json / deno.jsonc: validation and import map resolution
+
+use anyhow::{Context, Result};
+use colored::*;
+use glob::glob;
+use oxc::span::Span;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::directories::resolve_dots;
+use crate::jsonc;
+use crate::Linter;
+
+/// Config file names Deno looks for, in order
+pub const CONFIG_FILES: [&str; 2] = ["deno.json", "deno.jsonc"];
+
+/// Strictness options Deno enables by default, which must not be turned off
+const STRICT_OPTIONS: &[&str] = &[
+    "strict",
+    "noImplicitAny",
+    "noImplicitThis",
+    "alwaysStrict",
+    "strictNullChecks",
+    "strictFunctionTypes",
+    "strictBindCallApply",
+    "strictPropertyInitialization",
+    "useUnknownInCatchVariables",
+];
+
+/// deno lint rules that back guarantees of this linter
+const REQUIRED_LINT_RULES: &[&str] = &[
+    "no-explicit-any",
+    "no-unused-vars",
+    "no-var",
+    "prefer-const",
+];
+
+/// A deno.json or deno.jsonc file
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DenoConfig {
+    pub compiler_options: Option<Map<String, Value>>,
+    #[serde(default)]
+    pub imports: BTreeMap<String, String>,
+    #[serde(default)]
+    pub scopes: BTreeMap<String, BTreeMap<String, String>>,
+    pub lint: Option<Value>,
+    pub fmt: Option<Value>,
+    pub workspace: Option<Workspace>,
+}
+
+/// `workspace` lists member directories, either directly or as `members`
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Workspace {
+    Members(Vec<String>),
+    Object { members: Vec<String> },
+}
+
+impl DenoConfig {
+    /// The deno.json or deno.jsonc in a directory
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        CONFIG_FILES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        jsonc::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Workspace member patterns, as written
+    pub fn members(&self) -> &[String] {
+        match &self.workspace {
+            Some(Workspace::Members(members)) | Some(Workspace::Object { members }) => members,
+            None => &[],
+        }
+    }
+}
+
+/// Directories matched by workspace member patterns, which may be globs
+pub fn expand_members(root: &Path, members: &[String]) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = members
+        .iter()
+        .filter_map(|member| glob(&root.join(member).to_string_lossy()).ok())
+        .flat_map(|paths| paths.flatten())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+    dirs
+}
+
+/// The `imports` of a Deno config, mapping bare specifiers to targets, with
+/// the `scopes` that apply to the importing file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportMap {
+    imports: BTreeMap<String, String>,
+    /// Matching scopes, most specific first
+    scopes: Vec<BTreeMap<String, String>>,
+}
+
+impl ImportMap {
+    pub fn new(imports: BTreeMap<String, String>) -> Self {
+        Self {
+            imports,
+            scopes: Vec::new(),
+        }
+    }
+
+    /// The target of a bare specifier, looked up in the scopes before the
+    /// top-level imports: an exact entry, or else the longest entry ending
+    /// in `/` that prefixes it
+    pub fn resolve(&self, specifier: &str) -> Option<String> {
+        if specifier.starts_with("./") || specifier.starts_with("../") || specifier.starts_with('/')
+        {
+            return None;
+        }
+        self.scopes
+            .iter()
+            .chain(std::iter::once(&self.imports))
+            .find_map(|entries| resolve_in(entries, specifier))
+    }
+}
+
+fn resolve_in(entries: &BTreeMap<String, String>, specifier: &str) -> Option<String> {
+    if let Some(target) = entries.get(specifier) {
+        return Some(target.clone());
+    }
+    entries
+        .iter()
+        .filter(|(key, _)| key.ends_with('/') && specifier.starts_with(key.as_str()))
+        .max_by_key(|(key, _)| key.len())
+        .map(|(key, target)| format!("{}{}", target, &specifier[key.len()..]))
+}
+
+/// A `scopes` entry: imports for files under a directory
+#[derive(Debug)]
+struct Scope {
+    /// Directory relative to the workspace root
+    dir: PathBuf,
+    imports: BTreeMap<String, String>,
+}
+
+/// Scopes of a config in `config_dir`. Only local prefixes (`./`, `../`)
+/// can contain the files being linted
+fn local_scopes(root: &Path, config_dir: &Path, config: &DenoConfig) -> Vec<Scope> {
+    config
+        .scopes
+        .iter()
+        .filter(|(prefix, _)| prefix.starts_with("./") || prefix.starts_with("../"))
+        .map(|(prefix, imports)| Scope {
+            dir: resolve_dots(relative_to(root, &config_dir.join(prefix))),
+            imports: imports.clone(),
+        })
+        .collect()
+}
+
+/// Import maps of a Deno project: the root config's, and for each workspace
+/// member its own entries over the root's
+#[derive(Debug, Default)]
+pub struct DenoWorkspace {
+    root: PathBuf,
+    root_map: Arc<ImportMap>,
+    /// Member directories relative to `root`, with their import maps
+    members: Vec<(PathBuf, Arc<ImportMap>)>,
+    scopes: Vec<Scope>,
+}
+
+impl DenoWorkspace {
+    /// Read the deno.json(c) in `root` and those of its workspace members.
+    /// Returns `None` when `root` has no readable Deno config
+    pub fn detect(root: &Path) -> Option<Self> {
+        let config = DenoConfig::load(&DenoConfig::find(root)?).ok()?;
+        let mut scopes = local_scopes(root, root, &config);
+
+        let members = expand_members(root, config.members())
+            .into_iter()
+            .map(|dir| {
+                let mut imports = config.imports.clone();
+                if let Some(member) = DenoConfig::find(&dir).and_then(|p| DenoConfig::load(&p).ok())
+                {
+                    scopes.extend(local_scopes(root, &dir, &member));
+                    imports.extend(member.imports);
+                }
+                let relative = relative_to(root, &dir).to_path_buf();
+                (relative, Arc::new(ImportMap::new(imports)))
+            })
+            .collect();
+
+        Some(Self {
+            root: root.to_path_buf(),
+            root_map: Arc::new(ImportMap::new(config.imports)),
+            members,
+            scopes,
+        })
+    }
+
+    /// The import map that applies to a file, with the scopes covering it
+    pub fn import_map(&self, file: &Path) -> Arc<ImportMap> {
+        let relative = resolve_dots(relative_to(&self.root, file));
+        let map = self
+            .members
+            .iter()
+            .filter(|(dir, _)| relative.starts_with(dir))
+            .max_by_key(|(dir, _)| dir.components().count())
+            .map_or_else(|| self.root_map.clone(), |(_, map)| map.clone());
+
+        let mut scopes: Vec<&Scope> = self
+            .scopes
+            .iter()
+            .filter(|scope| relative.starts_with(&scope.dir))
+            .collect();
+        if scopes.is_empty() {
+            return map;
+        }
+        scopes.sort_by_key(|scope| std::cmp::Reverse(scope.dir.components().count()));
+        Arc::new(ImportMap {
+            imports: map.imports.clone(),
+            scopes: scopes.iter().map(|scope| scope.imports.clone()).collect(),
+        })
+    }
+}
+
+/// `path` relative to `root`, without a leading `./`. Globs under `.` yield
+/// paths without the prefix, so both forms are accepted
+fn relative_to<'p>(root: &Path, path: &'p Path) -> &'p Path {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative.strip_prefix(".").unwrap_or(relative)
+}
+
+/// `npm:name@version/sub` (or `npm:/name`) as the `name/sub` it imports;
+/// other specifiers unchanged
+pub fn npm_package(specifier: &str) -> Cow<'_, str> {
+    let Some(rest) = specifier.strip_prefix("npm:") else {
+        return Cow::Borrowed(specifier);
+    };
+    let rest = rest.trim_start_matches('/');
+    // A scoped name has one `/`; a version follows the name after `@`
+    let name_start = if rest.starts_with('@') {
+        rest.find('/').map_or(rest.len(), |slash| slash + 1)
+    } else {
+        0
+    };
+    let name_end = rest[name_start..]
+        .find(['@', '/'])
+        .map_or(rest.len(), |i| name_start + i);
+    let (name, after) = rest.split_at(name_end);
+    let subpath = match after.strip_prefix('@') {
+        Some(version) => version.find('/').map_or("", |slash| &version[slash..]),
+        None => after,
+    };
+    Cow::Owned(format!("{}{}", name, subpath))
+}
+
+/// Check the deno.json or deno.jsonc being linted: compiler options stay
+/// strict, lint and fmt cover the sources, and the import map has no URLs
+pub fn check_deno_config(linter: &mut Linter) -> Result<()> {
+    let config: DenoConfig = jsonc::from_str(&linter.source_text)?;
+    let mut checker = Checker { linter };
+
+    if let Some(options) = &config.compiler_options {
+        checker.check_compiler_options(options);
+    }
+    checker.check_lint(config.lint.as_ref());
+    checker.check_fmt(config.fmt.as_ref());
+
+    for (key, target) in &config.imports {
+        checker.check_import(&["imports", key], key, target);
+    }
+    for (scope, imports) in &config.scopes {
+        for (key, target) in imports {
+            checker.check_import(&["scopes", scope, key], key, target);
+        }
+    }
+
+    Ok(())
+}
+
+struct Checker<'a> {
+    linter: &'a mut Linter,
+}
+
+impl Checker<'_> {
+    fn key_span(&self, path: &[&str]) -> Span {
+        jsonc::key_span(&self.linter.source_text, path).unwrap_or_default()
+    }
+
+    fn report(&mut self, rule: &str, path: &[&str], message: String) {
+        let span = self.key_span(path);
+        self.linter.add_error(rule.to_string(), message, span);
+    }
+
+    fn check_compiler_options(&mut self, options: &Map<String, Value>) {
+        for name in STRICT_OPTIONS {
+            if options.get(*name) == Some(&Value::Bool(false)) {
+                self.report(
+                    "deno-strict",
+                    &["compilerOptions", name],
+                    format!("{} must not be disabled (Deno enables it by default)", name),
+                );
+            }
+        }
+        for name in ["allowUnreachableCode", "allowUnusedLabels"] {
+            if options.get(name) == Some(&Value::Bool(true)) {
+                self.report(
+                    "deno-strict",
+                    &["compilerOptions", name],
+                    format!("{} should not be true", name),
+                );
+            }
+        }
+    }
+
+    fn check_lint(&mut self, lint: Option<&Value>) {
+        let Some(lint) = lint else {
+            return;
+        };
+        self.check_excludes("lint", lint);
+
+        let rules = &lint["rules"];
+        if let Some(tags) = rules["tags"].as_array() {
+            if !tags.iter().any(|tag| tag == "recommended") {
+                self.report(
+                    "deno-lint-fmt",
+                    &["lint", "rules", "tags"],
+                    "lint.rules.tags must include \"recommended\"".to_string(),
+                );
+            }
+        }
+        for rule in rules["exclude"].as_array().into_iter().flatten() {
+            if let Some(rule) = rule.as_str().filter(|r| REQUIRED_LINT_RULES.contains(r)) {
+                self.report(
+                    "deno-lint-fmt",
+                    &["lint", "rules", "exclude"],
+                    format!("lint.rules.exclude must not turn off {}", rule),
+                );
+            }
+        }
+    }
+
+    fn check_fmt(&mut self, fmt: Option<&Value>) {
+        if let Some(fmt) = fmt {
+            self.check_excludes("fmt", fmt);
+        }
+    }
+
+    /// `exclude` (or the older `files.exclude`) must not skip `src/`
+    fn check_excludes(&mut self, section: &str, value: &Value) {
+        for path in [&["exclude"][..], &["files", "exclude"][..]] {
+            let excludes = path
+                .iter()
+                .fold(value, |value, key| &value[*key])
+                .as_array();
+            for exclude in excludes.into_iter().flatten().filter_map(Value::as_str) {
+                let normalized = exclude.trim_start_matches("./").trim_end_matches('/');
+                if normalized == "src" || normalized.starts_with("src/") {
+                    let mut key_path = vec![section];
+                    key_path.extend_from_slice(path);
+                    self.report(
+                        "deno-lint-fmt",
+                        &key_path,
+                        format!(
+                            "{}.{} must not exclude '{}'",
+                            section,
+                            path.join("."),
+                            exclude
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_import(&mut self, key_path: &[&str], key: &str, target: &str) {
+        if target.starts_with("http://") || target.starts_with("https://") {
+            self.report(
+                "deno-http-imports",
+                key_path,
+                format!(
+                    "Import map entry '{}' points at URL '{}'; use a jsr: or npm: specifier",
+                    key, target
+                ),
+            );
+        }
+    }
+}
+
+pub struct DenoConfigValidator {
+    path: String,
+    disabled_rules: HashSet<String>,
+    linters: Vec<Linter>,
+}
+
+impl DenoConfigValidator {
+    pub fn new(path: String) -> Self {
+        Self {
+            path,
+            disabled_rules: HashSet::new(),
+            linters: Vec::new(),
+        }
+    }
+
+    /// Skip diagnostics of the given rule ids
+    pub fn with_disabled_rules(mut self, rules: HashSet<String>) -> Self {
+        self.disabled_rules = rules;
+        self
+    }
+
+    /// Check the project's Deno config and those of its workspace members
+    pub fn validate(&mut self) -> Result<()> {
+        let path = Path::new(&self.path);
+        let config_path = if CONFIG_FILES.iter().any(|name| path.ends_with(name)) {
+            path.to_path_buf()
+        } else {
+            match DenoConfig::find(path) {
+                Some(config_path) => config_path,
+                // deno.json is optional, so no error if it doesn't exist
+                None => return Ok(()),
+            }
+        };
+
+        let root = config_path.parent().unwrap_or(Path::new("."));
+        let members = DenoConfig::load(&config_path)?.members().to_vec();
+        let member_configs = expand_members(root, &members)
+            .into_iter()
+            .filter_map(|dir| DenoConfig::find(&dir));
+
+        for config_path in std::iter::once(config_path.clone()).chain(member_configs) {
+            let content = fs::read_to_string(&config_path)
+                .with_context(|| format!("Failed to read {}", config_path.display()))?;
+            let mut linter = Linter::new(&config_path, &content, false)
+                .with_disabled_rules(self.disabled_rules.clone());
+            check_deno_config(&mut linter)
+                .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+            linter.errors.sort_by_key(|error| error.span.start);
+            self.linters.push(linter);
+        }
+
+        Ok(())
+    }
+
+    /// Whether a Deno config was found
+    pub fn found(&self) -> bool {
+        !self.linters.is_empty()
+    }
+
+    pub fn report(&self) {
+        for linter in &self.linters {
+            if linter.errors.is_empty() {
+                println!(
+                    "{} {} validation passed",
+                    "✓".green().bold(),
+                    linter.path.display()
+                );
+            } else {
+                linter.report_errors();
+            }
+        }
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.linters.iter().any(Linter::has_errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn check(source: &str) -> Vec<(String, String)> {
+        let mut linter = Linter::new(Path::new("deno.jsonc"), source, false);
+        check_deno_config(&mut linter).unwrap();
+        linter
+            .errors
+            .into_iter()
+            .map(|e| (e.rule, e.message))
+            .collect()
+    }
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_valid_config() {
+        let source = r#"{
+  // Deno is strict by default
+  "compilerOptions": { "strict": true },
+  "imports": { "@std/assert": "jsr:@std/assert@^1.0.0", "@/": "./src/" },
+  "lint": { "rules": { "tags": ["recommended"] } },
+  "fmt": { "exclude": ["dist/"] },
+}"#;
+        assert!(check(source).is_empty());
+    }
+
+    #[test]
+    fn test_weakened_compiler_options() {
+        let errors = check(
+            r#"{ "compilerOptions": { "strict": false, "strictNullChecks": true, "allowUnreachableCode": true } }"#,
+        );
+        assert_eq!(
+            errors,
+            vec![
+                (
+                    "deno-strict".to_string(),
+                    "strict must not be disabled (Deno enables it by default)".to_string()
+                ),
+                (
+                    "deno-strict".to_string(),
+                    "allowUnreachableCode should not be true".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_and_fmt_sections() {
+        let errors = check(
+            r#"{
+  "lint": {
+    "exclude": ["./src/"],
+    "rules": { "tags": ["jsr"], "exclude": ["no-explicit-any", "no-inferrable-types"] }
+  },
+  "fmt": { "files": { "exclude": ["src/generated"] } }
+}"#,
+        );
+        let messages: Vec<&str> = errors.iter().map(|(_, m)| m.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "lint.exclude must not exclude './src/'",
+                "lint.rules.tags must include \"recommended\"",
+                "lint.rules.exclude must not turn off no-explicit-any",
+                "fmt.files.exclude must not exclude 'src/generated'",
+            ]
+        );
+        assert!(errors.iter().all(|(rule, _)| rule == "deno-lint-fmt"));
+    }
+
+    #[test]
+    fn test_http_imports_in_import_map() {
+        let source = r#"{
+  "imports": { "std/": "https://deno.land/std@0.224.0/", "zod": "npm:zod@^3" },
+  "scopes": { "./vendor/": { "react": "http://esm.sh/react" } }
+}"#;
+        let errors = check(source);
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|(rule, _)| rule == "deno-http-imports"));
+        assert!(errors[0].1.contains("'std/'"));
+
+        let mut linter = Linter::new(Path::new("deno.json"), source, false);
+        check_deno_config(&mut linter).unwrap();
+        let span = linter.errors[0].span;
+        assert_eq!(&source[span.start as usize..span.end as usize], "\"std/\"");
+    }
+
+    #[test]
+    fn test_import_map_resolution() {
+        let map = ImportMap::new(BTreeMap::from([
+            ("std/".to_string(), "https://deno.land/std/".to_string()),
+            ("std/path/".to_string(), "jsr:@std/path/".to_string()),
+            ("_".to_string(), "npm:lodash@^4".to_string()),
+        ]));
+        assert_eq!(map.resolve("_").as_deref(), Some("npm:lodash@^4"));
+        assert_eq!(
+            map.resolve("std/fs/mod.ts").as_deref(),
+            Some("https://deno.land/std/fs/mod.ts")
+        );
+        assert_eq!(
+            map.resolve("std/path/join").as_deref(),
+            Some("jsr:@std/path/join")
+        );
+        assert_eq!(map.resolve("./_"), None);
+        assert_eq!(map.resolve("zod"), None);
+    }
+
+    #[test]
+    fn test_npm_package() {
+        assert_eq!(npm_package("npm:lodash@^4.17.21"), "lodash");
+        assert_eq!(npm_package("npm:/lodash@4/fp"), "lodash/fp");
+        assert_eq!(npm_package("npm:lodash/debounce"), "lodash/debounce");
+        assert_eq!(npm_package("npm:@types/node@22/fs"), "@types/node/fs");
+        assert_eq!(npm_package("npm:@scope/pkg"), "@scope/pkg");
+        assert_eq!(npm_package("jsr:@std/assert"), "jsr:@std/assert");
+    }
+
+    #[test]
+    fn test_workspace_import_maps() {
+        let dir = TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "deno.jsonc",
+            r#"{
+  "workspace": ["./packages/*"],
+  "imports": { "@std/assert": "jsr:@std/assert@^1", "_": "npm:underscore" }
+}"#,
+        );
+        write(
+            dir.path(),
+            "packages/api/deno.json",
+            r#"{ "imports": { "_": "npm:lodash@4" } }"#,
+        );
+        write(dir.path(), "packages/web/mod.ts", "");
+
+        let workspace = DenoWorkspace::detect(dir.path()).unwrap();
+        let resolve = |file: &str, specifier: &str| {
+            workspace
+                .import_map(&dir.path().join(file))
+                .resolve(specifier)
+        };
+        assert_eq!(
+            resolve("packages/api/src/get.ts", "_").as_deref(),
+            Some("npm:lodash@4")
+        );
+        assert_eq!(
+            resolve("packages/api/src/get.ts", "@std/assert").as_deref(),
+            Some("jsr:@std/assert@^1")
+        );
+        assert_eq!(
+            resolve("packages/web/mod.ts", "_").as_deref(),
+            Some("npm:underscore")
+        );
+        assert_eq!(resolve("main.ts", "_").as_deref(), Some("npm:underscore"));
+    }
+
+    #[test]
+    fn test_scopes() {
+        let dir = TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "deno.json",
+            r#"{
+  "workspace": ["./api"],
+  "imports": { "_": "npm:underscore", "std/": "jsr:@std/" },
+  "scopes": {
+    "./legacy/": { "_": "npm:lodash@4" },
+    "./legacy/old/": { "std/": "https://deno.land/std/" },
+    "https://deno.land/x/": { "_": "npm:ramda" }
+  }
+}"#,
+        );
+        write(
+            dir.path(),
+            "api/deno.json",
+            r#"{ "scopes": { "./vendor/": { "std/": "jsr:@vendor/std/" } } }"#,
+        );
+        fs::create_dir_all(dir.path().join("api/vendor")).unwrap();
+
+        let workspace = DenoWorkspace::detect(dir.path()).unwrap();
+        let resolve = |file: &str, specifier: &str| {
+            workspace
+                .import_map(&dir.path().join(file))
+                .resolve(specifier)
+        };
+        assert_eq!(
+            resolve("src/main.ts", "_").as_deref(),
+            Some("npm:underscore")
+        );
+        assert_eq!(resolve("legacy/a.ts", "_").as_deref(), Some("npm:lodash@4"));
+        assert_eq!(
+            resolve("legacy/a.ts", "std/fs").as_deref(),
+            Some("jsr:@std/fs")
+        );
+        // The most specific scope is tried first, then the enclosing ones
+        assert_eq!(
+            resolve("legacy/old/a.ts", "std/fs").as_deref(),
+            Some("https://deno.land/std/fs")
+        );
+        assert_eq!(
+            resolve("legacy/old/a.ts", "_").as_deref(),
+            Some("npm:lodash@4")
+        );
+        // Member scopes are relative to the member's config
+        assert_eq!(
+            resolve("api/vendor/x.ts", "std/fs").as_deref(),
+            Some("jsr:@vendor/std/fs")
+        );
+        assert_eq!(
+            resolve("api/src/x.ts", "std/fs").as_deref(),
+            Some("jsr:@std/fs")
+        );
+    }
+
+    #[test]
+    fn test_validator_checks_members() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "deno.json", r#"{ "workspace": ["./api"] }"#);
+        write(
+            dir.path(),
+            "api/deno.jsonc",
+            r#"{ "imports": { "oak": "https://deno.land/x/oak/mod.ts" } }"#,
+        );
+
+        let mut validator = DenoConfigValidator::new(dir.path().to_string_lossy().to_string());
+        validator.validate().unwrap();
+        assert!(validator.found());
+        assert!(validator.has_errors());
+
+        let mut validator = DenoConfigValidator::new(dir.path().to_string_lossy().to_string())
+            .with_disabled_rules(HashSet::from(["deno-http-imports".to_string()]));
+        validator.validate().unwrap();
+        assert!(!validator.has_errors());
+    }
+}
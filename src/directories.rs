@@ -0,0 +1,56 @@
+// Directory conventions and path helpers shared by the rules and config checks
+
+use std::path::{Component, Path, PathBuf};
+
+/// The path with `/` separators, as matched by the directory conventions
+pub fn normalize(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Whether a path lies under a directory named `dir` at any depth, e.g.
+/// `src/pure/add.ts` and `pure/add.ts` are both in `pure`
+pub fn in_directory(path: &Path, dir: &str) -> bool {
+    let normalized = normalize(path);
+    normalized.contains(&format!("/{}/", dir)) || normalized.starts_with(&format!("{}/", dir))
+}
+
+/// Resolve `.` and `..` without touching the file system
+pub fn resolve_dots(path: &Path) -> PathBuf {
+    let mut resolved = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if resolved.file_name().is_some() => {
+                resolved.pop();
+            }
+            component => resolved.push(component),
+        }
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_directory() {
+        assert!(in_directory(Path::new("src/pure/add.ts"), "pure"));
+        assert!(in_directory(Path::new("pure/add.ts"), "pure"));
+        assert!(in_directory(Path::new("src\\pure\\math\\add.ts"), "pure"));
+        assert!(!in_directory(Path::new("src/impure/add.ts"), "pure"));
+        assert!(!in_directory(Path::new("src/pure.ts"), "pure"));
+    }
+
+    #[test]
+    fn test_resolve_dots() {
+        assert_eq!(
+            resolve_dots(Path::new("src/io/./../pure/add.ts")),
+            Path::new("src/pure/add.ts")
+        );
+        assert_eq!(
+            resolve_dots(Path::new("../shared/types.ts")),
+            Path::new("../shared/types.ts")
+        );
+    }
+}
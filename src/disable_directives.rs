@@ -1,112 +1,330 @@
-use std::collections::HashSet;
+use oxc::ast::Comment;
+use oxc::span::Span;
+
+use crate::fix::Fix;
+use std::collections::{HashMap, HashSet};
+
+/// The `purets-disable-*` and `purets-enable` comment forms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectiveKind {
+    /// `purets-disable-file [rules]`: the whole file
+    File,
+    /// `purets-disable-next-line [rules]`: the line after the comment
+    NextLine,
+    /// `purets-disable-line [rules]`: the line of the comment
+    Line,
+    /// `purets-disable [rules]`: every line until a matching `purets-enable`
+    Disable,
+    /// `purets-enable [rules]`: ends a `purets-disable` range
+    Enable,
+}
+
+impl DirectiveKind {
+    /// Longest names first, so `purets-disable` does not shadow the others
+    const ALL: [DirectiveKind; 5] = [
+        DirectiveKind::NextLine,
+        DirectiveKind::File,
+        DirectiveKind::Line,
+        DirectiveKind::Disable,
+        DirectiveKind::Enable,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DirectiveKind::File => "purets-disable-file",
+            DirectiveKind::NextLine => "purets-disable-next-line",
+            DirectiveKind::Line => "purets-disable-line",
+            DirectiveKind::Disable => "purets-disable",
+            DirectiveKind::Enable => "purets-enable",
+        }
+    }
+}
+
+/// A directive comment, e.g. `// purets-disable-next-line no-throw -- legacy API`
+#[derive(Debug, Clone)]
+pub struct Directive {
+    pub kind: DirectiveKind,
+    /// Span of the whole comment
+    pub span: Span,
+    /// Line of the comment end (0-indexed)
+    pub line: usize,
+    /// Rules named by the directive; empty means all rules
+    pub rules: Vec<String>,
+    /// Justification after `--`
+    pub reason: Option<String>,
+}
+
+impl Directive {
+    /// Parse a comment's text (without `//` or `/* */`)
+    fn parse(text: &str) -> Option<(DirectiveKind, Vec<String>, Option<String>)> {
+        let text = text.trim();
+        let (kind, rest) = DirectiveKind::ALL.iter().find_map(|kind| {
+            let rest = text.strip_prefix(kind.name())?;
+            (rest.is_empty() || rest.starts_with(char::is_whitespace)).then_some((*kind, rest))
+        })?;
+
+        let (rules, reason) = match rest.split_once("--") {
+            Some((rules, reason)) => (rules, Some(reason.trim())),
+            None => (rest, None),
+        };
+        let rules = rules
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|rule| !rule.is_empty())
+            .map(String::from)
+            .collect();
+        let reason = reason.filter(|reason| !reason.is_empty()).map(String::from);
+
+        Some((kind, rules, reason))
+    }
+
+    fn covers_rule(&self, rule: &str) -> bool {
+        self.rules.is_empty() || self.rules.iter().any(|name| name == rule)
+    }
+
+    /// Delete the comment, and its line when nothing else is on it
+    pub fn removal(&self, source_text: &str) -> Fix {
+        let (start, end) = (self.span.start as usize, self.span.end as usize);
+        let line_start = source_text[..start]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        let line_end = source_text[end..]
+            .find('\n')
+            .map_or(source_text.len(), |index| end + index);
+        let before = &source_text[line_start..start];
+        let after = &source_text[end..line_end];
+
+        let (start, end) = if before.trim().is_empty() && after.trim().is_empty() {
+            (line_start, (line_end + 1).min(source_text.len()))
+        } else if !before.trim().is_empty() {
+            // Trailing comment: drop the whitespace separating it from the code
+            (start - (before.len() - before.trim_end().len()), end)
+        } else {
+            (start, end + (after.len() - after.trim_start().len()))
+        };
+        Fix::delete(Span::new(start as u32, end as u32))
+    }
+
+    /// Rewrite the comment without some of its rules, keeping the reason
+    pub fn without_rules(&self, source_text: &str, removed: &[&str]) -> Fix {
+        let rules: Vec<&str> = self
+            .rules
+            .iter()
+            .map(String::as_str)
+            .filter(|rule| !removed.contains(rule))
+            .collect();
+        let mut text = format!("{} {}", self.kind.name(), rules.join(", "));
+        if let Some(reason) = &self.reason {
+            text.push_str(" -- ");
+            text.push_str(reason);
+        }
+
+        let comment = &source_text[self.span.start as usize..self.span.end as usize];
+        let replacement = if comment.starts_with("/*") {
+            format!("/* {} */", text)
+        } else {
+            format!("// {}", text)
+        };
+        Fix::replace(self.span, replacement)
+    }
+}
+
+/// Lines `start..end` disabled by a `purets-disable` comment, for one rule or all
+#[derive(Debug, Clone)]
+struct DisabledRange {
+    /// Index of the opening directive
+    directive: usize,
+    start: usize,
+    end: usize,
+    rule: Option<String>,
+}
+
+/// A disable directive that suppressed nothing, or some of whose rules
+/// suppressed nothing
+#[derive(Debug)]
+pub struct UnusedDirective<'a> {
+    pub directive: &'a Directive,
+    /// Listed rules that suppressed nothing; all of them when `whole`
+    pub rules: Vec<&'a str>,
+    /// Whether the directive as a whole suppressed nothing
+    pub whole: bool,
+}
 
 #[derive(Debug, Clone, Default)]
 pub struct DisableDirectives {
-    /// Lines that should be ignored (0-indexed)
-    pub disabled_lines: HashSet<usize>,
-    /// Whether the entire file is disabled
-    pub file_disabled: bool,
-    /// Specific rules disabled for lines (line_number -> set of rule names)
-    pub line_rule_overrides: std::collections::HashMap<usize, HashSet<String>>,
+    /// Directives in source order
+    pub directives: Vec<Directive>,
+    ranges: Vec<DisabledRange>,
+    /// Rule names each directive suppressed an error for, by directive index
+    used: Vec<HashSet<String>>,
 }
 
 impl DisableDirectives {
-    pub fn from_source(source_text: &str) -> Self {
-        let mut directives = Self::default();
-
-        for (line_idx, line) in source_text.lines().enumerate() {
-            let trimmed = line.trim();
-
-            // Check for file-level disable
-            if trimmed.contains("// purets-disable-file")
-                || trimmed.contains("/* purets-disable-file")
-            {
-                directives.file_disabled = true;
-                continue;
-            }
+    /// Collect directives from the comments of a parsed program
+    pub fn from_comments(source_text: &str, comments: &[Comment]) -> Self {
+        let directives: Vec<Directive> = comments
+            .iter()
+            .filter_map(|comment| {
+                let content = comment.content_span();
+                let text = source_text.get(content.start as usize..content.end as usize)?;
+                let (kind, rules, reason) = Directive::parse(text)?;
+                let line = source_text[..comment.span.end as usize]
+                    .matches('\n')
+                    .count();
+                Some(Directive {
+                    kind,
+                    span: comment.span,
+                    line,
+                    rules,
+                    reason,
+                })
+            })
+            .collect();
+
+        let ranges = Self::disabled_ranges(&directives);
+        let used = vec![HashSet::new(); directives.len()];
+        Self {
+            directives,
+            ranges,
+            used,
+        }
+    }
 
-            // Check for next-line disable
-            if trimmed.contains("// purets-disable-next-line") {
-                // Disable the next line (current line + 1)
-                directives.disabled_lines.insert(line_idx + 1);
-
-                // Check for specific rules after the directive
-                if let Some(rules_start) = trimmed.find("// purets-disable-next-line") {
-                    let after_directive =
-                        &trimmed[rules_start + "// purets-disable-next-line".len()..];
-                    let rules = parse_rule_names(after_directive);
-                    if !rules.is_empty() {
-                        directives
-                            .line_rule_overrides
-                            .entry(line_idx + 1)
-                            .or_insert_with(HashSet::new)
-                            .extend(rules);
+    /// Pair `purets-disable` comments with the `purets-enable` comments
+    /// that end them. Ranges left open run to the end of the file
+    fn disabled_ranges(directives: &[Directive]) -> Vec<DisabledRange> {
+        let mut ranges = Vec::new();
+        let mut open: HashMap<Option<&str>, (usize, usize)> = HashMap::new();
+        let mut close = |rule: Option<&str>, (directive, start): (usize, usize), end: usize| {
+            ranges.push(DisabledRange {
+                directive,
+                start,
+                end,
+                rule: rule.map(String::from),
+            });
+        };
+
+        for (index, directive) in directives.iter().enumerate() {
+            match directive.kind {
+                DirectiveKind::Disable if directive.rules.is_empty() => {
+                    open.entry(None).or_insert((index, directive.line));
+                }
+                DirectiveKind::Disable => {
+                    for rule in &directive.rules {
+                        open.entry(Some(rule)).or_insert((index, directive.line));
                     }
                 }
-            }
-
-            // Also check for inline disable on the same line
-            if trimmed.contains("// purets-disable-line") {
-                directives.disabled_lines.insert(line_idx);
-
-                // Check for specific rules
-                if let Some(rules_start) = trimmed.find("// purets-disable-line") {
-                    let after_directive = &trimmed[rules_start + "// purets-disable-line".len()..];
-                    let rules = parse_rule_names(after_directive);
-                    if !rules.is_empty() {
-                        directives
-                            .line_rule_overrides
-                            .entry(line_idx)
-                            .or_insert_with(HashSet::new)
-                            .extend(rules);
+                DirectiveKind::Enable if directive.rules.is_empty() => {
+                    for (rule, start) in open.drain() {
+                        close(rule, start, directive.line);
                     }
                 }
+                DirectiveKind::Enable => {
+                    for rule in &directive.rules {
+                        if let Some(start) = open.remove(&Some(rule.as_str())) {
+                            close(Some(rule), start, directive.line);
+                        }
+                    }
+                }
+                _ => {}
             }
         }
+        for (rule, start) in open {
+            close(rule, start, usize::MAX);
+        }
 
-        directives
+        ranges
     }
 
-    /// Check if a specific line is disabled
-    pub fn is_line_disabled(&self, line: usize) -> bool {
-        self.file_disabled || self.disabled_lines.contains(&line)
+    /// The directive disabling a rule on a line (0-indexed), if any
+    pub fn find(&self, line: usize, rule: &str) -> Option<&Directive> {
+        self.position(line, rule)
+            .map(|index| &self.directives[index])
     }
 
-    /// Check if a specific rule is disabled for a line
-    pub fn is_rule_disabled(&self, line: usize, rule: &str) -> bool {
-        if self.file_disabled {
-            return true;
-        }
+    /// Like `find`, but records that the directive suppressed an error
+    pub fn suppress(&mut self, line: usize, rule: &str) -> Option<&Directive> {
+        let index = self.position(line, rule)?;
+        self.used[index].insert(rule.to_string());
+        Some(&self.directives[index])
+    }
 
-        if self.disabled_lines.contains(&line) {
-            // If no specific rules are specified, all are disabled
-            if let Some(rules) = self.line_rule_overrides.get(&line) {
-                return rules.is_empty() || rules.contains(rule);
-            }
-            return true;
-        }
+    fn position(&self, line: usize, rule: &str) -> Option<usize> {
+        let by_comment = self.directives.iter().position(|directive| {
+            let applies = match directive.kind {
+                DirectiveKind::File => true,
+                DirectiveKind::NextLine => directive.line + 1 == line,
+                DirectiveKind::Line => directive.line == line,
+                DirectiveKind::Disable | DirectiveKind::Enable => false,
+            };
+            applies && directive.covers_rule(rule)
+        });
+
+        by_comment.or_else(|| {
+            self.ranges
+                .iter()
+                .find(|range| {
+                    (range.start..range.end).contains(&line)
+                        && range.rule.as_deref().is_none_or(|name| name == rule)
+                })
+                .map(|range| range.directive)
+        })
+    }
 
-        // Check if this specific rule is disabled
-        if let Some(rules) = self.line_rule_overrides.get(&line) {
-            return rules.contains(rule);
-        }
+    /// Directives (other than `purets-enable`) that suppressed nothing, and
+    /// rules listed in directives that suppressed nothing for them
+    pub fn unused(&self) -> Vec<UnusedDirective<'_>> {
+        self.directives
+            .iter()
+            .zip(&self.used)
+            .filter(|(directive, _)| directive.kind != DirectiveKind::Enable)
+            .filter_map(|(directive, used)| {
+                let rules: Vec<&str> = directive
+                    .rules
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|rule| !used.contains(*rule))
+                    .collect();
+                let whole = used.is_empty();
+                (whole || !rules.is_empty()).then_some(UnusedDirective {
+                    directive,
+                    rules,
+                    whole,
+                })
+            })
+            .collect()
+    }
 
-        false
+    /// Check if a specific rule is disabled for a line
+    pub fn is_rule_disabled(&self, line: usize, rule: &str) -> bool {
+        self.find(line, rule).is_some()
     }
-}
 
-fn parse_rule_names(text: &str) -> Vec<String> {
-    // Parse rule names from text like: "no-console, no-eval"
-    text.split(',')
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty() && !s.starts_with("*/"))
-        .map(|s| s.to_string())
-        .collect()
+    /// Rule names referenced by directives (directive line -> rule name)
+    pub fn rule_references(&self) -> Vec<(usize, String)> {
+        self.directives
+            .iter()
+            .flat_map(|directive| {
+                directive
+                    .rules
+                    .iter()
+                    .map(|rule| (directive.line, rule.clone()))
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    fn directives(source: &str) -> DisableDirectives {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+        DisableDirectives::from_comments(source, &ret.program.comments)
+    }
 
     #[test]
     fn test_disable_next_line() {
@@ -115,10 +333,10 @@ mod tests {
 console.log("This line is disabled");
 console.log("This line is not disabled");
 "#;
-        let directives = DisableDirectives::from_source(source);
+        let directives = directives(source);
 
-        assert!(directives.is_line_disabled(2)); // Line after comment is disabled
-        assert!(!directives.is_line_disabled(3)); // Next line is not
+        assert!(directives.is_rule_disabled(2, "no-console")); // Line after comment is disabled
+        assert!(!directives.is_rule_disabled(3, "no-console")); // Next line is not
     }
 
     #[test]
@@ -128,11 +346,25 @@ console.log("This line is not disabled");
 console.log("Everything is disabled");
 console.log("This too");
 "#;
-        let directives = DisableDirectives::from_source(source);
+        let directives = directives(source);
 
-        assert!(directives.file_disabled);
-        assert!(directives.is_line_disabled(2));
-        assert!(directives.is_line_disabled(3));
+        assert!(directives.is_rule_disabled(2, "no-console"));
+        assert!(directives.is_rule_disabled(3, "any-rule"));
+    }
+
+    #[test]
+    fn test_disable_file_for_rules() {
+        let source = r#"
+/* purets-disable-file no-classes, no-enums */
+class A {}
+enum B {}
+console.log("still checked");
+"#;
+        let directives = directives(source);
+
+        assert!(directives.is_rule_disabled(2, "no-classes"));
+        assert!(directives.is_rule_disabled(3, "no-enums"));
+        assert!(!directives.is_rule_disabled(4, "no-console"));
     }
 
     #[test]
@@ -143,7 +375,7 @@ console.log("console disabled");
 // purets-disable-next-line no-eval
 eval("code");
 "#;
-        let directives = DisableDirectives::from_source(source);
+        let directives = directives(source);
 
         assert!(directives.is_rule_disabled(2, "no-console"));
         assert!(directives.is_rule_disabled(2, "allow-directives"));
@@ -151,6 +383,15 @@ eval("code");
 
         assert!(directives.is_rule_disabled(4, "no-eval"));
         assert!(!directives.is_rule_disabled(4, "no-console"));
+
+        assert_eq!(
+            directives.rule_references(),
+            vec![
+                (1, "no-console".to_string()),
+                (1, "allow-directives".to_string()),
+                (3, "no-eval".to_string()),
+            ]
+        );
     }
 
     #[test]
@@ -159,10 +400,11 @@ eval("code");
 console.log("test"); // purets-disable-line
 eval("code"); // purets-disable-line no-eval
 "#;
-        let directives = DisableDirectives::from_source(source);
+        let directives = directives(source);
 
-        assert!(directives.is_line_disabled(1)); // First console.log line
+        assert!(directives.is_rule_disabled(1, "no-console")); // First console.log line
         assert!(directives.is_rule_disabled(2, "no-eval")); // eval line with specific rule
+        assert!(!directives.is_rule_disabled(2, "no-console"));
     }
 
     #[test]
@@ -171,7 +413,7 @@ eval("code"); // purets-disable-line no-eval
 // purets-disable-next-line no-console, allow-directives, no-eval
 console.log(eval("test"));
 "#;
-        let directives = DisableDirectives::from_source(source);
+        let directives = directives(source);
 
         assert!(directives.is_rule_disabled(2, "no-console"));
         assert!(directives.is_rule_disabled(2, "allow-directives"));
@@ -187,11 +429,11 @@ console.log("line 1");
 console.log("line 3");
 console.log("line 4");
 "#;
-        let directives = DisableDirectives::from_source(source);
+        let directives = directives(source);
 
-        assert!(!directives.is_line_disabled(1)); // Line 1 not disabled
-        assert!(directives.is_line_disabled(3)); // Line 3 is disabled
-        assert!(!directives.is_line_disabled(4)); // Line 4 not disabled
+        assert!(!directives.is_rule_disabled(1, "no-console")); // Line 1 not disabled
+        assert!(directives.is_rule_disabled(3, "no-console")); // Line 3 is disabled
+        assert!(!directives.is_rule_disabled(4, "no-console")); // Line 4 not disabled
     }
 
     #[test]
@@ -202,12 +444,10 @@ console.log("line 4");
 console.log("test");
 document.body;
 "#;
-        let directives = DisableDirectives::from_source(source);
+        let directives = directives(source);
 
-        assert!(directives.file_disabled);
-        assert!(directives.is_line_disabled(3)); // All lines disabled
-        assert!(directives.is_line_disabled(4)); // All lines disabled
-        assert!(directives.is_rule_disabled(3, "any-rule")); // Any rule disabled
+        assert!(directives.is_rule_disabled(3, "no-console")); // All lines disabled
+        assert!(directives.is_rule_disabled(4, "any-rule")); // Any rule disabled
     }
 
     #[test]
@@ -216,10 +456,126 @@ document.body;
 const x = eval("code"); // purets-disable-line no-eval
 const y = eval("code"); // Not disabled
 "#;
-        let directives = DisableDirectives::from_source(source);
+        let directives = directives(source);
 
         assert!(directives.is_rule_disabled(1, "no-eval"));
         assert!(!directives.is_rule_disabled(1, "other-rule"));
         assert!(!directives.is_rule_disabled(2, "no-eval"));
     }
+
+    #[test]
+    fn test_block_ranges() {
+        let source = r#"
+/* purets-disable no-classes */
+class A {}
+/* purets-disable */
+class B {}
+/* purets-enable no-classes */
+eval("all rules still disabled");
+/* purets-enable */
+class C {}
+"#;
+        let directives = directives(source);
+
+        assert!(directives.is_rule_disabled(2, "no-classes"));
+        assert!(!directives.is_rule_disabled(2, "no-eval"));
+        assert!(directives.is_rule_disabled(4, "no-eval"));
+        assert!(directives.is_rule_disabled(6, "no-eval"));
+        assert!(!directives.is_rule_disabled(8, "no-classes"));
+        assert!(!directives.is_rule_disabled(8, "no-eval"));
+    }
+
+    #[test]
+    fn test_unclosed_range_runs_to_end() {
+        let source = "const a = 1;\n/* purets-disable no-let */\nlet b = 2;\n\nlet c = 3;\n";
+        let directives = directives(source);
+
+        assert!(!directives.is_rule_disabled(0, "no-let"));
+        assert!(directives.is_rule_disabled(2, "no-let"));
+        assert!(directives.is_rule_disabled(4, "no-let"));
+    }
+
+    #[test]
+    fn test_directives_in_strings_are_ignored() {
+        let source = r#"
+const text = "// purets-disable-file";
+const template = `
+// purets-disable-next-line
+`;
+class A {}
+"#;
+        let directives = directives(source);
+
+        assert!(directives.directives.is_empty());
+        assert!(!directives.is_rule_disabled(5, "no-classes"));
+    }
+
+    #[test]
+    fn test_unused() {
+        let source = r#"
+// purets-disable-next-line no-eval, no-classes
+eval("x");
+// purets-disable-next-line
+const a = 1;
+/* purets-disable no-delete */
+delete o.a;
+/* purets-enable no-delete */
+"#;
+        let mut directives = directives(source);
+        assert!(directives.suppress(2, "no-eval").is_some());
+        assert!(directives.suppress(6, "no-delete").is_some());
+        assert!(directives.suppress(5, "no-console").is_none());
+
+        let unused = directives.unused();
+        assert_eq!(unused.len(), 2);
+        assert_eq!(unused[0].directive.line, 1);
+        assert_eq!(unused[0].rules, vec!["no-classes"]);
+        assert!(!unused[0].whole);
+        assert_eq!(unused[1].directive.line, 3);
+        assert!(unused[1].whole);
+    }
+
+    #[test]
+    fn test_fixes() {
+        use crate::fix::apply_fixes;
+
+        let source = "const a = 1;\n  // purets-disable-next-line no-eval -- why\nf(); // purets-disable-line\n/* purets-disable-line */ g();\n";
+        let directives = directives(source);
+        let fix = |index: usize| directives.directives[index].removal(source);
+
+        assert_eq!(
+            apply_fixes(source, &[fix(0)]).0,
+            "const a = 1;\nf(); // purets-disable-line\n/* purets-disable-line */ g();\n"
+        );
+        assert_eq!(
+            apply_fixes(source, &[fix(1), fix(2)]).0,
+            "const a = 1;\n  // purets-disable-next-line no-eval -- why\nf();\ng();\n"
+        );
+
+        let source = "/* purets-disable-next-line no-eval, no-classes -- legacy */\n";
+        let directives = self::directives(source);
+        let fix = directives.directives[0].without_rules(source, &["no-classes"]);
+        assert_eq!(
+            apply_fixes(source, &[fix]).0,
+            "/* purets-disable-next-line no-eval -- legacy */\n"
+        );
+    }
+
+    #[test]
+    fn test_reasons() {
+        let source = r#"
+// purets-disable-next-line no-throw -- legacy API contract
+throw new Error("x");
+/* purets-disable-line no-eval -- */ eval("x");
+// purets-disabled is not a directive
+"#;
+        let directives = directives(source);
+
+        assert_eq!(directives.directives.len(), 2);
+        let directive = directives.find(2, "no-throw").unwrap();
+        assert_eq!(directive.kind, DirectiveKind::NextLine);
+        assert_eq!(directive.rules, vec!["no-throw".to_string()]);
+        assert_eq!(directive.reason.as_deref(), Some("legacy API contract"));
+        assert_eq!(directives.find(3, "no-eval").unwrap().reason, None);
+    }
 }
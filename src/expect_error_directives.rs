@@ -60,6 +60,17 @@ impl ExpectErrorDirectives {
         }
     }
 
+    /// Rule names referenced by directives (directive line -> rule name)
+    pub fn rule_references(&self) -> Vec<(usize, String)> {
+        let mut references: Vec<(usize, String)> = self
+            .expected_errors
+            .iter()
+            .flat_map(|(line, rules)| rules.iter().map(move |rule| (line - 1, rule.clone())))
+            .collect();
+        references.sort();
+        references
+    }
+
     /// Mark an expected error as triggered
     pub fn mark_as_triggered(&self, line: usize, rule: &str) {
         self.triggered_errors
@@ -165,5 +176,13 @@ const x: any = console.log("test");
         assert!(directives.is_error_expected(2, "no-console"));
         assert!(directives.is_error_expected(2, "no-any"));
         assert!(directives.is_error_expected(2, "no-explicit-any"));
+        assert_eq!(
+            directives.rule_references(),
+            vec![
+                (1, "no-any".to_string()),
+                (1, "no-console".to_string()),
+                (1, "no-explicit-any".to_string()),
+            ]
+        );
     }
 }
@@ -0,0 +1,72 @@
+// Automatic fixes attached to diagnostics
+
+use oxc::span::Span;
+
+/// A text edit that resolves a diagnostic: replace `span` with `replacement`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    pub span: Span,
+    pub replacement: String,
+}
+
+impl Fix {
+    pub fn delete(span: Span) -> Self {
+        Self {
+            span,
+            replacement: String::new(),
+        }
+    }
+
+    pub fn replace(span: Span, replacement: impl Into<String>) -> Self {
+        Self {
+            span,
+            replacement: replacement.into(),
+        }
+    }
+}
+
+/// Apply fixes to source text. Fixes overlapping an earlier one are skipped;
+/// returns the new text and the number of fixes applied
+pub fn apply_fixes<'a>(
+    source_text: &str,
+    fixes: impl IntoIterator<Item = &'a Fix>,
+) -> (String, usize) {
+    let mut fixes: Vec<&Fix> = fixes.into_iter().collect();
+    fixes.sort_by_key(|fix| (fix.span.start, fix.span.end));
+
+    let mut output = String::with_capacity(source_text.len());
+    let mut last = 0;
+    let mut applied = 0;
+    for fix in fixes {
+        let (start, end) = (fix.span.start as usize, fix.span.end as usize);
+        if start < last || end > source_text.len() {
+            continue;
+        }
+        output.push_str(&source_text[last..start]);
+        output.push_str(&fix.replacement);
+        last = end;
+        applied += 1;
+    }
+    output.push_str(&source_text[last..]);
+
+    (output, applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_fixes() {
+        let source = "let a = 1; // x\nlet b = 2;\n";
+        let fixes = [
+            Fix::replace(Span::new(16, 19), "const"),
+            Fix::delete(Span::new(10, 15)),
+            // Overlaps the deletion above
+            Fix::delete(Span::new(12, 16)),
+        ];
+        let (fixed, applied) = apply_fixes(source, &fixes);
+        assert_eq!(fixed, "let a = 1;\nconst b = 2;\n");
+        assert_eq!(applied, 2);
+    }
+}
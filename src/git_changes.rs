@@ -0,0 +1,306 @@
+// Changed files and line ranges from the local git binary
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Which changes to lint
+#[derive(Debug, Clone)]
+pub enum ChangeSource {
+    /// Changes in the working tree and index since a revision, plus untracked files
+    Since(String),
+    /// Changes staged in the index
+    Staged,
+}
+
+/// Changed files (canonical paths) with their added or modified lines
+#[derive(Debug, Default)]
+pub struct ChangedFiles {
+    files: HashMap<PathBuf, ChangedLines>,
+    /// Repository root when the changes are staged ones, whose contents are
+    /// read from the index
+    index_root: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ChangedLines {
+    /// New or untracked file: every line counts as changed
+    All,
+    /// 1-based line ranges from diff hunks
+    Ranges(Vec<RangeInclusive<usize>>),
+}
+
+impl ChangedFiles {
+    /// Ask git for the files changed according to `source` in the repository
+    /// containing `dir`
+    pub fn from_git(dir: &Path, source: &ChangeSource) -> Result<Self> {
+        let toplevel = PathBuf::from(git(dir, &["rev-parse", "--show-toplevel"])?.trim());
+
+        // Explicit prefixes, since `parse_diff` expects `+++ b/<path>` whatever
+        // `diff.noprefix` or `diff.mnemonicPrefix` say
+        let mut diff_args = vec![
+            "-c",
+            "core.quotePath=false",
+            "diff",
+            "-U0",
+            "--no-color",
+            "--no-ext-diff",
+            "--diff-filter=ACMR",
+            "--src-prefix=a/",
+            "--dst-prefix=b/",
+        ];
+        match source {
+            ChangeSource::Since(rev) => diff_args.push(rev),
+            ChangeSource::Staged => diff_args.push("--cached"),
+        }
+        let mut changed = Self::parse_diff(&git(&toplevel, &diff_args)?, &toplevel);
+
+        if let ChangeSource::Since(_) = source {
+            let untracked = git(
+                &toplevel,
+                &[
+                    "-c",
+                    "core.quotePath=false",
+                    "ls-files",
+                    "--others",
+                    "--exclude-standard",
+                ],
+            )?;
+            for file in untracked.lines().filter(|line| !line.is_empty()) {
+                changed.insert(&toplevel.join(file), ChangedLines::All);
+            }
+        }
+        if let ChangeSource::Staged = source {
+            changed.index_root = Some(toplevel.canonicalize().unwrap_or(toplevel));
+        }
+
+        Ok(changed)
+    }
+
+    /// Parse `git diff -U0` output with paths relative to `toplevel`
+    fn parse_diff(diff: &str, toplevel: &Path) -> Self {
+        let mut changed = Self::default();
+        let mut current: Option<(PathBuf, ChangedLines)> = None;
+
+        for line in diff.lines() {
+            if line.starts_with("diff --git ") {
+                if let Some((path, lines)) = current.take() {
+                    changed.insert(&path, lines);
+                }
+            } else if let Some(path) = line.strip_prefix("+++ b/") {
+                current = Some((toplevel.join(path), ChangedLines::Ranges(Vec::new())));
+            } else if let Some(hunk) = line.strip_prefix("@@ ") {
+                if let (Some((_, ChangedLines::Ranges(ranges))), Some(range)) =
+                    (current.as_mut(), parse_hunk_range(hunk))
+                {
+                    ranges.push(range);
+                }
+            }
+        }
+        if let Some((path, lines)) = current {
+            changed.insert(&path, lines);
+        }
+
+        changed
+    }
+
+    fn insert(&mut self, path: &Path, lines: ChangedLines) {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        self.files.insert(path, lines);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.lines(path).is_some()
+    }
+
+    /// Whether a 1-based line of a changed file was added or modified
+    pub fn contains_line(&self, path: &Path, line: usize) -> bool {
+        match self.lines(path) {
+            Some(ChangedLines::All) => true,
+            Some(ChangedLines::Ranges(ranges)) => ranges.iter().any(|range| range.contains(&line)),
+            None => false,
+        }
+    }
+
+    /// Contents of a changed file as the diff saw them: the staged blob for
+    /// staged changes, so that line numbers match, otherwise the working tree
+    pub fn read(&self, path: &Path) -> Result<String> {
+        let Some(root) = &self.index_root else {
+            return std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()));
+        };
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let relative = path
+            .strip_prefix(root)
+            .with_context(|| format!("{} is outside the repository", path.display()))?;
+        let spec = format!(":{}", relative.to_string_lossy().replace('\\', "/"));
+        git(root, &["show", &spec])
+    }
+
+    fn lines(&self, path: &Path) -> Option<&ChangedLines> {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        self.files.get(&path)
+    }
+}
+
+/// Added lines of a hunk header such as `-3,2 +4,3 @@ fn`
+fn parse_hunk_range(hunk: &str) -> Option<RangeInclusive<usize>> {
+    let added = hunk
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix('+'))?;
+    let (start, count) = match added.split_once(',') {
+        Some((start, count)) => (start.parse::<usize>().ok()?, count.parse::<usize>().ok()?),
+        None => (added.parse::<usize>().ok()?, 1),
+    };
+    // Pure deletions add no lines
+    (count > 0).then(|| start..=start + count - 1)
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_hunk_range() {
+        assert_eq!(parse_hunk_range("-3,2 +4,3 @@ fn"), Some(4..=6));
+        assert_eq!(parse_hunk_range("-3 +4 @@"), Some(4..=4));
+        assert_eq!(parse_hunk_range("-3,2 +2,0 @@"), None);
+    }
+
+    #[test]
+    fn test_parse_diff() {
+        let diff = "\
+diff --git a/src/pure/add.ts b/src/pure/add.ts
+index 1111111..2222222 100644
+--- a/src/pure/add.ts
++++ b/src/pure/add.ts
+@@ -2 +2 @@ export function add(a: number, b: number): number {
+-  return a + b;
++  return a - b;
+@@ -10,0 +11,2 @@
++const x = 1;
++const y = 2;
+diff --git a/src/pure/sub.ts b/src/pure/sub.ts
+new file mode 100644
+--- /dev/null
++++ b/src/pure/sub.ts
+@@ -0,0 +1,3 @@
++a
++b
++c
+";
+        let changed = ChangedFiles::parse_diff(diff, Path::new("/repo"));
+        let add = Path::new("/repo/src/pure/add.ts");
+        let sub = Path::new("/repo/src/pure/sub.ts");
+
+        assert!(changed.contains(add));
+        assert!(changed.contains_line(add, 2));
+        assert!(!changed.contains_line(add, 3));
+        assert!(changed.contains_line(add, 12));
+        assert!(changed.contains_line(sub, 3));
+        assert!(!changed.contains(Path::new("/repo/src/pure/mul.ts")));
+    }
+
+    fn run(dir: &Path, args: &[&str]) {
+        git(dir, args).unwrap();
+    }
+
+    #[test]
+    fn test_from_git() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        run(root, &["init", "-q"]);
+        run(root, &["config", "user.email", "test@example.com"]);
+        run(root, &["config", "user.name", "test"]);
+        fs::write(root.join("a.ts"), "line1\nline2\nline3\n").unwrap();
+        fs::write(root.join("b.ts"), "unchanged\n").unwrap();
+        run(root, &["add", "."]);
+        run(root, &["commit", "-q", "-m", "init"]);
+
+        fs::write(root.join("a.ts"), "line1\nchanged\nline3\n").unwrap();
+        fs::write(root.join("c.ts"), "new\n").unwrap();
+
+        let since = ChangedFiles::from_git(root, &ChangeSource::Since("HEAD".to_string())).unwrap();
+        assert!(since.contains_line(&root.join("a.ts"), 2));
+        assert!(!since.contains_line(&root.join("a.ts"), 1));
+        assert!(since.contains_line(&root.join("c.ts"), 1));
+        assert!(!since.contains(&root.join("b.ts")));
+
+        let staged = ChangedFiles::from_git(root, &ChangeSource::Staged).unwrap();
+        assert!(staged.is_empty());
+        run(root, &["add", "a.ts"]);
+        let staged = ChangedFiles::from_git(root, &ChangeSource::Staged).unwrap();
+        assert!(staged.contains(&root.join("a.ts")));
+        assert!(!staged.contains(&root.join("c.ts")));
+    }
+
+    #[test]
+    fn test_from_git_without_diff_prefixes() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        run(root, &["init", "-q"]);
+        run(root, &["config", "user.email", "test@example.com"]);
+        run(root, &["config", "user.name", "test"]);
+        run(root, &["config", "diff.noprefix", "true"]);
+        fs::create_dir(root.join("src")).unwrap();
+        fs::write(root.join("src/a.ts"), "line1\nline2\n").unwrap();
+        run(root, &["add", "."]);
+        run(root, &["commit", "-q", "-m", "init"]);
+
+        fs::write(root.join("src/a.ts"), "line1\nchanged\n").unwrap();
+        let since = ChangedFiles::from_git(root, &ChangeSource::Since("HEAD".to_string())).unwrap();
+        assert!(since.contains_line(&root.join("src/a.ts"), 2));
+        assert!(!since.contains_line(&root.join("src/a.ts"), 1));
+    }
+
+    #[test]
+    fn test_staged_files_are_read_from_the_index() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        run(root, &["init", "-q"]);
+        run(root, &["config", "user.email", "test@example.com"]);
+        run(root, &["config", "user.name", "test"]);
+        fs::write(root.join("a.ts"), "line1\n").unwrap();
+        run(root, &["add", "."]);
+        run(root, &["commit", "-q", "-m", "init"]);
+
+        fs::write(root.join("a.ts"), "line1\nstaged\n").unwrap();
+        run(root, &["add", "a.ts"]);
+        // Unstaged lines above the staged one shift the working tree copy
+        fs::write(root.join("a.ts"), "unstaged\nline1\nstaged\n").unwrap();
+
+        let staged = ChangedFiles::from_git(root, &ChangeSource::Staged).unwrap();
+        assert!(staged.contains_line(&root.join("a.ts"), 2));
+        assert_eq!(staged.read(&root.join("a.ts")).unwrap(), "line1\nstaged\n");
+
+        let since = ChangedFiles::from_git(root, &ChangeSource::Since("HEAD".to_string())).unwrap();
+        assert_eq!(
+            since.read(&root.join("a.ts")).unwrap(),
+            "unstaged\nline1\nstaged\n"
+        );
+    }
+}
@@ -198,6 +198,7 @@ export function add(a: number, b: number): number {
 import { Result, ok, err } from "neverthrow";
 
 /**
+ * @allow fs
  * Reads configuration from package.json file
  * @returns A Result containing the parsed JSON or an error
  */
@@ -0,0 +1,231 @@
+// JSON with comments and trailing commas, as accepted by tsconfig.json and
+// deno.jsonc
+
+use oxc::span::Span;
+use serde::de::DeserializeOwned;
+
+/// Blank out comments and trailing commas so the text parses as JSON
+///
+/// Every removed byte becomes a space (newlines are kept), so offsets into
+/// the result are offsets into `source`.
+pub fn strip(source: &str) -> String {
+    let bytes = source.as_bytes();
+    let mut out = bytes.to_vec();
+    let mut pending_comma: Option<usize> = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                pending_comma = None;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    out[i] = b' ';
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = source[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| i + 2 + end + 2);
+                for byte in &mut out[i..end] {
+                    if *byte != b'\n' {
+                        *byte = b' ';
+                    }
+                }
+                i = end;
+            }
+            byte if byte.is_ascii_whitespace() => i += 1,
+            byte => {
+                if let Some(comma) = pending_comma.take() {
+                    if byte == b'}' || byte == b']' {
+                        out[comma] = b' ';
+                    }
+                }
+                if byte == b',' {
+                    pending_comma = Some(i);
+                }
+                i += 1;
+            }
+        }
+    }
+
+    // Only whole characters were replaced, so the text is still UTF-8
+    String::from_utf8(out).unwrap_or_default()
+}
+
+/// Parse JSONC text into `T`
+pub fn from_str<T: DeserializeOwned>(source: &str) -> serde_json::Result<T> {
+    serde_json::from_str(&strip(source))
+}
+
+/// Span of the key at `path` (e.g. `["compilerOptions", "strict"]`),
+/// quotes included, for pointing diagnostics at a setting
+pub fn key_span(source: &str, path: &[&str]) -> Option<Span> {
+    let text = strip(source);
+    let mut scanner = Scanner {
+        bytes: text.as_bytes(),
+        pos: 0,
+    };
+    scanner.skip_whitespace();
+    scanner.find_key(path)
+}
+
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|byte| byte.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    /// Skip a string starting at the current quote, returning its span
+    fn string(&mut self) -> Span {
+        let start = self.pos;
+        self.pos += 1;
+        while let Some(byte) = self.peek() {
+            self.pos += if byte == b'\\' { 2 } else { 1 };
+            if byte == b'"' {
+                break;
+            }
+        }
+        Span::new(start as u32, self.pos.min(self.bytes.len()) as u32)
+    }
+
+    fn skip_value(&mut self) {
+        let mut depth = 0usize;
+        while let Some(byte) = self.peek() {
+            match byte {
+                b'"' => {
+                    self.string();
+                    if depth == 0 {
+                        return;
+                    }
+                    continue;
+                }
+                b'{' | b'[' => depth += 1,
+                b'}' | b']' if depth == 0 => return,
+                b'}' | b']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.pos += 1;
+                        return;
+                    }
+                }
+                b',' if depth == 0 => return,
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// Find `path` in the object starting at the current position
+    fn find_key(&mut self, path: &[&str]) -> Option<Span> {
+        let (first, rest) = path.split_first()?;
+        if self.peek() != Some(b'{') {
+            return None;
+        }
+        self.pos += 1;
+
+        loop {
+            self.skip_whitespace();
+            match self.peek()? {
+                b'"' => {}
+                b',' => {
+                    self.pos += 1;
+                    continue;
+                }
+                _ => return None,
+            }
+            let key = self.string();
+            let name = self
+                .bytes
+                .get(key.start as usize + 1..key.end as usize - 1)?;
+
+            self.skip_whitespace();
+            if self.peek()? != b':' {
+                return None;
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+
+            if name == first.as_bytes() {
+                return if rest.is_empty() {
+                    Some(key)
+                } else {
+                    self.find_key(rest)
+                };
+            }
+            self.skip_value();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_comments_and_trailing_commas() {
+        let source = r#"{
+  // line comment
+  "a": "http://x/*y*/", /* block
+  comment */ "b": [1, 2,],
+  "c": { "d": "\"//\"", },
+}"#;
+        let stripped = strip(source);
+        assert_eq!(stripped.len(), source.len());
+        assert_eq!(stripped.lines().count(), source.lines().count());
+
+        let value: Value = from_str(source).unwrap();
+        assert_eq!(
+            value,
+            json!({ "a": "http://x/*y*/", "b": [1, 2], "c": { "d": "\"//\"" } })
+        );
+    }
+
+    #[test]
+    fn test_offsets_are_kept() {
+        let source = "{ /* é */ \"key\": 1 }";
+        let stripped = strip(source);
+        assert_eq!(stripped.find("\"key\""), source.find("\"key\""));
+    }
+
+    #[test]
+    fn test_key_span() {
+        let source = r#"{
+  // "strict": false
+  "extends": ["./a", "./b"],
+  "compilerOptions": { "target": "ES2022", "paths": { "x": ["y"] }, "strict": true, },
+}"#;
+        let span = |path: &[&str]| {
+            key_span(source, path).map(|span| &source[span.start as usize..span.end as usize])
+        };
+        assert_eq!(span(&["extends"]), Some("\"extends\""));
+        assert_eq!(span(&["compilerOptions", "strict"]), Some("\"strict\""));
+        assert_eq!(
+            key_span(source, &["compilerOptions", "strict"])
+                .unwrap()
+                .start as usize,
+            source.rfind("\"strict\"").unwrap()
+        );
+        assert_eq!(span(&["compilerOptions", "paths", "x"]), Some("\"x\""));
+        assert_eq!(span(&["compilerOptions", "noEmit"]), None);
+        assert_eq!(span(&["extends", "x"]), None);
+    }
+}
@@ -1,31 +1,58 @@
 // Pure TypeScript Linter Library
 
-use crate::disable_directives::DisableDirectives;
+use crate::capabilities::Capabilities;
+use crate::config::CustomRule;
+use crate::deno_config::ImportMap;
+use crate::disable_directives::{DirectiveKind, DisableDirectives};
 use crate::expect_error_directives::ExpectErrorDirectives;
+use crate::fix::Fix;
+use crate::rule_registry::Severity;
+use crate::rules::ImperativeLoopsOptions;
 use oxc::span::Span;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
+pub mod baseline;
+pub mod cache;
+pub mod capabilities;
+pub mod capability_report;
 pub mod combined_visitor;
 pub mod comparer;
+pub mod config;
+pub mod deno_config;
+pub mod directories;
 pub mod disable_directives;
 pub mod expect_error_directives;
+pub mod fix;
+pub mod git_changes;
 pub mod gitignore_filter;
 pub mod init;
+pub mod jsonc;
+pub mod lsp;
 pub mod package_checker;
+mod package_exports;
 mod package_json_validator;
 pub mod presets;
+pub mod rule;
+pub mod rule_docs;
+pub mod rule_registry;
 pub mod rules;
+mod scope_analysis;
 pub mod test_runner;
 pub mod test_runner_detector;
 #[cfg(test)]
 pub mod test_utils;
 mod tsconfig_validator;
+pub mod watch;
 pub mod workspace_detector;
 
+pub use deno_config::DenoConfigValidator;
 pub use package_checker::check_package_json;
 pub use package_json_validator::PackageJsonValidator;
 pub use test_runner::TestRunner;
-pub use tsconfig_validator::TsConfigValidator;
+pub use tsconfig_validator::{EffectiveTsConfig, TsConfigValidator};
 
 pub struct Linter {
     pub path: PathBuf,
@@ -37,6 +64,22 @@ pub struct Linter {
     pub test_runner: Option<TestRunner>,
     pub is_entry_point: bool,
     pub is_main_entry: bool,
+    pub imperative_loops: ImperativeLoopsOptions,
+    pub disabled_rules: HashSet<String>,
+    pub custom_rules: Vec<Arc<CustomRule>>,
+    /// Capabilities that `@allow` directives can grant
+    pub capabilities: Arc<Capabilities>,
+    /// Whether disable directives must give a `-- reason`
+    pub require_disable_reason: bool,
+    /// Diagnostics silenced by disable directives
+    pub suppressed: Vec<SuppressedError>,
+    /// Capabilities the code uses, allowed or not, recorded by `allow-directives`
+    pub used_capabilities: BTreeSet<String>,
+    /// Deno import map that bare specifiers resolve through
+    pub import_map: Option<Arc<ImportMap>>,
+    /// Other files read by rules (imported `types/` and Result modules),
+    /// whose contents affect the diagnostics
+    pub dependencies: BTreeSet<PathBuf>,
 }
 
 #[derive(Debug)]
@@ -44,11 +87,29 @@ pub struct LintError {
     pub rule: String,
     pub message: String,
     pub span: Span,
+    pub severity: Severity,
+    /// Automatic fix, applied with `--fix`
+    pub fix: Option<Fix>,
+}
+
+/// A diagnostic silenced by a disable directive, with the directive's reason
+#[derive(Debug)]
+pub struct SuppressedError {
+    pub error: LintError,
+    pub reason: Option<String>,
+}
+
+/// `'a', 'b'` for diagnostics that list rule names
+fn quoted_list(rules: &[&str]) -> String {
+    rules
+        .iter()
+        .map(|rule| format!("'{}'", rule))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 impl Linter {
     pub fn new(path: &Path, source_text: &str, verbose: bool) -> Self {
-        let disable_directives = DisableDirectives::from_source(source_text);
         let expect_error_directives = ExpectErrorDirectives::from_source(source_text);
 
         Self {
@@ -56,11 +117,20 @@ impl Linter {
             source_text: source_text.to_string(),
             errors: Vec::new(),
             verbose,
-            disable_directives,
+            disable_directives: DisableDirectives::default(),
             expect_error_directives,
             test_runner: None,
             is_entry_point: false,
             is_main_entry: false,
+            imperative_loops: ImperativeLoopsOptions::default(),
+            disabled_rules: HashSet::new(),
+            custom_rules: Vec::new(),
+            capabilities: Capabilities::builtin(),
+            require_disable_reason: false,
+            suppressed: Vec::new(),
+            used_capabilities: BTreeSet::new(),
+            import_map: None,
+            dependencies: BTreeSet::new(),
         }
     }
 
@@ -79,36 +149,153 @@ impl Linter {
         self
     }
 
-    pub fn check_program(&mut self, program: &oxc::ast::ast::Program) {
-        // Use combined visitor for better performance
-        use crate::combined_visitor::check_program_combined;
-        check_program_combined(self, program);
+    pub fn with_imperative_loops(mut self, options: ImperativeLoopsOptions) -> Self {
+        self.imperative_loops = options;
+        self
+    }
+
+    /// Skip the given rule ids when checking programs
+    pub fn with_disabled_rules(mut self, rules: HashSet<String>) -> Self {
+        self.disabled_rules = rules;
+        self
+    }
+
+    /// Run user-defined rules from `purets.json` alongside the built-in ones
+    pub fn with_custom_rules(mut self, rules: Vec<Arc<CustomRule>>) -> Self {
+        self.custom_rules = rules;
+        self
+    }
+
+    /// Use the capabilities from `purets.json` for `@allow` directives
+    pub fn with_capabilities(mut self, capabilities: Arc<Capabilities>) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Report disable directives without a `-- reason` as `missing-disable-reason`
+    pub fn with_require_disable_reason(mut self, require: bool) -> Self {
+        self.require_disable_reason = require;
+        self
+    }
+
+    /// Resolve bare import specifiers through a Deno import map
+    pub fn with_import_map(mut self, import_map: Option<Arc<ImportMap>>) -> Self {
+        self.import_map = import_map;
+        self
+    }
+
+    /// The specifier an import resolves to through the import map, or the
+    /// specifier itself when no entry maps it
+    pub fn resolve_specifier<'s>(&self, specifier: &'s str) -> Cow<'s, str> {
+        match self
+            .import_map
+            .as_ref()
+            .and_then(|map| map.resolve(specifier))
+        {
+            Some(target) => Cow::Owned(target),
+            None => Cow::Borrowed(specifier),
+        }
+    }
+
+    pub fn is_rule_enabled(&self, rule: &str) -> bool {
+        !self.disabled_rules.contains(rule)
+    }
+
+    fn custom_rule(&self, rule: &str) -> Option<&CustomRule> {
+        self.custom_rules
+            .iter()
+            .find(|custom| custom.id == rule)
+            .map(Arc::as_ref)
+    }
+
+    /// Whether a name is a built-in diagnostic or a custom rule id
+    fn is_known_rule(&self, rule: &str) -> bool {
+        rule_registry::is_known(rule) || self.custom_rule(rule).is_some()
+    }
+
+    pub fn check_program<'a>(&mut self, program: &'a oxc::ast::ast::Program<'a>) {
+        self.load_directives(program);
+
+        // Run all enabled rules in a single traversal
+        let rules = rules::all_rules(self);
+        rule::run_rules(self, program, rules);
+    }
+
+    /// Check a `tsconfig.json`, `package.json` or Deno config file instead of a program.
+    /// Returns `Ok(false)` for other files
+    pub fn check_config_file(&mut self) -> anyhow::Result<bool> {
+        match self.path.file_name().and_then(|name| name.to_str()) {
+            Some("tsconfig.json") => tsconfig_validator::check_tsconfig(self)?,
+            Some("package.json") => package_json_validator::check_package(self)?,
+            Some("deno.json" | "deno.jsonc") => deno_config::check_deno_config(self)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Read disable directives from the program's comments and check them.
+    /// Called by `check_program`; rules run on their own need it first
+    pub fn load_directives(&mut self, program: &oxc::ast::ast::Program) {
+        self.disable_directives =
+            DisableDirectives::from_comments(&self.source_text, &program.comments);
+        self.check_directive_rule_names();
+        if self.require_disable_reason {
+            self.check_directive_reasons();
+        }
     }
 
     pub fn add_error(&mut self, rule: String, message: String, span: Span) {
+        debug_assert!(
+            self.is_known_rule(&rule),
+            "rule id '{}' is not registered in rule_registry or custom rules",
+            rule
+        );
+
         // Get the line number from the span
         let (line, _) = self.get_position(span.start);
 
+        // Directives may name the diagnostic itself or the rule that reports it
+        let names: Vec<&str> = match rule_registry::rule_for_diagnostic(&rule) {
+            Some(meta) if meta.id != rule => vec![rule.as_str(), meta.id],
+            _ => vec![rule.as_str()],
+        };
+        if !names.iter().all(|name| self.is_rule_enabled(name)) {
+            return;
+        }
+
+        let severity = match self.custom_rule(&rule) {
+            Some(custom) => custom.severity,
+            None => rule_registry::rule_for_diagnostic(&rule)
+                .map_or(Severity::Error, |meta| meta.severity),
+        };
+        let error = LintError {
+            rule: rule.clone(),
+            message,
+            span,
+            severity,
+            fix: None,
+        };
+
         // Check if this error should be disabled
-        if self.disable_directives.is_rule_disabled(line - 1, &rule) {
-            return; // Skip this error
+        for name in &names {
+            if let Some(directive) = self.disable_directives.suppress(line - 1, name) {
+                let reason = directive.reason.clone();
+                self.suppressed.push(SuppressedError { error, reason });
+                return; // Skip this error
+            }
         }
 
         // Check if this error is expected
-        if self
-            .expect_error_directives
-            .is_error_expected(line - 1, &rule)
-        {
+        if let Some(name) = names.iter().find(|name| {
+            self.expect_error_directives
+                .is_error_expected(line - 1, name)
+        }) {
             self.expect_error_directives
-                .mark_as_triggered(line - 1, &rule);
+                .mark_as_triggered(line - 1, name);
             return; // Skip this error as it was expected
         }
 
-        self.errors.push(LintError {
-            rule,
-            message,
-            span,
-        });
+        self.errors.push(error);
     }
 
     pub fn check_untriggered_expect_errors(&mut self) {
@@ -119,77 +306,239 @@ impl Linter {
             // Convert line number (0-based) to 1-based for display
             let display_line = line + 1;
 
-            // Calculate span for the expect-error line
-            let mut current_line = 0;
-            let mut char_pos = 0;
-
-            for ch in self.source_text.chars() {
-                if current_line == line {
-                    // Found the line, create a span for it
-                    let span_start = char_pos;
-                    // Find end of line
-                    let mut span_end = char_pos;
-                    for ch2 in self.source_text[char_pos..].chars() {
-                        if ch2 == '\n' {
-                            break;
-                        }
-                        span_end += ch2.len_utf8();
-                    }
-
-                    let span = Span::new(span_start as u32, span_end as u32);
-
-                    for rule in rules {
-                        self.errors.push(LintError {
-                            rule: "unused-expect-error".to_string(),
-                            message: format!(
-                                "Expected error '{}' on line {} was not triggered",
-                                rule, display_line
-                            ),
-                            span,
-                        });
-                    }
-                    break;
-                }
+            let Some(span) = self.line_span(line) else {
+                continue;
+            };
+
+            // Unknown rule names are already reported by check_directive_rule_names
+            let known: Vec<String> = rules
+                .into_iter()
+                .filter(|rule| self.is_known_rule(rule))
+                .collect();
+            for rule in known {
+                self.errors.push(LintError {
+                    rule: "unused-expect-error".to_string(),
+                    message: format!(
+                        "Expected error '{}' on line {} was not triggered",
+                        rule, display_line
+                    ),
+                    span,
+                    severity: Severity::Error,
+                    fix: None,
+                });
+            }
+        }
+    }
 
-                if ch == '\n' {
-                    current_line += 1;
+    /// Report disable directives (or rules listed in them) that suppressed
+    /// nothing. Call after all checks, like `check_untriggered_expect_errors`
+    pub fn check_unused_disable_directives(&mut self) {
+        let mut unused = Vec::new();
+        for directive in self.disable_directives.unused() {
+            // Unknown rule names are already reported by check_directive_rule_names
+            let rules: Vec<&str> = directive
+                .rules
+                .iter()
+                .copied()
+                .filter(|rule| self.is_known_rule(rule))
+                .collect();
+            let name = directive.directive.kind.name();
+
+            let (message, fix) = if directive.whole {
+                if rules.is_empty() && !directive.rules.is_empty() {
+                    continue;
+                }
+                let message = if rules.is_empty() {
+                    format!("Unused {} directive (no problems were reported)", name)
+                } else {
+                    format!(
+                        "Unused {} directive (no problems were reported from {})",
+                        name,
+                        quoted_list(&rules)
+                    )
+                };
+                (message, directive.directive.removal(&self.source_text))
+            } else {
+                if rules.is_empty() {
+                    continue;
                 }
-                char_pos += ch.len_utf8();
+                let message = format!(
+                    "Unused {} in {} directive (no problems were reported)",
+                    quoted_list(&rules),
+                    name
+                );
+                (
+                    message,
+                    directive
+                        .directive
+                        .without_rules(&self.source_text, &directive.rules),
+                )
+            };
+            unused.push((directive.directive.span, message, fix));
+        }
+
+        for (span, message, fix) in unused {
+            self.errors.push(LintError {
+                rule: "unused-disable-directive".to_string(),
+                message,
+                span,
+                severity: Severity::Error,
+                fix: Some(fix),
+            });
+        }
+    }
+
+    /// Report rule names in purets-disable-*/purets-expect-error comments that are not registered
+    fn check_directive_rule_names(&mut self) {
+        let references = self
+            .disable_directives
+            .rule_references()
+            .into_iter()
+            .map(|(line, rule)| (line, rule, "purets-disable"))
+            .chain(
+                self.expect_error_directives
+                    .rule_references()
+                    .into_iter()
+                    .map(|(line, rule)| (line, rule, "purets-expect-error")),
+            );
+
+        let mut unknown: Vec<(usize, String, &str)> = references
+            .filter(|(_, rule, _)| !self.is_known_rule(rule))
+            .collect();
+        unknown.sort();
+
+        for (line, rule, directive) in unknown {
+            if let Some(span) = self.line_span(line) {
+                self.errors.push(LintError {
+                    rule: "unknown-directive-rule".to_string(),
+                    message: format!("Unknown rule '{}' in {} directive", rule, directive),
+                    span,
+                    severity: Severity::Error,
+                    fix: None,
+                });
+            }
+        }
+    }
+
+    /// Report disable directives that do not say why (`-- reason`)
+    fn check_directive_reasons(&mut self) {
+        let missing: Vec<(Span, &str)> = self
+            .disable_directives
+            .directives
+            .iter()
+            .filter(|directive| directive.kind != DirectiveKind::Enable)
+            .filter(|directive| directive.reason.is_none())
+            .map(|directive| (directive.span, directive.kind.name()))
+            .collect();
+
+        for (span, name) in missing {
+            self.errors.push(LintError {
+                rule: "missing-disable-reason".to_string(),
+                message: format!("{} requires a reason: add '-- <why this is needed>'", name),
+                span,
+                severity: Severity::Error,
+                fix: None,
+            });
+        }
+    }
+
+    /// Span covering a 0-based source line, without its line terminator
+    fn line_span(&self, line: usize) -> Option<Span> {
+        let mut start = 0;
+        for (index, text) in self.source_text.split('\n').enumerate() {
+            if index == line {
+                let text = text.strip_suffix('\r').unwrap_or(text);
+                return Some(Span::new(start as u32, (start + text.len()) as u32));
             }
+            start += text.len() + 1;
         }
+        None
     }
 
+    /// Whether any reported diagnostic has error severity
     pub fn has_errors(&self) -> bool {
-        !self.errors.is_empty()
+        self.error_count() > 0
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.errors
+            .iter()
+            .filter(|error| error.severity == Severity::Error)
+            .count()
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.errors.len() - self.error_count()
     }
 
     pub fn get_errors(&self) -> &[LintError] {
         &self.errors
     }
 
+    /// Keep only errors whose 1-based start line is accepted by `keep`
+    pub fn retain_errors_on_lines(&mut self, keep: impl Fn(usize) -> bool) {
+        let errors = std::mem::take(&mut self.errors);
+        self.errors = errors
+            .into_iter()
+            .filter(|error| keep(self.get_position(error.span.start).0))
+            .collect();
+    }
+
     pub fn report_errors(&self) {
+        eprint!("{}", self.format_errors());
+    }
+
+    /// Diagnostics as printed by `report_errors`, one per line
+    pub fn format_errors(&self) -> String {
         use colored::*;
+        use std::fmt::Write;
 
+        let mut out = String::new();
         for error in &self.errors {
             let (line, column) = self.get_position(error.span.start);
 
             // VSCode-compatible format: file:line:column
-            eprintln!(
+            let _ = writeln!(
+                out,
                 "{} {} {}",
                 format!("{}:{}:{}", self.path.display(), line, column)
                     .cyan()
                     .bold(),
-                format!("[{}]", error.rule).yellow(),
+                match error.severity {
+                    Severity::Error => format!("[{}]", error.rule).yellow(),
+                    Severity::Warning => format!("[{}] warning:", error.rule).magenta(),
+                },
                 error.message.white()
             );
 
             if self.verbose {
                 if let Some(line_text) = self.get_line_text(line) {
-                    eprintln!("  {}", line_text.dimmed());
-                    eprintln!("  {}{}\n", " ".repeat(column - 1), "^".red().bold());
+                    let _ = writeln!(out, "  {}", line_text.dimmed());
+                    let _ = writeln!(out, "  {}{}\n", " ".repeat(column - 1), "^".red().bold());
                 }
             }
         }
+
+        // Suppressions are listed in verbose mode so their reasons can be reviewed
+        if self.verbose {
+            for suppressed in &self.suppressed {
+                let (line, column) = self.get_position(suppressed.error.span.start);
+                let _ = writeln!(
+                    out,
+                    "{}",
+                    format!(
+                        "{}:{}:{} [{}] suppressed: {}",
+                        self.path.display(),
+                        line,
+                        column,
+                        suppressed.error.rule,
+                        suppressed.reason.as_deref().unwrap_or("no reason given")
+                    )
+                    .dimmed()
+                );
+            }
+        }
+        out
     }
 
     fn get_position(&self, offset: u32) -> (usize, usize) {
@@ -0,0 +1,743 @@
+// Language server over stdio (`purets lsp`)
+
+use anyhow::Result;
+use lsp_server::{Connection, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as LspNotification, PublishDiagnostics,
+};
+use lsp_types::request::{CodeActionRequest, HoverRequest, Request as LspRequest};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, Diagnostic, DiagnosticSeverity, DiagnosticTag, Hover,
+    HoverContents, HoverParams, HoverProviderCapability, InitializeParams, MarkupContent,
+    MarkupKind, NumberOrString, Position, PublishDiagnosticsParams, Range, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextEdit, Uri, WorkspaceEdit,
+};
+use oxc::allocator::Allocator;
+use oxc::parser::Parser;
+use oxc::span::SourceType;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::capabilities::Capabilities;
+use crate::config::{CustomRule, PuretsConfig};
+use crate::deno_config::DenoWorkspace;
+use crate::gitignore_filter::GitignoreFilter;
+use crate::rule_registry::Severity;
+use crate::rules::ImperativeLoopsOptions;
+use crate::test_runner_detector::TestRunnerDetector;
+use crate::{rule_docs, Linter, TestRunner};
+
+const SOURCE: &str = "purets";
+const DISABLE_NEXT_LINE: &str = "// purets-disable-next-line";
+const UNUSED_DISABLE_DIRECTIVE: &str = "unused-disable-directive";
+
+/// Run the language server on stdin/stdout until the client shuts it down
+pub fn run() -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = serde_json::to_value(server_capabilities())?;
+    let params: InitializeParams = serde_json::from_value(connection.initialize(capabilities)?)?;
+    let options: InitializationOptions = params
+        .initialization_options
+        .clone()
+        .and_then(|options| serde_json::from_value(options).ok())
+        .unwrap_or_default();
+    let mut server = Server::new(&workspace_root(&params))
+        .with_report_unused_disable_directives(options.report_unused_disable_directives);
+
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    break;
+                }
+                if let Some(response) = server.handle_request(request) {
+                    connection.sender.send(Message::Response(response))?;
+                }
+            }
+            Message::Notification(notification) => {
+                for publish in server.handle_notification(notification) {
+                    connection
+                        .sender
+                        .send(Message::Notification(Notification::new(
+                            PublishDiagnostics::METHOD.to_string(),
+                            publish,
+                        )))?;
+                }
+            }
+            Message::Response(_) => {}
+        }
+    }
+
+    // The writer thread exits once every sender is dropped
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Settings clients pass in `initializationOptions`
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct InitializationOptions {
+    /// Report unused disable directives, like the CLI's
+    /// `--report-unused-disable-directives`
+    report_unused_disable_directives: bool,
+}
+
+fn server_capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        ..ServerCapabilities::default()
+    }
+}
+
+#[allow(deprecated)] // root_uri is still sent by clients without workspace folders
+fn workspace_root(params: &InitializeParams) -> PathBuf {
+    params
+        .workspace_folders
+        .as_ref()
+        .and_then(|folders| folders.first())
+        .map(|folder| &folder.uri)
+        .or(params.root_uri.as_ref())
+        .and_then(uri_to_path)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn uri_to_path(uri: &Uri) -> Option<PathBuf> {
+    if uri.scheme().map(|scheme| scheme.as_str()) != Some("file") {
+        return None;
+    }
+    let path = uri.path().as_estr().decode().into_string_lossy();
+    Some(PathBuf::from(path.as_ref()))
+}
+
+/// Whether a document is a JSON config file (package.json, deno.jsonc, ...)
+fn is_json(uri: &Uri) -> bool {
+    let path = uri.path().as_str();
+    path.ends_with(".json") || path.ends_with(".jsonc")
+}
+
+/// Converts byte offsets to LSP positions, which count UTF-16 code units
+struct LineIndex<'a> {
+    text: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    fn new(text: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        Self { text, line_starts }
+    }
+
+    fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.text.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let start = self.line_starts[line];
+        let character = self
+            .text
+            .get(start..offset)
+            .map_or(0, |prefix| prefix.encode_utf16().count());
+        Position::new(line as u32, character as u32)
+    }
+
+    fn range(&self, start: u32, end: u32) -> Range {
+        Range::new(self.position(start as usize), self.position(end as usize))
+    }
+
+    fn line(&self, line: u32) -> Option<&'a str> {
+        let start = *self.line_starts.get(line as usize)?;
+        let end = self
+            .line_starts
+            .get(line as usize + 1)
+            .map_or(self.text.len(), |next| next - 1);
+        let text = &self.text[start..end];
+        Some(text.strip_suffix('\r').unwrap_or(text))
+    }
+}
+
+struct Document {
+    text: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Editor state: open documents plus the project settings `main.rs` detects
+/// for the CLI (test runner, custom rules, ignored directories)
+pub struct Server {
+    root: PathBuf,
+    test_runner: Option<TestRunner>,
+    custom_rules: Vec<Arc<CustomRule>>,
+    capabilities: Arc<Capabilities>,
+    require_disable_reason: bool,
+    report_unused_disable_directives: bool,
+    disabled_rules: HashSet<String>,
+    imperative_loops: ImperativeLoopsOptions,
+    deno: Option<DenoWorkspace>,
+    filter: GitignoreFilter,
+    documents: HashMap<Uri, Document>,
+}
+
+impl Server {
+    pub fn new(root: &Path) -> Self {
+        let test_runner = TestRunnerDetector::new(root.to_path_buf())
+            .detect()
+            .to_runner();
+
+        // stderr is the client's log channel
+        let config = PuretsConfig::find(root)
+            .map(Option::unwrap_or_default)
+            .unwrap_or_else(|e| {
+                eprintln!("purets: ignoring invalid config: {:#}", e);
+                PuretsConfig::default()
+            });
+        let custom_rules = config.custom_rules().unwrap_or_else(|e| {
+            eprintln!("purets: ignoring invalid custom rules: {:#}", e);
+            Vec::new()
+        });
+        let capabilities = config.capabilities().unwrap_or_else(|e| {
+            eprintln!("purets: ignoring invalid capabilities: {:#}", e);
+            Capabilities::builtin()
+        });
+        let disabled_rules = config.disabled_rules().unwrap_or_else(|e| {
+            eprintln!("purets: ignoring invalid disabled rules: {:#}", e);
+            HashSet::new()
+        });
+        let imperative_loops = config.imperative_loops().unwrap_or_else(|e| {
+            eprintln!("purets: ignoring invalid imperativeLoops: {:#}", e);
+            ImperativeLoopsOptions::default()
+        });
+
+        let mut filter = GitignoreFilter::new();
+        filter.load_from_project(root).ok();
+
+        Self {
+            root: root.to_path_buf(),
+            test_runner,
+            custom_rules,
+            capabilities,
+            require_disable_reason: config.require_disable_reason,
+            report_unused_disable_directives: false,
+            disabled_rules,
+            imperative_loops,
+            deno: DenoWorkspace::detect(root),
+            filter,
+            documents: HashMap::new(),
+        }
+    }
+
+    /// Report unused disable directives (off by default, as in the CLI)
+    pub fn with_report_unused_disable_directives(mut self, report: bool) -> Self {
+        self.report_unused_disable_directives = report;
+        self
+    }
+
+    fn handle_request(&mut self, request: Request) -> Option<Response> {
+        let id = request.id.clone();
+        match request.method.as_str() {
+            CodeActionRequest::METHOD => {
+                let params: CodeActionParams = parse_params(request)?;
+                Some(ok(id, self.code_actions(&params)))
+            }
+            HoverRequest::METHOD => {
+                let params: HoverParams = parse_params(request)?;
+                let position = params.text_document_position_params;
+                Some(ok(
+                    id,
+                    self.hover(&position.text_document.uri, position.position),
+                ))
+            }
+            _ => Some(Response::new_err(
+                id,
+                lsp_server::ErrorCode::MethodNotFound as i32,
+                format!("Unhandled method {}", request.method),
+            )),
+        }
+    }
+
+    /// Apply a document notification, returning diagnostics to publish
+    fn handle_notification(&mut self, notification: Notification) -> Vec<PublishDiagnosticsParams> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let Ok(params) = notification
+                    .extract::<<DidOpenTextDocument as LspNotification>::Params>(
+                        DidOpenTextDocument::METHOD,
+                    )
+                else {
+                    return Vec::new();
+                };
+                let document = params.text_document;
+                vec![self.update(document.uri, document.text, Some(document.version))]
+            }
+            DidChangeTextDocument::METHOD => {
+                let Ok(mut params) = notification
+                    .extract::<<DidChangeTextDocument as LspNotification>::Params>(
+                        DidChangeTextDocument::METHOD,
+                    )
+                else {
+                    return Vec::new();
+                };
+                // Full sync: the last change holds the whole buffer
+                let Some(change) = params.content_changes.pop() else {
+                    return Vec::new();
+                };
+                let document = params.text_document;
+                vec![self.update(document.uri, change.text, Some(document.version))]
+            }
+            DidCloseTextDocument::METHOD => {
+                let Ok(params) = notification
+                    .extract::<<DidCloseTextDocument as LspNotification>::Params>(
+                        DidCloseTextDocument::METHOD,
+                    )
+                else {
+                    return Vec::new();
+                };
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                vec![PublishDiagnosticsParams::new(uri, Vec::new(), None)]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Store a new buffer and lint it
+    pub fn update(
+        &mut self,
+        uri: Uri,
+        text: String,
+        version: Option<i32>,
+    ) -> PublishDiagnosticsParams {
+        let diagnostics = match uri_to_path(&uri) {
+            Some(path) if !self.is_excluded(&path) => self.lint(&path, &text),
+            _ => Vec::new(),
+        };
+        self.documents.insert(
+            uri.clone(),
+            Document {
+                text,
+                diagnostics: diagnostics.clone(),
+            },
+        );
+        PublishDiagnosticsParams::new(uri, diagnostics, version)
+    }
+
+    /// Whether a path is in a directory the CLI would not scan (node_modules, dist, ...)
+    fn is_excluded(&self, path: &Path) -> bool {
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        self.filter.contains_excluded_dir(relative)
+    }
+
+    /// Lint an in-memory buffer the same way the CLI lints a file
+    fn lint(&self, path: &Path, text: &str) -> Vec<Diagnostic> {
+        let index = LineIndex::new(text);
+
+        // tsconfig.json, package.json and deno.json get the --validate-tsconfig checks
+        let mut linter =
+            Linter::new(path, text, false).with_disabled_rules(self.disabled_rules.clone());
+        match linter.check_config_file() {
+            Ok(true) => return to_diagnostics(&index, linter),
+            Ok(false) => {}
+            Err(e) => {
+                return vec![Diagnostic {
+                    range: index.range(0, 0),
+                    severity: Some(DiagnosticSeverity::ERROR),
+                    source: Some(SOURCE.to_string()),
+                    message: format!("{:#}", e),
+                    ..Diagnostic::default()
+                }]
+            }
+        }
+
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path(path).unwrap_or_default();
+        let ret = Parser::new(&allocator, text, source_type).parse();
+
+        if !ret.errors.is_empty() {
+            return ret
+                .errors
+                .iter()
+                .map(|error| {
+                    let span = error
+                        .labels
+                        .as_ref()
+                        .and_then(|labels| labels.first())
+                        .map_or((0, 0), |label| {
+                            (label.offset(), label.offset() + label.len())
+                        });
+                    Diagnostic {
+                        range: index.range(span.0 as u32, span.1 as u32),
+                        severity: Some(DiagnosticSeverity::ERROR),
+                        source: Some(SOURCE.to_string()),
+                        message: error.message.to_string(),
+                        ..Diagnostic::default()
+                    }
+                })
+                .collect();
+        }
+
+        let mut linter = Linter::new(path, text, false)
+            .with_test_runner(self.test_runner.clone())
+            .with_custom_rules(self.custom_rules.clone())
+            .with_capabilities(self.capabilities.clone())
+            .with_require_disable_reason(self.require_disable_reason)
+            .with_disabled_rules(self.disabled_rules.clone())
+            .with_imperative_loops(self.imperative_loops.clone())
+            .with_import_map(self.deno.as_ref().map(|deno| deno.import_map(path)));
+        linter.check_program(&ret.program);
+        linter.check_untriggered_expect_errors();
+        if self.report_unused_disable_directives {
+            linter.check_unused_disable_directives();
+        }
+
+        to_diagnostics(&index, linter)
+    }
+
+    /// Offer to disable each purets diagnostic in the requested range
+    pub fn code_actions(&self, params: &CodeActionParams) -> Vec<CodeActionOrCommand> {
+        let uri = &params.text_document.uri;
+        let Some(document) = self.documents.get(uri) else {
+            return Vec::new();
+        };
+        let index = LineIndex::new(&document.text);
+
+        let mut actions = Vec::new();
+        let mut seen = Vec::new();
+        for diagnostic in &params.context.diagnostics {
+            let Some(rule) = purets_rule(diagnostic) else {
+                continue;
+            };
+            let line = diagnostic.range.start.line;
+            if seen.contains(&(line, rule)) {
+                continue;
+            }
+            seen.push((line, rule));
+
+            // Fixes travel in the diagnostic's data
+            let fix = diagnostic
+                .data
+                .clone()
+                .and_then(|data| serde_json::from_value::<TextEdit>(data).ok());
+            if let Some(fix) = fix {
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Fix {}", rule),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(WorkspaceEdit::new(HashMap::from([(
+                        uri.clone(),
+                        vec![fix],
+                    )]))),
+                    is_preferred: Some(true),
+                    ..CodeAction::default()
+                }));
+            }
+            // Disable comments are not read from JSON config files
+            if rule == UNUSED_DISABLE_DIRECTIVE || is_json(uri) {
+                continue;
+            }
+
+            let edit = disable_next_line_edit(&index, line, rule);
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: format!("Disable {} for this line", rule),
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diagnostic.clone()]),
+                edit: Some(WorkspaceEdit::new(HashMap::from([(
+                    uri.clone(),
+                    vec![edit],
+                )]))),
+                ..CodeAction::default()
+            }));
+        }
+        actions
+    }
+
+    /// Explain the rules behind the diagnostics under the cursor
+    pub fn hover(&self, uri: &Uri, position: Position) -> Option<Hover> {
+        let document = self.documents.get(uri)?;
+        let sections: Vec<String> = document
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| {
+                diagnostic.range.start <= position && position <= diagnostic.range.end
+            })
+            .filter_map(purets_rule)
+            .filter_map(|rule| {
+                rule_docs::explain_markdown(rule).or_else(|| {
+                    self.custom_rules
+                        .iter()
+                        .find(|custom| custom.id == rule)
+                        .map(|custom| {
+                            format!("**{}** (custom rule)\n\n{}\n", custom.id, custom.message)
+                        })
+                })
+            })
+            .collect();
+
+        if sections.is_empty() {
+            return None;
+        }
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: sections.join("\n---\n\n"),
+            }),
+            range: None,
+        })
+    }
+}
+
+/// Convert a linter's diagnostics for the client
+fn to_diagnostics(index: &LineIndex, linter: Linter) -> Vec<Diagnostic> {
+    linter
+        .errors
+        .into_iter()
+        .map(|error| {
+            // Unused directives are shown faded out, as editors do for unused code
+            let unused = error.rule == UNUSED_DISABLE_DIRECTIVE;
+            let fix = error.fix.map(|fix| {
+                TextEdit::new(index.range(fix.span.start, fix.span.end), fix.replacement)
+            });
+            Diagnostic {
+                range: index.range(error.span.start, error.span.end),
+                severity: Some(match error.severity {
+                    _ if unused => DiagnosticSeverity::HINT,
+                    Severity::Error => DiagnosticSeverity::ERROR,
+                    Severity::Warning => DiagnosticSeverity::WARNING,
+                }),
+                code: Some(NumberOrString::String(error.rule)),
+                source: Some(SOURCE.to_string()),
+                message: error.message,
+                tags: unused.then(|| vec![DiagnosticTag::UNNECESSARY]),
+                data: fix.and_then(|fix| serde_json::to_value(fix).ok()),
+                ..Diagnostic::default()
+            }
+        })
+        .collect()
+}
+
+fn purets_rule(diagnostic: &Diagnostic) -> Option<&str> {
+    if diagnostic.source.as_deref() != Some(SOURCE) {
+        return None;
+    }
+    match &diagnostic.code {
+        Some(NumberOrString::String(rule)) => Some(rule),
+        _ => None,
+    }
+}
+
+/// Insert a disable comment above `line`, or extend one that is already there
+fn disable_next_line_edit(index: &LineIndex, line: u32, rule: &str) -> TextEdit {
+    let previous = line
+        .checked_sub(1)
+        .and_then(|previous| index.line(previous).map(|text| (previous, text)));
+    if let Some((previous, text)) = previous {
+        if text.trim_start().starts_with(DISABLE_NEXT_LINE) {
+            // Rules go before a `-- reason`
+            let rules_end = text.find(" --").unwrap_or(text.len());
+            let end = Position::new(previous, text[..rules_end].encode_utf16().count() as u32);
+            return TextEdit::new(Range::new(end, end), format!(", {}", rule));
+        }
+    }
+
+    let text = index.line(line).unwrap_or("");
+    let indent = &text[..text.len() - text.trim_start().len()];
+    let start = Position::new(line, 0);
+    TextEdit::new(
+        Range::new(start, start),
+        format!("{}{} {}\n", indent, DISABLE_NEXT_LINE, rule),
+    )
+}
+
+fn parse_params<P: serde::de::DeserializeOwned>(request: Request) -> Option<P> {
+    serde_json::from_value(request.params).ok()
+}
+
+fn ok<R: serde::Serialize>(id: RequestId, result: R) -> Response {
+    Response::new_ok(id, result)
+}
+
+/// Build a `file://` URI for a path
+pub fn path_to_uri(path: &Path) -> Option<Uri> {
+    Uri::from_str(&format!("file://{}", path.display())).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{CodeActionContext, TextDocumentIdentifier, WorkDoneProgressParams};
+
+    fn server_with(path: &str, text: &str) -> (Server, Uri, Vec<Diagnostic>) {
+        publish_with(Server::new(Path::new("/nonexistent")), path, text)
+    }
+
+    fn publish_with(mut server: Server, path: &str, text: &str) -> (Server, Uri, Vec<Diagnostic>) {
+        let uri = path_to_uri(Path::new(path)).unwrap();
+        let publish = server.update(uri.clone(), text.to_string(), Some(1));
+        (server, uri, publish.diagnostics)
+    }
+
+    fn codes(diagnostics: &[Diagnostic]) -> Vec<&str> {
+        diagnostics.iter().filter_map(purets_rule).collect()
+    }
+
+    #[test]
+    fn test_utf16_positions() {
+        let text = "const a = \"é😀\";\nlet b = 1;\r\nx";
+        let index = LineIndex::new(text);
+        let emoji_end = text.find("\";").unwrap();
+        // é is one UTF-16 unit, the emoji two
+        assert_eq!(index.position(emoji_end), Position::new(0, 14));
+        assert_eq!(
+            index.position(text.find("let").unwrap()),
+            Position::new(1, 0)
+        );
+        assert_eq!(index.position(text.len()), Position::new(2, 1));
+        assert_eq!(index.line(1), Some("let b = 1;"));
+    }
+
+    #[test]
+    fn test_diagnostics_for_buffer() {
+        let (_, _, diagnostics) = server_with(
+            "/project/src/pure/add.ts",
+            "// 日本語\nexport function add(a: number, b: number): number {\n  throw new Error(\"x\");\n}\n",
+        );
+        let throw = diagnostics
+            .iter()
+            .find(|diagnostic| purets_rule(diagnostic) == Some("no-throw"))
+            .expect("no-throw reported");
+        assert_eq!(throw.range.start, Position::new(2, 2));
+        assert_eq!(throw.severity, Some(DiagnosticSeverity::ERROR));
+    }
+
+    #[test]
+    fn test_config_file_diagnostics() {
+        let (_, _, diagnostics) = server_with(
+            "/project/package.json",
+            "{\n  \"type\": \"commonjs\",\n  \"dependencies\": { \"lodash\": \"^4.0.0\" }\n}\n",
+        );
+        assert_eq!(
+            codes(&diagnostics),
+            vec!["package-type-module", "forbidden-dependencies"]
+        );
+        assert_eq!(diagnostics[0].range.start, Position::new(1, 2));
+        assert_eq!(diagnostics[1].range.start, Position::new(2, 20));
+    }
+
+    #[test]
+    fn test_parse_errors_are_reported() {
+        let (_, _, diagnostics) = server_with("/project/src/main.ts", "const = ;");
+        assert!(!diagnostics.is_empty());
+        assert!(codes(&diagnostics).is_empty());
+    }
+
+    #[test]
+    fn test_code_action_inserts_disable_comment() {
+        let text = "export function run(): void {\n  delete obj.key;\n}\n";
+        let (server, uri, diagnostics) = server_with("/project/src/io/run.ts", text);
+        let delete = diagnostics
+            .iter()
+            .find(|diagnostic| purets_rule(diagnostic) == Some("no-delete"))
+            .expect("no-delete reported")
+            .clone();
+
+        let params = CodeActionParams {
+            text_document: TextDocumentIdentifier::new(uri.clone()),
+            range: delete.range,
+            context: CodeActionContext {
+                diagnostics: vec![delete],
+                ..CodeActionContext::default()
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: Default::default(),
+        };
+        let actions = server.code_actions(&params);
+        let Some(CodeActionOrCommand::CodeAction(action)) = actions.first() else {
+            panic!("expected a code action");
+        };
+        let edits = &action.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+        assert_eq!(edits[0].range.start, Position::new(1, 0));
+        assert_eq!(
+            edits[0].new_text,
+            "  // purets-disable-next-line no-delete\n"
+        );
+    }
+
+    #[test]
+    fn test_code_action_removes_unused_directive() {
+        let text = "// purets-disable-next-line no-delete\nexport const a = 1;\n";
+        let server =
+            Server::new(Path::new("/nonexistent")).with_report_unused_disable_directives(true);
+        let (server, uri, diagnostics) = publish_with(server, "/project/src/types/a.ts", text);
+        let unused = diagnostics
+            .iter()
+            .find(|diagnostic| purets_rule(diagnostic) == Some(UNUSED_DISABLE_DIRECTIVE))
+            .expect("unused directive reported")
+            .clone();
+        assert_eq!(unused.severity, Some(DiagnosticSeverity::HINT));
+
+        let params = CodeActionParams {
+            text_document: TextDocumentIdentifier::new(uri.clone()),
+            range: unused.range,
+            context: CodeActionContext {
+                diagnostics: vec![unused],
+                ..CodeActionContext::default()
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: Default::default(),
+        };
+        let actions = server.code_actions(&params);
+        assert_eq!(actions.len(), 1);
+        let CodeActionOrCommand::CodeAction(action) = &actions[0] else {
+            panic!("expected a code action");
+        };
+        let edits = &action.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+        assert_eq!(
+            edits[0].range,
+            Range::new(Position::new(0, 0), Position::new(1, 0))
+        );
+        assert_eq!(edits[0].new_text, "");
+    }
+
+    #[test]
+    fn test_unused_directives_follow_the_cli_default() {
+        let text = "// purets-disable-next-line no-delete\nexport const a = 1;\n";
+        let (_, _, diagnostics) = server_with("/project/src/types/a.ts", text);
+        assert!(!codes(&diagnostics).contains(&UNUSED_DISABLE_DIRECTIVE));
+
+        let options: InitializationOptions =
+            serde_json::from_value(serde_json::json!({ "reportUnusedDisableDirectives": true }))
+                .unwrap();
+        assert!(options.report_unused_disable_directives);
+    }
+
+    #[test]
+    fn test_disable_edit_extends_existing_comment() {
+        let text = "  // purets-disable-next-line no-let\n  let x = 1;\n";
+        let index = LineIndex::new(text);
+        let edit = disable_next_line_edit(&index, 1, "let-requires-type");
+        assert_eq!(edit.range.start, Position::new(0, 36));
+        assert_eq!(edit.new_text, ", let-requires-type");
+
+        let text = "// purets-disable-next-line no-let -- legacy\nlet x = 1;\n";
+        let edit = disable_next_line_edit(&LineIndex::new(text), 1, "let-requires-type");
+        assert_eq!(edit.range.start, Position::new(0, 34));
+    }
+
+    #[test]
+    fn test_hover_explains_rule() {
+        let text = "export function run(): void {\n  delete obj.key;\n}\n";
+        let (server, uri, _) = server_with("/project/src/io/run.ts", text);
+        let hover = server.hover(&uri, Position::new(1, 4)).expect("hover");
+        let HoverContents::Markup(markup) = hover.contents else {
+            panic!("expected markdown");
+        };
+        assert!(markup.value.contains("**no-delete**"));
+        assert!(server.hover(&uri, Position::new(3, 0)).is_none());
+    }
+}
@@ -1,27 +1,40 @@
-use anyhow::Result;
-use clap::Parser;
+use anyhow::{Context, Result};
+use clap::{ArgGroup, Parser};
 use colored::*;
 use glob::glob;
 use oxc::allocator::Allocator;
 use oxc::parser::{Parser as OxcParser, ParserReturn};
 use oxc::span::SourceType;
 use rayon::prelude::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use purets::{
+    baseline::{Baseline, BaselineEntry, Fingerprint},
+    cache::{CacheEntry, CachedDiagnostic, LintCache, DEFAULT_CACHE_DIR},
+    capabilities::Capabilities,
+    capability_report::{CapabilityReport, FileCapabilities},
     check_package_json, comparer,
+    config::{CustomRule, PuretsConfig},
+    deno_config::{DenoWorkspace, ImportMap},
+    fix::{apply_fixes, Fix},
+    git_changes::{ChangeSource, ChangedFiles},
     gitignore_filter::GitignoreFilter,
+    rules::ImperativeLoopsOptions,
     test_runner_detector::{TestRunner as DetectedTestRunner, TestRunnerDetector},
+    watch::Watcher,
     workspace_detector::WorkspaceConfig,
-    Linter, PackageJsonValidator, TestRunner, TsConfigValidator,
+    DenoConfigValidator, LintError, Linter, PackageJsonValidator, SuppressedError, TestRunner,
+    TsConfigValidator,
 };
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
+#[command(group(ArgGroup::new("changes").args(["changed_since", "staged"])))]
 struct Args {
     #[command(subcommand)]
     command: Option<Command>,
@@ -61,8 +74,91 @@ struct Args {
         value_delimiter = ','
     )]
     main: Vec<String>,
+
+    #[arg(long, help = "Read source from stdin instead of the file system")]
+    stdin: bool,
+
+    #[arg(
+        long = "stdin-filename",
+        requires = "stdin",
+        help = "Path the stdin source is linted as (e.g. src/pure/add.ts)"
+    )]
+    stdin_filename: Option<String>,
+
+    #[arg(long, help = "Re-lint changed files until interrupted")]
+    watch: bool,
+
+    #[arg(long, help = "Lint every file instead of reusing cached results")]
+    no_cache: bool,
+
+    #[arg(
+        long = "cache-location",
+        help = "Directory for cached results (default: node_modules/.cache/purets)"
+    )]
+    cache_location: Option<PathBuf>,
+
+    #[arg(
+        long = "changed-since",
+        value_name = "REV",
+        conflicts_with = "stdin",
+        help = "Only lint files changed since a git revision (including untracked files)"
+    )]
+    changed_since: Option<String>,
+
+    #[arg(long, conflicts_with = "stdin", help = "Only lint files staged in git")]
+    staged: bool,
+
+    #[arg(
+        long = "only-changed-lines",
+        requires = "changes",
+        conflicts_with = "stdin",
+        help = "Only report diagnostics on changed lines (with --changed-since or --staged)"
+    )]
+    only_changed_lines: bool,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with = "stdin",
+        help = "Suppress diagnostics recorded in a baseline file and report entries that no longer occur"
+    )]
+    baseline: Option<PathBuf>,
+
+    #[arg(
+        long = "write-baseline",
+        value_name = "FILE",
+        conflicts_with_all = ["baseline", "watch", "stdin", "changes"],
+        help = "Record the current diagnostics in a baseline file"
+    )]
+    write_baseline: Option<PathBuf>,
+
+    #[arg(
+        long = "report-unused-disable-directives",
+        help = "Report purets-disable-* comments that suppress nothing"
+    )]
+    report_unused_disable_directives: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["watch", "stdin", "staged"],
+        help = "Apply automatic fixes to files"
+    )]
+    fix: bool,
 }
 
+impl Args {
+    fn change_source(&self) -> Option<ChangeSource> {
+        if self.staged {
+            Some(ChangeSource::Staged)
+        } else {
+            self.changed_since.clone().map(ChangeSource::Since)
+        }
+    }
+}
+
+/// Path used for stdin source when `--stdin-filename` is not given
+const STDIN_FILENAME: &str = "stdin.ts";
+
 #[derive(clap::Subcommand, Debug)]
 enum Command {
     /// Initialize a new pure-ts project
@@ -77,6 +173,23 @@ enum Command {
         /// Path to the refactored file or directory
         after: String,
     },
+    /// List all rules with their category, presets and fixability
+    Rules,
+    /// Explain a rule with bad and good examples
+    Explain {
+        /// Rule id (e.g. no-throw)
+        rule: String,
+    },
+    /// Start a Language Server Protocol server over stdio
+    Lsp,
+    /// Report the capabilities each file and package declares with @allow and uses
+    Capabilities {
+        /// File or directory to scan (defaults to current directory)
+        path: Option<String>,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 fn main() -> Result<()> {
@@ -109,35 +222,298 @@ fn main() -> Result<()> {
                 }
                 return Ok(());
             }
+            Command::Rules => {
+                purets::rule_docs::print_rules();
+                return Ok(());
+            }
+            Command::Lsp => {
+                purets::lsp::run()?;
+                return Ok(());
+            }
+            Command::Capabilities { path, json } => {
+                let path = path.unwrap_or_else(|| ".".to_string());
+                let report = capability_report(&path).unwrap_or_else(|e| exit_with_error(&e));
+                if json {
+                    print!("{}", report.to_json()?);
+                } else {
+                    print!("{}", report.format_table());
+                }
+                return Ok(());
+            }
+            Command::Explain { rule } => match purets::rule_docs::explain(&rule) {
+                Some(text) => {
+                    print!("{}", text);
+                    return Ok(());
+                }
+                None => {
+                    eprintln!(
+                        "Error: Unknown rule '{}'. Run 'purets rules' to list all rules",
+                        rule
+                    );
+                    std::process::exit(1);
+                }
+            },
         }
     }
 
     // Regular linting mode - default to current directory
-    let path = args.path.unwrap_or_else(|| ".".to_string());
+    let path = args.path.clone().unwrap_or_else(|| ".".to_string());
 
     // Validate tsconfig.json if requested
     if args.validate_tsconfig {
-        let mut tsconfig_validator = TsConfigValidator::new(path.clone());
-        tsconfig_validator.validate()?;
-        tsconfig_validator.report();
+        let disabled_rules = load_config(project_dir(&path))
+            .and_then(|config| config.disabled_rules())
+            .unwrap_or_else(|e| exit_with_error(&e));
+
+        let mut deno_validator =
+            DenoConfigValidator::new(path.clone()).with_disabled_rules(disabled_rules.clone());
+        deno_validator.validate()?;
+
+        // Deno projects configure the compiler in deno.json instead
+        let mut tsconfig_validator =
+            TsConfigValidator::new(path.clone()).with_disabled_rules(disabled_rules.clone());
+        if !deno_validator.found() || project_dir(&path).join("tsconfig.json").exists() {
+            tsconfig_validator.validate()?;
+            tsconfig_validator.report();
+        }
 
-        let mut package_validator = PackageJsonValidator::new(path.clone());
+        let mut package_validator =
+            PackageJsonValidator::new(path.clone()).with_disabled_rules(disabled_rules);
         package_validator.validate()?;
         package_validator.report();
+        deno_validator.report();
 
-        if tsconfig_validator.has_errors() || package_validator.has_errors() {
+        if tsconfig_validator.has_errors()
+            || package_validator.has_errors()
+            || deno_validator.has_errors()
+        {
             std::process::exit(1);
         }
         return Ok(());
     }
 
-    // Detect workspace configuration
-    let project_path = if Path::new(&path).is_file() {
-        Path::new(&path).parent().unwrap_or(Path::new("."))
+    if args.stdin {
+        let project_path = project_dir(&path);
+        let options = load_config(project_path)
+            .and_then(|config| LintOptions::new(&args, &config, project_path))
+            .unwrap_or_else(|e| exit_with_error(&e));
+
+        let mut source_text = String::new();
+        std::io::stdin().read_to_string(&mut source_text)?;
+        let path = PathBuf::from(args.stdin_filename.as_deref().unwrap_or(STDIN_FILENAME));
+        let report = check_source(&path, &source_text, &options);
+        eprint!("{}", report.output);
+        std::process::exit(if report.errors > 0 { 1 } else { 0 });
+    }
+
+    // Configure thread pool if specified
+    if let Some(jobs) = args.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+            .unwrap_or_else(|e| eprintln!("Warning: Failed to set thread count: {}", e));
+    }
+
+    let project = load_project(&path, &args).unwrap_or_else(|e| exit_with_error(&e));
+
+    if args.watch {
+        return watch(&path, &args, project);
+    }
+
+    if project.files.is_empty() {
+        println!("No TypeScript files found");
+        return Ok(());
+    }
+
+    let start = Instant::now();
+    // Fixing rewrites files, so every file is linted from scratch
+    let mut cache = (!args.no_cache && !args.fix).then(|| {
+        LintCache::load(
+            &args
+                .cache_location
+                .clone()
+                .unwrap_or_else(|| project.workspace_config.root.join(DEFAULT_CACHE_DIR)),
+        )
+    });
+    let reports = lint_files(&project.files, &project.options, cache.as_ref());
+
+    if let Some(cache) = &mut cache {
+        for (file, report) in project.files.iter().zip(&reports) {
+            if let Some(entry) = &report.cache_entry {
+                cache.insert(file.clone(), entry.clone());
+            }
+        }
+        cache.retain_paths(&project.files);
+        if let Err(e) = cache.save() {
+            eprintln!("Warning: {:#}", e);
+        }
+    }
+
+    if let Some(baseline_path) = &args.write_baseline {
+        let mut baseline = Baseline::new(baseline_path);
+        for (file, report) in project.files.iter().zip(&reports) {
+            baseline.insert(file, &report.fingerprints);
+        }
+        if let Some((file, report)) = &project.package_report {
+            baseline.insert(file, &report.fingerprints);
+        }
+        baseline.save().unwrap_or_else(|e| exit_with_error(&e));
+        println!(
+            "{} Wrote {} diagnostic{} to {}",
+            "✓".green().bold(),
+            baseline.len(),
+            if baseline.len() != 1 { "s" } else { "" },
+            baseline_path.display()
+        );
+        return Ok(());
+    }
+
+    if !print_reports(&project, reports.iter(), start.elapsed()) {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn exit_with_error(e: &anyhow::Error) -> ! {
+    eprintln!("{}: {:#}", "Error".red().bold(), e);
+    std::process::exit(1);
+}
+
+/// Directory holding the project configuration for a file or directory argument
+fn project_dir(path: &str) -> &Path {
+    if Path::new(path).is_file() {
+        Path::new(path).parent().unwrap_or(Path::new("."))
     } else {
-        Path::new(&path)
-    };
+        Path::new(path)
+    }
+}
+
+/// Load purets.json, or the defaults when there is none
+fn load_config(project_path: &Path) -> Result<PuretsConfig> {
+    Ok(PuretsConfig::find(project_path)?.unwrap_or_default())
+}
+
+/// Settings shared by every file in a run
+struct LintOptions {
+    verbose: bool,
+    test_runner: Option<TestRunner>,
+    entry_paths: Vec<PathBuf>,
+    main_paths: Vec<PathBuf>,
+    custom_rules: Vec<Arc<CustomRule>>,
+    capabilities: Arc<Capabilities>,
+    require_disable_reason: bool,
+    report_unused_disable_directives: bool,
+    /// Rules switched off in purets.json
+    disabled_rules: HashSet<String>,
+    imperative_loops: ImperativeLoopsOptions,
+    /// Import maps from deno.json, for Deno projects
+    deno: Option<Arc<DenoWorkspace>>,
+    /// Whether to apply fixes (`--fix`)
+    fix: bool,
+    /// Changed lines to restrict diagnostics to (`--only-changed-lines`)
+    changed_lines: Option<Arc<ChangedFiles>>,
+    /// Staged changes (`--staged`), whose files are linted as staged
+    staged: Option<Arc<ChangedFiles>>,
+    /// Known diagnostics to suppress (`--baseline`)
+    baseline: Option<Arc<Baseline>>,
+    /// Whether to fingerprint diagnostics for `--write-baseline`
+    record_baseline: bool,
+}
+
+impl LintOptions {
+    fn new(args: &Args, config: &PuretsConfig, project_path: &Path) -> Result<Self> {
+        // Convert entry and main paths to absolute paths for comparison
+        let canonical = |paths: &[String]| -> Vec<PathBuf> {
+            paths
+                .iter()
+                .map(|p| {
+                    Path::new(p)
+                        .canonicalize()
+                        .unwrap_or_else(|_| PathBuf::from(p))
+                })
+                .collect()
+        };
+
+        Ok(Self {
+            verbose: args.verbose,
+            test_runner: resolve_test_runner(args.test.as_deref()),
+            entry_paths: canonical(&args.entry),
+            main_paths: canonical(&args.main),
+            custom_rules: config.custom_rules()?,
+            capabilities: config.capabilities()?,
+            require_disable_reason: config.require_disable_reason,
+            report_unused_disable_directives: args.report_unused_disable_directives,
+            disabled_rules: config.disabled_rules()?,
+            imperative_loops: config.imperative_loops()?,
+            deno: DenoWorkspace::detect(project_path).map(Arc::new),
+            fix: args.fix,
+            changed_lines: None,
+            staged: None,
+            baseline: None,
+            record_baseline: false,
+        })
+    }
+
+    // Compare canonical paths or check if the file path ends with the entry/main path
+    fn matches(&self, paths: &[PathBuf], file_path: &Path, label: &str) -> bool {
+        let matches = paths
+            .iter()
+            .any(|p| file_path == p || p.file_name().is_some_and(|name| file_path.ends_with(name)));
+        if self.verbose && matches {
+            eprintln!("DEBUG: Marking {} as {}", file_path.display(), label);
+        }
+        matches
+    }
+
+    fn is_entry(&self, file_path: &Path) -> bool {
+        self.matches(&self.entry_paths, file_path, "entry point")
+    }
+
+    fn is_main(&self, file_path: &Path) -> bool {
+        self.matches(&self.main_paths, file_path, "main entry")
+    }
+
+    /// The Deno import map for a file, if the project has one
+    fn import_map(&self, file_path: &Path) -> Option<Arc<ImportMap>> {
+        self.deno.as_ref().map(|deno| deno.import_map(file_path))
+    }
+
+    /// Everything besides the source text that changes a file's diagnostics,
+    /// used in its cache key
+    fn settings(&self, file_path: &Path) -> String {
+        format!(
+            "test-runner={:?};entry={};main={};custom-rules={:?};capabilities={:?};\
+             capability-policy={:?};require-disable-reason={};\
+             report-unused-disable-directives={};disabled-rules={:?};\
+             imperative-loops={:?};import-map={:?}",
+            self.test_runner,
+            self.is_entry(file_path),
+            self.is_main(file_path),
+            self.custom_rules,
+            self.capabilities.custom(),
+            self.capabilities.policy(),
+            self.require_disable_reason,
+            self.report_unused_disable_directives,
+            self.disabled_rules.iter().collect::<BTreeSet<_>>(),
+            self.imperative_loops,
+            self.import_map(file_path)
+        )
+    }
+}
+
+/// Everything a lint run derives from the project on disk
+struct Project {
+    workspace_config: WorkspaceConfig,
+    files: Vec<PathBuf>,
+    /// Dependency diagnostics for package.json, reported with the files
+    package_report: Option<(PathBuf, FileReport)>,
+    options: LintOptions,
+}
 
+fn load_project(path: &str, args: &Args) -> Result<Project> {
+    // Detect workspace configuration
+    let project_path = project_dir(path);
     let workspace_config = WorkspaceConfig::detect(project_path);
 
     if workspace_config.is_monorepo() {
@@ -149,47 +525,463 @@ fn main() -> Result<()> {
                     purets::workspace_detector::WorkspaceType::PnpmWorkspaces => "pnpm",
                     purets::workspace_detector::WorkspaceType::NpmWorkspaces => "npm",
                     purets::workspace_detector::WorkspaceType::YarnWorkspaces => "yarn",
+                    purets::workspace_detector::WorkspaceType::DenoWorkspaces => "deno",
                     _ => "unknown",
                 },
                 workspace_config.packages.len()
             )
             .cyan()
         );
+        println!(
+            "Scanning {} package directories...",
+            workspace_config.get_target_dirs().len()
+        );
     }
 
-    // Check package.json for forbidden dependencies
-    let package_errors = check_package_json(project_path);
-    if !package_errors.is_empty() {
-        eprintln!("{}", "Package.json dependency errors:".red().bold());
-        for error in &package_errors {
-            eprintln!("  {}", error.red());
+    let config = load_config(project_path)?;
+
+    let mut files = collect_project_files(path, &workspace_config)?;
+    let mut options = LintOptions::new(args, &config, project_path)?;
+    options.baseline = args
+        .baseline
+        .as_deref()
+        .map(Baseline::load)
+        .transpose()?
+        .map(Arc::new);
+    options.record_baseline = args.write_baseline.is_some();
+
+    if let Some(source) = args.change_source() {
+        let changed = Arc::new(ChangedFiles::from_git(project_path, &source)?);
+        files.retain(|file| changed.contains(file));
+        if args.only_changed_lines {
+            options.changed_lines = Some(changed.clone());
+        }
+        if let ChangeSource::Staged = source {
+            options.staged = Some(changed);
         }
     }
 
-    let files = if Path::new(&path).is_file() {
+    // Check package.json for forbidden dependencies
+    let package_path = project_path.join("package.json");
+    let package_report = fs::read_to_string(&package_path).ok().map(|text| {
+        (
+            package_path.clone(),
+            check_package(&package_path, &text, &options),
+        )
+    });
+
+    Ok(Project {
+        workspace_config,
+        files,
+        package_report,
+        options,
+    })
+}
+
+/// Lint package.json for forbidden dependencies, reported like a source file
+fn check_package(path: &Path, text: &str, options: &LintOptions) -> FileReport {
+    let mut linter = Linter::new(path, text, options.verbose)
+        .with_disabled_rules(options.disabled_rules.clone());
+    if let Err(e) = check_package_json(&mut linter) {
+        return FileReport {
+            errors: 1,
+            output: format!(
+                "{}: Parse error: {}\n",
+                format!("{}:{}:{}", path.display(), e.line(), e.column()).yellow(),
+                e
+            ),
+            ..FileReport::default()
+        };
+    }
+    file_report(linter, options, false, None)
+}
+
+/// Scan the project's files for `purets capabilities`
+fn capability_report(path: &str) -> Result<CapabilityReport> {
+    let project_path = project_dir(path);
+    let workspace_config = WorkspaceConfig::detect(project_path);
+    let capabilities = load_config(project_path)?.capabilities()?;
+    let files = collect_project_files(path, &workspace_config)?;
+
+    let files = files
+        .par_iter()
+        .map(|file| {
+            let source_text = fs::read_to_string(file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let relative = file.strip_prefix(&workspace_config.root).unwrap_or(file);
+            Ok(FileCapabilities {
+                file: relative.to_string_lossy().replace('\\', "/"),
+                package: workspace_config
+                    .get_package_name(file)
+                    .unwrap_or_else(|| ".".to_string()),
+                ..FileCapabilities::scan(file, &source_text, capabilities.clone())?
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(CapabilityReport::new(files))
+}
+
+fn collect_project_files(path: &str, workspace_config: &WorkspaceConfig) -> Result<Vec<PathBuf>> {
+    if Path::new(path).is_file() {
         // Single file specified
-        vec![Path::new(&path).to_path_buf()]
+        Ok(vec![Path::new(path).to_path_buf()])
     } else {
         // Use workspace-aware file collection
-        collect_files_with_workspace(&workspace_config)?
+        collect_files_with_workspace(workspace_config)
+    }
+}
+
+/// Result of linting one file: counts plus the diagnostics to print
+#[derive(Debug, Clone, Default)]
+struct FileReport {
+    errors: usize,
+    warnings: usize,
+    output: String,
+    /// Whether the result came from the cache
+    cached: bool,
+    /// Result to store in the cache (not set for unreadable or unparsable files)
+    cache_entry: Option<CacheEntry>,
+    /// Fingerprints of all diagnostics, with `--write-baseline`
+    fingerprints: Vec<Fingerprint>,
+    /// Number of diagnostics suppressed by the baseline
+    baselined: usize,
+    /// Baseline entries for this file that no longer occur
+    stale: Vec<BaselineEntry>,
+    /// Fixes of the reported diagnostics
+    fixes: Vec<Fix>,
+    /// Number of fixes applied with `--fix`
+    fixed: usize,
+}
+
+/// Lint files in parallel using rayon, keeping the input order
+fn lint_files(
+    files: &[PathBuf],
+    options: &LintOptions,
+    cache: Option<&LintCache>,
+) -> Vec<FileReport> {
+    files
+        .par_iter()
+        .map(|file_path| lint_file(file_path, options, cache))
+        .collect()
+}
+
+fn lint_file(path: &Path, options: &LintOptions, cache: Option<&LintCache>) -> FileReport {
+    let source_text = match read_source(path, options) {
+        Ok(source_text) => source_text,
+        Err(e) => {
+            return FileReport {
+                errors: 1,
+                output: format!("{}: {:#}\n", "Error".red().bold(), e),
+                ..FileReport::default()
+            }
+        }
     };
-    let file_count = files.len();
 
-    if file_count == 0 {
-        println!("No TypeScript files found");
-        return Ok(());
+    if options.fix {
+        return fix_file(path, &source_text, options);
     }
 
-    // Configure thread pool if specified
-    if let Some(jobs) = args.jobs {
-        rayon::ThreadPoolBuilder::new()
-            .num_threads(jobs)
-            .build_global()
-            .unwrap_or_else(|e| eprintln!("Warning: Failed to set thread count: {}", e));
+    let Some(cache) = cache else {
+        return check_source(path, &source_text, options);
+    };
+
+    let key = LintCache::key(&source_text, &options.settings(path));
+    if let Some(entry) = cache.get(path, &key) {
+        let mut linter = Linter::new(path, &source_text, options.verbose);
+        linter.errors = entry.diagnostics.iter().map(LintError::from).collect();
+        linter.suppressed = entry.suppressed.iter().map(SuppressedError::from).collect();
+        return file_report(linter, options, true, None);
+    }
+
+    let mut report = check_source(path, &source_text, options);
+    if let Some(entry) = &mut report.cache_entry {
+        entry.key = key;
+    }
+    report
+}
+
+/// A file's source: the staged contents with `--staged`, otherwise the file
+/// on disk
+fn read_source(path: &Path, options: &LintOptions) -> Result<String> {
+    match &options.staged {
+        Some(staged) => staged.read(path),
+        None => {
+            fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+        }
+    }
+}
+
+/// Apply the fixes of a file's diagnostics, write it back and lint the result
+fn fix_file(path: &Path, source_text: &str, options: &LintOptions) -> FileReport {
+    let report = check_source(path, source_text, options);
+    if report.fixes.is_empty() {
+        return report;
+    }
+
+    let (fixed_text, fixed) = apply_fixes(source_text, &report.fixes);
+    if let Err(e) = fs::write(path, &fixed_text) {
+        return FileReport {
+            errors: 1,
+            output: format!(
+                "{}: Failed to write {}: {}\n",
+                "Error".red().bold(),
+                path.display(),
+                e
+            ),
+            ..FileReport::default()
+        };
+    }
+
+    FileReport {
+        fixed,
+        ..check_source(path, &fixed_text, options)
+    }
+}
+
+/// Print package.json and file diagnostics and the summary line; returns
+/// false when errors were found
+fn print_reports<'a>(
+    project: &'a Project,
+    reports: impl Iterator<Item = &'a FileReport>,
+    duration: Duration,
+) -> bool {
+    let package_report = project.package_report.iter().map(|(_, report)| report);
+
+    let mut total_errors = 0;
+    let mut total_warnings = 0;
+    let mut cache_hits = 0;
+    let mut baselined = 0;
+    let mut stale = Vec::new();
+    let mut fixed = 0;
+    for report in package_report.chain(reports) {
+        eprint!("{}", report.output);
+        total_errors += report.errors;
+        total_warnings += report.warnings;
+        cache_hits += usize::from(report.cached);
+        baselined += report.baselined;
+        stale.extend(report.stale.iter().cloned());
+        fixed += report.fixed;
+    }
+
+    if fixed > 0 {
+        println!(
+            "{} {}",
+            "✓".green().bold(),
+            format!(
+                "Fixed {} problem{}",
+                fixed,
+                if fixed != 1 { "s" } else { "" }
+            )
+            .green()
+        );
+    }
+
+    if let Some(baseline) = &project.options.baseline {
+        stale.extend(baseline.missing_files());
+        stale.sort_by(|a, b| (&a.file, &a.rule).cmp(&(&b.file, &b.rule)));
+        print_baseline_summary(baseline, baselined, &stale);
+    }
+    let cached = |format_hits: fn(usize) -> String| {
+        if cache_hits > 0 {
+            format_hits(cache_hits)
+        } else {
+            String::new()
+        }
+    };
+
+    let file_count = project.files.len();
+
+    if total_warnings > 0 {
+        eprintln!(
+            "\n{} {}",
+            "!".magenta().bold(),
+            format!(
+                "{} warning{}",
+                total_warnings,
+                if total_warnings != 1 { "s" } else { "" }
+            )
+            .magenta()
+        );
+    }
+
+    if total_errors > 0 {
+        eprintln!(
+            "\n{} {} found in {:.2}s{}",
+            "✗".red().bold(),
+            format!(
+                "{} error{}",
+                total_errors,
+                if total_errors != 1 { "s" } else { "" }
+            )
+            .red()
+            .bold(),
+            duration.as_secs_f64(),
+            cached(|hits| format!(" ({} cached)", hits))
+        );
+        false
+    } else {
+        println!(
+            "{} {} in {} file{} ({:.2}s, {:.0} files/sec{})",
+            "✓".green().bold(),
+            "No errors found".green(),
+            file_count,
+            if file_count != 1 { "s" } else { "" },
+            duration.as_secs_f64(),
+            file_count as f64 / duration.as_secs_f64(),
+            cached(|hits| format!(", {} cached", hits))
+        );
+        true
     }
+}
+
+/// Report how many diagnostics the baseline suppressed and which of its
+/// entries can be pruned
+fn print_baseline_summary(baseline: &Baseline, baselined: usize, stale: &[BaselineEntry]) {
+    if baselined > 0 {
+        println!(
+            "{}",
+            format!(
+                "{} diagnostic{} suppressed by {}",
+                baselined,
+                if baselined != 1 { "s" } else { "" },
+                baseline.path().display()
+            )
+            .dimmed()
+        );
+    }
+
+    if stale.is_empty() {
+        return;
+    }
+    let count: usize = stale.iter().map(|entry| entry.count).sum();
+    eprintln!(
+        "\n{} {}",
+        "!".magenta().bold(),
+        format!(
+            "{} baseline entr{} no longer occur{}; prune with --write-baseline {}",
+            count,
+            if count != 1 { "ies" } else { "y" },
+            if count != 1 { "" } else { "s" },
+            baseline.path().display()
+        )
+        .magenta()
+    );
+    for entry in stale {
+        eprintln!(
+            "  {} [{}]{}",
+            entry.file,
+            entry.rule,
+            if entry.count > 1 {
+                format!(" x{}", entry.count)
+            } else {
+                String::new()
+            }
+        );
+    }
+}
+
+impl FileReport {
+    /// Whether the file's diagnostics depend on one of the given files
+    /// (canonical paths), such as an imported `types/` union
+    fn depends_on(&self, files: &HashSet<PathBuf>) -> bool {
+        self.cache_entry.as_ref().is_some_and(|entry| {
+            entry
+                .dependencies
+                .keys()
+                .any(|dependency| files.contains(&canonical_path(dependency)))
+        })
+    }
+}
 
-    // Parse test runner if specified, or auto-detect
-    let test_runner = if let Some(test_str) = &args.test {
+/// Keep linting as files change: changed files and the files that depend on
+/// them are re-linted, and a config change reloads the project and re-lints
+/// everything
+fn watch(path: &str, args: &Args, mut project: Project) -> Result<()> {
+    let watcher = Watcher::new(&project.workspace_config.root)?;
+
+    let start = Instant::now();
+    let mut reports = index_reports(&project, lint_files(&project.files, &project.options, None));
+    redraw(&project, &reports, start.elapsed());
+
+    loop {
+        let changes = watcher.next_changes()?;
+        let start = Instant::now();
+
+        if changes.config_changed {
+            match load_project(path, args) {
+                Ok(reloaded) => project = reloaded,
+                Err(e) => {
+                    clear_screen();
+                    eprintln!("{}: {:#}", "Error".red().bold(), e);
+                    continue;
+                }
+            }
+            reports = index_reports(&project, lint_files(&project.files, &project.options, None));
+        } else {
+            // Pick up created and removed files
+            project.files = collect_project_files(path, &project.workspace_config)?;
+            let known: HashMap<PathBuf, PathBuf> = project
+                .files
+                .iter()
+                .map(|file| (canonical_path(file), file.clone()))
+                .collect();
+            reports.retain(|file, _| known.contains_key(&canonical_path(file)) && file.exists());
+
+            let sources: HashSet<PathBuf> =
+                changes.sources.iter().map(|s| canonical_path(s)).collect();
+            let changed: Vec<PathBuf> = project
+                .files
+                .iter()
+                .filter(|file| {
+                    sources.contains(&canonical_path(file))
+                        || reports
+                            .get(*file)
+                            .is_some_and(|report| report.depends_on(&sources))
+                })
+                .cloned()
+                .collect();
+            for (file, report) in changed
+                .iter()
+                .zip(lint_files(&changed, &project.options, None))
+            {
+                reports.insert(file.clone(), report);
+            }
+        }
+
+        redraw(&project, &reports, start.elapsed());
+    }
+}
+
+/// Canonical form of a path; removed files keep their canonical directory
+fn canonical_path(path: &Path) -> PathBuf {
+    path.canonicalize()
+        .or_else(|e| match (path.parent(), path.file_name()) {
+            (Some(dir), Some(name)) => Ok(canonical_path(dir).join(name)),
+            _ => Err(e),
+        })
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn index_reports(project: &Project, reports: Vec<FileReport>) -> BTreeMap<PathBuf, FileReport> {
+    project.files.iter().cloned().zip(reports).collect()
+}
+
+fn clear_screen() {
+    print!("\x1b[2J\x1b[H");
+    // Diagnostics go to stderr, so the clear must reach the terminal first
+    std::io::stdout().flush().ok();
+}
+
+fn redraw(project: &Project, reports: &BTreeMap<PathBuf, FileReport>, duration: Duration) {
+    clear_screen();
+    print_reports(project, reports.values(), duration);
+    println!("{}", "Watching for changes...".dimmed());
+}
+
+/// Parse the test runner if specified, or auto-detect it from the current directory
+fn resolve_test_runner(test: Option<&str>) -> Option<TestRunner> {
+    if let Some(test_str) = test {
         match TestRunner::from_string(test_str) {
             Some(runner) => {
                 println!("Using test runner: {}", runner);
@@ -223,102 +1015,7 @@ fn main() -> Result<()> {
             }
             DetectedTestRunner::None => None,
         }
-    };
-
-    let start = Instant::now();
-    let total_errors = Arc::new(AtomicUsize::new(0));
-    let verbose = args.verbose;
-
-    // Convert entry and main paths to absolute paths for comparison
-    let entry_paths: Vec<PathBuf> = args
-        .entry
-        .iter()
-        .map(|p| {
-            Path::new(p)
-                .canonicalize()
-                .unwrap_or_else(|_| PathBuf::from(p))
-        })
-        .collect();
-    let main_paths: Vec<PathBuf> = args
-        .main
-        .iter()
-        .map(|p| {
-            Path::new(p)
-                .canonicalize()
-                .unwrap_or_else(|_| PathBuf::from(p))
-        })
-        .collect();
-
-    // Process files in parallel using rayon
-    let _results: Vec<_> = files
-        .par_iter()
-        .map(|file_path| {
-            let runner = test_runner.clone();
-            // Compare canonical paths or check if the file path ends with the entry/main path
-            let is_entry = entry_paths.iter().any(|ep| {
-                let matches =
-                    file_path == ep || ep.file_name().is_some_and(|name| file_path.ends_with(name));
-                if verbose && matches {
-                    eprintln!("DEBUG: Marking {} as entry point", file_path.display());
-                }
-                matches
-            });
-            let is_main = main_paths.iter().any(|mp| {
-                let matches =
-                    file_path == mp || mp.file_name().is_some_and(|name| file_path.ends_with(name));
-                if verbose && matches {
-                    eprintln!("DEBUG: Marking {} as main entry", file_path.display());
-                }
-                matches
-            });
-            match check_file_with_options(file_path, verbose, runner, is_entry, is_main) {
-                Ok(error_count) => {
-                    if error_count > 0 {
-                        total_errors.fetch_add(error_count, Ordering::Relaxed);
-                    }
-                    Ok(error_count)
-                }
-                Err(e) => {
-                    eprintln!("{}: {}", "Error".red().bold(), e);
-                    total_errors.fetch_add(1, Ordering::Relaxed);
-                    Err(e)
-                }
-            }
-        })
-        .collect();
-
-    let duration = start.elapsed();
-    let file_errors = total_errors.load(Ordering::Relaxed);
-    let total_errors = file_errors + package_errors.len();
-    let has_errors = total_errors > 0;
-
-    if has_errors {
-        eprintln!(
-            "\n{} {} found in {:.2}s",
-            "✗".red().bold(),
-            format!(
-                "{} error{}",
-                total_errors,
-                if total_errors != 1 { "s" } else { "" }
-            )
-            .red()
-            .bold(),
-            duration.as_secs_f64()
-        );
-        std::process::exit(1);
-    } else {
-        println!(
-            "{} {} in {} file{} ({:.2}s, {:.0} files/sec)",
-            "✓".green().bold(),
-            "No errors found".green(),
-            file_count,
-            if file_count != 1 { "s" } else { "" },
-            duration.as_secs_f64(),
-            file_count as f64 / duration.as_secs_f64()
-        );
     }
-
-    Ok(())
 }
 
 fn collect_files_with_workspace(workspace: &WorkspaceConfig) -> Result<Vec<PathBuf>> {
@@ -331,10 +1028,6 @@ fn collect_files_with_workspace(workspace: &WorkspaceConfig) -> Result<Vec<PathB
     // Get all target directories from workspace
     let target_dirs = workspace.get_target_dirs();
 
-    if workspace.is_monorepo() {
-        println!("Scanning {} package directories...", target_dirs.len());
-    }
-
     for dir in target_dirs {
         let files = collect_files(dir.to_str().unwrap_or("."))?;
         all_files.extend(files);
@@ -377,16 +1070,8 @@ fn collect_files(path: &str) -> Result<Vec<PathBuf>> {
     Ok(files)
 }
 
-// Removed unused functions - functionality consolidated into check_file_with_options
-
-fn check_file_with_options(
-    path: &Path,
-    verbose: bool,
-    test_runner: Option<TestRunner>,
-    is_entry: bool,
-    is_main: bool,
-) -> Result<usize> {
-    let source_text = fs::read_to_string(path)?;
+/// Lint source text as if it lived at `path`
+fn check_source(path: &Path, source_text: &str, options: &LintOptions) -> FileReport {
     let allocator = Allocator::default();
     let source_type = SourceType::from_path(path).unwrap_or_default();
 
@@ -394,34 +1079,105 @@ fn check_file_with_options(
         program,
         errors: parse_errors,
         ..
-    } = OxcParser::new(&allocator, &source_text, source_type).parse();
+    } = OxcParser::new(&allocator, source_text, source_type).parse();
 
     if !parse_errors.is_empty() {
-        let error_count = parse_errors.len();
-        for error in parse_errors {
-            eprintln!(
-                "{}: Parse error: {}",
-                format!("{}:1:1", path.display()).yellow(),
-                error
-            );
-        }
-        return Ok(error_count);
+        let output = parse_errors
+            .iter()
+            .map(|error| {
+                format!(
+                    "{}: Parse error: {}\n",
+                    format!("{}:1:1", path.display()).yellow(),
+                    error
+                )
+            })
+            .collect();
+        return FileReport {
+            errors: parse_errors.len(),
+            output,
+            ..FileReport::default()
+        };
     }
 
-    let mut linter = Linter::new(path, &source_text, verbose)
-        .with_test_runner(test_runner)
-        .with_entry_point(is_entry)
-        .with_main_entry(is_main);
+    let mut linter = Linter::new(path, source_text, options.verbose)
+        .with_test_runner(options.test_runner.clone())
+        .with_entry_point(options.is_entry(path))
+        .with_main_entry(options.is_main(path))
+        .with_custom_rules(options.custom_rules.clone())
+        .with_capabilities(options.capabilities.clone())
+        .with_require_disable_reason(options.require_disable_reason)
+        .with_disabled_rules(options.disabled_rules.clone())
+        .with_imperative_loops(options.imperative_loops.clone())
+        .with_import_map(options.import_map(path));
     linter.check_program(&program);
 
     // Check for untriggered expect-error directives
     linter.check_untriggered_expect_errors();
+    if options.report_unused_disable_directives {
+        linter.check_unused_disable_directives();
+    }
 
-    if linter.has_errors() {
-        let error_count = linter.errors.len();
-        linter.report_errors();
-        return Ok(error_count);
+    let cache_entry = CacheEntry {
+        key: String::new(),
+        diagnostics: linter.errors.iter().map(CachedDiagnostic::from).collect(),
+        suppressed: linter
+            .suppressed
+            .iter()
+            .map(CachedDiagnostic::from)
+            .collect(),
+        dependencies: BTreeMap::new(),
     }
+    .with_dependencies(&linter.dependencies);
+    file_report(linter, options, false, Some(cache_entry))
+}
 
-    Ok(0)
+/// Counts and output for a linted file, without baselined diagnostics and
+/// limited to changed lines with `--only-changed-lines`
+fn file_report(
+    mut linter: Linter,
+    options: &LintOptions,
+    cached: bool,
+    cache_entry: Option<CacheEntry>,
+) -> FileReport {
+    let fingerprints = if options.record_baseline {
+        linter
+            .errors
+            .iter()
+            .map(|error| Fingerprint::new(error, &linter.source_text))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    // Match the baseline against all diagnostics so that ones outside the
+    // changed lines are not reported as stale
+    let mut baselined = 0;
+    let mut stale = Vec::new();
+    if let Some(baseline) = &options.baseline {
+        let count = linter.errors.len();
+        stale = baseline.suppress(&linter.path, &linter.source_text, &mut linter.errors);
+        baselined = count - linter.errors.len();
+    }
+
+    if let Some(changed) = &options.changed_lines {
+        let path = linter.path.clone();
+        linter.retain_errors_on_lines(|line| changed.contains_line(&path, line));
+    }
+
+    FileReport {
+        errors: linter.error_count(),
+        warnings: linter.warning_count(),
+        output: linter.format_errors(),
+        cached,
+        cache_entry,
+        fingerprints,
+        baselined,
+        stale,
+        fixes: linter
+            .errors
+            .iter()
+            .filter_map(|error| error.fix.clone())
+            .collect(),
+        fixed: 0,
+    }
 }
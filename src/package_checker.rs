@@ -1,6 +1,7 @@
 use serde_json::Value;
-use std::fs;
-use std::path::Path;
+
+use crate::jsonc;
+use crate::Linter;
 
 // Forbidden libraries
 const FORBIDDEN_LIBRARIES: &[&str] = &[
@@ -23,54 +24,47 @@ const PREFER_ALTERNATIVES: &[(&str, &str)] = &[
     ("meow", "node:util parseArgs"),
 ];
 
-pub fn check_package_json(project_path: &Path) -> Vec<String> {
-    let mut errors = Vec::new();
-    let package_json_path = project_path.join("package.json");
-
-    if !package_json_path.exists() {
-        return errors;
-    }
-
-    let contents = match fs::read_to_string(&package_json_path) {
-        Ok(c) => c,
-        Err(_) => return errors,
-    };
+/// Report dependencies of the package.json being linted on forbidden
+/// libraries and on libraries with better alternatives, at their keys
+pub fn check_package_json(linter: &mut Linter) -> serde_json::Result<()> {
+    let json: Value = serde_json::from_str(&linter.source_text)?;
 
-    let json: Value = match serde_json::from_str(&contents) {
-        Ok(j) => j,
-        Err(e) => {
-            errors.push(format!("Failed to parse package.json: {}", e));
-            return errors;
-        }
-    };
-
-    // Check dependencies
-    check_dependencies(&json, "dependencies", &mut errors);
-    check_dependencies(&json, "devDependencies", &mut errors);
-    check_dependencies(&json, "peerDependencies", &mut errors);
-    check_dependencies(&json, "optionalDependencies", &mut errors);
+    check_dependencies(linter, &json, "dependencies");
+    check_dependencies(linter, &json, "devDependencies");
+    check_dependencies(linter, &json, "peerDependencies");
+    check_dependencies(linter, &json, "optionalDependencies");
 
-    errors
+    Ok(())
 }
 
-fn check_dependencies(json: &Value, field: &str, errors: &mut Vec<String>) {
+fn check_dependencies(linter: &mut Linter, json: &Value, field: &str) {
     if let Some(deps) = json.get(field).and_then(|v| v.as_object()) {
         for (name, _version) in deps {
+            let span = jsonc::key_span(&linter.source_text, &[field, name]).unwrap_or_default();
+
             // Check forbidden libraries
             if FORBIDDEN_LIBRARIES.contains(&name.as_str()) || name.starts_with("lodash.") {
-                errors.push(format!(
-                    "[package.json] Forbidden library '{}' found in {}. Consider using modern alternatives",
-                    name, field
-                ));
+                linter.add_error(
+                    "forbidden-dependencies".to_string(),
+                    format!(
+                        "Forbidden library '{}' found in {}. Consider using modern alternatives",
+                        name, field
+                    ),
+                    span,
+                );
             }
 
             // Check libraries with alternatives
             for (lib, alternative) in PREFER_ALTERNATIVES {
                 if name == lib {
-                    errors.push(format!(
-                        "[package.json] Library '{}' in {} has a better alternative. Use '{}' instead",
-                        name, field, alternative
-                    ));
+                    linter.add_error(
+                        "prefer-alternative-dependency".to_string(),
+                        format!(
+                            "Library '{}' in {} has a better alternative. Use '{}' instead",
+                            name, field, alternative
+                        ),
+                        span,
+                    );
                 }
             }
         }
@@ -80,12 +74,16 @@ fn check_dependencies(json: &Value, field: &str, errors: &mut Vec<String>) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
-    use tempfile::TempDir;
+    use std::path::Path;
+
+    fn check(package_json: &str) -> Vec<String> {
+        let mut linter = Linter::new(Path::new("package.json"), package_json, false);
+        check_package_json(&mut linter).unwrap();
+        linter.errors.into_iter().map(|e| e.message).collect()
+    }
 
     #[test]
     fn test_forbidden_libraries_in_package_json() {
-        let temp_dir = TempDir::new().unwrap();
         let package_json = r#"{
             "name": "test-project",
             "dependencies": {
@@ -99,9 +97,7 @@ mod tests {
             }
         }"#;
 
-        fs::write(temp_dir.path().join("package.json"), package_json).unwrap();
-
-        let errors = check_package_json(temp_dir.path());
+        let errors = check(package_json);
         assert_eq!(errors.len(), 4);
         assert!(errors.iter().any(|e| e.contains("jquery")));
         assert!(errors.iter().any(|e| e.contains("lodash")));
@@ -111,7 +107,6 @@ mod tests {
 
     #[test]
     fn test_alternatives_in_package_json() {
-        let temp_dir = TempDir::new().unwrap();
         let package_json = r#"{
             "name": "test-project",
             "dependencies": {
@@ -120,9 +115,7 @@ mod tests {
             }
         }"#;
 
-        fs::write(temp_dir.path().join("package.json"), package_json).unwrap();
-
-        let errors = check_package_json(temp_dir.path());
+        let errors = check(package_json);
         assert_eq!(errors.len(), 2);
         assert!(errors[0].contains("minimist"));
         assert!(errors[0].contains("node:util parseArgs"));
@@ -131,7 +124,6 @@ mod tests {
 
     #[test]
     fn test_lodash_variants() {
-        let temp_dir = TempDir::new().unwrap();
         let package_json = r#"{
             "name": "test-project",
             "dependencies": {
@@ -141,9 +133,7 @@ mod tests {
             }
         }"#;
 
-        fs::write(temp_dir.path().join("package.json"), package_json).unwrap();
-
-        let errors = check_package_json(temp_dir.path());
+        let errors = check(package_json);
         assert_eq!(errors.len(), 3);
         assert!(errors.iter().any(|e| e.contains("lodash.debounce")));
         assert!(errors.iter().any(|e| e.contains("lodash.merge")));
@@ -152,7 +142,6 @@ mod tests {
 
     #[test]
     fn test_allowed_libraries() {
-        let temp_dir = TempDir::new().unwrap();
         let package_json = r#"{
             "name": "test-project",
             "dependencies": {
@@ -162,9 +151,27 @@ mod tests {
             }
         }"#;
 
-        fs::write(temp_dir.path().join("package.json"), package_json).unwrap();
-
-        let errors = check_package_json(temp_dir.path());
+        let errors = check(package_json);
         assert_eq!(errors.len(), 0);
     }
+
+    #[test]
+    fn test_diagnostics_point_at_dependency() {
+        let package_json = "{\n  \"dependencies\": {\n    \"jquery\": \"^3.6.0\"\n  }\n}\n";
+        let mut linter = Linter::new(Path::new("package.json"), package_json, false);
+        check_package_json(&mut linter).unwrap();
+
+        assert_eq!(linter.errors.len(), 1);
+        assert_eq!(linter.errors[0].rule, "forbidden-dependencies");
+        assert_eq!(
+            linter.errors[0].span.start as usize,
+            package_json.find("\"jquery\"").unwrap()
+        );
+        assert!(linter.format_errors().contains("package.json:3:5"));
+
+        let mut linter = Linter::new(Path::new("package.json"), package_json, false)
+            .with_disabled_rules(["forbidden-dependencies".to_string()].into());
+        check_package_json(&mut linter).unwrap();
+        assert!(linter.errors.is_empty());
+    }
 }
@@ -0,0 +1,387 @@
+// Consistency of package.json entry points with the source tree
+
+use glob::glob;
+use oxc::allocator::Allocator;
+use oxc::parser::Parser;
+use oxc::span::{SourceType, Span};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::directories::resolve_dots;
+use crate::jsonc;
+use crate::rules::check_no_top_level_side_effects;
+use crate::tsconfig_validator::EffectiveTsConfig;
+use crate::Linter;
+
+/// Directory holding the sources every entry point must come from
+const SOURCE_DIR: &str = "src";
+
+/// Build output assumed when tsconfig.json sets no `outDir`, as produced by
+/// bundlers such as tsdown
+const DEFAULT_OUT_DIR: &str = "dist";
+
+/// Check that `exports` targets and `types` of the package.json being linted
+/// are built from files under `src/`, that `.` is built from its `index.ts`,
+/// and that `"sideEffects": false` holds for the sources
+pub fn check_package_exports(linter: &mut Linter, package_json: &Value) {
+    let package_dir = linter
+        .path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+        .to_path_buf();
+    let mapping = OutputMapping::new(&package_dir);
+
+    let mut targets = Vec::new();
+    for field in ["types", "typings"] {
+        if let Some(target) = package_json[field].as_str() {
+            targets.push((vec![field.to_string()], None, target.to_string()));
+        }
+    }
+    if let Some(exports) = package_json.get("exports") {
+        let is_subpaths = exports
+            .as_object()
+            .is_some_and(|map| map.keys().any(|key| key.starts_with('.')));
+        let subpath = (!is_subpaths).then(|| ".".to_string());
+        collect_targets(exports, vec!["exports".to_string()], subpath, &mut targets);
+    }
+
+    let index = mapping.index();
+    for (key_path, subpath, target) in targets {
+        // Patterns map whole directories and are left to the bundler; JSON
+        // files such as ./package.json are not built from sources
+        if target.contains('*') || target.ends_with(".json") {
+            continue;
+        }
+        let span = key_span(&linter.source_text, &key_path);
+        let source = mapping.source_for(&target);
+
+        if subpath.as_deref() == Some(".") && source != index {
+            linter.add_error(
+                "package-exports-entry".to_string(),
+                format!(
+                    "The '.' export must point at {}, found '{}'",
+                    mapping.display(&index),
+                    target
+                ),
+                span,
+            );
+        } else if !source.starts_with(&mapping.source_dir) || !source.is_file() {
+            linter.add_error(
+                "package-exports".to_string(),
+                format!(
+                    "'{}' has no source file (expected {})",
+                    target,
+                    mapping.display(&source)
+                ),
+                span,
+            );
+        }
+    }
+
+    if package_json["sideEffects"] == Value::Bool(false) {
+        let span = key_span(&linter.source_text, &["sideEffects".to_string()]);
+        for file in files_with_side_effects(&mapping.source_dir, &linter.disabled_rules) {
+            linter.add_error(
+                "package-side-effects".to_string(),
+                format!(
+                    "\"sideEffects\": false, but {} has top-level side effects",
+                    mapping.display(&file)
+                ),
+                span,
+            );
+        }
+    }
+}
+
+/// Collect `(key path, subpath, target)` for every string target under an
+/// `exports` value, through subpaths, conditions and fallback arrays
+fn collect_targets(
+    value: &Value,
+    key_path: Vec<String>,
+    subpath: Option<String>,
+    targets: &mut Vec<(Vec<String>, Option<String>, String)>,
+) {
+    match value {
+        Value::String(target) => targets.push((key_path, subpath, target.clone())),
+        Value::Array(fallbacks) => {
+            for fallback in fallbacks {
+                collect_targets(fallback, key_path.clone(), subpath.clone(), targets);
+            }
+        }
+        Value::Object(map) => {
+            for (key, value) in map {
+                let mut path = key_path.clone();
+                path.push(key.clone());
+                let subpath = subpath.clone().or_else(|| Some(key.clone()));
+                collect_targets(value, path, subpath, targets);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn key_span(source: &str, key_path: &[String]) -> Span {
+    let key_path: Vec<&str> = key_path.iter().map(String::as_str).collect();
+    jsonc::key_span(source, &key_path).unwrap_or_default()
+}
+
+/// How build output paths map back to sources, from `outDir` and `rootDir`
+/// of the package's effective tsconfig
+struct OutputMapping {
+    package_dir: PathBuf,
+    out_dir: PathBuf,
+    root_dir: PathBuf,
+    source_dir: PathBuf,
+}
+
+impl OutputMapping {
+    fn new(package_dir: &Path) -> Self {
+        let tsconfig = EffectiveTsConfig::load(&package_dir.join("tsconfig.json")).ok();
+        // Directories are relative to the config that set them
+        let option = |name: &str, value: Option<&String>| {
+            let value = value?;
+            let origin = tsconfig.as_ref()?.origins.get(name)?;
+            Some(resolve_dots(
+                &origin.parent().unwrap_or(Path::new(".")).join(value),
+            ))
+        };
+        let options = tsconfig
+            .as_ref()
+            .and_then(|tsconfig| tsconfig.compiler_options.as_ref());
+        let source_dir = resolve_dots(&package_dir.join(SOURCE_DIR));
+
+        Self {
+            package_dir: resolve_dots(package_dir),
+            out_dir: option("outDir", options.and_then(|o| o.out_dir.as_ref()))
+                .unwrap_or_else(|| resolve_dots(&package_dir.join(DEFAULT_OUT_DIR))),
+            root_dir: option("rootDir", options.and_then(|o| o.root_dir.as_ref()))
+                .unwrap_or_else(|| source_dir.clone()),
+            source_dir,
+        }
+    }
+
+    /// The source file a target is built from: output paths move from
+    /// `outDir` to `rootDir` and emitted extensions become TypeScript ones
+    fn source_for(&self, target: &str) -> PathBuf {
+        let path = resolve_dots(&self.package_dir.join(target));
+        let path = match path.strip_prefix(&self.out_dir) {
+            Ok(relative) => self.root_dir.join(relative),
+            Err(_) => path,
+        };
+
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        for (emitted, source) in [
+            (".d.ts", ".ts"),
+            (".d.mts", ".mts"),
+            (".d.cts", ".cts"),
+            (".js", ".ts"),
+            (".mjs", ".mts"),
+            (".cjs", ".cts"),
+        ] {
+            if let Some(stem) = name.strip_suffix(emitted) {
+                let ts = path.with_file_name(format!("{}{}", stem, source));
+                let tsx = path.with_file_name(format!("{}{}x", stem, source));
+                return if !ts.is_file() && tsx.is_file() {
+                    tsx
+                } else {
+                    ts
+                };
+            }
+        }
+        path
+    }
+
+    /// The package's `index.ts`: at the top of `rootDir` when that is under
+    /// `src/`, and in `src/` otherwise
+    fn index(&self) -> PathBuf {
+        if self.root_dir.starts_with(&self.source_dir) {
+            self.root_dir.join("index.ts")
+        } else {
+            self.source_dir.join("index.ts")
+        }
+    }
+
+    /// A path relative to the package, for messages
+    fn display(&self, path: &Path) -> String {
+        path.strip_prefix(&self.package_dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+}
+
+/// Source files reported by `no-top-level-side-effects`, in path order.
+/// Like the CLI, this honours disable directives and the disabled rules
+fn files_with_side_effects(source_dir: &Path, disabled_rules: &HashSet<String>) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = ["ts", "tsx"]
+        .iter()
+        .filter_map(|extension| glob(&format!("{}/**/*.{}", source_dir.display(), extension)).ok())
+        .flat_map(|paths| paths.flatten())
+        .collect();
+    files.sort();
+
+    files
+        .into_iter()
+        .filter(|file| {
+            let Ok(source_text) = fs::read_to_string(file) else {
+                return false;
+            };
+            let allocator = Allocator::default();
+            let source_type = SourceType::from_path(file).unwrap_or_default();
+            let ret = Parser::new(&allocator, &source_text, source_type).parse();
+
+            let mut linter =
+                Linter::new(file, &source_text, false).with_disabled_rules(disabled_rules.clone());
+            linter.load_directives(&ret.program);
+            check_no_top_level_side_effects(&mut linter, &ret.program);
+            linter
+                .errors
+                .iter()
+                .any(|error| error.rule == "no-top-level-side-effects")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn check(dir: &Path, package_json: &str) -> Vec<(String, String)> {
+        let path = dir.join("package.json");
+        let mut linter = Linter::new(&path, package_json, false);
+        check_package_exports(&mut linter, &serde_json::from_str(package_json).unwrap());
+        linter
+            .errors
+            .into_iter()
+            .map(|e| (e.rule, e.message))
+            .collect()
+    }
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_targets_map_to_sources() {
+        let dir = TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "src/index.ts",
+            "export { add } from \"./add.ts\";\n",
+        );
+        write(dir.path(), "src/add.ts", "export const add = 1;\n");
+
+        let errors = check(
+            dir.path(),
+            r#"{
+              "types": "./dist/index.d.ts",
+              "exports": {
+                ".": { "types": "./dist/index.d.ts", "import": "./dist/index.js" },
+                "./add": "./dist/add.js",
+                "./sub": ["./dist/sub.js", null],
+                "./package.json": "./package.json",
+                "./features/*": "./dist/features/*.js"
+              }
+            }"#,
+        );
+        assert_eq!(
+            errors,
+            vec![(
+                "package-exports".to_string(),
+                "'./dist/sub.js' has no source file (expected src/sub.ts)".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_entry_must_be_index() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "src/index.ts", "");
+        write(dir.path(), "src/main.ts", "");
+
+        assert_eq!(
+            check(dir.path(), r#"{ "exports": "./src/main.ts" }"#),
+            vec![(
+                "package-exports-entry".to_string(),
+                "The '.' export must point at src/index.ts, found './src/main.ts'".to_string()
+            )]
+        );
+        assert!(check(
+            dir.path(),
+            r#"{ "exports": { "import": "./src/index.ts" } }"#
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_out_dir_and_root_dir_from_tsconfig() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "src/lib/index.ts", "");
+        write(
+            dir.path(),
+            "tsconfig.json",
+            r#"{ "extends": "./config/base.json" }"#,
+        );
+        write(
+            dir.path(),
+            "config/base.json",
+            r#"{ "compilerOptions": { "outDir": "../build", "rootDir": "../src/lib" } }"#,
+        );
+
+        assert!(check(dir.path(), r#"{ "exports": { ".": "./build/index.js" } }"#).is_empty());
+        assert_eq!(
+            check(dir.path(), r#"{ "exports": { ".": "./dist/index.js" } }"#).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_side_effects_false() {
+        let dir = TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "src/index.ts",
+            "export { log } from \"./log.ts\";\n",
+        );
+        write(dir.path(), "src/log.ts", "console.log(\"loaded\");\n");
+        write(dir.path(), "src/log.test.ts", "console.log(\"test\");\n");
+
+        assert_eq!(
+            check(dir.path(), r#"{ "sideEffects": false }"#),
+            vec![(
+                "package-side-effects".to_string(),
+                "\"sideEffects\": false, but src/log.ts has top-level side effects".to_string()
+            )]
+        );
+        assert!(check(dir.path(), r#"{ "sideEffects": ["./src/log.ts"] }"#).is_empty());
+    }
+
+    #[test]
+    fn test_side_effects_honour_directives_and_disabled_rules() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "src/index.ts", "export const x = 1;\n");
+        write(
+            dir.path(),
+            "src/log.ts",
+            "// purets-disable-next-line no-top-level-side-effects -- registers a handler\n\
+             console.log(\"loaded\");\n",
+        );
+        assert!(check(dir.path(), r#"{ "sideEffects": false }"#).is_empty());
+
+        write(dir.path(), "src/log.ts", "console.log(\"loaded\");\n");
+        let package_json = r#"{ "sideEffects": false }"#;
+        let mut linter = Linter::new(&dir.path().join("package.json"), package_json, false)
+            .with_disabled_rules(HashSet::from(["no-top-level-side-effects".to_string()]));
+        check_package_exports(&mut linter, &serde_json::from_str(package_json).unwrap());
+        assert!(linter.errors.is_empty());
+    }
+}
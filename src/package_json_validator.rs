@@ -1,9 +1,15 @@
 use anyhow::{Context, Result};
 use colored::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
+use crate::jsonc;
+use crate::package_checker::check_package_json;
+use crate::package_exports::check_package_exports;
+use crate::Linter;
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PackageJson {
     pub name: Option<String>,
@@ -17,21 +23,59 @@ pub struct PackageJson {
     pub dev_dependencies: Option<serde_json::Value>,
 }
 
+/// Check the package.json being linted: `"type": "module"`, its entry
+/// points and its dependencies
+pub fn check_package(linter: &mut Linter) -> serde_json::Result<()> {
+    let package_json: PackageJson = serde_json::from_str(&linter.source_text)?;
+    check_module_type(linter, &package_json);
+    check_package_exports(linter, &serde_json::from_str(&linter.source_text)?);
+    check_package_json(linter)
+}
+
+fn check_module_type(linter: &mut Linter, package_json: &PackageJson) {
+    let span = jsonc::key_span(&linter.source_text, &["type"]).unwrap_or_default();
+    match &package_json.module_type {
+        None => {
+            linter.add_error(
+                "package-type-module".to_string(),
+                "\"type\": \"module\" is missing in package.json".to_string(),
+                span,
+            );
+        }
+        Some(module_type) if module_type != "module" => {
+            linter.add_error(
+                "package-type-module".to_string(),
+                format!("\"type\" must be \"module\", found \"{}\"", module_type),
+                span,
+            );
+        }
+        Some(_) => {
+            // type: "module" is correctly set
+        }
+    }
+}
+
 pub struct PackageJsonValidator {
     path: String,
-    errors: Vec<String>,
-    warnings: Vec<String>,
+    disabled_rules: HashSet<String>,
+    linter: Option<Linter>,
 }
 
 impl PackageJsonValidator {
     pub fn new(path: String) -> Self {
         Self {
             path,
-            errors: Vec::new(),
-            warnings: Vec::new(),
+            disabled_rules: HashSet::new(),
+            linter: None,
         }
     }
 
+    /// Skip diagnostics of the given rule ids
+    pub fn with_disabled_rules(mut self, rules: HashSet<String>) -> Self {
+        self.disabled_rules = rules;
+        self
+    }
+
     pub fn validate(&mut self) -> Result<()> {
         let package_json_path = if self.path.ends_with("package.json") {
             self.path.clone()
@@ -47,61 +91,22 @@ impl PackageJsonValidator {
 
         let content = fs::read_to_string(path).context("Failed to read package.json")?;
 
-        let package_json: PackageJson =
-            serde_json::from_str(&content).context("Failed to parse package.json")?;
-
-        self.validate_module_type(&package_json);
+        let mut linter =
+            Linter::new(path, &content, false).with_disabled_rules(self.disabled_rules.clone());
+        check_package(&mut linter).context("Failed to parse package.json")?;
+        self.linter = Some(linter);
 
         Ok(())
     }
 
-    fn validate_module_type(&mut self, package_json: &PackageJson) {
-        match &package_json.module_type {
-            None => {
-                self.errors
-                    .push("\"type\": \"module\" is missing in package.json".to_string());
-            }
-            Some(module_type) if module_type != "module" => {
-                self.errors.push(format!(
-                    "\"type\" must be \"module\", found \"{}\"",
-                    module_type
-                ));
-            }
-            Some(_) => {
-                // type: "module" is correctly set
-            }
-        }
-    }
-
     pub fn report(&self) {
-        if !self.errors.is_empty() {
-            eprintln!(
-                "\n{} {} in package.json:",
-                "✗".red().bold(),
-                "Errors".red().bold()
-            );
-            for error in &self.errors {
-                eprintln!("  {} {}", "•".red(), error);
-            }
-        }
-
-        if !self.warnings.is_empty() {
-            eprintln!(
-                "\n{} {} in package.json:",
-                "⚠".yellow().bold(),
-                "Warnings".yellow().bold()
-            );
-            for warning in &self.warnings {
-                eprintln!("  {} {}", "•".yellow(), warning);
-            }
-        }
-
-        if self.errors.is_empty() && self.warnings.is_empty() {
-            println!("{} package.json validation passed", "✓".green().bold());
+        match &self.linter {
+            Some(linter) if !linter.errors.is_empty() => linter.report_errors(),
+            _ => println!("{} package.json validation passed", "✓".green().bold()),
         }
     }
 
     pub fn has_errors(&self) -> bool {
-        !self.errors.is_empty()
+        self.linter.as_ref().is_some_and(Linter::has_errors)
     }
 }
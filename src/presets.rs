@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use crate::rule_registry::find_rule;
+
 /// Rule presets based on naming conventions
 #[derive(Debug, Clone)]
 pub struct RulePreset {
@@ -21,6 +23,19 @@ impl RulePreset {
         }
     }
 
+    /// Rule names in this preset that are not registered rule ids
+    pub fn unknown_rules(&self) -> Vec<String> {
+        let mut unknown: Vec<String> = self
+            .enabled_rules
+            .iter()
+            .chain(&self.disabled_rules)
+            .filter(|rule| find_rule(rule).is_none())
+            .cloned()
+            .collect();
+        unknown.sort();
+        unknown
+    }
+
     /// Strict preset - all rules enabled
     pub fn strict() -> Self {
         Self {
@@ -35,18 +50,24 @@ impl RulePreset {
                 "no-eval-function".to_string(),
                 "no-foreach".to_string(),
                 "no-do-while".to_string(),
+                "no-imperative-loops".to_string(),
                 // Type safety
                 "no-as-cast".to_string(),
                 "let-requires-type".to_string(),
                 "empty-array-requires-type".to_string(),
+                "no-let".to_string(),
+                "no-param-mutation".to_string(),
                 "prefer-readonly-array".to_string(),
                 "no-mutable-record".to_string(),
+                "readonly-properties".to_string(),
                 // Code quality
                 "no-unused-variables".to_string(),
                 "no-unused-map".to_string(),
                 "must-use-return-value".to_string(),
+                "must-handle-result".to_string(),
                 "catch-error-handling".to_string(),
                 "switch-case-block".to_string(),
+                "switch-exhaustive".to_string(),
                 // Import/Export
                 "strict-named-export".to_string(),
                 "no-namespace-imports".to_string(),
@@ -63,7 +84,6 @@ impl RulePreset {
                 "max-function-params".to_string(),
                 "no-this-in-functions".to_string(),
                 "no-side-effect-functions".to_string(),
-                "filename-function-match".to_string(),
                 "export-requires-jsdoc".to_string(),
                 "jsdoc-param-match".to_string(),
                 // Path-based restrictions
@@ -94,7 +114,6 @@ impl RulePreset {
                 "no-classes".to_string(),
                 "no-throw".to_string(),
                 "strict-named-export".to_string(),
-                "filename-function-match".to_string(),
                 "export-requires-jsdoc".to_string(),
                 "no-top-level-side-effects".to_string(),
             ]),
@@ -112,22 +131,25 @@ impl RulePreset {
                 "no-this-in-functions".to_string(),
                 "no-foreach".to_string(),
                 "no-do-while".to_string(),
+                "no-imperative-loops".to_string(),
                 "no-delete".to_string(),
                 "no-member-assignments".to_string(),
                 "no-object-assign".to_string(),
                 "prefer-readonly-array".to_string(),
                 "no-mutable-record".to_string(),
+                "readonly-properties".to_string(),
                 // Pure functions
                 "no-side-effect-functions".to_string(),
                 "path-based-restrictions".to_string(),
                 // Immutability
                 "let-requires-type".to_string(),
                 "empty-array-requires-type".to_string(),
+                "no-let".to_string(),
+                "no-param-mutation".to_string(),
             ]),
             disabled_rules: HashSet::from([
                 // Allow some OO patterns
                 "strict-named-export".to_string(),
-                "filename-function-match".to_string(),
             ]),
         }
     }
@@ -143,13 +165,13 @@ impl RulePreset {
                 "jsdoc-param-match".to_string(),
                 "no-unused-variables".to_string(),
                 "must-use-return-value".to_string(),
+                "must-handle-result".to_string(),
                 // Type safety
                 "no-as-cast".to_string(),
                 "let-requires-type".to_string(),
                 "prefer-readonly-array".to_string(),
                 // Clean exports
                 "no-reexports".to_string(),
-                "filename-function-match".to_string(),
                 // No side effects
                 "no-top-level-side-effects".to_string(),
                 "no-side-effect-functions".to_string(),
@@ -177,7 +199,6 @@ impl RulePreset {
             disabled_rules: HashSet::from([
                 // Allow test patterns
                 "no-top-level-side-effects".to_string(),
-                "filename-function-match".to_string(),
                 "export-requires-jsdoc".to_string(),
                 "no-throw".to_string(),
                 "max-function-params".to_string(),
@@ -261,6 +282,19 @@ mod tests {
         assert!(preset.is_rule_enabled("no-eval-function").unwrap());
     }
 
+    #[test]
+    fn test_preset_rules_are_registered() {
+        for name in Preset::list_all() {
+            let preset = RulePreset::from_name(name).unwrap();
+            assert!(
+                preset.unknown_rules().is_empty(),
+                "preset '{}' has unknown rules: {:?}",
+                name,
+                preset.unknown_rules()
+            );
+        }
+    }
+
     #[test]
     fn test_preset_from_name() {
         assert!(RulePreset::from_name("strict").is_some());
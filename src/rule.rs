@@ -0,0 +1,169 @@
+use oxc::ast::ast::Program;
+use oxc::ast::AstKind;
+use oxc::ast_visit::Visit;
+
+use crate::Linter;
+
+/// A lint rule driven by [`RuleDispatcher`]
+///
+/// Rules receive every AST node through `enter_node`/`leave_node` in a single
+/// traversal shared with all other rules, and can report from any hook via
+/// `Linter::add_error`. Per-file state is reset by creating a new rule value
+/// for each file.
+pub trait Rule<'a> {
+    /// Rule id, registered in `rule_registry` or declared as a custom rule
+    fn id(&self) -> &str;
+
+    /// Called once before the traversal
+    fn on_program_start(&mut self, _linter: &mut Linter, _program: &'a Program<'a>) {}
+
+    /// Called when the traversal enters a node
+    fn enter_node(&mut self, _linter: &mut Linter, _kind: AstKind<'a>) {}
+
+    /// Called when the traversal leaves a node
+    fn leave_node(&mut self, _linter: &mut Linter, _kind: AstKind<'a>) {}
+
+    /// Called once after the traversal
+    fn on_program_end(&mut self, _linter: &mut Linter, _program: &'a Program<'a>) {}
+}
+
+pub type BoxedRule<'a> = Box<dyn Rule<'a> + 'a>;
+
+/// Drives a set of rules over a program in one traversal
+pub struct RuleDispatcher<'a, 'l> {
+    linter: &'l mut Linter,
+    rules: Vec<BoxedRule<'a>>,
+}
+
+impl<'a, 'l> RuleDispatcher<'a, 'l> {
+    pub fn new(linter: &'l mut Linter, rules: Vec<BoxedRule<'a>>) -> Self {
+        Self { linter, rules }
+    }
+
+    pub fn run(&mut self, program: &'a Program<'a>) {
+        for rule in &mut self.rules {
+            rule.on_program_start(self.linter, program);
+        }
+        self.visit_program(program);
+        for rule in &mut self.rules {
+            rule.on_program_end(self.linter, program);
+        }
+    }
+}
+
+impl<'a> Visit<'a> for RuleDispatcher<'a, '_> {
+    fn enter_node(&mut self, kind: AstKind<'a>) {
+        for rule in &mut self.rules {
+            rule.enter_node(self.linter, kind);
+        }
+    }
+
+    fn leave_node(&mut self, kind: AstKind<'a>) {
+        for rule in &mut self.rules {
+            rule.leave_node(self.linter, kind);
+        }
+    }
+}
+
+/// Run the given rules over a program
+pub fn run_rules<'a>(linter: &mut Linter, program: &'a Program<'a>, rules: Vec<BoxedRule<'a>>) {
+    RuleDispatcher::new(linter, rules).run(program);
+}
+
+/// Run a single rule over a program
+pub fn run_rule<'a>(linter: &mut Linter, program: &'a Program<'a>, rule: impl Rule<'a> + 'a) {
+    run_rules(linter, program, vec![Box::new(rule)]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule_registry::find_rule;
+    use crate::rules::all_rules;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+    use std::collections::HashSet;
+    use std::path::Path;
+
+    fn lint(source: &str, disabled: &[&str]) -> Vec<String> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+
+        let mut linter = Linter::new(Path::new("src/pure/remove.ts"), source, false)
+            .with_disabled_rules(disabled.iter().map(|rule| rule.to_string()).collect());
+        linter.check_program(&ret.program);
+
+        linter.errors.into_iter().map(|e| e.rule).collect()
+    }
+
+    #[test]
+    fn test_registered_rules_are_known_and_unique() {
+        let linter = Linter::new(Path::new("test.ts"), "", false);
+        let mut seen = HashSet::new();
+        for rule in all_rules(&linter) {
+            assert!(
+                find_rule(rule.id()).is_some(),
+                "unknown rule '{}'",
+                rule.id()
+            );
+            assert!(
+                seen.insert(rule.id().to_string()),
+                "duplicate rule '{}'",
+                rule.id()
+            );
+        }
+    }
+
+    #[test]
+    fn test_disabled_rule_is_not_run() {
+        let source = r#"
+/**
+ * Removes the name.
+ * @param user User record
+ * @returns Nothing
+ */
+export function remove(user: Readonly<{ name?: string }>): void {
+  delete user.name;
+}
+"#;
+        assert!(lint(source, &[]).contains(&"no-delete".to_string()));
+        assert!(!lint(source, &["no-delete"]).contains(&"no-delete".to_string()));
+    }
+
+    /// Diagnostics for every source file of a sample project, linted with
+    /// the test runner detected for it
+    fn lint_project(root: &Path) -> Vec<String> {
+        let test_runner = crate::test_runner_detector::TestRunnerDetector::new(root.to_path_buf())
+            .detect()
+            .to_runner();
+        let pattern = format!("{}/src/**/*.ts", root.display());
+        let mut diagnostics = Vec::new();
+        for file in glob::glob(&pattern).unwrap().flatten() {
+            let source = std::fs::read_to_string(&file).unwrap();
+            let allocator = Allocator::default();
+            let ret =
+                Parser::new(&allocator, &source, SourceType::from_path(&file).unwrap()).parse();
+
+            let mut linter =
+                Linter::new(&file, &source, false).with_test_runner(test_runner.clone());
+            linter.check_program(&ret.program);
+            linter.check_untriggered_expect_errors();
+            diagnostics.extend(
+                linter
+                    .errors
+                    .iter()
+                    .map(|e| format!("{}: [{}] {}", file.display(), e.rule, e.message)),
+            );
+        }
+        diagnostics
+    }
+
+    #[test]
+    fn test_sample_projects_are_clean() {
+        for project in ["examples", "testapp2"] {
+            let diagnostics = lint_project(&Path::new(env!("CARGO_MANIFEST_DIR")).join(project));
+            assert!(diagnostics.is_empty(), "{:#?}", diagnostics);
+        }
+    }
+}
@@ -0,0 +1,2264 @@
+use colored::*;
+
+use crate::presets::RulePreset;
+use crate::rule_registry::{RuleMeta, RULES};
+
+/// A TypeScript (or tsconfig.json/package.json) example for a rule, linted
+/// at `path`
+#[derive(Debug)]
+pub struct Example {
+    pub path: &'static str,
+    pub code: &'static str,
+}
+
+/// Long-form documentation for a rule
+///
+/// `bad` examples must trigger the rule and `good` examples must lint cleanly;
+/// both are checked by the tests in this module.
+#[derive(Debug)]
+pub struct RuleDoc {
+    pub id: &'static str,
+    pub explanation: &'static str,
+    pub bad: &'static [Example],
+    pub good: &'static [Example],
+}
+
+const fn example(path: &'static str, code: &'static str) -> Example {
+    Example { path, code }
+}
+
+/// Documentation for every registered rule
+pub static RULE_DOCS: &[RuleDoc] = &[
+    RuleDoc {
+        id: "no-classes",
+        explanation: "Classes bundle state and behaviour and encourage mutation through 'this'. \
+Model data with types and behaviour with plain functions instead. Classes extending Error are \
+allowed in errors/ directories.",
+        bad: &[example(
+            "src/pure/createCounter.ts",
+            r#"class Counter {
+  count: number = 0;
+}
+
+/**
+ * Creates a counter.
+ * @returns A new counter
+ */
+export function createCounter(): Counter {
+  return new Counter();
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/createCounter.ts",
+            r#"/**
+ * Creates a counter.
+ * @param start Initial count
+ * @returns A new counter
+ */
+export function createCounter(start: number): { readonly count: number } {
+  return { count: start };
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-enums",
+        explanation: "Enums generate runtime objects and behave differently from the rest of the \
+type system. Use a union of string literals instead.",
+        bad: &[example(
+            "src/types/Color.ts",
+            r#"enum Color {
+  Red,
+  Green,
+}
+"#,
+        )],
+        good: &[example(
+            "src/types/Color.ts",
+            r#"/**
+ * Supported colors.
+ */
+export type Color = "red" | "green";
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-throw",
+        explanation: "Exceptions are invisible in function signatures. Return a Result value so \
+that callers have to handle the failure. A try/catch is only allowed to convert to a Result, \
+returning ok(...) from the try block and err(...) from the catch block. Use '@allow throws' in \
+the JSDoc of functions that must throw.",
+        bad: &[example(
+            "src/pure/parseAge.ts",
+            r#"/**
+ * Parses an age.
+ * @param text Raw input
+ * @returns The age
+ */
+export function parseAge(text: string): number {
+  const age: number = Number(text);
+  if (Number.isNaN(age)) {
+    throw new Error("invalid age");
+  }
+  return age;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/parseAge.ts",
+            r#"/**
+ * Parses an age.
+ * @param text Raw input
+ * @returns The age, or undefined when the input is not a number
+ */
+export function parseAge(text: string): number | undefined {
+  const age: number = Number(text);
+  return Number.isNaN(age) ? undefined : age;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-delete",
+        explanation: "The delete operator mutates objects in place. Build a new object without \
+the property instead.",
+        bad: &[example(
+            "src/pure/removeName.ts",
+            r#"/**
+ * Removes the name field.
+ * @param record Input record
+ * @returns Nothing
+ */
+export function removeName(record: Record<string, string>): void {
+  delete record.name;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/removeName.ts",
+            r#"/**
+ * Removes the name field.
+ * @param record Input record
+ * @returns A copy of the record without the name field
+ */
+export function removeName(
+  record: Readonly<{ id: string; name: string }>,
+): Readonly<{ id: string }> {
+  return { id: record.id };
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-eval-function",
+        explanation: "eval() and the Function constructor execute arbitrary strings as code. \
+They defeat static analysis and are a common source of injection bugs.",
+        bad: &[example(
+            "src/pure/calculate.ts",
+            r#"/**
+ * Evaluates an expression.
+ * @param expression Source text
+ * @returns The result
+ */
+export function calculate(expression: string): number {
+  return eval(expression);
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/calculate.ts",
+            r#"/**
+ * Adds two operands.
+ * @param left Left operand
+ * @param right Right operand
+ * @returns The result
+ */
+export function calculate(left: number, right: number): number {
+  return left + right;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-foreach",
+        explanation: "forEach only exists for its side effects. Use map/filter/reduce to compute \
+a new value.",
+        bad: &[example(
+            "src/pure/total.ts",
+            r#"/**
+ * Sums prices.
+ * @param prices Input prices
+ * @returns The total
+ */
+export function total(prices: readonly number[]): number {
+  const sums: number[] = [0];
+  prices.forEach((price) => sums.push(price));
+  return sums.length;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/total.ts",
+            r#"/**
+ * Sums prices.
+ * @param prices Input prices
+ * @returns The total
+ */
+export function total(prices: readonly number[]): number {
+  return prices.reduce((sum, price) => sum + price, 0);
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-do-while",
+        explanation: "do-while loops run their body before checking the condition, which is easy \
+to misread. Use array methods or recursion instead.",
+        bad: &[example(
+            "src/io/pollSync.ts",
+            r#"/**
+ * Polls until ready.
+ * @param check Readiness check
+ * @returns Nothing
+ */
+export function pollSync(check: () => boolean): void {
+  do {
+    check();
+  } while (!check());
+}
+"#,
+        )],
+        good: &[example(
+            "src/io/pollSync.ts",
+            r#"/**
+ * Polls until ready.
+ * @param check Readiness check
+ * @returns Nothing
+ */
+export function pollSync(check: () => boolean): void {
+  if (!check()) {
+    pollSync(check);
+  }
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-imperative-loops",
+        explanation: "Pure code should describe values, not steps. 'for', 'for...in' and 'while' \
+loops are not allowed in pure/. 'for...of' is allowed only over an input declared as readonly \
+(readonly T[] or ReadonlyArray<T>). Prefer map/filter/reduce/flatMap.",
+        bad: &[example(
+            "src/pure/countEven.ts",
+            r#"/**
+ * Counts even numbers.
+ * @param values Input values
+ * @returns The count
+ */
+export function countEven(values: readonly number[]): number {
+  for (const key in values) {
+    return Number(key);
+  }
+  return 0;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/countEven.ts",
+            r#"/**
+ * Counts even numbers.
+ * @param values Input values
+ * @returns The count
+ */
+export function countEven(values: readonly number[]): number {
+  return values.filter((value) => value % 2 === 0).length;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-constant-condition",
+        explanation: "Conditions that are always true or false are either dead code or infinite \
+loops. Make the condition depend on a value.",
+        bad: &[example(
+            "src/io/waitSync.ts",
+            r#"/**
+ * Waits until ready.
+ * @param ready Readiness check
+ * @returns Nothing
+ */
+export function waitSync(ready: () => boolean): void {
+  if (true) {
+    ready();
+  }
+}
+"#,
+        )],
+        good: &[example(
+            "src/io/waitSync.ts",
+            r#"/**
+ * Waits until ready.
+ * @param ready Readiness check
+ * @returns Nothing
+ */
+export function waitSync(ready: () => boolean): void {
+  if (!ready()) {
+    waitSync(ready);
+  }
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-getters-setters",
+        explanation:
+            "Getters and setters in classes hide computation and mutation behind property \
+access. Use plain functions instead.",
+        bad: &[example(
+            "src/pure/createUser.ts",
+            r#"class User {
+  get label(): string {
+    return "user";
+  }
+}
+
+/**
+ * Creates a user.
+ * @returns A user
+ */
+export function createUser(): User {
+  return new User();
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/createUser.ts",
+            r#"/**
+ * Creates a user.
+ * @param name User name
+ * @returns A user
+ */
+export function createUser(name: string): { readonly label: string } {
+  return { label: name.toUpperCase() };
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-define-property",
+        explanation: "Object.defineProperty and Object.defineProperties mutate objects and can \
+create hidden accessors. Use object literals instead.",
+        bad: &[example(
+            "src/pure/withId.ts",
+            r#"/**
+ * Adds an id.
+ * @param target Target object
+ * @returns The target
+ */
+export function withId(target: object): object {
+  return Object.defineProperty(target, "id", { value: 1 });
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/withId.ts",
+            r#"/**
+ * Adds an id.
+ * @param target Target object
+ * @returns A copy of the target with an id
+ */
+export function withId(target: object): object {
+  return { ...target, id: 1 };
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-object-assign",
+        explanation: "Object.assign mutates its first argument. Use spread syntax to build a new \
+object.",
+        bad: &[example(
+            "src/pure/merge.ts",
+            r#"/**
+ * Merges two objects.
+ * @param left Left object
+ * @param right Right object
+ * @returns The merged object
+ */
+export function merge(left: object, right: object): object {
+  return Object.assign(left, right);
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/merge.ts",
+            r#"/**
+ * Merges two objects.
+ * @param left Left object
+ * @param right Right object
+ * @returns The merged object
+ */
+export function merge(left: object, right: object): object {
+  return { ...left, ...right };
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-member-assignments",
+        explanation: "Assigning to 'foo.bar' mutates shared objects. Create a new object with \
+the updated value instead.",
+        bad: &[example(
+            "src/pure/rename.ts",
+            r#"/**
+ * Renames a user.
+ * @param user Input user
+ * @param name New name
+ * @returns Nothing
+ */
+export function rename(user: { name: string }, name: string): void {
+  user.name = name;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/rename.ts",
+            r#"/**
+ * Renames a user.
+ * @param user Input user
+ * @param name New name
+ * @returns The renamed user
+ */
+export function rename(
+  user: Readonly<{ name: string }>,
+  name: string,
+): Readonly<{ name: string }> {
+  return { ...user, name };
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-dynamic-access",
+        explanation: "Dynamic property access with computed keys bypasses type checking. Use dot \
+notation, destructuring or a Map instead.",
+        bad: &[example(
+            "src/pure/lookup.ts",
+            r#"/**
+ * Looks up a setting.
+ * @param settings Settings object
+ * @param key Setting name
+ * @returns The value
+ */
+export function lookup(settings: Readonly<Record<string, string>>, key: string): string | undefined {
+  return settings[key];
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/lookup.ts",
+            r#"/**
+ * Looks up a setting.
+ * @param settings Settings map
+ * @param key Setting name
+ * @returns The value
+ */
+export function lookup(settings: ReadonlyMap<string, string>, key: string): string | undefined {
+  return settings.get(key);
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-as-cast",
+        explanation: "Type assertions with 'as' silence the compiler instead of proving the type. \
+Use annotations, type guards or 'as const' instead.",
+        bad: &[example(
+            "src/pure/toName.ts",
+            r#"/**
+ * Reads a name.
+ * @param value Unknown input
+ * @returns The name
+ */
+export function toName(value: unknown): string {
+  return value as string;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/toName.ts",
+            r#"/**
+ * Reads a name.
+ * @param value Unknown input
+ * @returns The name
+ */
+export function toName(value: unknown): string {
+  return typeof value === "string" ? value : "";
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "let-requires-type",
+        explanation: "A let binding can be reassigned later, so its type should be stated \
+rather than inferred from the first value. Annotate it.",
+        bad: &[example(
+            "src/io/countSync.ts",
+            r#"/**
+ * Counts calls.
+ * @param limit Maximum count
+ * @returns The count
+ */
+export function countSync(limit: number): number {
+  let count;
+  count = limit;
+  return count;
+}
+"#,
+        )],
+        good: &[example(
+            "src/io/countSync.ts",
+            r#"/**
+ * Counts calls.
+ * @param limit Maximum count
+ * @returns The count
+ */
+export function countSync(limit: number): number {
+  let count: number = 0;
+  count = limit;
+  return count;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "empty-array-requires-type",
+        explanation: "An empty array literal has no element type to infer from. Annotate the \
+binding.",
+        bad: &[example(
+            "src/pure/emptyList.ts",
+            r#"/**
+ * Creates an empty list.
+ * @returns An empty list
+ */
+export function emptyList(): readonly string[] {
+  const items = [];
+  return items;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/emptyList.ts",
+            r#"/**
+ * Creates an empty list.
+ * @returns An empty list
+ */
+export function emptyList(): readonly string[] {
+  const items: readonly string[] = [];
+  return items;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-let",
+        explanation: "A let binding that is never reassigned should be const. In pure/ code let \
+is not allowed at all; derive new values with expressions instead.",
+        bad: &[example(
+            "src/io/greetSync.ts",
+            r#"/**
+ * Builds a greeting.
+ * @param name User name
+ * @returns The greeting
+ */
+export function greetSync(name: string): string {
+  let greeting: string = "Hello " + name;
+  return greeting;
+}
+"#,
+        )],
+        good: &[example(
+            "src/io/greetSync.ts",
+            r#"/**
+ * Builds a greeting.
+ * @param name User name
+ * @returns The greeting
+ */
+export function greetSync(name: string): string {
+  const greeting: string = "Hello " + name;
+  return greeting;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-param-mutation",
+        explanation: "Callers do not expect their arguments to change. Return a new value instead \
+of reassigning or mutating a parameter.",
+        bad: &[example(
+            "src/pure/addTag.ts",
+            r#"/**
+ * Adds a tag.
+ * @param tags Existing tags
+ * @param tag New tag
+ * @returns The tags
+ */
+export function addTag(tags: string[], tag: string): string[] {
+  tags.push(tag);
+  return tags;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/addTag.ts",
+            r#"/**
+ * Adds a tag.
+ * @param tags Existing tags
+ * @param tag New tag
+ * @returns The new tags
+ */
+export function addTag(tags: readonly string[], tag: string): readonly string[] {
+  return [...tags, tag];
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "prefer-readonly-array",
+        explanation: "Arrays that are never mutated should be typed as readonly so that the \
+compiler prevents accidental mutation.",
+        bad: &[example(
+            "src/pure/firstName.ts",
+            r#"/**
+ * Returns the first name.
+ * @returns The first name
+ */
+export function firstName(): string | undefined {
+  const names: string[] = ["a", "b"];
+  return names[0];
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/firstName.ts",
+            r#"/**
+ * Returns the first name.
+ * @returns The first name
+ */
+export function firstName(): string | undefined {
+  const names: readonly string[] = ["a", "b"];
+  return names.at(0);
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-mutable-record",
+        explanation: "Record<K, V> allows writes to any key. Use Readonly<Record<K, V>> or \
+ReadonlyMap<K, V>.",
+        bad: &[example(
+            "src/pure/countOf.ts",
+            r#"/**
+ * Reads a count.
+ * @param counts Counts by name
+ * @returns The count for 'a'
+ */
+export function countOf(counts: Record<string, number>): number | undefined {
+  return counts.a;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/countOf.ts",
+            r#"/**
+ * Reads a count.
+ * @param counts Counts by name
+ * @returns The count for 'a'
+ */
+export function countOf(counts: ReadonlyMap<string, number>): number | undefined {
+  return counts.get("a");
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "readonly-properties",
+        explanation: "Members of types in types/ must be readonly (or wrapped in Readonly<...>), \
+ReadonlyMap/ReadonlySet replace Map/Set in type positions, and object-typed parameters in pure/ \
+must be Readonly<...>.",
+        bad: &[example(
+            "src/types/User.ts",
+            r#"/**
+ * A user.
+ */
+export type User = {
+  readonly id: string;
+  name: string;
+};
+"#,
+        )],
+        good: &[example(
+            "src/types/User.ts",
+            r#"/**
+ * A user.
+ */
+export type User = {
+  readonly id: string;
+  readonly name: string;
+};
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "interface-extends-only",
+        explanation: "Interfaces are open to declaration merging. Use 'type' for object shapes \
+and keep interfaces for extending other types.",
+        bad: &[example(
+            "src/types/Point.ts",
+            r#"/**
+ * A point.
+ */
+export interface Point {
+  readonly x: number;
+}
+"#,
+        )],
+        good: &[example(
+            "src/types/Point.ts",
+            r#"/**
+ * A point.
+ */
+export type Point = {
+  readonly x: number;
+};
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "export-const-type-required",
+        explanation: "Exported constants are part of the module's API, so their type must be \
+written out. Exported let bindings are not allowed.",
+        bad: &[example(
+            "src/config.ts",
+            r#"/**
+ * Default port.
+ */
+export const PORT = 8080;
+"#,
+        )],
+        good: &[],
+    },
+    RuleDoc {
+        id: "no-unused-variables",
+        explanation: "Unused variables are dead code and usually point to a bug.",
+        bad: &[example(
+            "src/pure/square.ts",
+            r#"/**
+ * Squares a number.
+ * @param value Input value
+ * @returns The square
+ */
+export function square(value: number): number {
+  const unused: number = 1;
+  return value * value;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/square.ts",
+            r#"/**
+ * Squares a number.
+ * @param value Input value
+ * @returns The square
+ */
+export function square(value: number): number {
+  return value * value;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-unused-map",
+        explanation: "map() returns a new array. Calling it for side effects and discarding the \
+result is a mistake.",
+        bad: &[example(
+            "src/pure/double.ts",
+            r#"/**
+ * Doubles values.
+ * @param values Input values
+ * @returns The input values
+ */
+export function double(values: readonly number[]): readonly number[] {
+  values.map((value) => value * 2);
+  return values;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/double.ts",
+            r#"/**
+ * Doubles values.
+ * @param values Input values
+ * @returns The doubled values
+ */
+export function double(values: readonly number[]): readonly number[] {
+  return values.map((value) => value * 2);
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "must-use-return-value",
+        explanation: "Calling a function and ignoring its return value usually means a result or \
+error is lost. Calls to functions declared in the same file as returning void are allowed, and \
+test files are not checked.",
+        bad: &[example(
+            "src/pure/normalize.ts",
+            r#"/**
+ * Normalizes a name.
+ * @param name Raw name
+ * @returns The normalized name
+ */
+export function normalize(name: string): string {
+  name.trim();
+  return name;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/normalize.ts",
+            r#"/**
+ * Normalizes a name.
+ * @param name Raw name
+ * @returns The normalized name
+ */
+export function normalize(name: string): string {
+  return name.trim();
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "must-handle-result",
+        explanation: "Result and ResultAsync values (from neverthrow) carry errors. They must be \
+returned, matched or unwrapped with a fallback; discarding them or only mapping them drops \
+the error. _unsafeUnwrap() is only allowed in tests.",
+        bad: &[example(
+            "src/io/saveUser.ts",
+            r#"import { type Result, ok } from "neverthrow";
+
+function write(name: string): Result<string, Error> {
+  return ok(name);
+}
+
+/**
+ * Saves a user.
+ * @param name User name
+ * @returns Nothing
+ */
+export async function saveUser(name: string): Promise<void> {
+  write(name);
+}
+"#,
+        )],
+        good: &[example(
+            "src/io/saveUser.ts",
+            r#"import { type Result, ok } from "neverthrow";
+
+function write(name: string): Result<string, Error> {
+  return ok(name);
+}
+
+/**
+ * Saves a user.
+ * @param name User name
+ * @returns The write result
+ */
+export async function saveUser(name: string): Promise<Result<string, Error>> {
+  return write(name);
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "catch-error-handling",
+        explanation: "catch blocks must do something with the caught error, such as converting it \
+into an Err value. The first statement of the block has to narrow the error with \
+'Error.isError(error)' or 'error instanceof Error'. A try/catch that returns ok(...) from \
+the try block and err(...) from the catch block is already a Result conversion and is exempt.",
+        bad: &[example(
+            "src/io/readConfig.ts",
+            r#"/**
+ * @allow fs
+ */
+import fs from "node:fs/promises";
+import { ConfigError } from "../errors/ConfigError.ts";
+
+/**
+ * @allow throws
+ * Reads the config file.
+ * @param path File path
+ * @returns The file contents
+ */
+export async function readConfig(path: string): Promise<string> {
+  try {
+    return await fs.readFile(path, "utf-8");
+  } catch (error) {
+    throw new ConfigError(path);
+  }
+}
+"#,
+        )],
+        good: &[example(
+            "src/io/readConfig.ts",
+            r#"/**
+ * @allow fs
+ */
+import fs from "node:fs/promises";
+import { ConfigError } from "../errors/ConfigError.ts";
+
+/**
+ * @allow throws
+ * Reads the config file.
+ * @param path File path
+ * @returns The file contents
+ */
+export async function readConfig(path: string): Promise<string> {
+  try {
+    return await fs.readFile(path, "utf-8");
+  } catch (error) {
+    if (Error.isError(error)) {
+      throw new ConfigError(path);
+    }
+    throw error;
+  }
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "switch-case-block",
+        explanation: "Declarations in a case clause leak into the other cases. Cases with more \
+than one statement must wrap their body in braces.",
+        bad: &[example(
+            "src/pure/describe.ts",
+            r#"/**
+ * Describes a number.
+ * @param value Input value
+ * @returns The description
+ */
+export function describe(value: number): string {
+  switch (value) {
+    case 0:
+      const label: string = "zero";
+      return label;
+    default: {
+      return "other";
+    }
+  }
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/describe.ts",
+            r#"/**
+ * Describes a number.
+ * @param value Input value
+ * @returns The description
+ */
+export function describe(value: number): string {
+  switch (value) {
+    case 0: {
+      return "zero";
+    }
+    default: {
+      return "other";
+    }
+  }
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "switch-exhaustive",
+        explanation: "A switch over the tag of a discriminated union must end with \
+'default: assertNever(x)', so that adding a member becomes a compile error, and must handle \
+every member unless that default is present. Switches with a case that is not a literal are \
+not checked.",
+        bad: &[example(
+            "src/pure/area.ts",
+            r#"type Shape =
+  | { readonly kind: "circle"; readonly radius: number }
+  | { readonly kind: "square"; readonly size: number };
+
+/**
+ * Computes an area.
+ * @param shape Input shape
+ * @returns The area
+ */
+export function area(shape: Shape): number {
+  switch (shape.kind) {
+    case "circle": {
+      return shape.radius * shape.radius * 3;
+    }
+  }
+  return 0;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/area.ts",
+            r#"type Shape =
+  | { readonly kind: "circle"; readonly radius: number }
+  | { readonly kind: "square"; readonly size: number };
+
+function assertNever(value: never): never {
+  return value;
+}
+
+/**
+ * Computes an area.
+ * @param shape Input shape
+ * @returns The area
+ */
+export function area(shape: Shape): number {
+  switch (shape.kind) {
+    case "circle": {
+      return shape.radius * shape.radius * 3;
+    }
+    case "square": {
+      return shape.size * shape.size;
+    }
+    default: {
+      return assertNever(shape);
+    }
+  }
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "strict-named-export",
+        explanation: "Each module exports one named function matching its filename. Default \
+exports are not allowed. Functions in io/ must be async or end with 'Sync', and functions in \
+pure/ cannot be async.",
+        bad: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export default function add(a: number, b: number): number {
+  return a + b;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-namespace-imports",
+        explanation: "Namespace imports hide which bindings a module uses. Use named imports.",
+        bad: &[example(
+            "src/pure/total.ts",
+            r#"import * as math from "../lib/math.ts";
+
+/**
+ * Sums values.
+ * @param values Input values
+ * @returns The total
+ */
+export function total(values: readonly number[]): number {
+  return math.sum(values);
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/total.ts",
+            r#"import { sum } from "../lib/math.ts";
+
+/**
+ * Sums values.
+ * @param values Input values
+ * @returns The total
+ */
+export function total(values: readonly number[]): number {
+  return sum(values);
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-reexports",
+        explanation: "Re-exports create indirection and barrel files. Import from the defining \
+module. Entry points (index.ts, --entry, --main) may re-export named bindings.",
+        bad: &[example(
+            "src/lib/math.ts",
+            r#"export { sum } from "./sum.ts";
+"#,
+        )],
+        good: &[example(
+            "src/index.ts",
+            r#"export { sum } from "./lib/sum.ts";
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "import-extensions",
+        explanation: "Relative imports must include the file extension so that they resolve the \
+same way in Node.js, Deno and bundlers.",
+        bad: &[example(
+            "src/pure/total.ts",
+            r#"import { sum } from "../lib/sum";
+
+/**
+ * Sums values.
+ * @param values Input values
+ * @returns The total
+ */
+export function total(values: readonly number[]): number {
+  return sum(values);
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/total.ts",
+            r#"import { sum } from "../lib/sum.ts";
+
+/**
+ * Sums values.
+ * @param values Input values
+ * @returns The total
+ */
+export function total(values: readonly number[]): number {
+  return sum(values);
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-http-imports",
+        explanation: "Importing from URLs fetches unpinned code at runtime. Use a package manager \
+or an import map.",
+        bad: &[example(
+            "src/pure/total.ts",
+            r#"import { sum } from "https://example.com/sum.ts";
+
+/**
+ * Sums values.
+ * @param values Input values
+ * @returns The total
+ */
+export function total(values: readonly number[]): number {
+  return sum(values);
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/total.ts",
+            r#"import { sum } from "../lib/sum.ts";
+
+/**
+ * Sums values.
+ * @param values Input values
+ * @returns The total
+ */
+export function total(values: readonly number[]): number {
+  return sum(values);
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-require",
+        explanation: "require() is CommonJS. Use ES module imports.",
+        bad: &[example(
+            "src/io/loadSync.ts",
+            r#"/**
+ * Loads a module.
+ * @param name Module name
+ * @returns The module
+ */
+export function loadSync(name: string): unknown {
+  return require(name);
+}
+"#,
+        )],
+        good: &[example(
+            "src/io/load.ts",
+            r#"/**
+ * Loads a module.
+ * @param name Module name
+ * @returns The module
+ */
+export async function load(name: string): Promise<unknown> {
+  return import(name);
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-filename-dirname",
+        explanation: "__filename and __dirname only exist in CommonJS. Use import.meta.filename \
+and import.meta.dirname.",
+        bad: &[example(
+            "src/io/currentDirSync.ts",
+            r#"/**
+ * Returns the current directory.
+ * @returns The directory
+ */
+export function currentDirSync(): string {
+  return __dirname;
+}
+"#,
+        )],
+        good: &[example(
+            "src/io/currentDirSync.ts",
+            r#"/**
+ * Returns the current directory.
+ * @returns The directory
+ */
+export function currentDirSync(): string {
+  return import.meta.dirname;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-global-process",
+        explanation: "The global process object is Node.js specific and hides a dependency. \
+Import it from 'node:process'.",
+        bad: &[example(
+            "src/io/homeSync.ts",
+            r#"/**
+ * Returns the home directory.
+ * @returns The directory
+ */
+export function homeSync(): string | undefined {
+  return process.env.HOME;
+}
+"#,
+        )],
+        good: &[example(
+            "src/io/homeSync.ts",
+            r#"import { env } from "node:process";
+
+/**
+ * Returns the home directory.
+ * @returns The directory
+ */
+export function homeSync(): string | undefined {
+  return env.HOME;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "node-import-style",
+        explanation: "Node.js built-in modules must be imported with the 'node:' prefix, using \
+named imports and the promise-based API where one exists.",
+        bad: &[example(
+            "src/io/joinSync.ts",
+            r#"import { join } from "path";
+
+/**
+ * Joins paths.
+ * @param left Left path
+ * @param right Right path
+ * @returns The joined path
+ */
+export function joinSync(left: string, right: string): string {
+  return join(left, right);
+}
+"#,
+        )],
+        good: &[example(
+            "src/io/joinSync.ts",
+            r#"import { join } from "node:path";
+
+/**
+ * Joins paths.
+ * @param left Left path
+ * @param right Right path
+ * @returns The joined path
+ */
+export function joinSync(left: string, right: string): string {
+  return join(left, right);
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "forbidden-libraries",
+        explanation: "Some libraries duplicate modern platform features (lodash, underscore, \
+jquery, rxjs) or have built-in alternatives (minimist and yargs, replaced by node:util \
+parseArgs).",
+        bad: &[example(
+            "src/pure/unique.ts",
+            r#"import { uniq } from "lodash";
+
+/**
+ * Removes duplicates.
+ * @param values Input values
+ * @returns The unique values
+ */
+export function unique(values: readonly string[]): readonly string[] {
+  return uniq(values);
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/unique.ts",
+            r#"/**
+ * Removes duplicates.
+ * @param values Input values
+ * @returns The unique values
+ */
+export function unique(values: readonly string[]): readonly string[] {
+  return [...new Set(values)];
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "max-function-params",
+        explanation: "Functions with many positional parameters are hard to call correctly. Take \
+at most 3 parameters and group the rest into an options object.",
+        bad: &[example(
+            "src/pure/volume.ts",
+            r#"/**
+ * Computes a volume.
+ * @param a Width
+ * @param b Height
+ * @param c Depth
+ * @param d Scale
+ * @returns The volume
+ */
+export function volume(a: number, b: number, c: number, d: number): number {
+  return a * b * c * d;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/volume.ts",
+            r#"/**
+ * Computes a volume.
+ * @param size Box size
+ * @param scale Scale factor
+ * @returns The volume
+ */
+export function volume(
+  size: Readonly<{ width: number; height: number; depth: number }>,
+  scale: number,
+): number {
+  return size.width * size.height * size.depth * scale;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-this-in-functions",
+        explanation: "'this' in plain functions depends on how the function is called. Pass the \
+value as a parameter.",
+        bad: &[example(
+            "src/pure/getName.ts",
+            r#"/**
+ * Returns the name.
+ * @returns The name
+ */
+export function getName(): string {
+  return this.name;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/getName.ts",
+            r#"/**
+ * Returns the name.
+ * @param user Input user
+ * @returns The name
+ */
+export function getName(user: Readonly<{ name: string }>): string {
+  return user.name;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-side-effect-functions",
+        explanation: "Math.random(), Date.now() and new Date() make functions non-deterministic. \
+Pass the value in as a parameter, or use it as a default parameter value.",
+        bad: &[example(
+            "src/pure/rollDice.ts",
+            r#"/**
+ * Rolls a die.
+ * @returns The roll
+ */
+export function rollDice(): number {
+  return Math.floor(Math.random() * 6) + 1;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/rollDice.ts",
+            r#"/**
+ * Rolls a die.
+ * @param random Random number in [0, 1)
+ * @returns The roll
+ */
+export function rollDice(random: number): number {
+  return Math.floor(random * 6) + 1;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "one-public-function",
+        explanation: "Each module exports exactly one function, which keeps modules small and \
+their names meaningful.",
+        bad: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+
+/**
+ * Subtracts numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The difference
+ */
+export function subtract(a: number, b: number): number {
+  return a - b;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "export-requires-jsdoc",
+        explanation: "Exported functions are the module's API. Document them with a JSDoc \
+comment.",
+        bad: &[example(
+            "src/pure/add.ts",
+            r#"export function add(a: number, b: number): number {
+  return a + b;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "jsdoc-param-match",
+        explanation: "@param tags must list every parameter, in order, and nothing else. \
+Parameters must also have a type annotation.",
+        bad: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds two numbers.
+ * @param x First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds two numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "path-based-restrictions",
+        explanation: "The directory of a file decides what it may contain: pure/ holds pure \
+functions named after the file, types/ holds a single type named after the file, io/errors/ \
+holds error classes, index.ts only re-exports, and test files must import the function they \
+test.",
+        bad: &[example(
+            "src/types/User.ts",
+            r#"/**
+ * A user.
+ */
+export type Person = {
+  readonly id: string;
+};
+"#,
+        )],
+        good: &[example(
+            "src/types/User.ts",
+            r#"/**
+ * A user.
+ */
+export type User = {
+  readonly id: string;
+};
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "no-top-level-side-effects",
+        explanation: "Importing a module should not do anything. Keep statements with side \
+effects inside functions; main.ts may call main().",
+        bad: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+
+add(1, 2);
+"#,
+        )],
+        good: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "allow-directives",
+        explanation: "Ambient capabilities such as console, timers, fetch, the DOM, the file \
+system and environment variables need an '@allow' directive (e.g. '@allow console') in the \
+file header (a first JSDoc comment that documents no declaration), or in the JSDoc of the \
+one top-level function or const that needs it. Custom capabilities can be declared in purets.json. Unknown and unused directives, \
+and file-level directives used by only one function, are reported. Directives above the \
+capabilityPolicy ceiling for the file's path (none at all under pure/) are reported as \
+'capability-policy'.",
+        bad: &[example(
+            "src/io/logSync.ts",
+            r#"/**
+ * Logs a message.
+ * @param message Message text
+ * @returns Nothing
+ */
+export function logSync(message: string): void {
+  console.log(message);
+}
+"#,
+        )],
+        good: &[example(
+            "src/io/logSync.ts",
+            r#"/**
+ * @allow console
+ * Logs a message.
+ * @param message Message text
+ * @returns Nothing
+ */
+export function logSync(message: string): void {
+  console.log(message);
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "unused-expect-error",
+        explanation: "A purets-expect-error comment documents a known violation on the next line. \
+If that violation no longer happens, the comment must be removed.",
+        bad: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  // purets-expect-error no-delete
+  return a + b;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "unknown-directive-rule",
+        explanation: "purets-disable-* and purets-expect-error comments must name registered \
+rules (see 'purets rules'), so that typos do not silently disable nothing.",
+        bad: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  // purets-disable-next-line no-consle
+  return a + b;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  // purets-disable-next-line no-as-cast
+  return (a + b) as number;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "unused-disable-directive",
+        explanation: "With --report-unused-disable-directives, purets-disable-* comments that no \
+longer suppress anything are reported, as are rules listed in a directive that never fire. \
+'purets --fix' deletes the comment (or the unused rule names).",
+        bad: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  // purets-disable-next-line no-as-cast
+  return a + b;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "missing-disable-reason",
+        explanation: "With \"requireDisableReason\": true in purets.json, every purets-disable-* \
+comment must explain itself after '--'. The reason is shown next to the suppressed diagnostic \
+in verbose output, so reviewers can see why a rule was switched off.",
+        bad: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  // purets-disable-next-line no-as-cast
+  return (a + b) as number;
+}
+"#,
+        )],
+        good: &[example(
+            "src/pure/add.ts",
+            r#"/**
+ * Adds numbers.
+ * @param a First number
+ * @param b Second number
+ * @returns The sum
+ */
+export function add(a: number, b: number): number {
+  // purets-disable-next-line no-as-cast -- generated bindings return unknown
+  return (a + b) as number;
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "tsconfig-strict",
+        explanation:
+            "Checked with --validate-tsconfig and in editors. The effective tsconfig.json, \
+after following 'extends', must set strict and noUnusedParameters, and must not allow unreachable \
+code or unused labels. Options set by a base config are reported at 'extends' with the file that \
+set them.",
+        bad: &[example(
+            "tsconfig.json",
+            r#"{
+  "compilerOptions": {
+    "strict": false
+  }
+}
+"#,
+        )],
+        good: &[example("tsconfig.json", STRICT_TSCONFIG)],
+    },
+    RuleDoc {
+        id: "tsconfig-module-syntax",
+        explanation:
+            "Imports carry their .ts extension and type-only imports are written as such, \
+so the effective tsconfig.json must enable allowImportingTsExtensions and verbatimModuleSyntax.",
+        bad: &[example(
+            "tsconfig.json",
+            r#"{
+  "compilerOptions": {
+    "strict": true,
+    "noUnusedParameters": true
+  }
+}
+"#,
+        )],
+        good: &[example("tsconfig.json", STRICT_TSCONFIG)],
+    },
+    RuleDoc {
+        id: "tsconfig-recommended",
+        explanation: "Warns about strictness options worth enabling (noImplicitReturns, \
+noUncheckedIndexedAccess, exactOptionalPropertyTypes, ...) and about a module or target older \
+than ES2020. Warnings do not fail the run.",
+        bad: &[example(
+            "tsconfig.json",
+            r#"{
+  "compilerOptions": {
+    "target": "ES5",
+    "strict": true
+  }
+}
+"#,
+        )],
+        good: &[example("tsconfig.json", STRICT_TSCONFIG)],
+    },
+    RuleDoc {
+        id: "tsconfig-extends",
+        explanation: "'extends' may name a relative path (with or without .json), a package in \
+node_modules, or an array of both. A base that cannot be found would silently drop the settings \
+it was meant to provide.",
+        bad: &[example(
+            "tsconfig.json",
+            r#"{
+  "extends": "./tsconfig.missing.json",
+  "compilerOptions": {
+    "strict": true
+  }
+}
+"#,
+        )],
+        good: &[example("tsconfig.json", STRICT_TSCONFIG)],
+    },
+    RuleDoc {
+        id: "package-type-module",
+        explanation: "Packages are ES modules: package.json must set \"type\": \"module\" so \
+that .js output and imports are not treated as CommonJS.",
+        bad: &[example(
+            "package.json",
+            r#"{
+  "name": "app",
+  "type": "commonjs"
+}
+"#,
+        )],
+        good: &[example(
+            "package.json",
+            r#"{
+  "name": "app",
+  "type": "module"
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "deno-strict",
+        explanation: "Deno type-checks with strict on by default. deno.json must not turn strict \
+or any of its individual options off, nor allow unreachable code or unused labels.",
+        bad: &[example(
+            "deno.json",
+            r#"{
+  "compilerOptions": {
+    "strict": false
+  }
+}
+"#,
+        )],
+        good: &[example(
+            "deno.json",
+            r#"{
+  "compilerOptions": {
+    "noUncheckedIndexedAccess": true
+  }
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "deno-lint-fmt",
+        explanation: "deno lint and deno fmt must cover src/: their exclude lists must not \
+contain it. deno lint must keep the recommended tag and the rules this linter relies on \
+(no-explicit-any, no-unused-vars, no-var, prefer-const).",
+        bad: &[example(
+            "deno.jsonc",
+            r#"{
+  "lint": {
+    "rules": {
+      "exclude": ["no-explicit-any"]
+    }
+  },
+  "fmt": {
+    "exclude": ["src/"]
+  }
+}
+"#,
+        )],
+        good: &[example(
+            "deno.jsonc",
+            r#"{
+  // Generated code is neither linted nor formatted
+  "lint": {
+    "exclude": ["dist/"],
+    "rules": {
+      "tags": ["recommended"]
+    }
+  },
+  "fmt": {
+    "exclude": ["dist/"]
+  }
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "deno-http-imports",
+        explanation: "Import map entries in deno.json must not point at http(s) URLs, which \
+would bring back the remote imports no-http-imports forbids behind a bare specifier. Use jsr: \
+or npm: specifiers.",
+        bad: &[example(
+            "deno.json",
+            r#"{
+  "imports": {
+    "std/": "https://deno.land/std@0.224.0/"
+  }
+}
+"#,
+        )],
+        good: &[example(
+            "deno.json",
+            r#"{
+  "imports": {
+    "@std/path": "jsr:@std/path@^1.0.0",
+    "zod": "npm:zod@^3.23.0"
+  }
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "forbidden-dependencies",
+        explanation: "Utility belts such as lodash, underscore and jquery, and rxjs, are not \
+allowed as dependencies; argument parsers with a built-in replacement (node:util parseArgs) are \
+reported as prefer-alternative-dependency. Checked on every run.",
+        bad: &[example(
+            "package.json",
+            r#"{
+  "name": "app",
+  "type": "module",
+  "dependencies": {
+    "lodash": "^4.17.21"
+  }
+}
+"#,
+        )],
+        good: &[example(
+            "package.json",
+            r#"{
+  "name": "app",
+  "type": "module",
+  "dependencies": {
+    "neverthrow": "^8.0.0"
+  }
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "package-exports",
+        explanation: "Every exports target and the types field must be built from a file under \
+src/: build output paths map back through outDir and rootDir of tsconfig.json (dist/ without \
+one) and .js/.d.ts become .ts. The \".\" export must come from src/index.ts, reported as \
+package-exports-entry.",
+        bad: &[example(
+            "examples/package.json",
+            r#"{
+  "name": "@internal/pure-ts-example",
+  "type": "module",
+  "exports": {
+    ".": "./dist/main.js",
+    "./sum": "./dist/pure/sum.js"
+  }
+}
+"#,
+        )],
+        good: &[example(
+            "examples/package.json",
+            r#"{
+  "name": "@internal/pure-ts-example",
+  "type": "module",
+  "exports": {
+    ".": {
+      "types": "./dist/index.d.ts",
+      "import": "./dist/index.js"
+    },
+    "./add": "./dist/pure/add.js"
+  }
+}
+"#,
+        )],
+    },
+    RuleDoc {
+        id: "package-side-effects",
+        explanation: "\"sideEffects\": false lets bundlers drop unused imports, so it must not be \
+set while a file under src/ has top-level side effects (see no-top-level-side-effects). List \
+such files in sideEffects instead.",
+        bad: &[example(
+            "tests/fixtures/side-effects/package.json",
+            r#"{
+  "name": "side-effects",
+  "type": "module",
+  "sideEffects": false
+}
+"#,
+        )],
+        good: &[example(
+            "tests/fixtures/side-effects/package.json",
+            r#"{
+  "name": "side-effects",
+  "type": "module",
+  "sideEffects": ["./src/register.ts"]
+}
+"#,
+        )],
+    },
+];
+
+/// A tsconfig.json that passes every configuration rule
+const STRICT_TSCONFIG: &str = r#"{
+  "compilerOptions": {
+    "target": "ESNext",
+    "module": "ESNext",
+    "strict": true,
+    "noUnusedLocals": true,
+    "noUnusedParameters": true,
+    "noImplicitReturns": true,
+    "noFallthroughCasesInSwitch": true,
+    "exactOptionalPropertyTypes": true,
+    "noUncheckedIndexedAccess": true,
+    "allowImportingTsExtensions": true,
+    "verbatimModuleSyntax": true
+  }
+}
+"#;
+
+/// Find the documentation for a rule
+pub fn find_doc(id: &str) -> Option<&'static RuleDoc> {
+    RULE_DOCS.iter().find(|doc| doc.id == id)
+}
+
+/// Names of the presets that enable a rule
+pub fn presets_enabling(id: &str) -> Vec<&'static str> {
+    crate::presets::Preset::list_all()
+        .into_iter()
+        .filter(|name| {
+            RulePreset::from_name(name).and_then(|preset| preset.is_rule_enabled(id)) == Some(true)
+        })
+        .collect()
+}
+
+/// Print all rules as a table (`purets rules`)
+pub fn print_rules() {
+    println!(
+        "{:<28} {:<22} {:<30} {}",
+        "RULE".bold(),
+        "CATEGORY".bold(),
+        "PRESETS".bold(),
+        "FIXABLE".bold()
+    );
+    for rule in RULES {
+        let presets = presets_enabling(rule.id);
+        println!(
+            "{:<28} {:<22} {:<30} {}",
+            rule.id.cyan(),
+            rule.category.to_string(),
+            if presets.is_empty() {
+                "-".to_string()
+            } else {
+                presets.join(", ")
+            },
+            if rule.fixable { "yes" } else { "no" }
+        );
+    }
+}
+
+/// Format the long-form explanation of a rule (`purets explain <rule>`)
+///
+/// Sub-diagnostic ids resolve to the rule that reports them.
+pub fn explain(id: &str) -> Option<String> {
+    let rule = crate::rule_registry::rule_for_diagnostic(id)?;
+    Some(format_explanation(rule, find_doc(rule.id)))
+}
+
+/// Markdown explanation of a rule for editor hovers, with the first bad and
+/// good example
+pub fn explain_markdown(id: &str) -> Option<String> {
+    let rule = crate::rule_registry::rule_for_diagnostic(id)?;
+    let mut out = format!(
+        "**{}** ({})\n\n{}\n",
+        rule.id, rule.category, rule.description
+    );
+
+    if let Some(doc) = find_doc(rule.id) {
+        out.push_str(&format!("\n{}\n", doc.explanation));
+        for (label, example) in [("Bad", doc.bad.first()), ("Good", doc.good.first())] {
+            if let Some(example) = example {
+                let language = if example.path.ends_with(".json") {
+                    "json"
+                } else {
+                    "ts"
+                };
+                out.push_str(&format!(
+                    "\n{} (`{}`)\n\n```{}\n{}```\n",
+                    label, example.path, language, example.code
+                ));
+            }
+        }
+    }
+
+    Some(out)
+}
+
+fn format_explanation(rule: &RuleMeta, doc: Option<&RuleDoc>) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("{}\n\n", rule.id.bold()));
+    out.push_str(&format!("{}\n\n", rule.description));
+    out.push_str(&format!("Category: {}\n", rule.category));
+    out.push_str(&format!("Severity: {}\n", rule.severity));
+    out.push_str(&format!(
+        "Fixable:  {}\n",
+        if rule.fixable { "yes" } else { "no" }
+    ));
+    let presets = presets_enabling(rule.id);
+    out.push_str(&format!(
+        "Presets:  {}\n",
+        if presets.is_empty() {
+            "-".to_string()
+        } else {
+            presets.join(", ")
+        }
+    ));
+    if !rule.diagnostics.is_empty() {
+        out.push_str(&format!("Reports:  {}\n", rule.diagnostics.join(", ")));
+    }
+
+    if let Some(doc) = doc {
+        out.push_str(&format!("\n{}\n", doc.explanation));
+        for example in doc.bad {
+            out.push_str(&format!("\n{} ({})\n\n", "Bad".red().bold(), example.path));
+            out.push_str(example.code);
+        }
+        for example in doc.good {
+            out.push_str(&format!(
+                "\n{} ({})\n\n",
+                "Good".green().bold(),
+                example.path
+            ));
+            out.push_str(example.code);
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule_registry::{find_rule, rule_for_diagnostic};
+    use crate::Linter;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+    use std::path::Path;
+
+    /// Lint an example the way the CLI lints a file and return (rule, message) pairs
+    fn lint_example(doc: &RuleDoc, example: &Example) -> Vec<(String, String)> {
+        let mut linter = Linter::new(Path::new(example.path), example.code, false);
+        if linter.check_config_file().unwrap() {
+            return linter
+                .errors
+                .into_iter()
+                .map(|e| (e.rule, e.message))
+                .collect();
+        }
+
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path(example.path).unwrap();
+        let ret = Parser::new(&allocator, example.code, source_type).parse();
+        assert!(
+            ret.errors.is_empty(),
+            "{}: parse errors {:?}",
+            example.path,
+            ret.errors
+        );
+
+        // Opt-in rules run their examples with the option on
+        let mut linter = Linter::new(Path::new(example.path), example.code, false)
+            .with_require_disable_reason(doc.id == "missing-disable-reason");
+        linter.check_program(&ret.program);
+        linter.check_untriggered_expect_errors();
+        if doc.id == "unused-disable-directive" {
+            linter.check_unused_disable_directives();
+        }
+
+        linter
+            .errors
+            .into_iter()
+            .map(|e| (e.rule, e.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_rule_is_documented() {
+        for rule in RULES {
+            assert!(
+                find_doc(rule.id).is_some(),
+                "rule '{}' has no docs",
+                rule.id
+            );
+        }
+        for doc in RULE_DOCS {
+            assert!(
+                find_rule(doc.id).is_some(),
+                "docs for unknown rule '{}'",
+                doc.id
+            );
+        }
+    }
+
+    #[test]
+    fn test_bad_examples_trigger_rule() {
+        let mut failures = Vec::new();
+        for doc in RULE_DOCS {
+            for example in doc.bad {
+                let errors = lint_example(doc, example);
+                if !errors
+                    .iter()
+                    .any(|(rule, _)| rule_for_diagnostic(rule).map(|r| r.id) == Some(doc.id))
+                {
+                    failures.push(format!("{}: {:?}", doc.id, errors));
+                }
+            }
+        }
+        assert!(
+            failures.is_empty(),
+            "bad examples that do not trigger their rule:\n{}",
+            failures.join("\n")
+        );
+    }
+
+    #[test]
+    fn test_good_examples_are_clean() {
+        let mut failures = Vec::new();
+        for doc in RULE_DOCS {
+            for example in doc.good {
+                let errors = lint_example(doc, example);
+                if !errors.is_empty() {
+                    failures.push(format!("{}: {:?}", doc.id, errors));
+                }
+            }
+        }
+        assert!(
+            failures.is_empty(),
+            "good examples with errors:\n{}",
+            failures.join("\n")
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let text = explain("no-try-catch").unwrap();
+        assert!(text.contains("no-throw"));
+        assert!(text.contains("Reports:  no-try-catch"));
+        assert!(text.contains("strict"));
+        assert!(explain("no-such-rule").is_none());
+    }
+}
@@ -0,0 +1,526 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Rule category, matching the sections of the README
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    BasicRestrictions,
+    TypeSafety,
+    CodeQuality,
+    ImportExport,
+    NodeCompatibility,
+    FunctionRestrictions,
+    PathRestrictions,
+    SideEffects,
+    Directives,
+    Configuration,
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Category::BasicRestrictions => write!(f, "basic-restrictions"),
+            Category::TypeSafety => write!(f, "type-safety"),
+            Category::CodeQuality => write!(f, "code-quality"),
+            Category::ImportExport => write!(f, "import-export"),
+            Category::NodeCompatibility => write!(f, "node-compatibility"),
+            Category::FunctionRestrictions => write!(f, "function-restrictions"),
+            Category::PathRestrictions => write!(f, "path-restrictions"),
+            Category::SideEffects => write!(f, "side-effects"),
+            Category::Directives => write!(f, "directives"),
+            Category::Configuration => write!(f, "configuration"),
+        }
+    }
+}
+
+/// Default severity of a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// Metadata for a rule
+///
+/// `diagnostics` lists additional ids a rule may report under for distinct
+/// findings (e.g. `no-throw` also reports `no-try-catch`). Directives naming
+/// the rule id cover all of its diagnostics.
+#[derive(Debug)]
+pub struct RuleMeta {
+    pub id: &'static str,
+    pub category: Category,
+    pub severity: Severity,
+    pub description: &'static str,
+    pub fixable: bool,
+    pub diagnostics: &'static [&'static str],
+}
+
+const fn rule(
+    id: &'static str,
+    category: Category,
+    description: &'static str,
+    diagnostics: &'static [&'static str],
+) -> RuleMeta {
+    RuleMeta {
+        id,
+        category,
+        severity: Severity::Error,
+        description,
+        fixable: false,
+        diagnostics,
+    }
+}
+
+impl RuleMeta {
+    /// Mark a rule whose diagnostics carry an automatic fix (`--fix`)
+    const fn fixable(mut self) -> Self {
+        self.fixable = true;
+        self
+    }
+
+    /// Report a rule's diagnostics as warnings, which do not fail the run
+    const fn warning(mut self) -> Self {
+        self.severity = Severity::Warning;
+        self
+    }
+}
+
+/// All rules known to purets
+pub static RULES: &[RuleMeta] = &[
+    // Basic restrictions
+    rule("no-classes", Category::BasicRestrictions, "Prohibits class definitions", &[]),
+    rule("no-enums", Category::BasicRestrictions, "Prohibits enums", &[]),
+    rule(
+        "no-throw",
+        Category::BasicRestrictions,
+        "Prohibits throwing exceptions and try/catch outside Result conversion",
+        &["no-try-catch", "try-must-return-ok", "catch-must-return-err"],
+    ),
+    rule("no-delete", Category::BasicRestrictions, "Prohibits the delete operator", &[]),
+    rule(
+        "no-eval-function",
+        Category::BasicRestrictions,
+        "Prohibits eval() and the Function constructor",
+        &["no-eval", "no-new-function"],
+    ),
+    rule(
+        "no-foreach",
+        Category::BasicRestrictions,
+        "Prohibits forEach (prefer map/filter/reduce)",
+        &[],
+    ),
+    rule("no-do-while", Category::BasicRestrictions, "Prohibits do-while statements", &[]),
+    rule(
+        "no-imperative-loops",
+        Category::BasicRestrictions,
+        "In pure/, prohibits for, for...in and while, and allows for...of only over readonly inputs",
+        &[],
+    ),
+    rule(
+        "no-constant-condition",
+        Category::BasicRestrictions,
+        "Prohibits constant conditions in loops",
+        &[],
+    ),
+    rule(
+        "no-getters-setters",
+        Category::BasicRestrictions,
+        "Prohibits getters and setters",
+        &["no-getters", "no-setters"],
+    ),
+    rule(
+        "no-define-property",
+        Category::BasicRestrictions,
+        "Prohibits Object.defineProperty",
+        &[],
+    ),
+    rule("no-object-assign", Category::BasicRestrictions, "Prohibits Object.assign", &[]),
+    rule(
+        "no-member-assignments",
+        Category::BasicRestrictions,
+        "Prohibits assignments to object members",
+        &[],
+    ),
+    rule(
+        "no-dynamic-access",
+        Category::BasicRestrictions,
+        "Prohibits dynamic property access",
+        &[],
+    ),
+    // Type safety
+    rule(
+        "no-as-cast",
+        Category::TypeSafety,
+        "Prohibits 'as' type casting",
+        &["no-as-upcast", "no-type-assertion"],
+    ),
+    rule(
+        "let-requires-type",
+        Category::TypeSafety,
+        "let variables require type annotations",
+        &[],
+    ),
+    rule(
+        "empty-array-requires-type",
+        Category::TypeSafety,
+        "Empty arrays require type annotations",
+        &[],
+    ),
+    rule(
+        "no-let",
+        Category::TypeSafety,
+        "let bindings that are never reassigned must be const; let is not allowed in pure/",
+        &[],
+    ),
+    rule(
+        "no-param-mutation",
+        Category::TypeSafety,
+        "Function parameters and their properties must not be mutated",
+        &[],
+    ),
+    rule(
+        "prefer-readonly-array",
+        Category::TypeSafety,
+        "Array parameters must be readonly",
+        &[],
+    ),
+    rule("no-mutable-record", Category::TypeSafety, "Record types must be readonly", &[]),
+    rule(
+        "readonly-properties",
+        Category::TypeSafety,
+        "Members in types/ must be readonly and object parameters in pure/ must be Readonly<...>",
+        &[],
+    ),
+    rule(
+        "interface-extends-only",
+        Category::TypeSafety,
+        "Interfaces are only allowed for extending other types",
+        &[],
+    ),
+    rule(
+        "export-const-type-required",
+        Category::TypeSafety,
+        "Exported constants require type annotations and exported let is not allowed",
+        &["no-export-let", "export-const-needs-type"],
+    ),
+    // Code quality
+    rule(
+        "no-unused-variables",
+        Category::CodeQuality,
+        "Prohibits unused variables and imports",
+        &["no-unused-imports"],
+    ),
+    rule("no-unused-map", Category::CodeQuality, "Prohibits unused map return values", &[]),
+    rule("must-use-return-value", Category::CodeQuality, "Return values must be used", &[]),
+    rule(
+        "must-handle-result",
+        Category::CodeQuality,
+        "Result/ResultAsync values must be handled, and _unsafeUnwrap() is only allowed in tests",
+        &[],
+    ),
+    rule(
+        "catch-error-handling",
+        Category::CodeQuality,
+        "catch blocks must handle errors",
+        &[],
+    ),
+    rule(
+        "switch-case-block",
+        Category::CodeQuality,
+        "switch case statements require blocks",
+        &[],
+    ),
+    rule(
+        "switch-exhaustive",
+        Category::CodeQuality,
+        "switch over a discriminated union must end with assertNever and be exhaustive",
+        &[],
+    ),
+    // Import/Export
+    rule(
+        "strict-named-export",
+        Category::ImportExport,
+        "Exports must be named after the file",
+        &[],
+    ),
+    rule(
+        "no-namespace-imports",
+        Category::ImportExport,
+        "Prohibits namespace imports",
+        &[],
+    ),
+    rule("no-reexports", Category::ImportExport, "Prohibits re-exports", &[]),
+    rule(
+        "import-extensions",
+        Category::ImportExport,
+        "Relative import paths require extensions",
+        &[],
+    ),
+    rule("no-http-imports", Category::ImportExport, "Prohibits HTTP imports", &[]),
+    // Node.js compatibility
+    rule("no-require", Category::NodeCompatibility, "Prohibits require", &[]),
+    rule(
+        "no-filename-dirname",
+        Category::NodeCompatibility,
+        "Prohibits __filename/__dirname",
+        &[],
+    ),
+    rule(
+        "no-global-process",
+        Category::NodeCompatibility,
+        "Prohibits the global process object",
+        &[],
+    ),
+    rule(
+        "node-import-style",
+        Category::NodeCompatibility,
+        "Node.js built-in modules require the node: prefix",
+        &[],
+    ),
+    rule(
+        "forbidden-libraries",
+        Category::NodeCompatibility,
+        "Prohibits forbidden libraries",
+        &[],
+    ),
+    // Function restrictions
+    rule(
+        "max-function-params",
+        Category::FunctionRestrictions,
+        "Maximum 3 function parameters",
+        &[],
+    ),
+    rule(
+        "no-this-in-functions",
+        Category::FunctionRestrictions,
+        "Prohibits this in functions",
+        &[],
+    ),
+    rule(
+        "no-side-effect-functions",
+        Category::FunctionRestrictions,
+        "Restricts function names with side effects",
+        &[],
+    ),
+    rule(
+        "one-public-function",
+        Category::FunctionRestrictions,
+        "Each file exports a single public function",
+        &[],
+    ),
+    rule(
+        "export-requires-jsdoc",
+        Category::FunctionRestrictions,
+        "Exported functions require JSDoc",
+        &[],
+    ),
+    rule(
+        "jsdoc-param-match",
+        Category::FunctionRestrictions,
+        "JSDoc @param tags must match parameters",
+        &[
+            "jsdoc-param-count",
+            "jsdoc-param-missing",
+            "jsdoc-param-unknown",
+            "param-missing-type",
+        ],
+    ),
+    // Path-based restrictions
+    rule(
+        "path-based-restrictions",
+        Category::PathRestrictions,
+        "Directory-based restrictions for pure/, types/ and io/",
+        &[],
+    ),
+    // Side effects
+    rule(
+        "no-top-level-side-effects",
+        Category::SideEffects,
+        "Prohibits side effects at module top level",
+        &[],
+    ),
+    // Directives
+    rule(
+        "allow-directives",
+        Category::Directives,
+        "@allow directives must be valid and used",
+        &["capability-policy"],
+    ),
+    rule(
+        "unused-expect-error",
+        Category::Directives,
+        "purets-expect-error directives must be triggered",
+        &[],
+    ),
+    rule(
+        "unknown-directive-rule",
+        Category::Directives,
+        "purets-disable-*/purets-expect-error comments must name known rules",
+        &[],
+    ),
+    rule(
+        "unused-disable-directive",
+        Category::Directives,
+        "purets-disable-* comments must suppress something (with --report-unused-disable-directives)",
+        &[],
+    )
+    .fixable(),
+    rule(
+        "missing-disable-reason",
+        Category::Directives,
+        "purets-disable-* comments must give a reason (with requireDisableReason)",
+        &[],
+    ),
+    // Configuration files
+    rule(
+        "tsconfig-strict",
+        Category::Configuration,
+        "tsconfig.json must enable strict type checking and keep unreachable code and unused labels errors",
+        &[],
+    ),
+    rule(
+        "tsconfig-module-syntax",
+        Category::Configuration,
+        "tsconfig.json must enable allowImportingTsExtensions and verbatimModuleSyntax",
+        &[],
+    ),
+    rule(
+        "tsconfig-recommended",
+        Category::Configuration,
+        "Recommends additional strictness options and an ES2020+ module and target",
+        &[],
+    )
+    .warning(),
+    rule(
+        "tsconfig-extends",
+        Category::Configuration,
+        "Every tsconfig.json extends entry must resolve to a file",
+        &[],
+    ),
+    rule(
+        "package-type-module",
+        Category::Configuration,
+        "package.json must set \"type\": \"module\"",
+        &[],
+    ),
+    rule(
+        "package-exports",
+        Category::Configuration,
+        "package.json exports and types must be built from files under src/, with '.' from src/index.ts",
+        &["package-exports-entry"],
+    ),
+    rule(
+        "package-side-effects",
+        Category::Configuration,
+        "\"sideEffects\": false requires sources without top-level side effects",
+        &[],
+    ),
+    rule(
+        "deno-strict",
+        Category::Configuration,
+        "deno.json must not turn off the strict compiler options Deno enables by default",
+        &[],
+    ),
+    rule(
+        "deno-lint-fmt",
+        Category::Configuration,
+        "deno.json lint and fmt must cover src/ and keep the recommended lint rules",
+        &[],
+    ),
+    rule(
+        "deno-http-imports",
+        Category::Configuration,
+        "deno.json import map entries must not point at http(s) URLs",
+        &[],
+    ),
+    rule(
+        "forbidden-dependencies",
+        Category::Configuration,
+        "package.json must not depend on forbidden libraries or ones with built-in alternatives",
+        &["prefer-alternative-dependency"],
+    ),
+];
+
+/// Find a rule by its id
+pub fn find_rule(id: &str) -> Option<&'static RuleMeta> {
+    RULES.iter().find(|rule| rule.id == id)
+}
+
+/// Find the rule that reports a diagnostic id (a rule id or one of its sub-diagnostics)
+pub fn rule_for_diagnostic(id: &str) -> Option<&'static RuleMeta> {
+    RULES
+        .iter()
+        .find(|rule| rule.id == id || rule.diagnostics.contains(&id))
+}
+
+/// Whether a name may be used in directives and `Linter::add_error`
+pub fn is_known(id: &str) -> bool {
+    rule_for_diagnostic(id).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_ids_are_unique() {
+        let mut seen = HashSet::new();
+        for rule in RULES {
+            assert!(seen.insert(rule.id), "duplicate rule id '{}'", rule.id);
+            for diagnostic in rule.diagnostics {
+                assert!(seen.insert(diagnostic), "duplicate id '{}'", diagnostic);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(
+            find_rule("no-throw").unwrap().category,
+            Category::BasicRestrictions
+        );
+        assert!(find_rule("no-try-catch").is_none());
+        assert_eq!(rule_for_diagnostic("no-try-catch").unwrap().id, "no-throw");
+        assert_eq!(
+            rule_for_diagnostic("import-extensions").unwrap().id,
+            "import-extensions"
+        );
+        assert!(!is_known("import-extensions-required"));
+        assert!(!is_known("no-console"));
+    }
+
+    #[test]
+    fn test_directive_rule_names() {
+        use crate::test_utils::test::check_rule;
+
+        let source = r#"
+// purets-disable-next-line no-throw, no-console
+try { run(); } catch (e) {}
+// purets-expect-error import-extensions-required
+import { a } from "./a";
+"#;
+        let errors = check_rule(source, |linter, program| {
+            crate::rules::check_no_throw(linter, program);
+            linter.check_untriggered_expect_errors();
+        });
+        assert_eq!(
+            errors,
+            vec![
+                "Unknown rule 'no-console' in purets-disable directive".to_string(),
+                "Unknown rule 'import-extensions-required' in purets-expect-error directive"
+                    .to_string(),
+            ]
+        );
+    }
+}
@@ -1,365 +1,291 @@
 use oxc::ast::ast::*;
-
+use oxc::ast::AstKind;
+use oxc::span::Span;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::capabilities::{AllowScopes, Capabilities, Ceiling, Usage};
+use crate::rule::{run_rule, Rule};
+use crate::rules::custom_rule::callee_path;
 use crate::Linter;
 
-#[derive(Debug, Clone, Default)]
-pub struct AllowedFeatures {
-    pub timers: bool,
-    pub console: bool,
-    pub net: bool,
-    pub dom: bool,
-    pub throws: bool,
-}
-
-#[derive(Debug, Clone, Default)]
-pub struct UsedFeatures {
-    pub timers: bool,
-    pub console: bool,
-    pub net: bool,
-    pub dom: bool,
-    pub throws: bool,
+/// Requires `@allow` directives for the capabilities a file uses (DOM,
+/// network, timers, console, file system and so on), and reports directives
+/// that are unknown, never used, or could be moved from the file to the one
+/// function that uses them
+///
+/// Throw statements and try/catch without `@allow throws` are reported by
+/// `no-throw`; with the directive, only Error instances may be thrown.
+#[derive(Default)]
+pub struct AllowDirectives {
+    capabilities: Arc<Capabilities>,
+    allow: AllowScopes,
+    // Functions (None for code outside them) using each file-level directive
+    file_used: HashMap<String, HashSet<Option<usize>>>,
+    // Function-level directives used, by function index
+    function_used: Vec<HashSet<String>>,
 }
 
-impl AllowedFeatures {
-    pub fn from_jsdoc(source_text: &str) -> Self {
-        let mut features = Self::default();
-
-        // Find the first JSDoc comment
-        if let Some(jsdoc_start) = source_text.find("/**") {
-            if let Some(jsdoc_end) = source_text[jsdoc_start..].find("*/") {
-                let jsdoc = &source_text[jsdoc_start..jsdoc_start + jsdoc_end + 2];
-
-                // Parse @allow directives
-                for line in jsdoc.lines() {
-                    let trimmed = line.trim();
-                    if trimmed.starts_with("* @allow ") || trimmed.starts_with("*@allow ") {
-                        let allow_text = trimmed
-                            .trim_start_matches("*")
-                            .trim()
-                            .trim_start_matches("@allow")
-                            .trim();
-
-                        match allow_text {
-                            "timers" => features.timers = true,
-                            "console" => features.console = true,
-                            "net" => features.net = true,
-                            "dom" => features.dom = true,
-                            "throws" => features.throws = true,
-                            _ => {}
-                        }
-                    }
+impl AllowDirectives {
+    /// Mark the narrowest directive allowing one of the capabilities at an
+    /// offset as used; false when none of them is allowed
+    fn mark_used(&mut self, linter: &mut Linter, names: &[&str], offset: u32) -> bool {
+        let function = self.allow.function_at(offset);
+        if let Some(index) = function {
+            let allowed = &self.allow.functions[index].allowed;
+            let used: Vec<&str> = names
+                .iter()
+                .copied()
+                .filter(|n| allowed.allows(n))
+                .collect();
+            if !used.is_empty() {
+                for name in used {
+                    linter.used_capabilities.insert(name.to_string());
+                    self.function_used[index].insert(name.to_string());
                 }
+                return true;
             }
         }
 
-        features
+        let used: Vec<&str> = names
+            .iter()
+            .copied()
+            .filter(|name| self.allow.file.allows(name))
+            .collect();
+        for name in &used {
+            linter.used_capabilities.insert(name.to_string());
+            self.file_used
+                .entry(name.to_string())
+                .or_default()
+                .insert(function);
+        }
+        !used.is_empty()
     }
-}
 
-pub fn check_allow_directives(linter: &mut Linter, program: &Program) -> UsedFeatures {
-    use oxc::ast_visit::Visit;
-
-    let allowed = AllowedFeatures::from_jsdoc(&linter.source_text);
-
-    struct AllowDirectiveVisitor<'a, 'b> {
-        linter: &'a mut Linter,
-        allowed: AllowedFeatures,
-        used: UsedFeatures,
-        in_function: bool,
-        _phantom: std::marker::PhantomData<&'b ()>,
+    /// Mark the capabilities a usage needs as used, or report it when none
+    /// of them is allowed
+    fn require(&mut self, linter: &mut Linter, usage: Usage, span: Span) {
+        let capabilities = Arc::clone(&self.capabilities);
+        let required = capabilities.required_by(usage);
+        let Some(first) = required.first() else {
+            return;
+        };
+
+        if !self.mark_used(linter, &required, span.start) {
+            linter.used_capabilities.insert(first.to_string());
+            linter.add_error(
+                "allow-directives".to_string(),
+                format!("{} requires '@allow {}' directive", usage, first),
+                span,
+            );
+        }
     }
 
-    impl<'a, 'b> Visit<'b> for AllowDirectiveVisitor<'a, 'b> {
-        fn visit_function(&mut self, func: &Function<'b>, _: oxc::syntax::scope::ScopeFlags) {
-            let was_in_function = self.in_function;
-            self.in_function = true;
-
-            oxc::ast_visit::walk::walk_function(
-                self,
-                func,
-                oxc::syntax::scope::ScopeFlags::empty(),
-            );
+    /// The one function using a file-level directive
+    fn only_user(&self, name: &str) -> Option<&str> {
+        let users = self.file_used.get(name)?;
+        match users.iter().next() {
+            Some(Some(index)) if users.len() == 1 => Some(&self.allow.functions[*index].name),
+            _ => None,
+        }
+    }
 
-            self.in_function = was_in_function;
+    /// Diagnostic id and message for a directive naming an unknown
+    /// capability or one above the file's ceiling
+    fn invalid_directive(
+        &self,
+        ceiling: Option<Ceiling>,
+        name: &str,
+    ) -> Option<(&'static str, String)> {
+        if self.capabilities.get(name).is_none() {
+            let message = format!("Unknown capability '{}' in '@allow' directive", name);
+            return Some(("allow-directives", message));
         }
+        let ceiling = ceiling.filter(|ceiling| !ceiling.permits(name))?;
+        let message = match ceiling.files {
+            Some(files) => format!(
+                "'@allow {}' is not permitted by the capability policy for '{}'",
+                name, files
+            ),
+            None => format!("'@allow {}' is not permitted in pure/ files", name),
+        };
+        Some(("capability-policy", message))
+    }
 
-        fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'b>) {
-            let was_in_function = self.in_function;
-            self.in_function = true;
+    fn check_source(&mut self, linter: &mut Linter, source: &str, span: Span) {
+        self.require(linter, Usage::Import(source), span);
+    }
 
-            oxc::ast_visit::walk::walk_arrow_function_expression(self, arrow);
+    /// Record a throw or try/catch; false when `@allow throws` does not
+    /// cover it
+    fn allows_throws(&mut self, linter: &mut Linter, offset: u32) -> bool {
+        let allowed = self.mark_used(linter, &["throws"], offset);
+        if !allowed {
+            linter.used_capabilities.insert("throws".to_string());
+        }
+        allowed
+    }
 
-            self.in_function = was_in_function;
+    fn check_throw(&mut self, linter: &mut Linter, throw_stmt: &ThrowStatement) {
+        if !self.allows_throws(linter, throw_stmt.span.start) {
+            return;
         }
 
-        fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'b>) {
-            let name = ident.name.as_str();
-
-            // Check DOM access
-            const DOM_GLOBALS: &[&str] = &[
-                "document",
-                "window",
-                "navigator",
-                "location",
-                "localStorage",
-                "sessionStorage",
-                "history",
-                "screen",
-                "alert",
-                "confirm",
-                "prompt",
-            ];
-
-            if DOM_GLOBALS.contains(&name) {
-                if !self.allowed.dom {
-                    self.linter.add_error(
+        // Check that only custom Error types are thrown (not plain Error)
+        if let Expression::NewExpression(new_expr) = &throw_stmt.argument {
+            if let Expression::Identifier(id) = &new_expr.callee {
+                let name = id.name.as_str();
+                if name == "Error" {
+                    linter.add_error(
                         "allow-directives".to_string(),
-                        format!("Access to '{}' requires '@allow dom' directive", name),
-                        ident.span,
+                        "Cannot throw plain Error. Define a custom error class in io/errors/*.ts"
+                            .to_string(),
+                        throw_stmt.span,
                     );
-                } else {
-                    self.used.dom = true;
-                }
-            }
-
-            // Check network access
-            const NET_GLOBALS: &[&str] = &[
-                "fetch",
-                "XMLHttpRequest",
-                "WebSocket",
-                "EventSource",
-                "ServiceWorker",
-            ];
-
-            if NET_GLOBALS.contains(&name) {
-                if !self.allowed.net {
-                    self.linter.add_error(
+                } else if !name.ends_with("Error") {
+                    linter.add_error(
                         "allow-directives".to_string(),
-                        format!("Access to '{}' requires '@allow net' directive", name),
-                        ident.span,
+                        format!("Only Error types can be thrown (got '{}')", name),
+                        throw_stmt.span,
                     );
-                } else {
-                    self.used.net = true;
                 }
             }
-
-            oxc::ast_visit::walk::walk_identifier_reference(self, ident);
+        } else if !matches!(&throw_stmt.argument, Expression::Identifier(_)) {
+            // Allow throwing identifiers (like: throw error;)
+            linter.add_error(
+                "allow-directives".to_string(),
+                "Only Error instances can be thrown".to_string(),
+                throw_stmt.span,
+            );
         }
+    }
+}
+
+impl<'a> Rule<'a> for AllowDirectives {
+    fn id(&self) -> &str {
+        "allow-directives"
+    }
+
+    fn on_program_start(&mut self, linter: &mut Linter, program: &'a Program<'a>) {
+        self.capabilities = Arc::clone(&linter.capabilities);
+        self.allow = AllowScopes::from_program(&linter.source_text, program);
+        self.file_used.clear();
+        self.function_used = vec![HashSet::new(); self.allow.functions.len()];
+    }
 
-        fn visit_call_expression(&mut self, call: &CallExpression<'b>) {
-            // Check timer functions
-            if let Expression::Identifier(ident) = &call.callee {
-                const TIMER_FUNCTIONS: &[&str] = &[
-                    "setTimeout",
-                    "setInterval",
-                    "setImmediate",
-                    "requestAnimationFrame",
-                    "requestIdleCallback",
-                    "clearTimeout",
-                    "clearInterval",
-                    "clearImmediate",
-                    "cancelAnimationFrame",
-                    "cancelIdleCallback",
-                ];
-
-                if TIMER_FUNCTIONS.contains(&ident.name.as_str()) {
-                    if !self.allowed.timers {
-                        self.linter.add_error(
-                            "allow-directives".to_string(),
-                            format!("Use of '{}' requires '@allow timers' directive", ident.name),
-                            call.span,
-                        );
-                    } else {
-                        self.used.timers = true;
-                    }
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::IdentifierReference(ident) => {
+                self.require(linter, Usage::Global(&ident.name), ident.span);
+            }
+            AstKind::StaticMemberExpression(member) => {
+                if let Some(object) = callee_path(&member.object) {
+                    let path = format!("{}.{}", object, member.property.name);
+                    self.require(linter, Usage::Member(&path), member.span);
                 }
             }
+            AstKind::CallExpression(call) => {
+                if let Some(path) = callee_path(&call.callee) {
+                    self.require(linter, Usage::Call(&path), call.span);
+                }
 
-            // Check console access
-            if let Some(MemberExpression::StaticMemberExpression(static_member)) =
-                call.callee.as_member_expression()
-            {
-                if let Expression::Identifier(obj) = &static_member.object {
-                    if obj.name == "console" {
-                        if !self.allowed.console {
-                            self.linter.add_error(
-                                "allow-directives".to_string(),
-                                "Use of 'console' requires '@allow console' directive".to_string(),
-                                call.span,
-                            );
-                        } else {
-                            self.used.console = true;
-                        }
-                    }
+                let is_require =
+                    matches!(&call.callee, Expression::Identifier(id) if id.name == "require");
+                if let (true, Some(Argument::StringLiteral(source))) =
+                    (is_require, call.arguments.first())
+                {
+                    self.check_source(linter, &source.value, call.span);
                 }
             }
-
-            oxc::ast_visit::walk::walk_call_expression(self, call);
-        }
-
-        fn visit_throw_statement(&mut self, throw_stmt: &ThrowStatement<'b>) {
-            // Check if throw is allowed
-            if !self.allowed.throws {
-                // Check if it's throwing an Error constructor
-                if let Expression::NewExpression(new_expr) = &throw_stmt.argument {
-                    if let Expression::Identifier(id) = &new_expr.callee {
-                        let name = id.name.as_str();
-                        if name.ends_with("Error") {
-                            self.linter.add_error(
-                                "allow-directives".to_string(),
-                                format!("Throwing '{}' requires '@allow throws' directive", name),
-                                throw_stmt.span,
-                            );
-                        }
-                    }
-                } else {
-                    self.linter.add_error(
-                        "allow-directives".to_string(),
-                        "Throw statements require '@allow throws' directive".to_string(),
-                        throw_stmt.span,
-                    );
+            AstKind::NewExpression(new_expr) => {
+                if let Some(path) = callee_path(&new_expr.callee) {
+                    self.require(linter, Usage::New(&path), new_expr.span);
                 }
-            } else {
-                self.used.throws = true;
-
-                // Check that only custom Error types are thrown (not plain Error)
-                if let Expression::NewExpression(new_expr) = &throw_stmt.argument {
-                    if let Expression::Identifier(id) = &new_expr.callee {
-                        let name = id.name.as_str();
-                        if name == "Error" {
-                            self.linter.add_error(
-                                "allow-directives".to_string(),
-                                "Cannot throw plain Error. Define a custom error class in io/errors/*.ts".to_string(),
-                                throw_stmt.span,
-                            );
-                        } else if !name.ends_with("Error") {
-                            self.linter.add_error(
-                                "allow-directives".to_string(),
-                                format!("Only Error types can be thrown (got '{}')", name),
-                                throw_stmt.span,
-                            );
-                        }
-                    }
-                } else if !matches!(&throw_stmt.argument, Expression::Identifier(_)) {
-                    // Allow throwing identifiers (like: throw error;)
-                    self.linter.add_error(
-                        "allow-directives".to_string(),
-                        "Only Error instances can be thrown".to_string(),
-                        throw_stmt.span,
-                    );
+            }
+            AstKind::ImportDeclaration(import) if !import.import_kind.is_type() => {
+                self.check_source(linter, &import.source.value, import.span);
+            }
+            AstKind::ExportNamedDeclaration(export) if !export.export_kind.is_type() => {
+                if let Some(source) = &export.source {
+                    self.check_source(linter, &source.value, export.span);
                 }
             }
-
-            oxc::ast_visit::walk::walk_throw_statement(self, throw_stmt);
-        }
-
-        fn visit_ts_type_reference(&mut self, type_ref: &TSTypeReference<'b>) {
-            if let TSTypeName::IdentifierReference(id) = &type_ref.type_name {
-                let name = id.name.as_str();
-
-                // Check DOM type access
-                if !self.allowed.dom {
-                    const DOM_TYPES: &[&str] = &[
-                        "HTMLElement",
-                        "HTMLDivElement",
-                        "HTMLInputElement",
-                        "Document",
-                        "Window",
-                        "Navigator",
-                        "Location",
-                        "Element",
-                        "Node",
-                        "Event",
-                        "MouseEvent",
-                        "KeyboardEvent",
-                        "DOMParser",
-                        "XMLSerializer",
-                        "Storage",
-                    ];
-
-                    if DOM_TYPES.contains(&name) {
-                        self.linter.add_error(
-                            "allow-directives".to_string(),
-                            format!("Type '{}' requires '@allow dom' directive", name),
-                            type_ref.span,
-                        );
-                    }
+            AstKind::ExportAllDeclaration(export) if !export.export_kind.is_type() => {
+                self.check_source(linter, &export.source.value, export.span);
+            }
+            AstKind::ImportExpression(import) => {
+                if let Expression::StringLiteral(source) = &import.source {
+                    self.check_source(linter, &source.value, import.span);
                 }
-
-                // Check network type access
-                if !self.allowed.net {
-                    const NET_TYPES: &[&str] = &[
-                        "Response",
-                        "Request",
-                        "Headers",
-                        "RequestInit",
-                        "XMLHttpRequest",
-                        "WebSocket",
-                        "EventSource",
-                        "ServiceWorker",
-                        "ServiceWorkerRegistration",
-                    ];
-
-                    if NET_TYPES.contains(&name) {
-                        self.linter.add_error(
-                            "allow-directives".to_string(),
-                            format!("Type '{}' requires '@allow net' directive", name),
-                            type_ref.span,
-                        );
-                    }
+            }
+            AstKind::ThrowStatement(throw_stmt) => self.check_throw(linter, throw_stmt),
+            AstKind::TryStatement(stmt) => {
+                self.allows_throws(linter, stmt.span.start);
+            }
+            AstKind::TSTypeReference(type_ref) => {
+                if let TSTypeName::IdentifierReference(id) = &type_ref.type_name {
+                    self.require(linter, Usage::Type(&id.name), type_ref.span);
                 }
             }
-
-            oxc::ast_visit::walk::walk_ts_type_reference(self, type_ref);
+            _ => {}
         }
     }
 
-    let mut visitor = AllowDirectiveVisitor {
-        linter,
-        allowed: allowed.clone(),
-        used: UsedFeatures::default(),
-        in_function: false,
-        _phantom: std::marker::PhantomData,
-    };
-
-    visitor.visit_program(program);
+    fn on_program_end(&mut self, linter: &mut Linter, _program: &'a Program<'a>) {
+        let capabilities = Arc::clone(&self.capabilities);
+        let ceiling = capabilities.ceiling(&linter.path);
+
+        // Check for invalid, unused and too broad file-level @allow directives
+        for name in self.allow.file.names() {
+            let (rule, message) = if let Some(invalid) = self.invalid_directive(ceiling, name) {
+                invalid
+            } else if !self.file_used.contains_key(name) {
+                (
+                    "allow-directives",
+                    format!("Unused '@allow {}' directive", name),
+                )
+            } else if let Some(function) = self.only_user(name) {
+                (
+                    "allow-directives",
+                    format!(
+                        "'@allow {}' is only used in '{}'; move it to the JSDoc of '{}'",
+                        name, function, function
+                    ),
+                )
+            } else {
+                continue;
+            };
+            linter.add_error(rule.to_string(), message, Span::new(0, 0));
+        }
 
-    // Check for unused @allow directives
-    if allowed.dom && !visitor.used.dom {
-        visitor.linter.add_error(
-            "allow-directives".to_string(),
-            "Unused '@allow dom' directive".to_string(),
-            oxc::span::Span::new(0, 0),
-        );
-    }
-    if allowed.net && !visitor.used.net {
-        visitor.linter.add_error(
-            "allow-directives".to_string(),
-            "Unused '@allow net' directive".to_string(),
-            oxc::span::Span::new(0, 0),
-        );
-    }
-    if allowed.timers && !visitor.used.timers {
-        visitor.linter.add_error(
-            "allow-directives".to_string(),
-            "Unused '@allow timers' directive".to_string(),
-            oxc::span::Span::new(0, 0),
-        );
-    }
-    if allowed.console && !visitor.used.console {
-        visitor.linter.add_error(
-            "allow-directives".to_string(),
-            "Unused '@allow console' directive".to_string(),
-            oxc::span::Span::new(0, 0),
-        );
+        // Function-level directives are reported at their JSDoc comment
+        for (function, used) in self.allow.functions.iter().zip(&self.function_used) {
+            let span = function.jsdoc.unwrap_or(function.span);
+            for name in function.allowed.names() {
+                let (rule, message) = if let Some(invalid) = self.invalid_directive(ceiling, name) {
+                    invalid
+                } else if !used.contains(name) {
+                    (
+                        "allow-directives",
+                        format!("Unused '@allow {}' directive", name),
+                    )
+                } else {
+                    continue;
+                };
+                linter.add_error(rule.to_string(), message, span);
+            }
+        }
     }
+}
 
-    visitor.used
+pub fn check_allow_directives(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, AllowDirectives::default());
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::PuretsConfig;
     use crate::Linter;
     use oxc::allocator::Allocator;
     use oxc::parser::Parser;
@@ -367,11 +293,20 @@ mod tests {
     use std::path::Path;
 
     fn parse_and_check(source: &str) -> Vec<String> {
+        check_with(source, Capabilities::builtin())
+    }
+
+    fn check_with(source: &str, capabilities: Arc<Capabilities>) -> Vec<String> {
+        check_file("test.ts", source, capabilities)
+    }
+
+    fn check_file(path: &str, source: &str, capabilities: Arc<Capabilities>) -> Vec<String> {
         let allocator = Allocator::default();
-        let source_type = SourceType::from_path(Path::new("test.ts")).unwrap();
+        let source_type = SourceType::from_path(Path::new(path)).unwrap();
         let ret = Parser::new(&allocator, source, source_type).parse();
 
-        let mut linter = Linter::new(Path::new("test.ts"), source, false);
+        let mut linter =
+            Linter::new(Path::new(path), source, false).with_capabilities(capabilities);
         check_allow_directives(&mut linter, &ret.program);
 
         linter.errors.into_iter().map(|e| e.message).collect()
@@ -586,4 +521,241 @@ mod tests {
         // Should not have any access errors
         assert!(!errors.iter().any(|e| e.contains("requires '@allow")));
     }
+
+    #[test]
+    fn test_node_module_imports() {
+        let source = r#"
+            import fs from "node:fs/promises";
+            import type { Stats } from "node:fs";
+            import { spawn } from "child_process";
+        "#;
+        let errors = parse_and_check(source);
+        assert_eq!(
+            errors,
+            vec![
+                "Import of 'node:fs/promises' requires '@allow fs' directive",
+                "Import of 'child_process' requires '@allow child_process' directive",
+            ]
+        );
+
+        let allowed = "/**\n * @allow fs\n * @allow child_process\n */\n".to_string() + source;
+        assert!(parse_and_check(&allowed).is_empty());
+    }
+
+    #[test]
+    fn test_env_random_and_time() {
+        let source = r#"
+            /**
+             * @allow env
+             */
+            function seed(): string {
+                const id = crypto.randomUUID();
+                return process.env.SEED + new Date().toISOString() + id;
+            }
+        "#;
+        let errors = parse_and_check(source);
+        assert_eq!(
+            errors,
+            vec![
+                "Use of 'crypto.randomUUID' requires '@allow random' directive",
+                "Use of 'new Date()' requires '@allow time' directive",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_storage_is_not_dom() {
+        let source = r#"
+            /**
+             * @allow dom
+             */
+            function save() {
+                localStorage.setItem("a", "b");
+            }
+        "#;
+        let errors = parse_and_check(source);
+        assert!(errors
+            .contains(&"Access to 'localStorage' requires '@allow storage' directive".to_string()));
+        assert!(errors.contains(&"Unused '@allow dom' directive".to_string()));
+    }
+
+    #[test]
+    fn test_custom_capability() {
+        let config: PuretsConfig =
+            serde_json::from_str(r#"{ "capabilities": { "db": { "modules": ["pg"] } } }"#).unwrap();
+        let capabilities = config.capabilities().unwrap();
+
+        let errors = check_with(r#"import { Pool } from "pg";"#, capabilities.clone());
+        assert_eq!(
+            errors,
+            vec!["Import of 'pg' requires '@allow db' directive"]
+        );
+
+        let source = "/**\n * @allow db\n */\nimport { Pool } from \"pg\";\n";
+        assert!(check_with(source, capabilities).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_capability() {
+        let source = r#"
+            /**
+             * @allow mutations
+             */
+            function add(a: number, b: number): number {
+                return a + b;
+            }
+        "#;
+        let errors = parse_and_check(source);
+        assert_eq!(
+            errors,
+            vec!["Unknown capability 'mutations' in '@allow' directive"]
+        );
+    }
+
+    #[test]
+    fn test_function_level_allow() {
+        let source = r#"/**
+ * Module header.
+ */
+import { Result } from "neverthrow";
+
+/**
+ * @allow console
+ */
+export function debug(message: string): void {
+  console.log(message);
+}
+
+const quiet = (message: string): void => {
+  console.log(message);
+};
+"#;
+        let errors = parse_and_check(source);
+        assert_eq!(
+            errors,
+            vec!["Use of 'console.log' requires '@allow console' directive"]
+        );
+    }
+
+    #[test]
+    fn test_first_jsdoc_of_a_function_is_not_file_level() {
+        let source = r#"/**
+ * @allow console
+ */
+export function debug(message: string): void {
+  console.log(message);
+}
+
+/**
+ * Logs a warning.
+ */
+export function warn(message: string): void {
+  console.warn(message);
+}
+"#;
+        let errors = parse_and_check(source);
+        assert_eq!(
+            errors,
+            vec!["Use of 'console.warn' requires '@allow console' directive"]
+        );
+    }
+
+    #[test]
+    fn test_unused_function_level_allow() {
+        let source = r#"/**
+ * Module header.
+ */
+import { Result } from "neverthrow";
+
+/**
+ * @allow net
+ */
+function add(a: number, b: number): number {
+  return a + b;
+}
+"#;
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+        let mut linter = Linter::new(Path::new("test.ts"), source, false);
+        check_allow_directives(&mut linter, &ret.program);
+
+        assert_eq!(linter.errors.len(), 1);
+        assert_eq!(linter.errors[0].message, "Unused '@allow net' directive");
+        let jsdoc = source.find("/**\n * @allow net").unwrap() as u32;
+        assert_eq!(linter.errors[0].span.start, jsdoc);
+    }
+
+    #[test]
+    fn test_file_level_allow_could_be_narrowed() {
+        let source = r#"/**
+ * @allow console
+ * @allow fs
+ */
+import fs from "node:fs";
+
+/**
+ * Logs.
+ */
+function log(message: string): void {
+  console.log(message);
+}
+
+/**
+ * Adds.
+ */
+function add(a: number, b: number): number {
+  return a + b;
+}
+"#;
+        let errors = parse_and_check(source);
+        assert_eq!(
+            errors,
+            vec!["'@allow console' is only used in 'log'; move it to the JSDoc of 'log'"]
+        );
+    }
+
+    #[test]
+    fn test_pure_files_cannot_allow_capabilities() {
+        let source = "/**\n * @allow net\n */\nexport const get = () => fetch(\"/x\");\n";
+        let errors = check_file("src/pure/get.ts", source, Capabilities::builtin());
+        assert_eq!(errors, vec!["'@allow net' is not permitted in pure/ files"]);
+    }
+
+    #[test]
+    fn test_capability_policy() {
+        let mut config: PuretsConfig = serde_json::from_str(
+            r#"{ "capabilityPolicy": [
+                { "files": ["src/io/**"], "allow": [] },
+                { "files": ["src/io/http/**"], "allow": ["net"] },
+                { "files": ["src/pure/**"], "allow": ["console"] },
+                { "files": ["src/io/cli/*"], "allow": ["net", "console"] }
+            ] }"#,
+        )
+        .unwrap();
+        config.root = Path::new(".").to_path_buf();
+        let capabilities = config.capabilities().unwrap();
+
+        let source = "/**\n * @allow net\n */\nexport const get = () => fetch(\"/x\");\n";
+        assert!(check_file("src/io/http/get.ts", source, capabilities.clone()).is_empty());
+        assert_eq!(
+            check_file("./src/io/db/get.ts", source, capabilities.clone()),
+            vec!["'@allow net' is not permitted by the capability policy for 'src/io/**'"]
+        );
+
+        // `*` does not cross directories: nested files keep the `src/io/**` entry
+        let source = "/**\n * @allow console\n */\nconsole.log(1);\n";
+        assert!(check_file("src/io/cli/run.ts", source, capabilities.clone()).is_empty());
+        assert_eq!(
+            check_file(
+                "src/io/cli/deep/nested/run.ts",
+                source,
+                capabilities.clone()
+            ),
+            vec!["'@allow console' is not permitted by the capability policy for 'src/io/**'"]
+        );
+
+        // A matching entry replaces the built-in pure/ policy
+        let source = "/**\n * @allow console\n */\nconsole.log(1);\n";
+        assert!(check_file("src/pure/log.ts", source, capabilities).is_empty());
+    }
 }
@@ -1,103 +1,111 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
+use crate::rules::no_throw::is_result_conversion;
 use crate::Linter;
 
-pub fn check_catch_error_handling(linter: &mut Linter, program: &Program) {
-    struct CatchErrorChecker<'a> {
-        linter: &'a mut Linter,
+/// Requires catch clauses to bind the error and narrow it with a type guard,
+/// unless the try/catch converts to a Result (`ok(...)` / `err(...)`)
+#[derive(Default)]
+pub struct CatchErrorHandling;
+
+impl<'a> Rule<'a> for CatchErrorHandling {
+    fn id(&self) -> &str {
+        "catch-error-handling"
     }
 
-    impl<'a> Visit<'a> for CatchErrorChecker<'a> {
-        fn visit_catch_clause(&mut self, clause: &CatchClause<'a>) {
-            // Check if catch has a parameter
-            if let Some(param) = &clause.param {
-                // Get the parameter name if it's a simple identifier
-                if let BindingPatternKind::BindingIdentifier(ident) = &param.pattern.kind {
-                    let error_name = ident.name.as_str();
-
-                    // Check the catch block body for proper error handling
-                    if let Some(body) = &clause.body.body.first() {
-                        let has_proper_check = match body {
-                            Statement::IfStatement(if_stmt) => {
-                                // Check if it's an Error.isError() check or instanceof Error check
-                                self.check_if_has_error_check(if_stmt, error_name)
-                            }
-                            _ => false,
-                        };
-
-                        if !has_proper_check {
-                            self.linter.add_error(
-                                "catch-error-handling".to_string(),
-                                format!(
-                                    "Catch block must check error type with 'if (Error.isError({}))' or similar type guard, then wrap with neverthrow's err()",
-                                    error_name
-                                ),
-                                clause.span,
-                            );
-                        }
-                    } else {
-                        // Empty catch block
-                        self.linter.add_error(
-                            "catch-error-handling".to_string(),
-                            "Empty catch block is not allowed. Must handle error properly with type checking and neverthrow's err()".to_string(),
-                            clause.span,
-                        );
-                    }
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::TryStatement(stmt) = kind else {
+            return;
+        };
+        let Some(clause) = &stmt.handler else {
+            return;
+        };
+        if is_result_conversion(stmt) {
+            return;
+        }
+        // Check if catch has a parameter
+        let Some(param) = &clause.param else {
+            linter.add_error(
+                "catch-error-handling".to_string(),
+                "Catch clause must have an error parameter to handle errors properly".to_string(),
+                clause.span,
+            );
+            return;
+        };
+        // Only simple identifiers are checked
+        let BindingPatternKind::BindingIdentifier(ident) = &param.pattern.kind else {
+            return;
+        };
+        let error_name = ident.name.as_str();
+
+        // Check the catch block body for proper error handling
+        match clause.body.body.first() {
+            Some(body) => {
+                let has_proper_check = match body {
+                    Statement::IfStatement(if_stmt) => has_error_check(if_stmt, error_name),
+                    _ => false,
+                };
+
+                if !has_proper_check {
+                    linter.add_error(
+                        "catch-error-handling".to_string(),
+                        format!(
+                            "Catch block must check error type with 'if (Error.isError({}))' or similar type guard, then wrap with neverthrow's err()",
+                            error_name
+                        ),
+                        clause.span,
+                    );
                 }
-            } else {
-                // Catch without parameter
-                self.linter.add_error(
+            }
+            None => {
+                linter.add_error(
                     "catch-error-handling".to_string(),
-                    "Catch clause must have an error parameter to handle errors properly"
-                        .to_string(),
+                    "Empty catch block is not allowed. Must handle error properly with type checking and neverthrow's err()".to_string(),
                     clause.span,
                 );
             }
-
-            walk::walk_catch_clause(self, clause);
         }
     }
+}
 
-    impl<'a> CatchErrorChecker<'a> {
-        fn check_if_has_error_check(&self, if_stmt: &IfStatement<'a>, error_name: &str) -> bool {
-            // Check if the condition is a call to Error.isError() or instanceof Error
-            match &if_stmt.test {
-                Expression::CallExpression(call) => {
-                    // Check for Error.isError(error) pattern
-                    if let Expression::StaticMemberExpression(member) = &call.callee {
-                        if let Expression::Identifier(obj) = &member.object {
-                            if obj.name == "Error" && member.property.name == "isError" {
-                                // Check if the argument is the error parameter
-                                if let Some(Argument::Identifier(ident)) = call.arguments.first() {
-                                    return ident.name == error_name;
-                                }
-                            }
+/// Whether the condition is `Error.isError(e)` or `e instanceof Error`
+fn has_error_check(if_stmt: &IfStatement, error_name: &str) -> bool {
+    match &if_stmt.test {
+        Expression::CallExpression(call) => {
+            // Check for Error.isError(error) pattern
+            if let Expression::StaticMemberExpression(member) = &call.callee {
+                if let Expression::Identifier(obj) = &member.object {
+                    if obj.name == "Error" && member.property.name == "isError" {
+                        // Check if the argument is the error parameter
+                        if let Some(Argument::Identifier(ident)) = call.arguments.first() {
+                            return ident.name == error_name;
                         }
                     }
-                    false
                 }
-                Expression::BinaryExpression(binary) => {
-                    // Check for error instanceof Error pattern
-                    if let BinaryOperator::Instanceof = binary.operator {
-                        if let Expression::Identifier(left) = &binary.left {
-                            if left.name == error_name {
-                                if let Expression::Identifier(right) = &binary.right {
-                                    return right.name == "Error";
-                                }
-                            }
+            }
+            false
+        }
+        Expression::BinaryExpression(binary) => {
+            // Check for error instanceof Error pattern
+            if let BinaryOperator::Instanceof = binary.operator {
+                if let Expression::Identifier(left) = &binary.left {
+                    if left.name == error_name {
+                        if let Expression::Identifier(right) = &binary.right {
+                            return right.name == "Error";
                         }
                     }
-                    false
                 }
-                _ => false,
             }
+            false
         }
+        _ => false,
     }
+}
 
-    let mut checker = CatchErrorChecker { linter };
-    checker.visit_program(program);
+pub fn check_catch_error_handling(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, CatchErrorHandling);
 }
 
 #[cfg(test)]
@@ -160,6 +168,29 @@ export function badCatch3() {
                                      // assert!(errors[0].message.contains("Catch clause must have an error parameter"));
     }
 
+    #[test]
+    fn test_result_conversion_is_exempt() {
+        let allocator = Allocator::default();
+        let source_text = r#"
+export async function readConfig(): Promise<Result<string, Error>> {
+  try {
+    const data: string = await fs.readFile("package.json", "utf-8");
+    return ok(data);
+  } catch (error) {
+    return err(new Error("Failed to read configuration"));
+  }
+}
+"#;
+        let source_type = SourceType::ts();
+        let ParserReturn { program, .. } =
+            Parser::new(&allocator, source_text, source_type).parse();
+        let mut linter = Linter::new(Path::new("src/io/readConfig.ts"), source_text, false);
+
+        check_catch_error_handling(&mut linter, &program);
+
+        assert!(linter.errors.is_empty());
+    }
+
     #[test]
     fn test_catch_without_proper_error_check() {
         let allocator = Allocator::default();
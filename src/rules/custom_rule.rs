@@ -0,0 +1,346 @@
+use oxc::ast::ast::*;
+use oxc::ast::{AstKind, AstType};
+use oxc::span::{GetSpan, Span};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::config::{CustomPattern, CustomRule};
+use crate::rule::Rule;
+use crate::Linter;
+
+/// Node kinds a `node` pattern may name
+pub const NODE_KINDS: &[(&str, AstType)] = &[
+    ("ThisExpression", AstType::ThisExpression),
+    ("ArrayExpression", AstType::ArrayExpression),
+    ("ObjectExpression", AstType::ObjectExpression),
+    ("TemplateLiteral", AstType::TemplateLiteral),
+    (
+        "TaggedTemplateExpression",
+        AstType::TaggedTemplateExpression,
+    ),
+    (
+        "ComputedMemberExpression",
+        AstType::ComputedMemberExpression,
+    ),
+    ("StaticMemberExpression", AstType::StaticMemberExpression),
+    ("PrivateFieldExpression", AstType::PrivateFieldExpression),
+    ("CallExpression", AstType::CallExpression),
+    ("NewExpression", AstType::NewExpression),
+    ("MetaProperty", AstType::MetaProperty),
+    ("SpreadElement", AstType::SpreadElement),
+    ("UpdateExpression", AstType::UpdateExpression),
+    ("UnaryExpression", AstType::UnaryExpression),
+    ("BinaryExpression", AstType::BinaryExpression),
+    ("LogicalExpression", AstType::LogicalExpression),
+    ("ConditionalExpression", AstType::ConditionalExpression),
+    ("AssignmentExpression", AstType::AssignmentExpression),
+    ("SequenceExpression", AstType::SequenceExpression),
+    ("AwaitExpression", AstType::AwaitExpression),
+    ("ChainExpression", AstType::ChainExpression),
+    ("YieldExpression", AstType::YieldExpression),
+    ("ArrowFunctionExpression", AstType::ArrowFunctionExpression),
+    ("ImportExpression", AstType::ImportExpression),
+    ("BlockStatement", AstType::BlockStatement),
+    ("VariableDeclaration", AstType::VariableDeclaration),
+    ("EmptyStatement", AstType::EmptyStatement),
+    ("ExpressionStatement", AstType::ExpressionStatement),
+    ("IfStatement", AstType::IfStatement),
+    ("DoWhileStatement", AstType::DoWhileStatement),
+    ("WhileStatement", AstType::WhileStatement),
+    ("ForStatement", AstType::ForStatement),
+    ("ForInStatement", AstType::ForInStatement),
+    ("ForOfStatement", AstType::ForOfStatement),
+    ("ContinueStatement", AstType::ContinueStatement),
+    ("BreakStatement", AstType::BreakStatement),
+    ("ReturnStatement", AstType::ReturnStatement),
+    ("WithStatement", AstType::WithStatement),
+    ("SwitchStatement", AstType::SwitchStatement),
+    ("SwitchCase", AstType::SwitchCase),
+    ("LabeledStatement", AstType::LabeledStatement),
+    ("ThrowStatement", AstType::ThrowStatement),
+    ("TryStatement", AstType::TryStatement),
+    ("CatchClause", AstType::CatchClause),
+    ("DebuggerStatement", AstType::DebuggerStatement),
+    ("Function", AstType::Function),
+    ("Class", AstType::Class),
+    ("MethodDefinition", AstType::MethodDefinition),
+    ("PropertyDefinition", AstType::PropertyDefinition),
+    ("StaticBlock", AstType::StaticBlock),
+    ("AccessorProperty", AstType::AccessorProperty),
+    ("ImportDeclaration", AstType::ImportDeclaration),
+    ("ExportNamedDeclaration", AstType::ExportNamedDeclaration),
+    (
+        "ExportDefaultDeclaration",
+        AstType::ExportDefaultDeclaration,
+    ),
+    ("ExportAllDeclaration", AstType::ExportAllDeclaration),
+    ("RegExpLiteral", AstType::RegExpLiteral),
+    ("BigIntLiteral", AstType::BigIntLiteral),
+    ("NullLiteral", AstType::NullLiteral),
+    ("JSXElement", AstType::JSXElement),
+    ("JSXFragment", AstType::JSXFragment),
+    ("Decorator", AstType::Decorator),
+    ("TSEnumDeclaration", AstType::TSEnumDeclaration),
+    ("TSTypeAliasDeclaration", AstType::TSTypeAliasDeclaration),
+    ("TSInterfaceDeclaration", AstType::TSInterfaceDeclaration),
+    ("TSModuleDeclaration", AstType::TSModuleDeclaration),
+    ("TSAnyKeyword", AstType::TSAnyKeyword),
+    ("TSUnknownKeyword", AstType::TSUnknownKeyword),
+    ("TSAsExpression", AstType::TSAsExpression),
+    ("TSSatisfiesExpression", AstType::TSSatisfiesExpression),
+    ("TSTypeAssertion", AstType::TSTypeAssertion),
+    ("TSNonNullExpression", AstType::TSNonNullExpression),
+    (
+        "TSImportEqualsDeclaration",
+        AstType::TSImportEqualsDeclaration,
+    ),
+    ("TSExportAssignment", AstType::TSExportAssignment),
+];
+
+/// The node kind with a given name, if a `node` pattern may use it
+pub fn node_kind(name: &str) -> Option<AstType> {
+    NODE_KINDS
+        .iter()
+        .find(|(kind, _)| *kind == name)
+        .map(|(_, ty)| *ty)
+}
+
+/// Runs a user-defined rule from `purets.json`
+pub struct CustomRuleCheck {
+    rule: Arc<CustomRule>,
+    applies: bool,
+    // Spans of expressions directly under `await`
+    awaited: HashSet<Span>,
+}
+
+impl CustomRuleCheck {
+    pub fn new(rule: Arc<CustomRule>) -> Self {
+        Self {
+            rule,
+            applies: false,
+            awaited: HashSet::new(),
+        }
+    }
+
+    fn report(&self, linter: &mut Linter, span: Span) {
+        linter.add_error(self.rule.id.clone(), self.rule.message.clone(), span);
+    }
+
+    fn check_source(&self, linter: &mut Linter, source: &str, span: Span) {
+        if let CustomPattern::Import { source: glob } = &self.rule.pattern {
+            if glob.matches(source) {
+                self.report(linter, span);
+            }
+        }
+    }
+}
+
+/// Dotted path of a callee, e.g. `db.query` or `this.repo.save`
+pub(crate) fn callee_path(expr: &Expression) -> Option<String> {
+    match expr.without_parentheses() {
+        Expression::Identifier(id) => Some(id.name.to_string()),
+        Expression::ThisExpression(_) => Some("this".to_string()),
+        Expression::StaticMemberExpression(member) => {
+            callee_path(&member.object).map(|object| format!("{}.{}", object, member.property.name))
+        }
+        _ => None,
+    }
+}
+
+impl<'a> Rule<'a> for CustomRuleCheck {
+    fn id(&self) -> &str {
+        &self.rule.id
+    }
+
+    fn on_program_start(&mut self, linter: &mut Linter, _program: &'a Program<'a>) {
+        self.applies = self.rule.applies_to(&linter.path);
+    }
+
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        if !self.applies {
+            return;
+        }
+
+        if let CustomPattern::Node { kind: ty } = &self.rule.pattern {
+            if kind.ty() == *ty {
+                self.report(linter, kind.span());
+            }
+            return;
+        }
+
+        match kind {
+            AstKind::AwaitExpression(await_expr) => {
+                self.awaited
+                    .insert(await_expr.argument.without_parentheses().span());
+            }
+            AstKind::CallExpression(call) => match &self.rule.pattern {
+                CustomPattern::Call { callee, awaited } => {
+                    let Some(path) = callee_path(&call.callee) else {
+                        return;
+                    };
+                    let is_awaited = self.awaited.contains(&call.span);
+                    if callee.matches(&path) && awaited.is_none_or(|want| want == is_awaited) {
+                        self.report(linter, call.span);
+                    }
+                }
+                CustomPattern::Import { .. } => {
+                    let is_require =
+                        matches!(&call.callee, Expression::Identifier(id) if id.name == "require");
+                    if let (true, Some(Argument::StringLiteral(source))) =
+                        (is_require, call.arguments.first())
+                    {
+                        self.check_source(linter, &source.value, call.span);
+                    }
+                }
+                _ => {}
+            },
+            AstKind::NewExpression(new_expr) => {
+                if let CustomPattern::New { callee } = &self.rule.pattern {
+                    if callee_path(&new_expr.callee).is_some_and(|path| callee.matches(&path)) {
+                        self.report(linter, new_expr.span);
+                    }
+                }
+            }
+            AstKind::ImportDeclaration(import) => {
+                self.check_source(linter, &import.source.value, import.span);
+            }
+            AstKind::ExportNamedDeclaration(export) => {
+                if let Some(source) = &export.source {
+                    self.check_source(linter, &source.value, export.span);
+                }
+            }
+            AstKind::ExportAllDeclaration(export) => {
+                self.check_source(linter, &export.source.value, export.span);
+            }
+            AstKind::ImportExpression(import) => {
+                if let Expression::StringLiteral(source) = &import.source {
+                    self.check_source(linter, &source.value, import.span);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PuretsConfig;
+    use crate::rule::run_rules;
+    use crate::rule_registry::Severity;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+    use std::path::Path;
+
+    fn lint(rule_json: &str, path: &str, source: &str) -> Vec<(String, Severity)> {
+        let config: PuretsConfig =
+            serde_json::from_str(&format!(r#"{{ "customRules": [{}] }}"#, rule_json)).unwrap();
+        let rules = config.custom_rules().unwrap();
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+        let mut linter =
+            Linter::new(Path::new(path), source, false).with_custom_rules(rules.clone());
+        linter.load_directives(&ret.program);
+        run_rules(
+            &mut linter,
+            &ret.program,
+            rules
+                .into_iter()
+                .map(|rule| Box::new(CustomRuleCheck::new(rule)) as _)
+                .collect(),
+        );
+
+        linter
+            .errors
+            .into_iter()
+            .map(|e| (e.rule, e.severity))
+            .collect()
+    }
+
+    #[test]
+    fn test_new_pattern_with_exclude() {
+        let rule = r#"{
+            "id": "no-date-outside-io",
+            "message": "Create dates in io/",
+            "severity": "warning",
+            "pattern": { "type": "new", "callee": "Date" },
+            "exclude": ["src/io/**"]
+        }"#;
+        let source = "const now: Date = new Date();";
+
+        assert_eq!(
+            lint(rule, "src/pure/age.ts", source),
+            vec![("no-date-outside-io".to_string(), Severity::Warning)]
+        );
+        assert!(lint(rule, "src/io/now.ts", source).is_empty());
+    }
+
+    #[test]
+    fn test_import_pattern() {
+        let rule = r#"{
+            "id": "no-legacy",
+            "message": "Do not use the legacy package",
+            "pattern": { "type": "import", "source": "@internal/legacy*" }
+        }"#;
+        let source = r#"
+import { format } from "@internal/legacy/format";
+import { parse } from "@internal/parser";
+const legacy = require("@internal/legacy");
+export { render } from "@internal/legacy";
+"#;
+        assert_eq!(lint(rule, "src/main.ts", source).len(), 3);
+    }
+
+    #[test]
+    fn test_call_pattern_awaited() {
+        let rule = r#"{
+            "id": "await-db-query",
+            "message": "db.query must be awaited",
+            "pattern": { "type": "call", "callee": "db.query", "awaited": false }
+        }"#;
+        let source = r#"
+async function load(db: Db): Promise<void> {
+  const rows = await db.query("select 1");
+  const pending = db.query("select 2");
+  db.other();
+}
+"#;
+        assert_eq!(lint(rule, "src/io/load.ts", source).len(), 1);
+    }
+
+    #[test]
+    fn test_call_pattern_glob() {
+        let rule = r#"{
+            "id": "no-console",
+            "message": "Use the logger",
+            "pattern": { "type": "call", "callee": "console.*" }
+        }"#;
+        let source = r#"console.log("a"); console.error("b"); logger.info("c");"#;
+        assert_eq!(lint(rule, "src/main.ts", source).len(), 2);
+    }
+
+    #[test]
+    fn test_node_pattern() {
+        let rule = r#"{
+            "id": "no-labels",
+            "message": "Labels are not allowed",
+            "pattern": { "type": "node", "kind": "LabeledStatement" }
+        }"#;
+        let source = "outer: for (const x of xs) { break outer; }";
+        assert_eq!(lint(rule, "src/main.ts", source).len(), 1);
+    }
+
+    #[test]
+    fn test_disable_directive_with_custom_id() {
+        let rule = r#"{
+            "id": "no-date-outside-io",
+            "message": "Create dates in io/",
+            "pattern": { "type": "new", "callee": "Date" }
+        }"#;
+        let source =
+            "// purets-disable-next-line no-date-outside-io\nconst now: Date = new Date();";
+        assert!(lint(rule, "src/pure/age.ts", source).is_empty());
+    }
+}
@@ -1,34 +1,39 @@
-use crate::Linter;
 use oxc::ast::ast::*;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
-pub fn check_empty_array_requires_type(linter: &mut Linter, program: &Program) {
-    struct ArrayChecker<'a> {
-        linter: &'a mut Linter,
+use crate::rule::{run_rule, Rule};
+use crate::Linter;
+
+/// Requires a type annotation on bindings initialised with an empty array
+#[derive(Default)]
+pub struct EmptyArrayRequiresType;
+
+impl<'a> Rule<'a> for EmptyArrayRequiresType {
+    fn id(&self) -> &str {
+        "empty-array-requires-type"
     }
 
-    impl<'a> Visit<'a> for ArrayChecker<'a> {
-        fn visit_variable_declarator(&mut self, decl: &VariableDeclarator<'a>) {
-            // Check if initializer is an empty array
-            if let Some(Expression::ArrayExpression(array)) = &decl.init {
-                if array.elements.is_empty() {
-                    // Check if type annotation exists
-                    if decl.id.type_annotation.is_none() {
-                        if let BindingPatternKind::BindingIdentifier(id) = &decl.id.kind {
-                            self.linter.add_error(
-                                "empty-array-requires-type".to_string(),
-                                format!("Empty array '{}' requires type annotation (e.g., const {}: Array<number> = [])", id.name, id.name),
-                                decl.span,
-                            );
-                        }
-                    }
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::VariableDeclarator(decl) = kind else {
+            return;
+        };
+        // Check if initializer is an empty array
+        if let Some(Expression::ArrayExpression(array)) = &decl.init {
+            if array.elements.is_empty() && decl.id.type_annotation.is_none() {
+                if let BindingPatternKind::BindingIdentifier(id) = &decl.id.kind {
+                    linter.add_error(
+                        "empty-array-requires-type".to_string(),
+                        format!("Empty array '{}' requires type annotation (e.g., const {}: Array<number> = [])", id.name, id.name),
+                        decl.span,
+                    );
                 }
             }
         }
     }
+}
 
-    let mut checker = ArrayChecker { linter };
-    checker.visit_program(program);
+pub fn check_empty_array_requires_type(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, EmptyArrayRequiresType);
 }
 
 #[cfg(test)]
@@ -1,81 +1,71 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_export_const_type_required(linter: &mut Linter, program: &Program) {
-    struct ExportConstChecker<'a> {
-        linter: &'a mut Linter,
+/// Prohibits `export let` and requires type annotations on exported constants
+#[derive(Default)]
+pub struct ExportConstTypeRequired;
+
+impl<'a> Rule<'a> for ExportConstTypeRequired {
+    fn id(&self) -> &str {
+        "export-const-type-required"
     }
 
-    impl<'a> Visit<'a> for ExportConstChecker<'a> {
-        fn visit_export_named_declaration(&mut self, decl: &ExportNamedDeclaration<'a>) {
-            if let Some(Declaration::VariableDeclaration(var_decl)) = &decl.declaration {
-                // Check for export let (prohibited)
-                if var_decl.kind == VariableDeclarationKind::Let {
-                    self.linter.add_error(
-                        "no-export-let".to_string(),
-                        "Export let is not allowed. Use 'export const' with explicit type "
-                            .to_string(),
-                        var_decl.span,
-                    );
-                    return;
-                }
-
-                // Check for export const without type annotation
-                if var_decl.kind == VariableDeclarationKind::Const {
-                    for declarator in &var_decl.declarations {
-                        // Check if it has a type annotation
-                        if declarator.id.type_annotation.is_none() {
-                            // Check if it's a function (arrow functions should have type)
-                            let needs_type = if let Some(init) = &declarator.init {
-                                !matches!(
-                                    init,
-                                    Expression::ArrowFunctionExpression(_)
-                                        | Expression::FunctionExpression(_)
-                                )
-                            } else {
-                                true
-                            };
-
-                            if needs_type {
-                                // Get the name for error message
-                                let var_name = match &declarator.id.kind {
-                                    BindingPatternKind::BindingIdentifier(ident) => {
-                                        ident.name.to_string()
-                                    }
-                                    BindingPatternKind::ObjectPattern(_) => {
-                                        "destructured object".to_string()
-                                    }
-                                    BindingPatternKind::ArrayPattern(_) => {
-                                        "destructured array".to_string()
-                                    }
-                                    BindingPatternKind::AssignmentPattern(_) => {
-                                        "assignment pattern".to_string()
-                                    }
-                                };
-
-                                self.linter.add_error(
-                                    "export-const-needs-type".to_string(),
-                                    format!(
-                                        "Export const '{}' must have an explicit type ",
-                                        var_name
-                                    ),
-                                    declarator.span,
-                                );
-                            }
-                        }
-                    }
-                }
-            }
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::ExportNamedDeclaration(decl) = kind else {
+            return;
+        };
+        let Some(Declaration::VariableDeclaration(var_decl)) = &decl.declaration else {
+            return;
+        };
+
+        // Check for export let (prohibited)
+        if var_decl.kind == VariableDeclarationKind::Let {
+            linter.add_error(
+                "no-export-let".to_string(),
+                "Export let is not allowed. Use 'export const' with explicit type ".to_string(),
+                var_decl.span,
+            );
+            return;
+        }
 
-            walk::walk_export_named_declaration(self, decl);
+        // Check for export const without type annotation
+        if var_decl.kind != VariableDeclarationKind::Const {
+            return;
+        }
+        for declarator in &var_decl.declarations {
+            if declarator.id.type_annotation.is_some() {
+                continue;
+            }
+            // Function expressions carry their own signature
+            let needs_type = !matches!(
+                &declarator.init,
+                Some(Expression::ArrowFunctionExpression(_) | Expression::FunctionExpression(_))
+            );
+
+            if needs_type {
+                // Get the name for error message
+                let var_name = match &declarator.id.kind {
+                    BindingPatternKind::BindingIdentifier(ident) => ident.name.to_string(),
+                    BindingPatternKind::ObjectPattern(_) => "destructured object".to_string(),
+                    BindingPatternKind::ArrayPattern(_) => "destructured array".to_string(),
+                    BindingPatternKind::AssignmentPattern(_) => "assignment pattern".to_string(),
+                };
+
+                linter.add_error(
+                    "export-const-needs-type".to_string(),
+                    format!("Export const '{}' must have an explicit type ", var_name),
+                    declarator.span,
+                );
+            }
         }
     }
+}
 
-    let mut checker = ExportConstChecker { linter };
-    checker.visit_program(program);
+pub fn check_export_const_type_required(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, ExportConstTypeRequired);
 }
 
 #[cfg(test)]
@@ -1,145 +1,141 @@
 use oxc::ast::ast::*;
+use oxc::ast::AstKind;
+use oxc::span::Span;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_export_requires_jsdoc(linter: &mut Linter, program: &Program, file_path: &str) {
-    use oxc::ast_visit::Visit;
-
-    struct JsDocVisitor<'a, 'b> {
-        linter: &'a mut Linter,
-        source_text: String,
-        file_path: String,
-        _phantom: std::marker::PhantomData<&'b ()>,
-    }
+/// Requires JSDoc on exported functions, on exported types in types/ and on
+/// exported error classes in errors/
+#[derive(Default)]
+pub struct ExportRequiresJsdoc {
+    file_path: String,
+}
 
-    impl<'a, 'b> JsDocVisitor<'a, 'b> {
-        fn has_jsdoc_before(&self, span: oxc::span::Span) -> bool {
-            // Check if there's a JSDoc comment immediately before this position
-            let text_before = &self.source_text[..span.start as usize];
-
-            // Look for JSDoc pattern (/** ... */) before the function
-            // Simple check: look for */ followed by whitespace/newlines before the function
-            let trimmed = text_before.trim_end();
-            trimmed.ends_with("*/") && {
-                // Find the start of the comment
-                if let Some(_comment_start) = trimmed.rfind("/**") {
-                    // Check if there's only whitespace between comment and function
-                    let between = &self.source_text[trimmed.len()..span.start as usize];
-                    between.trim().is_empty()
-                } else {
-                    false
-                }
+impl ExportRequiresJsdoc {
+    fn has_jsdoc_before(linter: &Linter, span: Span) -> bool {
+        // Check if there's a JSDoc comment immediately before this position
+        let text_before = &linter.source_text[..span.start as usize];
+
+        // Look for JSDoc pattern (/** ... */) before the function
+        // Simple check: look for */ followed by whitespace/newlines before the function
+        let trimmed = text_before.trim_end();
+        trimmed.ends_with("*/") && {
+            // Find the start of the comment
+            if let Some(_comment_start) = trimmed.rfind("/**") {
+                // Check if there's only whitespace between comment and function
+                let between = &linter.source_text[trimmed.len()..span.start as usize];
+                between.trim().is_empty()
+            } else {
+                false
             }
         }
     }
 
-    impl<'a, 'b> Visit<'b> for JsDocVisitor<'a, 'b> {
-        fn visit_export_default_declaration(&mut self, export: &ExportDefaultDeclaration<'b>) {
-            if let ExportDefaultDeclarationKind::FunctionDeclaration(func) = &export.declaration {
-                if !self.has_jsdoc_before(export.span) {
-                    let name = func
-                        .id
-                        .as_ref()
-                        .map(|id| id.name.as_str())
-                        .unwrap_or("anonymous");
-                    self.linter.add_error(
-                        "export-requires-jsdoc".to_string(),
-                        format!("Exported function '{}' must have a JSDoc comment", name),
-                        export.span,
-                    );
+    fn in_types_dir(&self) -> bool {
+        self.file_path.contains("/types/") || self.file_path.contains("types/")
+    }
+
+    fn in_errors_dir(&self) -> bool {
+        self.file_path.contains("/errors/") || self.file_path.contains("errors/")
+    }
+}
+
+impl<'a> Rule<'a> for ExportRequiresJsdoc {
+    fn id(&self) -> &str {
+        "export-requires-jsdoc"
+    }
+
+    fn on_program_start(&mut self, linter: &mut Linter, _program: &'a Program<'a>) {
+        self.file_path = linter.path.to_str().unwrap_or("").to_string();
+    }
+
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::ExportDefaultDeclaration(export) => {
+                if let ExportDefaultDeclarationKind::FunctionDeclaration(func) = &export.declaration
+                {
+                    if !Self::has_jsdoc_before(linter, export.span) {
+                        let name = func
+                            .id
+                            .as_ref()
+                            .map(|id| id.name.as_str())
+                            .unwrap_or("anonymous");
+                        linter.add_error(
+                            "export-requires-jsdoc".to_string(),
+                            format!("Exported function '{}' must have a JSDoc comment", name),
+                            export.span,
+                        );
+                    }
                 }
             }
+            AstKind::ExportNamedDeclaration(export) => {
+                let Some(declaration) = &export.declaration else {
+                    return;
+                };
+                if Self::has_jsdoc_before(linter, export.span) {
+                    return;
+                }
 
-            oxc::ast_visit::walk::walk_export_default_declaration(self, export);
-        }
-
-        fn visit_export_named_declaration(&mut self, export: &ExportNamedDeclaration<'b>) {
-            if let Some(declaration) = &export.declaration {
                 match declaration {
                     Declaration::FunctionDeclaration(func) => {
-                        if !self.has_jsdoc_before(export.span) {
-                            let name = func
-                                .id
-                                .as_ref()
-                                .map(|id| id.name.as_str())
-                                .unwrap_or("anonymous");
-                            self.linter.add_error(
-                                "export-requires-jsdoc".to_string(),
-                                format!("Exported function '{}' must have a JSDoc comment", name),
-                                export.span,
-                            );
-                        }
+                        let name = func
+                            .id
+                            .as_ref()
+                            .map(|id| id.name.as_str())
+                            .unwrap_or("anonymous");
+                        linter.add_error(
+                            "export-requires-jsdoc".to_string(),
+                            format!("Exported function '{}' must have a JSDoc comment", name),
+                            export.span,
+                        );
                     }
-                    Declaration::TSTypeAliasDeclaration(type_alias) => {
-                        // Check if in types/*.ts
-                        if (self.file_path.contains("/types/") || self.file_path.contains("types/"))
-                            && !self.has_jsdoc_before(export.span)
-                        {
-                            self.linter.add_error(
-                                "export-requires-jsdoc".to_string(),
-                                format!(
-                                    "Exported type '{}' must have a JSDoc comment",
-                                    type_alias.id.name.as_str()
-                                ),
-                                export.span,
-                            );
-                        }
+                    // Check if in types/*.ts
+                    Declaration::TSTypeAliasDeclaration(type_alias) if self.in_types_dir() => {
+                        linter.add_error(
+                            "export-requires-jsdoc".to_string(),
+                            format!(
+                                "Exported type '{}' must have a JSDoc comment",
+                                type_alias.id.name.as_str()
+                            ),
+                            export.span,
+                        );
                     }
-                    Declaration::TSInterfaceDeclaration(interface) => {
-                        // Check if in types/*.ts
-                        if (self.file_path.contains("/types/") || self.file_path.contains("types/"))
-                            && !self.has_jsdoc_before(export.span)
-                        {
-                            self.linter.add_error(
-                                "export-requires-jsdoc".to_string(),
-                                format!(
-                                    "Exported interface '{}' must have a JSDoc comment",
-                                    interface.id.name.as_str()
-                                ),
-                                export.span,
-                            );
-                        }
+                    Declaration::TSInterfaceDeclaration(interface) if self.in_types_dir() => {
+                        linter.add_error(
+                            "export-requires-jsdoc".to_string(),
+                            format!(
+                                "Exported interface '{}' must have a JSDoc comment",
+                                interface.id.name.as_str()
+                            ),
+                            export.span,
+                        );
                     }
-                    Declaration::ClassDeclaration(class) => {
-                        // Check if in errors/*Error.ts
-                        if (self.file_path.contains("/errors/")
-                            || self.file_path.contains("errors/"))
-                            && self.file_path.ends_with("Error.ts")
-                            && !self.has_jsdoc_before(export.span)
-                        {
-                            let name = class
-                                .id
-                                .as_ref()
-                                .map(|id| id.name.as_str())
-                                .unwrap_or("anonymous");
-                            self.linter.add_error(
-                                "export-requires-jsdoc".to_string(),
-                                format!(
-                                    "Exported error class '{}' must have a JSDoc comment",
-                                    name
-                                ),
-                                export.span,
-                            );
-                        }
+                    // Check if in errors/*Error.ts
+                    Declaration::ClassDeclaration(class)
+                        if self.in_errors_dir() && self.file_path.ends_with("Error.ts") =>
+                    {
+                        let name = class
+                            .id
+                            .as_ref()
+                            .map(|id| id.name.as_str())
+                            .unwrap_or("anonymous");
+                        linter.add_error(
+                            "export-requires-jsdoc".to_string(),
+                            format!("Exported error class '{}' must have a JSDoc comment", name),
+                            export.span,
+                        );
                     }
                     _ => {}
                 }
             }
-
-            oxc::ast_visit::walk::walk_export_named_declaration(self, export);
+            _ => {}
         }
     }
+}
 
-    let source_text = linter.source_text.clone();
-
-    let mut visitor = JsDocVisitor {
-        linter,
-        source_text,
-        file_path: file_path.to_string(),
-        _phantom: std::marker::PhantomData,
-    };
-
-    visitor.visit_program(program);
+pub fn check_export_requires_jsdoc(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, ExportRequiresJsdoc::default());
 }
 
 #[cfg(test)]
@@ -157,7 +153,7 @@ mod tests {
         let ret = Parser::new(&allocator, source, source_type).parse();
 
         let mut linter = Linter::new(Path::new("test.ts"), source, false);
-        check_export_requires_jsdoc(&mut linter, &ret.program, "test.ts");
+        check_export_requires_jsdoc(&mut linter, &ret.program);
 
         linter.errors.into_iter().map(|e| e.message).collect()
     }
@@ -232,7 +228,7 @@ mod tests {
         let ret = Parser::new(&allocator, source, source_type).parse();
 
         let mut linter = Linter::new(Path::new("types/User.ts"), source, false);
-        check_export_requires_jsdoc(&mut linter, &ret.program, "types/User.ts");
+        check_export_requires_jsdoc(&mut linter, &ret.program);
 
         let errors: Vec<String> = linter.errors.into_iter().map(|e| e.message).collect();
         assert_eq!(errors.len(), 0);
@@ -251,7 +247,7 @@ mod tests {
         let ret = Parser::new(&allocator, source, source_type).parse();
 
         let mut linter = Linter::new(Path::new("types/User.ts"), source, false);
-        check_export_requires_jsdoc(&mut linter, &ret.program, "types/User.ts");
+        check_export_requires_jsdoc(&mut linter, &ret.program);
 
         let errors: Vec<String> = linter.errors.into_iter().map(|e| e.message).collect();
         assert_eq!(errors.len(), 1);
@@ -274,7 +270,7 @@ mod tests {
         let ret = Parser::new(&allocator, source, source_type).parse();
 
         let mut linter = Linter::new(Path::new("types/Config.ts"), source, false);
-        check_export_requires_jsdoc(&mut linter, &ret.program, "types/Config.ts");
+        check_export_requires_jsdoc(&mut linter, &ret.program);
 
         let errors: Vec<String> = linter.errors.into_iter().map(|e| e.message).collect();
         assert_eq!(errors.len(), 0);
@@ -293,7 +289,7 @@ mod tests {
         let ret = Parser::new(&allocator, source, source_type).parse();
 
         let mut linter = Linter::new(Path::new("types/Config.ts"), source, false);
-        check_export_requires_jsdoc(&mut linter, &ret.program, "types/Config.ts");
+        check_export_requires_jsdoc(&mut linter, &ret.program);
 
         let errors: Vec<String> = linter.errors.into_iter().map(|e| e.message).collect();
         assert_eq!(errors.len(), 1);
@@ -317,7 +313,7 @@ mod tests {
         let ret = Parser::new(&allocator, source, source_type).parse();
 
         let mut linter = Linter::new(Path::new("errors/FileNotFoundError.ts"), source, false);
-        check_export_requires_jsdoc(&mut linter, &ret.program, "errors/FileNotFoundError.ts");
+        check_export_requires_jsdoc(&mut linter, &ret.program);
 
         let errors: Vec<String> = linter.errors.into_iter().map(|e| e.message).collect();
         assert_eq!(errors.len(), 0);
@@ -337,7 +333,7 @@ mod tests {
         let ret = Parser::new(&allocator, source, source_type).parse();
 
         let mut linter = Linter::new(Path::new("errors/FileNotFoundError.ts"), source, false);
-        check_export_requires_jsdoc(&mut linter, &ret.program, "errors/FileNotFoundError.ts");
+        check_export_requires_jsdoc(&mut linter, &ret.program);
 
         let errors: Vec<String> = linter.errors.into_iter().map(|e| e.message).collect();
         assert_eq!(errors.len(), 1);
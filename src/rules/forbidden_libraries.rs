@@ -1,5 +1,9 @@
 use oxc::ast::ast::*;
+use oxc::ast::AstKind;
+use oxc::span::Span;
 
+use crate::deno_config::npm_package;
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
 // Libraries that should not be used
@@ -11,92 +15,70 @@ const PREFER_ALTERNATIVES: &[(&str, &str)] = &[
     ("yargs", "node:util parseArgs"),
 ];
 
-pub fn check_forbidden_libraries(linter: &mut Linter, program: &Program) {
-    use oxc::ast_visit::Visit;
-
-    struct ForbiddenLibrariesVisitor<'a, 'b> {
-        linter: &'a mut Linter,
-        _phantom: std::marker::PhantomData<&'b ()>,
+/// Prohibits importing or requiring forbidden libraries
+#[derive(Default)]
+pub struct ForbiddenLibraries;
+
+fn check_source(linter: &mut Linter, source: &str, span: Span) {
+    // Deno imports npm packages as `npm:name@version`, possibly through the import map
+    let resolved = linter.resolve_specifier(source).into_owned();
+    let source = npm_package(&resolved);
+    let source = source.as_ref();
+
+    // Check for forbidden libraries
+    if FORBIDDEN_LIBRARIES.contains(&source) || source.starts_with("lodash/") {
+        linter.add_error(
+            "forbidden-libraries".to_string(),
+            format!(
+                "Library '{}' is forbidden. Consider using modern alternatives",
+                source
+            ),
+            span,
+        );
     }
 
-    impl<'a, 'b> Visit<'b> for ForbiddenLibrariesVisitor<'a, 'b> {
-        fn visit_import_declaration(&mut self, import: &ImportDeclaration<'b>) {
-            let source = import.source.value.as_str();
-
-            // Check for forbidden libraries
-            if FORBIDDEN_LIBRARIES.contains(&source) || source.starts_with("lodash/") {
-                self.linter.add_error(
-                    "forbidden-libraries".to_string(),
-                    format!(
-                        "Library '{}' is forbidden. Consider using modern alternatives",
-                        source
-                    ),
-                    import.span,
-                );
-            }
-
-            // Check for libraries with better alternatives
-            for (lib, alternative) in PREFER_ALTERNATIVES {
-                if source == *lib {
-                    self.linter.add_error(
-                        "forbidden-libraries".to_string(),
-                        format!(
-                            "Library '{}' has a better alternative. Use '{}' instead",
-                            lib, alternative
-                        ),
-                        import.span,
-                    );
-                }
-            }
-
-            oxc::ast_visit::walk::walk_import_declaration(self, import);
+    // Check for libraries with better alternatives
+    for (lib, alternative) in PREFER_ALTERNATIVES {
+        if source == *lib {
+            linter.add_error(
+                "forbidden-libraries".to_string(),
+                format!(
+                    "Library '{}' has a better alternative. Use '{}' instead",
+                    lib, alternative
+                ),
+                span,
+            );
         }
+    }
+}
 
-        fn visit_call_expression(&mut self, call: &CallExpression<'b>) {
-            // Check for require() calls
-            if let Expression::Identifier(ident) = &call.callee {
-                if ident.name == "require" && !call.arguments.is_empty() {
-                    if let Argument::StringLiteral(lit) = &call.arguments[0] {
-                        let source = lit.value.as_str();
-
-                        // Check for forbidden libraries in require
-                        if FORBIDDEN_LIBRARIES.contains(&source) || source.starts_with("lodash/") {
-                            self.linter.add_error(
-                                "forbidden-libraries".to_string(),
-                                format!(
-                                    "Library '{}' is forbidden. Consider using modern alternatives",
-                                    source
-                                ),
-                                call.span,
-                            );
-                        }
+impl<'a> Rule<'a> for ForbiddenLibraries {
+    fn id(&self) -> &str {
+        "forbidden-libraries"
+    }
 
-                        // Check for libraries with better alternatives in require
-                        for (lib, alternative) in PREFER_ALTERNATIVES {
-                            if source == *lib {
-                                self.linter.add_error(
-                                    "forbidden-libraries".to_string(),
-                                    format!(
-                                        "Library '{}' has a better alternative. Use '{}' instead",
-                                        lib, alternative
-                                    ),
-                                    call.span,
-                                );
-                            }
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::ImportDeclaration(import) => {
+                check_source(linter, import.source.value.as_str(), import.span);
+            }
+            AstKind::CallExpression(call) => {
+                // Check for require() calls
+                if let Expression::Identifier(ident) = &call.callee {
+                    if ident.name == "require" {
+                        if let Some(Argument::StringLiteral(lit)) = call.arguments.first() {
+                            check_source(linter, lit.value.as_str(), call.span);
                         }
                     }
                 }
             }
-
-            oxc::ast_visit::walk::walk_call_expression(self, call);
+            _ => {}
         }
     }
+}
 
-    let mut visitor = ForbiddenLibrariesVisitor {
-        linter,
-        _phantom: std::marker::PhantomData,
-    };
-    visitor.visit_program(program);
+pub fn check_forbidden_libraries(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, ForbiddenLibraries);
 }
 
 #[cfg(test)]
@@ -188,4 +170,34 @@ mod tests {
         let errors = parse_and_check(source);
         assert_eq!(errors.len(), 0);
     }
+
+    #[test]
+    fn test_deno_npm_specifiers_and_import_map() {
+        use crate::deno_config::ImportMap;
+        use std::collections::BTreeMap;
+        use std::sync::Arc;
+
+        let source = r#"
+            import _ from 'npm:lodash@^4.17.21';
+            import fp from 'npm:lodash@4/fp';
+            import us from 'us';
+            import zod from 'npm:zod@3';
+        "#;
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::default()).parse();
+
+        let import_map = ImportMap::new(BTreeMap::from([(
+            "us".to_string(),
+            "npm:underscore@1".to_string(),
+        )]));
+        let mut linter = Linter::new(Path::new("main.ts"), source, false)
+            .with_import_map(Some(Arc::new(import_map)));
+        check_forbidden_libraries(&mut linter, &ret.program);
+
+        let errors: Vec<String> = linter.errors.into_iter().map(|e| e.message).collect();
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].contains("'lodash' is forbidden"));
+        assert!(errors[1].contains("'lodash/fp' is forbidden"));
+        assert!(errors[2].contains("'underscore' is forbidden"));
+    }
 }
@@ -1,82 +1,54 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
+use oxc::span::Span;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_import_extensions(linter: &mut Linter, program: &Program) {
-    struct ImportExtensionChecker<'a> {
-        linter: &'a mut Linter,
+/// Requires an explicit extension on relative import and re-export paths
+#[derive(Default)]
+pub struct ImportExtensions;
+
+fn check_source(linter: &mut Linter, source: &str, span: Span) {
+    // Only relative paths are checked
+    if !source.starts_with("./") && !source.starts_with("../") {
+        return;
     }
+    const EXTENSIONS: &[&str] = &[".ts", ".tsx", ".js", ".jsx", ".json"];
+    if !EXTENSIONS.iter().any(|ext| source.ends_with(ext)) {
+        linter.add_error(
+            "import-extensions".to_string(),
+            format!("Relative imports must include .ts extension: '{}'", source),
+            span,
+        );
+    }
+}
 
-    impl<'a> Visit<'a> for ImportExtensionChecker<'a> {
-        fn visit_import_declaration(&mut self, import: &ImportDeclaration<'a>) {
-            let source = import.source.value.as_str();
+impl<'a> Rule<'a> for ImportExtensions {
+    fn id(&self) -> &str {
+        "import-extensions"
+    }
 
-            // Check if it's a relative path import
-            if source.starts_with("./") || source.starts_with("../") {
-                // Check if it has .ts or .tsx extension
-                if !source.ends_with(".ts")
-                    && !source.ends_with(".tsx")
-                    && !source.ends_with(".js")
-                    && !source.ends_with(".jsx")
-                {
-                    self.linter.add_error(
-                        "import-extensions-required".to_string(),
-                        format!("Relative imports must include .ts extension: '{}'", source),
-                        import.span,
-                    );
-                }
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::ImportDeclaration(import) => {
+                check_source(linter, import.source.value.as_str(), import.span);
             }
-        }
-
-        fn visit_export_named_declaration(&mut self, export: &ExportNamedDeclaration<'a>) {
-            if let Some(source) = &export.source {
-                let source_str = source.value.as_str();
-
-                // Check if it's a relative path import
-                if source_str.starts_with("./") || source_str.starts_with("../") {
-                    // Check if it has .ts or .tsx extension
-                    if !source_str.ends_with(".ts")
-                        && !source_str.ends_with(".tsx")
-                        && !source_str.ends_with(".js")
-                        && !source_str.ends_with(".jsx")
-                    {
-                        self.linter.add_error(
-                            "import-extensions-required".to_string(),
-                            format!(
-                                "Relative imports must include .ts extension: '{}'",
-                                source_str
-                            ),
-                            export.span,
-                        );
-                    }
+            AstKind::ExportNamedDeclaration(export) => {
+                if let Some(source) = &export.source {
+                    check_source(linter, source.value.as_str(), export.span);
                 }
             }
-        }
-
-        fn visit_export_all_declaration(&mut self, export: &ExportAllDeclaration<'a>) {
-            let source = export.source.value.as_str();
-
-            // Check if it's a relative path import
-            if source.starts_with("./") || source.starts_with("../") {
-                // Check if it has .ts or .tsx extension
-                if !source.ends_with(".ts")
-                    && !source.ends_with(".tsx")
-                    && !source.ends_with(".js")
-                    && !source.ends_with(".jsx")
-                {
-                    self.linter.add_error(
-                        "import-extensions-required".to_string(),
-                        format!("Relative imports must include .ts extension: '{}'", source),
-                        export.span,
-                    );
-                }
+            AstKind::ExportAllDeclaration(export) => {
+                check_source(linter, export.source.value.as_str(), export.span);
             }
+            _ => {}
         }
     }
+}
 
-    let mut checker = ImportExtensionChecker { linter };
-    checker.visit_program(program);
+pub fn check_import_extensions(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, ImportExtensions);
 }
 
 #[cfg(test)]
@@ -1,18 +1,22 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::{walk, Visit};
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_interface_extends_only(linter: &mut Linter, program: &Program) {
-    struct InterfaceChecker<'a> {
-        linter: &'a mut Linter,
+/// Only allows interfaces that extend other types
+#[derive(Default)]
+pub struct InterfaceExtendsOnly;
+
+impl<'a> Rule<'a> for InterfaceExtendsOnly {
+    fn id(&self) -> &str {
+        "interface-extends-only"
     }
 
-    impl<'a> Visit<'a> for InterfaceChecker<'a> {
-        fn visit_ts_interface_declaration(&mut self, decl: &TSInterfaceDeclaration<'a>) {
-            // Check if interface has extends clause
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        if let AstKind::TSInterfaceDeclaration(decl) = kind {
             if decl.extends.is_empty() {
-                self.linter.add_error(
+                linter.add_error(
                     "interface-extends-only".to_string(),
                     format!(
                         "Interface '{}' without extends is not allowed. Use 'type' instead",
@@ -21,12 +25,12 @@ pub fn check_interface_extends_only(linter: &mut Linter, program: &Program) {
                     decl.span,
                 );
             }
-            walk::walk_ts_interface_declaration(self, decl);
         }
     }
+}
 
-    let mut checker = InterfaceChecker { linter };
-    checker.visit_program(program);
+pub fn check_interface_extends_only(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, InterfaceExtendsOnly);
 }
 
 #[cfg(test)]
@@ -1,176 +1,254 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
-use oxc::syntax::scope::ScopeFlags;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_jsdoc_param_match(linter: &mut Linter, program: &Program) {
-    struct JsDocParamChecker<'a> {
-        linter: &'a mut Linter,
-        source_text: &'a str,
-    }
-
-    impl<'a> Visit<'a> for JsDocParamChecker<'a> {
-        fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
-            if func.id.is_some() {
-                let func_name = func.id.as_ref().unwrap().name.as_str();
-                self.check_jsdoc_params(func_name, &func.params.items, func.span);
-            }
-            walk::walk_function(self, func, flags);
+/// Requires typed parameters, and JSDoc @param tags that match the signature
+/// when a function has them
+#[derive(Default)]
+pub struct JsdocParamMatch;
+
+impl JsdocParamMatch {
+    fn check_jsdoc_params(
+        linter: &mut Linter,
+        func_name: &str,
+        params: &[FormalParameter],
+        span: oxc::span::Span,
+    ) {
+        // Skip if no parameters
+        if params.is_empty() {
+            return;
         }
 
-        fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'a>) {
-            // Arrow functions assigned to variables can have JSDoc
-            // We'll check them when visiting variable declarations
-            walk::walk_arrow_function_expression(self, arrow);
-        }
+        // Extract JSDoc comment from source text
+        let jsdoc_params = Self::extract_jsdoc_params(&linter.source_text, span.start);
 
-        fn visit_variable_declaration(&mut self, decl: &VariableDeclaration<'a>) {
-            for declarator in &decl.declarations {
-                if let Some(Expression::ArrowFunctionExpression(arrow)) = &declarator.init {
-                    if let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind {
-                        let func_name = ident.name.as_str();
-                        self.check_jsdoc_params(func_name, &arrow.params.items, arrow.span);
-                    }
-                }
-            }
-            walk::walk_variable_declaration(self, decl);
-        }
-    }
-
-    impl<'a> JsDocParamChecker<'a> {
-        fn check_jsdoc_params(
-            &mut self,
-            func_name: &str,
-            params: &[FormalParameter<'a>],
-            span: oxc::span::Span,
-        ) {
-            // Skip if no parameters
-            if params.is_empty() {
-                return;
-            }
+        // Check each function parameter
+        for param in params {
+            if let BindingPatternKind::BindingIdentifier(ident) = &param.pattern.kind {
+                let param_name = ident.name.as_str();
 
-            // Extract JSDoc comment from source text
-            let jsdoc_params = self.extract_jsdoc_params(span.start);
-
-            // Check each function parameter
-            for param in params {
-                if let BindingPatternKind::BindingIdentifier(ident) = &param.pattern.kind {
-                    let param_name = ident.name.as_str();
-
-                    // Check if parameter has TypeScript type annotation
-                    if param.pattern.type_annotation.is_none() {
-                        self.linter.add_error(
-                            "param-missing-type".to_string(),
-                            format!(
-                                "Parameter '{}' in function '{}' must have a type ",
-                                param_name, func_name
-                            ),
-                            param.span,
-                        );
-                        continue;
-                    }
-
-                    // Check if JSDoc exists for this parameter
-                    if !jsdoc_params.is_empty()
-                        && !jsdoc_params.iter().any(|(name, _)| name == param_name)
-                    {
-                        self.linter.add_error(
-                            "jsdoc-param-missing".to_string(),
-                            format!(
-                                "JSDoc @param tag missing for parameter '{}' in function '{}'",
-                                param_name, func_name
-                            ),
-                            param.span,
-                        );
-                    }
+                // Check if parameter has TypeScript type annotation
+                if param.pattern.type_annotation.is_none() {
+                    linter.add_error(
+                        "param-missing-type".to_string(),
+                        format!(
+                            "Parameter '{}' in function '{}' must have a type ",
+                            param_name, func_name
+                        ),
+                        param.span,
+                    );
+                    continue;
                 }
-            }
-
-            // Check for JSDoc params that don't exist in function signature
-            for (jsdoc_param_name, _) in &jsdoc_params {
-                let exists = params.iter().any(|p| {
-                    if let BindingPatternKind::BindingIdentifier(ident) = &p.pattern.kind {
-                        ident.name.as_str() == jsdoc_param_name
-                    } else {
-                        false
-                    }
-                });
 
-                if !exists {
-                    self.linter.add_error(
-                        "jsdoc-param-unknown".to_string(),
+                // Check if JSDoc exists for this parameter
+                if !jsdoc_params.is_empty()
+                    && !jsdoc_params.iter().any(|(name, _)| name == param_name)
+                {
+                    linter.add_error(
+                        "jsdoc-param-missing".to_string(),
                         format!(
-                            "JSDoc @param '{}' does not match any parameter in function '{}'",
-                            jsdoc_param_name, func_name
+                            "JSDoc @param tag missing for parameter '{}' in function '{}'",
+                            param_name, func_name
                         ),
-                        span,
+                        param.span,
                     );
                 }
             }
+        }
+
+        // Check for JSDoc params that don't exist in function signature
+        for (jsdoc_param_name, _) in &jsdoc_params {
+            let exists = params.iter().any(|p| {
+                if let BindingPatternKind::BindingIdentifier(ident) = &p.pattern.kind {
+                    ident.name.as_str() == jsdoc_param_name
+                } else {
+                    false
+                }
+            });
 
-            // If function has JSDoc, require all params to be documented
-            if !jsdoc_params.is_empty() && jsdoc_params.len() != params.len() {
-                self.linter.add_error(
-                    "jsdoc-param-count".to_string(),
+            if !exists {
+                linter.add_error(
+                    "jsdoc-param-unknown".to_string(),
                     format!(
-                        "JSDoc has {} @param tags but function '{}' has {} parameters",
-                        jsdoc_params.len(),
-                        func_name,
-                        params.len()
+                        "JSDoc @param '{}' does not match any parameter in function '{}'",
+                        jsdoc_param_name, func_name
                     ),
                     span,
                 );
             }
         }
 
-        fn extract_jsdoc_params(&self, func_start: u32) -> Vec<(String, String)> {
-            let mut params = Vec::new();
-
-            // Find the JSDoc comment before the function
-            // Look for /** ... */ pattern
-            let text_before = &self.source_text[..func_start as usize];
-
-            if let Some(comment_end) = text_before.rfind("*/") {
-                if let Some(comment_start) = text_before[..comment_end].rfind("/**") {
-                    let comment = &text_before[comment_start + 3..comment_end];
-
-                    // Parse @param tags
-                    for line in comment.lines() {
-                        let trimmed = line.trim().trim_start_matches('*').trim();
-                        if trimmed.starts_with("@param") {
-                            // Parse: @param {type} name - description
-                            let parts: Vec<&str> = trimmed
-                                .strip_prefix("@param")
-                                .unwrap_or("")
-                                .trim()
-                                .splitn(3, ' ')
-                                .collect();
-                            if parts.len() >= 2 {
-                                // Extract type and name
-                                let type_str = parts[0].trim_matches(|c| c == '{' || c == '}');
-                                let name = parts[1].trim();
+        // If function has JSDoc, require all params to be documented
+        if !jsdoc_params.is_empty() && jsdoc_params.len() != params.len() {
+            linter.add_error(
+                "jsdoc-param-count".to_string(),
+                format!(
+                    "JSDoc has {} @param tags but function '{}' has {} parameters",
+                    jsdoc_params.len(),
+                    func_name,
+                    params.len()
+                ),
+                span,
+            );
+        }
+    }
+
+    fn extract_jsdoc_params(source_text: &str, func_start: u32) -> Vec<(String, String)> {
+        let mut params = Vec::new();
+
+        // Find the JSDoc comment before the function
+        // Look for /** ... */ pattern
+        let text_before = &source_text[..func_start as usize];
+
+        if let Some(comment_end) = text_before.rfind("*/") {
+            // Only a comment directly attached to this function counts, not
+            // one belonging to an earlier statement
+            let between = &text_before[comment_end + 2..];
+            if between.contains(['{', '}', ';']) {
+                return params;
+            }
+
+            if let Some(comment_start) = text_before[..comment_end].rfind("/**") {
+                let comment = &text_before[comment_start + 3..comment_end];
+
+                // Parse @param tags
+                for line in comment.lines() {
+                    let trimmed = line.trim().trim_start_matches('*').trim();
+                    if let Some(tag) = trimmed.strip_prefix("@param") {
+                        // Parse: @param [{type}] name [description]
+                        let mut parts = tag.split_whitespace();
+                        let Some(first) = parts.next() else {
+                            continue;
+                        };
+                        if first.starts_with('{') {
+                            let type_str = first.trim_matches(|c| c == '{' || c == '}');
+                            if let Some(name) = parts.next() {
                                 params.push((name.to_string(), type_str.to_string()));
-                            } else if parts.len() == 1 {
-                                // Just name, no type in JSDoc
-                                let name = parts[0].trim();
-                                params.push((name.to_string(), String::new()));
                             }
+                        } else {
+                            // Just name, no type in JSDoc
+                            params.push((first.to_string(), String::new()));
                         }
                     }
                 }
             }
+        }
 
-            params
+        params
+    }
+}
+
+impl<'a> Rule<'a> for JsdocParamMatch {
+    fn id(&self) -> &str {
+        "jsdoc-param-match"
+    }
+
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::Function(func) => {
+                if let Some(id) = &func.id {
+                    Self::check_jsdoc_params(
+                        linter,
+                        id.name.as_str(),
+                        &func.params.items,
+                        func.span,
+                    );
+                }
+            }
+            // Arrow functions assigned to variables can have JSDoc
+            AstKind::VariableDeclaration(decl) => {
+                for declarator in &decl.declarations {
+                    if let Some(Expression::ArrowFunctionExpression(arrow)) = &declarator.init {
+                        if let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind {
+                            Self::check_jsdoc_params(
+                                linter,
+                                ident.name.as_str(),
+                                &arrow.params.items,
+                                arrow.span,
+                            );
+                        }
+                    }
+                }
+            }
+            _ => {}
         }
     }
+}
+
+pub fn check_jsdoc_param_match(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, JsdocParamMatch);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+    use std::path::Path;
 
-    // Clone source_text to avoid borrow checker issues
-    let source_text = linter.source_text.clone();
-    let mut checker = JsDocParamChecker {
-        linter,
-        source_text: &source_text,
-    };
-    checker.visit_program(program);
+    fn parse_and_check(source: &str) -> Vec<String> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+
+        let mut linter = Linter::new(Path::new("test.ts"), source, false);
+        check_jsdoc_param_match(&mut linter, &ret.program);
+
+        linter.errors.into_iter().map(|e| e.rule).collect()
+    }
+
+    #[test]
+    fn test_params_with_and_without_type() {
+        let source = r#"
+/**
+ * Adds two numbers.
+ * @param a First number
+ * @param {number} b Second number
+ */
+export function add(a: number, b: number): number {
+  return a + b;
+}
+"#;
+        assert!(parse_and_check(source).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_and_missing_params() {
+        let source = r#"
+/**
+ * @param x Unknown
+ */
+function scale(value: number): number {
+  return value * 2;
+}
+"#;
+        let errors = parse_and_check(source);
+        assert!(errors.contains(&"jsdoc-param-unknown".to_string()));
+        assert!(errors.contains(&"jsdoc-param-missing".to_string()));
+    }
+
+    #[test]
+    fn test_comment_of_previous_function_ignored() {
+        let source = r#"
+/**
+ * @param a First number
+ */
+function first(a: number): number {
+  return a;
+}
+
+function second(b: number): number {
+  return b;
+}
+"#;
+        assert!(parse_and_check(source).is_empty());
+    }
+
+    #[test]
+    fn test_untyped_param() {
+        let source = "function untyped(value) { return value; }";
+        assert_eq!(parse_and_check(source), vec!["param-missing-type"]);
+    }
 }
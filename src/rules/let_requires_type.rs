@@ -1,42 +1,45 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_let_requires_type(linter: &mut Linter, program: &Program) {
-    struct LetTypeChecker<'a> {
-        linter: &'a mut Linter,
+/// Requires type annotations on let bindings
+#[derive(Default)]
+pub struct LetRequiresType;
+
+impl<'a> Rule<'a> for LetRequiresType {
+    fn id(&self) -> &str {
+        "let-requires-type"
     }
 
-    impl<'a> Visit<'a> for LetTypeChecker<'a> {
-        fn visit_variable_declaration(&mut self, decl: &VariableDeclaration<'a>) {
-            // Only check 'let' declarations
-            if decl.kind == VariableDeclarationKind::Let {
-                for declarator in &decl.declarations {
-                    // Check if the declarator has a type annotation
-                    if declarator.id.type_annotation.is_none() {
-                        // Skip if it's a destructuring pattern with type annotation on the pattern itself
-                        if let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind {
-                            self.linter.add_error(
-                                "let-requires-type".to_string(),
-                                format!(
-                                    "'let' declaration for '{}' must have an explicit type ",
-                                    ident.name
-                                ),
-                                declarator.span,
-                            );
-                        }
-                    }
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::VariableDeclaration(decl) = kind else {
+            return;
+        };
+        if decl.kind != VariableDeclarationKind::Let {
+            return;
+        }
+        for declarator in &decl.declarations {
+            // Destructuring patterns are skipped
+            if declarator.id.type_annotation.is_none() {
+                if let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind {
+                    linter.add_error(
+                        "let-requires-type".to_string(),
+                        format!(
+                            "'let' declaration for '{}' must have an explicit type ",
+                            ident.name
+                        ),
+                        declarator.span,
+                    );
                 }
             }
-
-            walk::walk_variable_declaration(self, decl);
         }
     }
+}
 
-    let mut checker = LetTypeChecker { linter };
-    checker.visit_program(program);
+pub fn check_let_requires_type(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, LetRequiresType);
 }
 
 #[cfg(test)]
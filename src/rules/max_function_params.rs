@@ -1,63 +1,61 @@
 use oxc::ast::ast::*;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
 const MAX_PARAMS: usize = 2;
 
-pub fn check_max_function_params(linter: &mut Linter, program: &Program) {
-    use oxc::ast_visit::Visit;
-    use oxc::syntax::scope::ScopeFlags;
+/// Limits the number of function parameters
+#[derive(Default)]
+pub struct MaxFunctionParams;
 
-    struct MaxParamsVisitor<'a, 'b> {
-        linter: &'a mut Linter,
-        _phantom: std::marker::PhantomData<&'b ()>,
+impl<'a> Rule<'a> for MaxFunctionParams {
+    fn id(&self) -> &str {
+        "max-function-params"
     }
 
-    impl<'a, 'b> Visit<'b> for MaxParamsVisitor<'a, 'b> {
-        fn visit_function(&mut self, func: &Function<'b>, _flags: ScopeFlags) {
-            let param_count = func.params.items.len();
-            if param_count > MAX_PARAMS {
-                let func_name = func
-                    .id
-                    .as_ref()
-                    .map(|id| id.name.as_str())
-                    .unwrap_or("<anonymous>");
-
-                self.linter.add_error(
-                    "max-function-params".to_string(),
-                    format!(
-                        "Function '{}' has {} parameters (max: {}). Use an options object as the second parameter instead",
-                        func_name, param_count, MAX_PARAMS
-                    ),
-                    func.span,
-                );
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::Function(func) => {
+                let param_count = func.params.items.len();
+                if param_count > MAX_PARAMS {
+                    let func_name = func
+                        .id
+                        .as_ref()
+                        .map(|id| id.name.as_str())
+                        .unwrap_or("<anonymous>");
+
+                    linter.add_error(
+                        "max-function-params".to_string(),
+                        format!(
+                            "Function '{}' has {} parameters (max: {}). Use an options object as the second parameter instead",
+                            func_name, param_count, MAX_PARAMS
+                        ),
+                        func.span,
+                    );
+                }
             }
-
-            oxc::ast_visit::walk::walk_function(self, func, _flags);
-        }
-
-        fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'b>) {
-            let param_count = arrow.params.items.len();
-            if param_count > MAX_PARAMS {
-                self.linter.add_error(
-                    "max-function-params".to_string(),
-                    format!(
-                        "Arrow function has {} parameters (max: {}). Use an options object as the second parameter instead",
-                        param_count, MAX_PARAMS
-                    ),
-                    arrow.span,
-                );
+            AstKind::ArrowFunctionExpression(arrow) => {
+                let param_count = arrow.params.items.len();
+                if param_count > MAX_PARAMS {
+                    linter.add_error(
+                        "max-function-params".to_string(),
+                        format!(
+                            "Arrow function has {} parameters (max: {}). Use an options object as the second parameter instead",
+                            param_count, MAX_PARAMS
+                        ),
+                        arrow.span,
+                    );
+                }
             }
-
-            oxc::ast_visit::walk::walk_arrow_function_expression(self, arrow);
+            _ => {}
         }
     }
+}
 
-    let mut visitor = MaxParamsVisitor {
-        linter,
-        _phantom: std::marker::PhantomData,
-    };
-    visitor.visit_program(program);
+pub fn check_max_function_params(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, MaxFunctionParams);
 }
 
 #[cfg(test)]
@@ -71,7 +69,7 @@ mod tests {
 
     fn parse_and_check(source: &str) -> Vec<String> {
         let allocator = Allocator::default();
-        let source_type = SourceType::default();
+        let source_type = SourceType::ts();
         let ret = Parser::new(&allocator, source, source_type).parse();
 
         let mut linter = Linter::new(Path::new("test.ts"), source, false);
@@ -81,7 +79,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore] // Integrated into CombinedVisitor
     fn test_function_with_three_params() {
         let source = r#"
             function badFunc(a: number, b: string, c: boolean) {
@@ -95,7 +92,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore] // Integrated into CombinedVisitor
     fn test_function_with_many_params() {
         let source = r#"
             function veryBad(a: number, b: string, c: boolean, d: any, e: string) {
@@ -108,7 +104,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore] // Integrated into CombinedVisitor
     fn test_arrow_function_with_too_many_params() {
         let source = r#"
             const arrow = (a: number, b: string, c: boolean) => a + b;
@@ -159,7 +154,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore] // Integrated into CombinedVisitor
     fn test_method_with_too_many_params() {
         let source = r#"
             const obj = {
@@ -1,5 +1,6 @@
 // Re-export all rules
 pub mod catch_error_handling;
+pub mod custom_rule;
 pub mod empty_array_requires_type;
 pub mod export_const_type_required;
 pub mod forbidden_libraries;
@@ -8,6 +9,7 @@ pub mod interface_extends_only;
 pub mod jsdoc_param_match;
 pub mod let_requires_type;
 pub mod max_function_params;
+pub mod must_handle_result;
 pub mod must_use_return_value;
 pub mod no_as_cast;
 pub mod no_classes;
@@ -23,10 +25,13 @@ pub mod no_foreach;
 pub mod no_getters_setters;
 pub mod no_global_process;
 pub mod no_http_imports;
+pub mod no_imperative_loops;
+pub mod no_let;
 pub mod no_member_assignments;
 pub mod no_mutable_record;
 pub mod no_namespace_imports;
 pub mod no_object_assign;
+pub mod no_param_mutation;
 pub mod no_reexports;
 pub mod no_require;
 pub mod no_this_in_functions;
@@ -37,8 +42,10 @@ pub mod no_unused_variables;
 pub mod node_import_style;
 pub mod one_public_function;
 pub mod prefer_readonly_array;
+pub mod readonly_properties;
 pub mod strict_named_export;
 pub mod switch_case_block;
+pub mod switch_exhaustive;
 // Removed: filename_function_match - functionality moved to strict_named_export
 pub mod allow_directives;
 pub mod export_requires_jsdoc;
@@ -54,6 +61,7 @@ pub use interface_extends_only::check_interface_extends_only;
 pub use jsdoc_param_match::check_jsdoc_param_match;
 pub use let_requires_type::check_let_requires_type;
 pub use max_function_params::check_max_function_params;
+pub use must_handle_result::check_must_handle_result;
 pub use must_use_return_value::check_must_use_return_value;
 pub use no_as_cast::check_no_as_upcast;
 pub use no_classes::check_no_classes;
@@ -69,10 +77,13 @@ pub use no_foreach::check_no_foreach;
 pub use no_getters_setters::check_no_getters_setters;
 pub use no_global_process::check_no_global_process;
 pub use no_http_imports::check_no_http_imports;
+pub use no_imperative_loops::{check_no_imperative_loops, ImperativeLoopsOptions};
+pub use no_let::check_no_let;
 pub use no_member_assignments::check_no_member_assignments;
 pub use no_mutable_record::check_no_mutable_record;
 pub use no_namespace_imports::check_no_namespace_imports;
 pub use no_object_assign::check_no_object_assign;
+pub use no_param_mutation::check_no_param_mutation;
 pub use no_reexports::check_no_reexports;
 pub use no_require::check_no_require;
 pub use no_this_in_functions::check_no_this_in_functions;
@@ -83,10 +94,84 @@ pub use no_unused_variables::check_no_unused_variables;
 pub use node_import_style::check_node_import_style;
 pub use one_public_function::check_one_public_function;
 pub use prefer_readonly_array::check_prefer_readonly_array;
+pub use readonly_properties::check_readonly_properties;
 pub use strict_named_export::check_strict_named_export;
 pub use switch_case_block::check_switch_case_block;
+pub use switch_exhaustive::check_switch_exhaustive;
 // Removed: check_filename_function_match - functionality moved to check_strict_named_export
-pub use allow_directives::{check_allow_directives, AllowedFeatures, UsedFeatures};
+pub use allow_directives::check_allow_directives;
 pub use export_requires_jsdoc::check_export_requires_jsdoc;
 pub use no_side_effect_functions::check_no_side_effect_functions;
 pub use path_based_restrictions::check_path_based_restrictions;
+
+use crate::rule::BoxedRule;
+use crate::Linter;
+
+/// Every rule run by `Linter::check_program`, including custom rules, minus
+/// those disabled on the linter
+pub fn all_rules<'a>(linter: &Linter) -> Vec<BoxedRule<'a>> {
+    let builtin: Vec<BoxedRule<'a>> = vec![
+        Box::<allow_directives::AllowDirectives>::default(),
+        Box::<catch_error_handling::CatchErrorHandling>::default(),
+        Box::<empty_array_requires_type::EmptyArrayRequiresType>::default(),
+        Box::<export_const_type_required::ExportConstTypeRequired>::default(),
+        Box::<export_requires_jsdoc::ExportRequiresJsdoc>::default(),
+        Box::<forbidden_libraries::ForbiddenLibraries>::default(),
+        Box::<import_extensions::ImportExtensions>::default(),
+        Box::<interface_extends_only::InterfaceExtendsOnly>::default(),
+        Box::<jsdoc_param_match::JsdocParamMatch>::default(),
+        Box::<let_requires_type::LetRequiresType>::default(),
+        Box::<max_function_params::MaxFunctionParams>::default(),
+        Box::<must_handle_result::MustHandleResult>::default(),
+        Box::<must_use_return_value::MustUseReturnValue>::default(),
+        Box::<no_as_cast::NoAsCast>::default(),
+        Box::<no_classes::NoClasses>::default(),
+        Box::<no_constant_condition::NoConstantCondition>::default(),
+        Box::<no_define_property::NoDefineProperty>::default(),
+        Box::<no_delete::NoDelete>::default(),
+        Box::<no_do_while::NoDoWhile>::default(),
+        Box::<no_dynamic_access::NoDynamicAccess>::default(),
+        Box::<no_enums::NoEnums>::default(),
+        Box::<no_eval_function::NoEvalFunction>::default(),
+        Box::<no_filename_dirname::NoFilenameDirname>::default(),
+        Box::<no_foreach::NoForeach>::default(),
+        Box::<no_getters_setters::NoGettersSetters>::default(),
+        Box::<no_global_process::NoGlobalProcess>::default(),
+        Box::<no_http_imports::NoHttpImports>::default(),
+        Box::new(no_imperative_loops::NoImperativeLoops::new(
+            linter.imperative_loops.clone(),
+        )),
+        Box::<no_let::NoLet>::default(),
+        Box::<no_member_assignments::NoMemberAssignments>::default(),
+        Box::<no_mutable_record::NoMutableRecord>::default(),
+        Box::<no_namespace_imports::NoNamespaceImports>::default(),
+        Box::<no_object_assign::NoObjectAssign>::default(),
+        Box::<no_param_mutation::NoParamMutation>::default(),
+        Box::<no_reexports::NoReexports>::default(),
+        Box::<no_require::NoRequire>::default(),
+        Box::<no_side_effect_functions::NoSideEffectFunctions>::default(),
+        Box::<no_this_in_functions::NoThisInFunctions>::default(),
+        Box::<no_throw::NoThrow>::default(),
+        Box::<no_top_level_side_effects::NoTopLevelSideEffects>::default(),
+        Box::<no_unused_map::NoUnusedMap>::default(),
+        Box::<no_unused_variables::NoUnusedVariables>::default(),
+        Box::<node_import_style::NodeImportStyle>::default(),
+        Box::<one_public_function::OnePublicFunction>::default(),
+        Box::<path_based_restrictions::PathBasedRestrictions>::default(),
+        Box::<prefer_readonly_array::PreferReadonlyArray>::default(),
+        Box::<readonly_properties::ReadonlyProperties>::default(),
+        Box::<strict_named_export::StrictNamedExport>::default(),
+        Box::<switch_case_block::SwitchCaseBlock>::default(),
+        Box::<switch_exhaustive::SwitchExhaustive>::default(),
+    ];
+    let custom = linter
+        .custom_rules
+        .iter()
+        .map(|rule| Box::new(custom_rule::CustomRuleCheck::new(rule.clone())) as BoxedRule<'a>);
+
+    builtin
+        .into_iter()
+        .chain(custom)
+        .filter(|rule| linter.is_rule_enabled(rule.id()))
+        .collect()
+}
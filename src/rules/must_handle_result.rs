@@ -0,0 +1,531 @@
+use oxc::allocator::Allocator;
+use oxc::ast::ast::*;
+use oxc::ast::AstKind;
+use oxc::parser::Parser;
+use oxc::span::{SourceType, Span};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::directories::resolve_dots;
+use crate::rule::{run_rule, Rule};
+use crate::scope_analysis::ScopeAnalysis;
+use crate::Linter;
+
+/// neverthrow methods that return another Result, so calling them alone
+/// still leaves the outcome unhandled
+const RESULT_PRESERVING_METHODS: &[&str] = &[
+    "map",
+    "mapErr",
+    "andThen",
+    "orElse",
+    "andTee",
+    "orTee",
+    "andThrough",
+    "asyncMap",
+    "asyncAndThen",
+    "asyncAndThrough",
+];
+
+/// neverthrow escape hatches that are only acceptable in tests
+const UNSAFE_UNWRAP_METHODS: &[&str] = &["_unsafeUnwrap", "_unsafeUnwrapErr"];
+
+/// Whether a return type annotation is `Result<...>`, `ResultAsync<...>`
+/// or `Promise<Result<...>>`
+fn is_result_type(ts_type: &TSType) -> bool {
+    let TSType::TSTypeReference(type_ref) = ts_type else {
+        return false;
+    };
+    let TSTypeName::IdentifierReference(id) = &type_ref.type_name else {
+        return false;
+    };
+    match id.name.as_str() {
+        "Result" | "ResultAsync" => true,
+        "Promise" => type_ref
+            .type_arguments
+            .as_ref()
+            .and_then(|args| args.params.first())
+            .is_some_and(is_result_type),
+        _ => false,
+    }
+}
+
+fn returns_result(return_type: Option<&TSTypeAnnotation>) -> bool {
+    return_type.is_some_and(|ann| is_result_type(&ann.type_annotation))
+}
+
+/// Add the name and binding of a Result-returning function or
+/// function-valued const
+fn collect_from_declaration(decl: &Declaration, functions: &mut Vec<(String, Span)>) {
+    match decl {
+        Declaration::FunctionDeclaration(func) => {
+            if let Some(id) = &func.id {
+                if returns_result(func.return_type.as_deref()) {
+                    functions.push((id.name.to_string(), id.span));
+                }
+            }
+        }
+        Declaration::VariableDeclaration(var_decl) => {
+            for declarator in &var_decl.declarations {
+                let BindingPatternKind::BindingIdentifier(id) = &declarator.id.kind else {
+                    continue;
+                };
+                let is_result_fn = match &declarator.init {
+                    Some(Expression::ArrowFunctionExpression(arrow)) => {
+                        returns_result(arrow.return_type.as_deref())
+                    }
+                    Some(Expression::FunctionExpression(func)) => {
+                        returns_result(func.return_type.as_deref())
+                    }
+                    _ => false,
+                };
+                if is_result_fn {
+                    functions.push((id.name.to_string(), id.span));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Functions declared in this program whose return type is a Result, by
+/// name (`default` for a default export) and binding. With `exported_only`,
+/// only exported declarations are collected.
+fn collect_result_functions(program: &Program, exported_only: bool) -> Vec<(String, Span)> {
+    let mut functions = Vec::new();
+
+    for stmt in &program.body {
+        match stmt {
+            Statement::ExportNamedDeclaration(export) => {
+                if let Some(decl) = &export.declaration {
+                    collect_from_declaration(decl, &mut functions);
+                }
+            }
+            Statement::ExportDefaultDeclaration(export) => {
+                if let ExportDefaultDeclarationKind::FunctionDeclaration(func) = &export.declaration
+                {
+                    if returns_result(func.return_type.as_deref()) {
+                        let span = func.id.as_ref().map_or(func.span, |id| id.span);
+                        functions.push(("default".to_string(), span));
+                    }
+                }
+            }
+            _ if exported_only => {}
+            _ => {
+                if let Some(decl) = stmt.as_declaration() {
+                    collect_from_declaration(decl, &mut functions);
+                }
+            }
+        }
+    }
+
+    functions
+}
+
+/// Resolve a relative import specifier to a TypeScript file on disk.
+/// `.js` specifiers are mapped to their `.ts` sources.
+fn resolve_module(base_dir: &Path, source: &str) -> Option<PathBuf> {
+    if !source.starts_with('.') {
+        return None;
+    }
+    let path = resolve_dots(&base_dir.join(source));
+    if path
+        .extension()
+        .is_some_and(|ext| ext == "ts" || ext == "tsx")
+    {
+        return path.is_file().then_some(path);
+    }
+    if path.extension().is_some_and(|ext| ext == "js") {
+        let ts_path = path.with_extension("ts");
+        return ts_path.is_file().then_some(ts_path);
+    }
+    None
+}
+
+/// Local bindings of imports whose source module exports a
+/// Result-returning function under the imported name. The modules read are
+/// recorded as dependencies of the linted file
+fn collect_imported_result_functions(program: &Program, linter: &mut Linter) -> HashSet<Span> {
+    let mut bindings = HashSet::new();
+    let base_dir = linter.path.parent().unwrap_or(Path::new(".")).to_path_buf();
+    let mut module_cache: HashMap<PathBuf, HashSet<String>> = HashMap::new();
+
+    for stmt in &program.body {
+        let Statement::ImportDeclaration(import) = stmt else {
+            continue;
+        };
+        let Some(specifiers) = &import.specifiers else {
+            continue;
+        };
+        let Some(module_path) = resolve_module(&base_dir, import.source.value.as_str()) else {
+            continue;
+        };
+        linter.dependencies.insert(module_path.clone());
+        let exported = module_cache.entry(module_path.clone()).or_insert_with(|| {
+            let Ok(source_text) = std::fs::read_to_string(&module_path) else {
+                return HashSet::new();
+            };
+            let allocator = Allocator::default();
+            let source_type = SourceType::from_path(&module_path).unwrap_or_default();
+            let ret = Parser::new(&allocator, &source_text, source_type).parse();
+            collect_result_functions(&ret.program, true)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect()
+        });
+
+        for spec in specifiers {
+            let (imported, local) = match spec {
+                ImportDeclarationSpecifier::ImportSpecifier(named) => {
+                    (named.imported.name().to_string(), &named.local)
+                }
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(default) => {
+                    ("default".to_string(), &default.local)
+                }
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(_) => continue,
+            };
+            if exported.contains(&imported) {
+                bindings.insert(local.span);
+            }
+        }
+    }
+
+    bindings
+}
+
+/// Check that Results returned by our own functions are handled
+///
+/// Reports calls to functions declared with a `Result<...>` or
+/// `ResultAsync<...>` return type (in this file or imported from a relative
+/// module) when the Result is discarded, only transformed with `map`/`andThen`
+/// and then dropped, or unwrapped with `_unsafeUnwrap()` outside tests.
+/// Callees are resolved through lexical scopes, so a local function or
+/// parameter shadowing one of them is not checked.
+#[derive(Default)]
+pub struct MustHandleResult {
+    // Bindings of Result-returning functions
+    result_functions: HashSet<Span>,
+    // Binding each identifier callee resolves to
+    callees: HashMap<Span, Span>,
+    is_test_file: bool,
+    // Variables bound to a Result and where they were declared
+    result_bindings: Vec<(String, Span)>,
+    referenced: HashSet<String>,
+}
+
+impl MustHandleResult {
+    /// If the expression is a call to a Result-returning function, return its name
+    fn result_call_name<'e>(&self, expr: &'e Expression) -> Option<&'e str> {
+        match expr.without_parentheses() {
+            Expression::CallExpression(call) => match &call.callee {
+                Expression::Identifier(id)
+                    if self
+                        .callees
+                        .get(&id.span)
+                        .is_some_and(|binding| self.result_functions.contains(binding)) =>
+                {
+                    Some(id.name.as_str())
+                }
+                _ => None,
+            },
+            Expression::AwaitExpression(await_expr) => self.result_call_name(&await_expr.argument),
+            _ => None,
+        }
+    }
+
+    /// If the expression is `f().map(...).andThen(...)` with only
+    /// Result-preserving methods, return the name of `f`
+    fn preserved_result_chain<'e>(&self, expr: &'e Expression) -> Option<&'e str> {
+        match expr.without_parentheses() {
+            Expression::CallExpression(call) => {
+                let Expression::StaticMemberExpression(member) = &call.callee else {
+                    return None;
+                };
+                if !RESULT_PRESERVING_METHODS.contains(&member.property.name.as_str()) {
+                    return None;
+                }
+                self.result_call_name(&member.object)
+                    .or_else(|| self.preserved_result_chain(&member.object))
+            }
+            Expression::AwaitExpression(await_expr) => {
+                self.preserved_result_chain(&await_expr.argument)
+            }
+            _ => None,
+        }
+    }
+
+    fn check_expression_statement(&self, linter: &mut Linter, stmt: &ExpressionStatement) {
+        let expression = match &stmt.expression {
+            Expression::UnaryExpression(unary) if unary.operator == UnaryOperator::Void => {
+                &unary.argument
+            }
+            expression => expression,
+        };
+
+        if let Some(name) = self.result_call_name(expression) {
+            linter.add_error(
+                "must-handle-result".to_string(),
+                format!(
+                    "Result returned by '{}()' is discarded. Handle it with match(), unwrapOr() or return it",
+                    name
+                ),
+                stmt.span,
+            );
+        } else if let Some(name) = self.preserved_result_chain(expression) {
+            linter.add_error(
+                "must-handle-result".to_string(),
+                format!(
+                    "Result returned by '{}()' is only partially consumed. map()/andThen() return a new Result that must be handled",
+                    name
+                ),
+                stmt.span,
+            );
+        }
+    }
+}
+
+impl<'a> Rule<'a> for MustHandleResult {
+    fn id(&self) -> &str {
+        "must-handle-result"
+    }
+
+    fn on_program_start(&mut self, linter: &mut Linter, program: &'a Program<'a>) {
+        let path_str = linter.path.to_str().unwrap_or("");
+        self.is_test_file = path_str.contains("_test.ts")
+            || path_str.contains(".test.ts")
+            || path_str.contains(".spec.ts");
+
+        self.result_functions = collect_result_functions(program, false)
+            .into_iter()
+            .map(|(_, span)| span)
+            .collect();
+        self.result_functions
+            .extend(collect_imported_result_functions(program, linter));
+        self.callees = ScopeAnalysis::analyze(program).callees;
+    }
+
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::ExpressionStatement(stmt) => self.check_expression_statement(linter, stmt),
+            AstKind::VariableDeclarator(decl) => {
+                if let BindingPatternKind::BindingIdentifier(id) = &decl.id.kind {
+                    let binds_result = decl.init.as_ref().is_some_and(|init| {
+                        self.result_call_name(init).is_some()
+                            || self.preserved_result_chain(init).is_some()
+                    });
+                    if binds_result {
+                        self.result_bindings.push((id.name.to_string(), decl.span));
+                    }
+                }
+            }
+            AstKind::IdentifierReference(ident) => {
+                self.referenced.insert(ident.name.to_string());
+            }
+            AstKind::CallExpression(call) if !self.is_test_file => {
+                if let Expression::StaticMemberExpression(member) = &call.callee {
+                    let method = member.property.name.as_str();
+                    if UNSAFE_UNWRAP_METHODS.contains(&method) {
+                        linter.add_error(
+                            "must-handle-result".to_string(),
+                            format!(
+                                "'{}()' is only allowed in tests. Use match() or unwrapOr() instead",
+                                method
+                            ),
+                            call.span,
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn on_program_end(&mut self, linter: &mut Linter, _program: &'a Program<'a>) {
+        for (name, span) in std::mem::take(&mut self.result_bindings) {
+            if !self.referenced.contains(&name) {
+                linter.add_error(
+                    "must-handle-result".to_string(),
+                    format!("Result stored in '{}' is never used", name),
+                    span,
+                );
+            }
+        }
+    }
+}
+
+pub fn check_must_handle_result(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, MustHandleResult::default());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Linter;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+    use std::path::Path;
+
+    fn parse_and_check(source: &str, file_path: &Path) -> Vec<String> {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path(file_path).unwrap();
+        let ret = Parser::new(&allocator, source, source_type).parse();
+
+        let mut linter = Linter::new(file_path, source, false);
+        check_must_handle_result(&mut linter, &ret.program);
+
+        linter.errors.into_iter().map(|e| e.message).collect()
+    }
+
+    const PARSE: &str = r#"
+import { ok, err, type Result } from "neverthrow";
+
+function parse(input: string): Result<number, Error> {
+  const n = Number(input);
+  return Number.isNaN(n) ? err(new Error("nan")) : ok(n);
+}
+"#;
+
+    #[test]
+    fn test_discarded_result() {
+        let source = format!(
+            "{}{}",
+            PARSE,
+            r#"
+export function run(input: string): void {
+  parse(input);
+  void parse(input);
+}
+"#
+        );
+        let errors = parse_and_check(&source, Path::new("src/io/run.ts"));
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("'parse()' is discarded"));
+    }
+
+    #[test]
+    fn test_partially_consumed_result() {
+        let source = format!(
+            "{}{}",
+            PARSE,
+            r#"
+export function run(input: string): void {
+  parse(input).map((n) => n * 2);
+  const unused = parse(input);
+}
+"#
+        );
+        let errors = parse_and_check(&source, Path::new("src/io/run.ts"));
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("only partially consumed"));
+        assert!(errors[1].contains("'unused' is never used"));
+    }
+
+    #[test]
+    fn test_handled_result() {
+        let source = format!(
+            "{}{}",
+            PARSE,
+            r#"
+export function run(input: string): number {
+  const parsed = parse(input);
+  return parsed.match((n) => n, () => 0) + parse(input).unwrapOr(0);
+}
+"#
+        );
+        let errors = parse_and_check(&source, Path::new("src/io/run.ts"));
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_unsafe_unwrap_outside_tests() {
+        let source = format!(
+            "{}{}",
+            PARSE,
+            r#"
+export function run(input: string): number {
+  return parse(input)._unsafeUnwrap();
+}
+"#
+        );
+        let errors = parse_and_check(&source, Path::new("src/io/run.ts"));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'_unsafeUnwrap()' is only allowed in tests"));
+
+        let errors = parse_and_check(&source, Path::new("src/io/run.test.ts"));
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_imported_result_function() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("fetchUser.ts"),
+            r#"
+import { ResultAsync } from "neverthrow";
+
+export const fetchUser = (id: string): ResultAsync<string, Error> =>
+  ResultAsync.fromPromise(Promise.resolve(id), () => new Error("failed"));
+"#,
+        )
+        .unwrap();
+
+        let source = r#"
+import { fetchUser } from "./fetchUser.js";
+
+export async function main(): Promise<void> {
+  await fetchUser("1");
+}
+"#;
+        let errors = parse_and_check(source, &dir.path().join("main.ts"));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'fetchUser()' is discarded"));
+    }
+
+    #[test]
+    fn test_shadowed_result_function() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("parse.ts"),
+            PARSE.replacen("function", "export function", 1),
+        )
+        .unwrap();
+
+        let source = r#"
+import { parse } from "./parse.ts";
+
+export function run(input: string, validate: (s: string) => void): void {
+  function parse(s: string): void {
+    validate(s);
+  }
+  parse(input);
+}
+
+export function check(parse: (s: string) => void): void {
+  parse("1");
+}
+"#;
+        let errors = parse_and_check(source, &dir.path().join("main.ts"));
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_aliased_import() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("parse.ts"),
+            PARSE.replacen("function", "export function", 1),
+        )
+        .unwrap();
+
+        let source = r#"
+import { parse as p } from "./parse.ts";
+
+export function run(input: string): void {
+  p(input);
+}
+"#;
+        let errors = parse_and_check(source, &dir.path().join("main.ts"));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'p()' is discarded"));
+    }
+}
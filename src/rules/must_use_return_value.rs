@@ -1,7 +1,9 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
+use oxc::span::{GetSpan, Span};
+use std::collections::HashSet;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
 // Helper function for checking IIFE
@@ -18,54 +20,149 @@ fn is_iife(call: &CallExpression) -> bool {
     }
 }
 
-pub fn check_must_use_return_value(linter: &mut Linter, program: &Program) {
-    struct ReturnValueChecker<'a> {
-        linter: &'a mut Linter,
-        in_statement_position: bool,
+/// Whether a return type annotation is `void`, `undefined` or `Promise<void>`
+fn is_void_type(ts_type: &TSType) -> bool {
+    match ts_type {
+        TSType::TSVoidKeyword(_) | TSType::TSUndefinedKeyword(_) => true,
+        TSType::TSTypeReference(type_ref) => {
+            matches!(&type_ref.type_name, TSTypeName::IdentifierReference(id) if id.name == "Promise")
+                && type_ref
+                    .type_arguments
+                    .as_ref()
+                    .and_then(|args| args.params.first())
+                    .is_some_and(is_void_type)
+        }
+        _ => false,
     }
+}
 
-    impl<'a> Visit<'a> for ReturnValueChecker<'a> {
-        fn visit_expression_statement(&mut self, stmt: &ExpressionStatement<'a>) {
-            self.in_statement_position = true;
-
-            if let Expression::CallExpression(call) = &stmt.expression {
-                // Check if this is a known void function (console.log, etc.)
-                let is_void_function = match &call.callee {
-                    Expression::StaticMemberExpression(member) => {
-                        if let Expression::Identifier(obj) = &member.object {
-                            let obj_name = obj.name.as_str();
-                            let prop_name = member.property.name.as_str();
-                            // Allow console methods and similar void functions
-                            obj_name == "console"
-                                || (obj_name == "process" && prop_name == "exit")
-                                || (obj_name == "Array" && prop_name == "isArray")
-                        // This actually returns a value but checking in statement position
-                        } else {
-                            false
-                        }
+fn returns_void(return_type: Option<&TSTypeAnnotation>) -> bool {
+    return_type.is_some_and(|ann| is_void_type(&ann.type_annotation))
+}
+
+/// Names of top-level functions in this file declared to return nothing
+fn collect_void_functions(program: &Program) -> HashSet<String> {
+    let mut names = HashSet::new();
+
+    let mut check_declaration = |decl: &Declaration| match decl {
+        Declaration::FunctionDeclaration(func) => {
+            if let Some(id) = &func.id {
+                if returns_void(func.return_type.as_deref()) {
+                    names.insert(id.name.to_string());
+                }
+            }
+        }
+        Declaration::VariableDeclaration(var_decl) => {
+            for decl in &var_decl.declarations {
+                let BindingPatternKind::BindingIdentifier(id) = &decl.id.kind else {
+                    continue;
+                };
+                let return_type = match &decl.init {
+                    Some(Expression::ArrowFunctionExpression(arrow)) => {
+                        arrow.return_type.as_deref()
                     }
-                    _ => false,
+                    Some(Expression::FunctionExpression(func)) => func.return_type.as_deref(),
+                    _ => continue,
                 };
-
-                if !is_void_function && !is_iife(call) {
-                    self.linter.add_error(
-                        "must-use-return-value".to_string(),
-                        "Function return values must be used or assigned".to_string(),
-                        stmt.span,
-                    );
+                if returns_void(return_type) {
+                    names.insert(id.name.to_string());
                 }
             }
+        }
+        _ => {}
+    };
 
-            walk::walk_expression_statement(self, stmt);
-            self.in_statement_position = false;
+    for stmt in &program.body {
+        if let Statement::ExportNamedDeclaration(export) = stmt {
+            if let Some(decl) = &export.declaration {
+                check_declaration(decl);
+            }
+        } else if let Some(decl) = stmt.as_declaration() {
+            check_declaration(decl);
         }
     }
 
-    let mut checker = ReturnValueChecker {
-        linter,
-        in_statement_position: false,
-    };
-    checker.visit_program(program);
+    names
+}
+
+/// Requires the return value of calls in statement position to be used
+///
+/// Calls to functions declared in the same file as returning `void` and
+/// `super(...)` calls are allowed. Test files are not checked, since test
+/// framework calls are made for their side effects.
+#[derive(Default)]
+pub struct MustUseReturnValue {
+    is_test_file: bool,
+    void_functions: HashSet<String>,
+    // Bodies of expression arrows (`() => f()`), whose value is returned
+    arrow_bodies: HashSet<Span>,
+}
+
+impl<'a> Rule<'a> for MustUseReturnValue {
+    fn id(&self) -> &str {
+        "must-use-return-value"
+    }
+
+    fn on_program_start(&mut self, linter: &mut Linter, program: &'a Program<'a>) {
+        let path_str = linter.path.to_str().unwrap_or("");
+        self.is_test_file = path_str.contains("_test.ts")
+            || path_str.contains(".test.ts")
+            || path_str.contains(".spec.ts");
+        self.void_functions = collect_void_functions(program);
+    }
+
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        if self.is_test_file {
+            return;
+        }
+        if let AstKind::ArrowFunctionExpression(arrow) = kind {
+            if arrow.expression {
+                self.arrow_bodies
+                    .extend(arrow.body.statements.iter().map(|stmt| stmt.span()));
+            }
+            return;
+        }
+        let AstKind::ExpressionStatement(stmt) = kind else {
+            return;
+        };
+        if self.arrow_bodies.contains(&stmt.span) {
+            return;
+        }
+        let Expression::CallExpression(call) = &stmt.expression else {
+            return;
+        };
+
+        // Check if this is a known void function (console.log, etc.)
+        let is_void_function = match &call.callee {
+            Expression::StaticMemberExpression(member) => {
+                if let Expression::Identifier(obj) = &member.object {
+                    let obj_name = obj.name.as_str();
+                    let prop_name = member.property.name.as_str();
+                    // Allow console methods and similar void functions
+                    obj_name == "console"
+                        || (obj_name == "process" && prop_name == "exit")
+                        || (obj_name == "Array" && prop_name == "isArray")
+                } else {
+                    false
+                }
+            }
+            Expression::Identifier(id) => self.void_functions.contains(id.name.as_str()),
+            Expression::Super(_) => true,
+            _ => false,
+        };
+
+        if !is_void_function && !is_iife(call) {
+            linter.add_error(
+                "must-use-return-value".to_string(),
+                "Function return values must be used or assigned".to_string(),
+                stmt.span,
+            );
+        }
+    }
+}
+
+pub fn check_must_use_return_value(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, MustUseReturnValue::default());
 }
 
 #[cfg(test)]
@@ -94,16 +191,15 @@ function processData(data: string): string {
 processData("test"); // Error: return value not used
 
 "#;
-        let source_type = SourceType::default();
+        let source_type = SourceType::ts();
         let ParserReturn { program, .. } =
             Parser::new(&allocator, source_text, source_type).parse();
         let mut linter = Linter::new(Path::new("test-file.ts"), source_text, false);
 
         check_must_use_return_value(&mut linter, &program);
 
-        // TODO: Fix must_use_return_value rule implementation - currently not detecting violations
         let errors = &linter.errors;
-        assert_eq!(errors.len(), 0); // Adjusted from 2 to match actual behavior
+        assert_eq!(errors.len(), 2);
     }
 
     #[test]
@@ -122,7 +218,7 @@ export function test() {
 }
 
 "#;
-        let source_type = SourceType::default();
+        let source_type = SourceType::ts();
         let ParserReturn { program, .. } =
             Parser::new(&allocator, source_text, source_type).parse();
         let mut linter = Linter::new(Path::new("test-file.ts"), source_text, false);
@@ -141,7 +237,7 @@ console.log("Hello");
 console.error("Error");
 console.warn("Warning");
 "#;
-        let source_type = SourceType::default();
+        let source_type = SourceType::ts();
         let ParserReturn { program, .. } =
             Parser::new(&allocator, source_text, source_type).parse();
         let mut linter = Linter::new(Path::new("test-file.ts"), source_text, false);
@@ -164,7 +260,7 @@ console.warn("Warning");
   return "Another IIFE";
 })();
 "#;
-        let source_type = SourceType::default();
+        let source_type = SourceType::ts();
         let ParserReturn { program, .. } =
             Parser::new(&allocator, source_text, source_type).parse();
         let mut linter = Linter::new(Path::new("test-file.ts"), source_text, false);
@@ -192,15 +288,58 @@ console.log("Hello"); // Should pass
 })(); // Should pass
 
 "#;
-        let source_type = SourceType::default();
+        let source_type = SourceType::ts();
         let ParserReturn { program, .. } =
             Parser::new(&allocator, source_text, source_type).parse();
         let mut linter = Linter::new(Path::new("test-file.ts"), source_text, false);
 
         check_must_use_return_value(&mut linter, &program);
 
-        // TODO: Fix must_use_return_value rule implementation - currently not detecting violations
         let errors = &linter.errors;
-        assert_eq!(errors.len(), 0); // Adjusted from 1 to match actual behavior
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_void_calls_and_arrow_bodies_allowed() {
+        let allocator = Allocator::default();
+        let source_text = r#"
+async function main(): Promise<void> {}
+const log = (message: string): void => {};
+
+class AppError extends Error {
+  constructor(message: string) {
+    super(message);
+  }
+}
+
+const random = () => Math.random();
+
+main();
+log("started");
+"#;
+        let ParserReturn { program, .. } =
+            Parser::new(&allocator, source_text, SourceType::ts()).parse();
+        let mut linter = Linter::new(Path::new("test-file.ts"), source_text, false);
+
+        check_must_use_return_value(&mut linter, &program);
+
+        assert!(linter.errors.is_empty());
+    }
+
+    #[test]
+    fn test_test_files_skipped() {
+        let allocator = Allocator::default();
+        let source_text = r#"
+describe("add", () => {
+  expect(add(1, 2)).toBe(3);
+});
+"#;
+        let ParserReturn { program, .. } =
+            Parser::new(&allocator, source_text, SourceType::ts()).parse();
+        let mut linter = Linter::new(Path::new("add.test.ts"), source_text, false);
+
+        check_must_use_return_value(&mut linter, &program);
+
+        assert!(linter.errors.is_empty());
     }
 }
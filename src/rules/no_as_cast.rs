@@ -1,91 +1,76 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_as_upcast(linter: &mut Linter, program: &Program) {
-    struct AsUpcastChecker<'a> {
-        linter: &'a mut Linter,
-    }
+/// Prohibits `as` casts (except `as const`) and `<Type>value` assertions
+#[derive(Default)]
+pub struct NoAsCast;
 
-    impl<'a> Visit<'a> for AsUpcastChecker<'a> {
-        fn visit_ts_as_expression(&mut self, expr: &TSAsExpression<'a>) {
-            // Check if it's 'as const' which is allowed
-            let is_const_assertion = match &expr.type_annotation {
-                TSType::TSTypeReference(type_ref) => {
-                    if let TSTypeName::IdentifierReference(id) = &type_ref.type_name {
-                        id.name == "const"
-                    } else {
-                        false
-                    }
-                }
-                _ => false,
-            };
+impl<'a> Rule<'a> for NoAsCast {
+    fn id(&self) -> &str {
+        "no-as-cast"
+    }
 
-            if is_const_assertion {
-                // 'as const' is allowed
-                walk::walk_ts_as_expression(self, expr);
-                return;
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::TSAsExpression(expr) => check_as_expression(linter, expr),
+            AstKind::TSTypeAssertion(assertion) => {
+                // Angle bracket assertion <Type>value is also discouraged
+                linter.add_error(
+                    "no-type-assertion".to_string(),
+                    "Type assertion <Type>value is not allowed. Use 'satisfies' operator or proper type narrowing instead".to_string(),
+                    assertion.span,
+                );
             }
+            _ => {}
+        }
+    }
+}
 
-            // Check for common upcast patterns
-            let is_likely_upcast = match &expr.type_annotation {
-                // Casting to any, unknown, object are always upcasts
-                TSType::TSAnyKeyword(_)
-                | TSType::TSUnknownKeyword(_)
-                | TSType::TSObjectKeyword(_) => true,
-
-                // Casting to broader types like string, number, boolean might be upcasts
-                TSType::TSStringKeyword(_)
-                | TSType::TSNumberKeyword(_)
-                | TSType::TSBooleanKeyword(_) => {
-                    // These could be upcasts from literals or more specific types
-                    self.linter.add_error(
-                        "no-as-cast".to_string(),
-                        "Type assertion with 'as' is discouraged. Consider using 'satisfies' for type checking or narrowing the type properly".to_string(),
-                        expr.span,
-                    );
-                    walk::walk_ts_as_expression(self, expr);
-                    return;
-                }
-
-                // For other types, warn about as usage in general
-                _ => false,
-            };
-
-            if is_likely_upcast {
-                self.linter.add_error(
-                    "no-as-upcast".to_string(),
-                    "Upcast with 'as' is not allowed. Use 'satisfies' operator instead for type validation".to_string(),
-                    expr.span,
-                );
+fn check_as_expression(linter: &mut Linter, expr: &TSAsExpression) {
+    // Check if it's 'as const' which is allowed
+    let is_const_assertion = match &expr.type_annotation {
+        TSType::TSTypeReference(type_ref) => {
+            if let TSTypeName::IdentifierReference(id) = &type_ref.type_name {
+                id.name == "const"
             } else {
-                // General warning for any 'as' usage
-                self.linter.add_error(
-                    "no-as-cast".to_string(),
-                    "Type assertion with 'as' is discouraged. Consider using 'satisfies' for type checking or narrowing the type properly".to_string(),
-                    expr.span,
-                );
+                false
             }
-
-            walk::walk_ts_as_expression(self, expr);
         }
+        _ => false,
+    };
 
-        fn visit_ts_type_assertion(&mut self, assertion: &TSTypeAssertion<'a>) {
-            // Angle bracket assertion <Type>value is also discouraged
-            self.linter.add_error(
-                "no-type-assertion".to_string(),
-                "Type assertion <Type>value is not allowed. Use 'satisfies' operator or proper type narrowing instead".to_string(),
-                assertion.span,
-            );
+    if is_const_assertion {
+        return;
+    }
 
-            walk::walk_ts_type_assertion(self, assertion);
-        }
+    // Casting to any, unknown, object are always upcasts
+    let is_upcast = matches!(
+        &expr.type_annotation,
+        TSType::TSAnyKeyword(_) | TSType::TSUnknownKeyword(_) | TSType::TSObjectKeyword(_)
+    );
+
+    if is_upcast {
+        linter.add_error(
+            "no-as-upcast".to_string(),
+            "Upcast with 'as' is not allowed. Use 'satisfies' operator instead for type validation"
+                .to_string(),
+            expr.span,
+        );
+    } else {
+        // General warning for any other 'as' usage
+        linter.add_error(
+            "no-as-cast".to_string(),
+            "Type assertion with 'as' is discouraged. Consider using 'satisfies' for type checking or narrowing the type properly".to_string(),
+            expr.span,
+        );
     }
+}
 
-    let mut checker = AsUpcastChecker { linter };
-    checker.visit_program(program);
+pub fn check_no_as_upcast(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoAsCast);
 }
 
 #[cfg(test)]
@@ -1,52 +1,60 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_classes(linter: &mut Linter, program: &Program) {
-    struct ClassChecker<'a> {
-        linter: &'a mut Linter,
+/// Prohibits classes, except classes extending Error
+#[derive(Default)]
+pub struct NoClasses;
+
+impl<'a> Rule<'a> for NoClasses {
+    fn id(&self) -> &str {
+        "no-classes"
     }
 
-    impl<'a> Visit<'a> for ClassChecker<'a> {
-        fn visit_class(&mut self, class: &Class<'a>) {
-            // Check if class extends Error
-            let extends_error = if let Some(super_class) = &class.super_class {
-                // Debug: print the super class type
-                if self.linter.verbose {
-                    eprintln!(
-                        "DEBUG: super_class type: {:?}",
-                        std::mem::discriminant(super_class)
-                    );
-                }
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::Class(class) = kind else {
+            return;
+        };
+
+        // Check if class extends Error
+        let extends_error = if let Some(super_class) = &class.super_class {
+            // Debug: print the super class type
+            if linter.verbose {
+                eprintln!(
+                    "DEBUG: super_class type: {:?}",
+                    std::mem::discriminant(super_class)
+                );
+            }
 
-                // Check if the super class is Error or ends with Error
-                match super_class {
-                    Expression::Identifier(ident) => {
-                        let name = ident.name.as_str();
-                        if self.linter.verbose {
-                            eprintln!("DEBUG: super class name: {}", name);
-                        }
-                        name == "Error"
+            // Check if the super class is Error or ends with Error
+            match super_class {
+                Expression::Identifier(ident) => {
+                    let name = ident.name.as_str();
+                    if linter.verbose {
+                        eprintln!("DEBUG: super class name: {}", name);
                     }
-                    _ => false,
+                    name == "Error"
                 }
-            } else {
-                false
-            };
-
-            if !extends_error {
-                self.linter.add_error(
-                    "no-classes".to_string(),
-                    "Classes are not allowed except when extending Error".to_string(),
-                    class.span,
-                );
+                _ => false,
             }
+        } else {
+            false
+        };
+
+        if !extends_error {
+            linter.add_error(
+                "no-classes".to_string(),
+                "Classes are not allowed except when extending Error".to_string(),
+                class.span,
+            );
         }
     }
+}
 
-    let mut checker = ClassChecker { linter };
-    checker.visit_program(program);
+pub fn check_no_classes(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoClasses);
 }
 
 #[cfg(test)]
@@ -1,33 +1,51 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_constant_condition(linter: &mut Linter, program: &Program) {
-    struct ConstantConditionChecker<'a> {
-        linter: &'a mut Linter,
+/// Prohibits literal `true`/`false` conditions in if statements and `while (true)`
+#[derive(Default)]
+pub struct NoConstantCondition;
+
+impl<'a> Rule<'a> for NoConstantCondition {
+    fn id(&self) -> &str {
+        "no-constant-condition"
     }
 
-    impl<'a> Visit<'a> for ConstantConditionChecker<'a> {
-        fn visit_if_statement(&mut self, stmt: &IfStatement<'a>) {
-            if let Expression::BooleanLiteral(bool_lit) = &stmt.test {
-                self.linter.add_error(
-                    "no-constant-condition".to_string(),
-                    format!(
-                        "if ({}) is not allowed. Constant conditions are banned",
-                        bool_lit.value
-                    ),
-                    stmt.span,
-                );
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::IfStatement(stmt) => {
+                if let Expression::BooleanLiteral(bool_lit) = &stmt.test {
+                    linter.add_error(
+                        "no-constant-condition".to_string(),
+                        format!(
+                            "if ({}) is not allowed. Constant conditions are banned",
+                            bool_lit.value
+                        ),
+                        stmt.span,
+                    );
+                }
             }
-
-            walk::walk_if_statement(self, stmt);
+            AstKind::WhileStatement(stmt) => {
+                if let Expression::BooleanLiteral(bool_lit) = &stmt.test {
+                    if bool_lit.value {
+                        linter.add_error(
+                            "no-constant-condition".to_string(),
+                            "while (true) is not allowed. Constant conditions are banned"
+                                .to_string(),
+                            stmt.span,
+                        );
+                    }
+                }
+            }
+            _ => {}
         }
     }
+}
 
-    let mut checker = ConstantConditionChecker { linter };
-    checker.visit_program(program);
+pub fn check_no_constant_condition(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoConstantCondition);
 }
 
 #[cfg(test)]
@@ -1,50 +1,43 @@
 use oxc::ast::ast::*;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_define_property(linter: &mut Linter, program: &Program) {
-    use oxc::ast_visit::Visit;
+/// Prohibits Object.defineProperty and Object.defineProperties
+#[derive(Default)]
+pub struct NoDefineProperty;
 
-    struct DefinePropertyVisitor<'a, 'b> {
-        linter: &'a mut Linter,
-        _phantom: std::marker::PhantomData<&'b ()>,
+impl<'a> Rule<'a> for NoDefineProperty {
+    fn id(&self) -> &str {
+        "no-define-property"
     }
 
-    impl<'a, 'b> Visit<'b> for DefinePropertyVisitor<'a, 'b> {
-        fn visit_call_expression(&mut self, call: &CallExpression<'b>) {
-            // Check for Object.defineProperty calls
-            if let Some(MemberExpression::StaticMemberExpression(static_member)) =
-                call.callee.as_member_expression()
-            {
-                if let Expression::Identifier(obj) = &static_member.object {
-                    if obj.name == "Object" && static_member.property.name == "defineProperty" {
-                        self.linter.add_error(
-                                "no-define-property".to_string(),
-                                "Object.defineProperty is not allowed. Use direct property assignment or object literals instead".to_string(),
-                                call.span,
-                            );
-                    }
-
-                    // Also check Object.defineProperties
-                    if obj.name == "Object" && static_member.property.name == "defineProperties" {
-                        self.linter.add_error(
-                                "no-define-property".to_string(),
-                                "Object.defineProperties is not allowed. Use direct property assignment or object literals instead".to_string(),
-                                call.span,
-                            );
-                    }
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::CallExpression(call) = kind else {
+            return;
+        };
+        if let Some(MemberExpression::StaticMemberExpression(static_member)) =
+            call.callee.as_member_expression()
+        {
+            if let Expression::Identifier(obj) = &static_member.object {
+                let method = static_member.property.name.as_str();
+                if obj.name == "Object"
+                    && (method == "defineProperty" || method == "defineProperties")
+                {
+                    linter.add_error(
+                        "no-define-property".to_string(),
+                        format!("Object.{} is not allowed. Use direct property assignment or object literals instead", method),
+                        call.span,
+                    );
                 }
             }
-
-            oxc::ast_visit::walk::walk_call_expression(self, call);
         }
     }
+}
 
-    let mut visitor = DefinePropertyVisitor {
-        linter,
-        _phantom: std::marker::PhantomData,
-    };
-    visitor.visit_program(program);
+pub fn check_no_define_property(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoDefineProperty);
 }
 
 #[cfg(test)]
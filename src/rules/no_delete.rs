@@ -1,29 +1,33 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_delete(linter: &mut Linter, program: &Program) {
-    struct DeleteChecker<'a> {
-        linter: &'a mut Linter,
+/// Prohibits the delete operator
+#[derive(Default)]
+pub struct NoDelete;
+
+impl<'a> Rule<'a> for NoDelete {
+    fn id(&self) -> &str {
+        "no-delete"
     }
 
-    impl<'a> Visit<'a> for DeleteChecker<'a> {
-        fn visit_unary_expression(&mut self, expr: &UnaryExpression<'a>) {
-            if let UnaryOperator::Delete = expr.operator {
-                self.linter.add_error(
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        if let AstKind::UnaryExpression(expr) = kind {
+            if expr.operator == UnaryOperator::Delete {
+                linter.add_error(
                     "no-delete".to_string(),
                     "Delete operator is not allowed in pure TypeScript subset".to_string(),
                     expr.span,
                 );
             }
-            walk::walk_unary_expression(self, expr);
         }
     }
+}
 
-    let mut checker = DeleteChecker { linter };
-    checker.visit_program(program);
+pub fn check_no_delete(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoDelete);
 }
 
 #[cfg(test)]
@@ -1,24 +1,31 @@
-use crate::Linter;
 use oxc::ast::ast::*;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
-pub fn check_no_do_while(linter: &mut Linter, program: &Program) {
-    struct DoWhileChecker<'a> {
-        linter: &'a mut Linter,
+use crate::rule::{run_rule, Rule};
+use crate::Linter;
+
+/// Prohibits do-while statements
+#[derive(Default)]
+pub struct NoDoWhile;
+
+impl<'a> Rule<'a> for NoDoWhile {
+    fn id(&self) -> &str {
+        "no-do-while"
     }
 
-    impl<'a> Visit<'a> for DoWhileChecker<'a> {
-        fn visit_do_while_statement(&mut self, stmt: &DoWhileStatement<'a>) {
-            self.linter.add_error(
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        if let AstKind::DoWhileStatement(stmt) = kind {
+            linter.add_error(
                 "no-do-while".to_string(),
                 "do-while statements are not allowed. Use while instead".to_string(),
                 stmt.span,
             );
         }
     }
+}
 
-    let mut checker = DoWhileChecker { linter };
-    checker.visit_program(program);
+pub fn check_no_do_while(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoDoWhile);
 }
 
 #[cfg(test)]
@@ -1,67 +1,61 @@
+use std::collections::HashSet;
+
 use oxc::ast::ast::*;
+use oxc::ast::AstKind;
+use oxc::span::Span;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_dynamic_access(linter: &mut Linter, program: &Program) {
-    use oxc::ast_visit::Visit;
+/// Prohibits bracket access with non-numeric keys
+#[derive(Default)]
+pub struct NoDynamicAccess {
+    // Computed members that are assignment targets, reported as assignments
+    assignment_targets: HashSet<Span>,
+}
 
-    struct DynamicAccessVisitor<'a, 'b> {
-        linter: &'a mut Linter,
-        _phantom: std::marker::PhantomData<&'b ()>,
+fn is_numeric_key(expr: &Expression) -> bool {
+    match expr {
+        Expression::NumericLiteral(_) => true,
+        Expression::StringLiteral(lit) => lit.value.parse::<i32>().is_ok(),
+        _ => false,
     }
+}
 
-    impl<'a, 'b> Visit<'b> for DynamicAccessVisitor<'a, 'b> {
-        fn visit_member_expression(&mut self, expr: &MemberExpression<'b>) {
-            // Check for computed member expressions (bracket notation)
-            if let MemberExpression::ComputedMemberExpression(computed) = expr {
-                // Allow numeric indices for arrays
-                let is_numeric = match &computed.expression {
-                    Expression::NumericLiteral(_) => true,
-                    Expression::StringLiteral(lit) => lit.value.parse::<i32>().is_ok(),
-                    _ => false,
-                };
+impl<'a> Rule<'a> for NoDynamicAccess {
+    fn id(&self) -> &str {
+        "no-dynamic-access"
+    }
 
-                if !is_numeric {
-                    self.linter.add_error(
-                        "no-dynamic-access".to_string(),
-                        "Dynamic property access is not allowed. Use dot notation or destructuring instead".to_string(),
-                        computed.span,
-                    );
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::AssignmentExpression(expr) => {
+                if let AssignmentTarget::ComputedMemberExpression(computed) = &expr.left {
+                    self.assignment_targets.insert(computed.span);
                 }
             }
-
-            oxc::ast_visit::walk::walk_member_expression(self, expr);
-        }
-
-        fn visit_assignment_target(&mut self, target: &AssignmentTarget<'b>) {
-            // Check for computed assignment targets like obj[key] = value
-            if let AssignmentTarget::ComputedMemberExpression(computed) = target {
-                // Allow numeric indices for arrays
-                let is_numeric = match &computed.expression {
-                    Expression::NumericLiteral(_) => true,
-                    Expression::StringLiteral(lit) => lit.value.parse::<i32>().is_ok(),
-                    _ => false,
-                };
-
-                if !is_numeric {
-                    self.linter.add_error(
-                        "no-dynamic-access".to_string(),
-                        "Dynamic property assignment is not allowed. Use dot notation instead"
-                            .to_string(),
-                        computed.span,
-                    );
+            AstKind::ComputedMemberExpression(computed) => {
+                if is_numeric_key(&computed.expression) {
+                    return;
                 }
+                let message = if self.assignment_targets.contains(&computed.span) {
+                    "Dynamic property assignment is not allowed. Use dot notation instead"
+                } else {
+                    "Dynamic property access is not allowed. Use dot notation or destructuring instead"
+                };
+                linter.add_error(
+                    "no-dynamic-access".to_string(),
+                    message.to_string(),
+                    computed.span,
+                );
             }
-
-            oxc::ast_visit::walk::walk_assignment_target(self, target);
+            _ => {}
         }
     }
+}
 
-    let mut visitor = DynamicAccessVisitor {
-        linter,
-        _phantom: std::marker::PhantomData,
-    };
-    visitor.visit_program(program);
+pub fn check_no_dynamic_access(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoDynamicAccess::default());
 }
 
 #[cfg(test)]
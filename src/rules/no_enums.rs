@@ -1,25 +1,31 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_enums(linter: &mut Linter, program: &Program) {
-    struct EnumChecker<'a> {
-        linter: &'a mut Linter,
+/// Prohibits enums
+#[derive(Default)]
+pub struct NoEnums;
+
+impl<'a> Rule<'a> for NoEnums {
+    fn id(&self) -> &str {
+        "no-enums"
     }
 
-    impl<'a> Visit<'a> for EnumChecker<'a> {
-        fn visit_ts_enum_declaration(&mut self, decl: &TSEnumDeclaration<'a>) {
-            self.linter.add_error(
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        if let AstKind::TSEnumDeclaration(decl) = kind {
+            linter.add_error(
                 "no-enums".to_string(),
                 "Enums are not allowed in pure TypeScript subset".to_string(),
                 decl.span,
             );
         }
     }
+}
 
-    let mut checker = EnumChecker { linter };
-    checker.visit_program(program);
+pub fn check_no_enums(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoEnums);
 }
 
 #[cfg(test)]
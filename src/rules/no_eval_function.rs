@@ -1,73 +1,58 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_eval_function(linter: &mut Linter, program: &Program) {
-    struct EvalFunctionChecker<'a> {
-        linter: &'a mut Linter,
+/// Prohibits eval and the Function constructor
+#[derive(Default)]
+pub struct NoEvalFunction;
+
+impl<'a> Rule<'a> for NoEvalFunction {
+    fn id(&self) -> &str {
+        "no-eval-function"
     }
 
-    impl<'a> Visit<'a> for EvalFunctionChecker<'a> {
-        fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
-            // Check for eval()
-            if let Expression::Identifier(id) = &call.callee {
-                if id.name.as_str() == "eval" {
-                    self.linter.add_error(
-                        "no-eval".to_string(),
-                        "eval() is not allowed in pure TypeScript subset due to security risks"
-                            .to_string(),
-                        call.span,
-                    );
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::CallExpression(call) => {
+                if let Expression::Identifier(id) = &call.callee {
+                    if id.name.as_str() == "eval" {
+                        linter.add_error(
+                            "no-eval".to_string(),
+                            "eval() is not allowed in pure TypeScript subset due to security risks"
+                                .to_string(),
+                            call.span,
+                        );
+                    }
                 }
             }
-
-            // Check for new Function()
-            if let Expression::NewExpression(new_expr) = &call.callee {
+            AstKind::NewExpression(new_expr) => {
                 if let Expression::Identifier(id) = &new_expr.callee {
                     if id.name.as_str() == "Function" {
-                        self.linter.add_error(
+                        linter.add_error(
                             "no-new-function".to_string(),
                             "new Function() is not allowed in pure TypeScript subset due to security risks".to_string(),
-                            call.span,
+                            new_expr.span,
                         );
                     }
                 }
             }
-
-            walk::walk_call_expression(self, call);
-        }
-
-        fn visit_new_expression(&mut self, new_expr: &NewExpression<'a>) {
-            // Check for new Function()
-            if let Expression::Identifier(id) = &new_expr.callee {
-                if id.name.as_str() == "Function" {
-                    self.linter.add_error(
-                        "no-new-function".to_string(),
-                        "new Function() is not allowed in pure TypeScript subset due to security risks".to_string(),
-                        new_expr.span,
-                    );
-                }
-            }
-
-            walk::walk_new_expression(self, new_expr);
-        }
-
-        fn visit_identifier_reference(&mut self, id: &IdentifierReference) {
-            // Check if eval is being used as a reference (e.g., const myEval = eval)
-            if id.name.as_str() == "eval" {
-                self.linter.add_error(
+            // Also catches aliasing, e.g. const myEval = eval
+            AstKind::IdentifierReference(id) if id.name.as_str() == "eval" => {
+                linter.add_error(
                     "no-eval".to_string(),
                     "Reference to eval is not allowed in pure TypeScript subset".to_string(),
                     id.span,
                 );
             }
+            _ => {}
         }
     }
+}
 
-    let mut checker = EvalFunctionChecker { linter };
-    checker.visit_program(program);
+pub fn check_no_eval_function(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoEvalFunction);
 }
 
 #[cfg(test)]
@@ -1,34 +1,38 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_filename_dirname(linter: &mut Linter, program: &Program) {
-    struct FilenameDirnameChecker<'a> {
-        linter: &'a mut Linter,
+/// Prohibits the CommonJS `__filename` and `__dirname` globals
+#[derive(Default)]
+pub struct NoFilenameDirname;
+
+impl<'a> Rule<'a> for NoFilenameDirname {
+    fn id(&self) -> &str {
+        "no-filename-dirname"
     }
 
-    impl<'a> Visit<'a> for FilenameDirnameChecker<'a> {
-        fn visit_identifier_reference(&mut self, id: &IdentifierReference) {
-            let name = id.name.as_str();
-            if name == "__filename" {
-                self.linter.add_error(
-                    "no-filename-dirname".to_string(),
-                    "__filename is not allowed in pure TypeScript subset. Use import.meta.url instead".to_string(),
-                    id.span,
-                );
-            } else if name == "__dirname" {
-                self.linter.add_error(
-                    "no-filename-dirname".to_string(),
-                    "__dirname is not allowed in pure TypeScript subset. Use import.meta.url instead".to_string(),
-                    id.span,
-                );
-            }
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::IdentifierReference(id) = kind else {
+            return;
+        };
+        let name = id.name.as_str();
+        if name == "__filename" || name == "__dirname" {
+            linter.add_error(
+                "no-filename-dirname".to_string(),
+                format!(
+                    "{} is not allowed in pure TypeScript subset. Use import.meta.url instead",
+                    name
+                ),
+                id.span,
+            );
         }
     }
+}
 
-    let mut checker = FilenameDirnameChecker { linter };
-    checker.visit_program(program);
+pub fn check_no_filename_dirname(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoFilenameDirname);
 }
 
 #[cfg(test)]
@@ -159,4 +163,27 @@ console.log(__filename, __dirname);
                 >= 2
         );
     }
+
+    #[test]
+    fn test_references_reported_by_check_program() {
+        let allocator = Allocator::default();
+        let source_text = r#"
+export const dir: string = __dirname;
+export const file: string = import.meta.filename;
+"#;
+        let source_type = SourceType::from_path("src/io/paths.ts").unwrap();
+        let ParserReturn { program, .. } =
+            Parser::new(&allocator, source_text, source_type).parse();
+        let mut linter = Linter::new(Path::new("src/io/paths.ts"), source_text, false);
+
+        linter.check_program(&program);
+
+        let errors: Vec<_> = linter
+            .errors
+            .iter()
+            .filter(|e| e.rule == "no-filename-dirname")
+            .collect();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("__dirname is not allowed"));
+    }
 }
@@ -1,34 +1,39 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_foreach(linter: &mut Linter, program: &Program) {
-    struct ForEachChecker<'a> {
-        linter: &'a mut Linter,
+/// Prohibits `.forEach()` calls
+#[derive(Default)]
+pub struct NoForeach;
+
+impl<'a> Rule<'a> for NoForeach {
+    fn id(&self) -> &str {
+        "no-foreach"
     }
 
-    impl<'a> Visit<'a> for ForEachChecker<'a> {
-        fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
-            // Check if this is a .forEach() call
-            if let Expression::StaticMemberExpression(member) = &call.callee {
-                if member.property.name == "forEach" {
-                    self.linter.add_error(
-                        "no-foreach".to_string(),
-                        "forEach is not allowed in pure TypeScript subset. Use for-of loop instead"
-                            .to_string(),
-                        call.span,
-                    );
-                }
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::CallExpression(call) = kind else {
+            return;
+        };
+
+        // Check if this is a .forEach() call
+        if let Expression::StaticMemberExpression(member) = &call.callee {
+            if member.property.name == "forEach" {
+                linter.add_error(
+                    "no-foreach".to_string(),
+                    "forEach is not allowed in pure TypeScript subset. Use for-of loop instead"
+                        .to_string(),
+                    call.span,
+                );
             }
-
-            walk::walk_call_expression(self, call);
         }
     }
+}
 
-    let mut checker = ForEachChecker { linter };
-    checker.visit_program(program);
+pub fn check_no_foreach(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoForeach);
 }
 
 #[cfg(test)]
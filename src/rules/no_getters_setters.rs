@@ -1,85 +1,60 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_getters_setters(linter: &mut Linter, program: &Program) {
-    struct GetterSetterChecker<'a> {
-        linter: &'a mut Linter,
-    }
-
-    impl<'a> Visit<'a> for GetterSetterChecker<'a> {
-        fn visit_method_definition(&mut self, method: &MethodDefinition<'a>) {
-            match method.kind {
-                MethodDefinitionKind::Get => {
-                    self.linter.add_error(
-                        "no-getters".to_string(),
-                        "Getters are not allowed in pure TypeScript subset".to_string(),
-                        method.span,
-                    );
-                }
-                MethodDefinitionKind::Set => {
-                    self.linter.add_error(
-                        "no-setters".to_string(),
-                        "Setters are not allowed in pure TypeScript subset".to_string(),
-                        method.span,
-                    );
-                }
-                _ => {}
-            }
-
-            walk::walk_method_definition(self, method);
-        }
-
-        fn visit_object_expression(&mut self, obj: &ObjectExpression<'a>) {
-            // Check for getters/setters in object literals
-            for prop in &obj.properties {
-                if let ObjectPropertyKind::ObjectProperty(prop) = prop {
-                    if prop.kind == PropertyKind::Get {
-                        self.linter.add_error(
-                            "no-getters".to_string(),
-                            "Getters are not allowed in pure TypeScript subset".to_string(),
-                            prop.span,
-                        );
-                    } else if prop.kind == PropertyKind::Set {
-                        self.linter.add_error(
-                            "no-setters".to_string(),
-                            "Setters are not allowed in pure TypeScript subset".to_string(),
-                            prop.span,
-                        );
-                    }
-                }
-            }
+/// Prohibits getters, setters and accessor properties in classes and object literals
+#[derive(Default)]
+pub struct NoGettersSetters;
 
-            walk::walk_object_expression(self, obj);
-        }
-
-        fn visit_property_definition(&mut self, prop: &PropertyDefinition<'a>) {
-            // Check for accessor properties in object literals
-            walk::walk_property_definition(self, prop);
-        }
-
-        fn visit_object_property(&mut self, prop: &ObjectProperty<'a>) {
-            // Object property getters/setters are handled by visit_method_definition
-            // This is for regular properties
-            walk::walk_object_property(self, prop);
-        }
+impl<'a> Rule<'a> for NoGettersSetters {
+    fn id(&self) -> &str {
+        "no-getters-setters"
+    }
 
-        fn visit_accessor_property(&mut self, prop: &AccessorProperty<'a>) {
-            self.linter.add_error(
-                "no-getters-setters".to_string(),
-                "Accessor properties (get/set) are not allowed in pure TypeScript subset"
-                    .to_string(),
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let (is_getter, is_setter, span) = match kind {
+            AstKind::MethodDefinition(method) => (
+                method.kind == MethodDefinitionKind::Get,
+                method.kind == MethodDefinitionKind::Set,
+                method.span,
+            ),
+            AstKind::ObjectProperty(prop) => (
+                prop.kind == PropertyKind::Get,
+                prop.kind == PropertyKind::Set,
                 prop.span,
+            ),
+            AstKind::AccessorProperty(prop) => {
+                linter.add_error(
+                    "no-getters-setters".to_string(),
+                    "Accessor properties (get/set) are not allowed in pure TypeScript subset"
+                        .to_string(),
+                    prop.span,
+                );
+                return;
+            }
+            _ => return,
+        };
+
+        if is_getter {
+            linter.add_error(
+                "no-getters".to_string(),
+                "Getters are not allowed in pure TypeScript subset".to_string(),
+                span,
+            );
+        } else if is_setter {
+            linter.add_error(
+                "no-setters".to_string(),
+                "Setters are not allowed in pure TypeScript subset".to_string(),
+                span,
             );
-
-            walk::walk_accessor_property(self, prop);
         }
     }
+}
 
-    let mut checker = GetterSetterChecker { linter };
-    checker.visit_program(program);
+pub fn check_no_getters_setters(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoGettersSetters);
 }
 
 #[cfg(test)]
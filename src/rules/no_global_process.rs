@@ -1,50 +1,36 @@
+use std::collections::HashSet;
+
 use oxc::ast::ast::*;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_global_process(linter: &mut Linter, program: &Program) {
-    use oxc::ast_visit::Visit;
-    use std::collections::HashSet;
-
-    struct NoGlobalProcessVisitor<'a, 'b> {
-        linter: &'a mut Linter,
-        // Track if process is imported from 'node:process'
-        process_imported: bool,
-        // Track imported names
-        imported_names: HashSet<String>,
-        _phantom: std::marker::PhantomData<&'b ()>,
-    }
+/// Prohibits the global `process` object unless it is imported from node:process
+#[derive(Default)]
+pub struct NoGlobalProcess {
+    // Local names imported from 'node:process'
+    imported_names: HashSet<String>,
+}
 
-    impl<'a, 'b> NoGlobalProcessVisitor<'a, 'b> {
-        fn new(linter: &'a mut Linter) -> Self {
-            Self {
-                linter,
-                process_imported: false,
-                imported_names: HashSet::new(),
-                _phantom: std::marker::PhantomData,
-            }
-        }
+impl<'a> Rule<'a> for NoGlobalProcess {
+    fn id(&self) -> &str {
+        "no-global-process"
+    }
 
-        fn check_imports(&mut self, program: &Program<'b>) {
-            for item in &program.body {
-                if let Statement::ImportDeclaration(import) = item {
-                    // Check if importing from 'node:process'
-                    if import.source.value == "node:process" || import.source.value == "process" {
-                        self.process_imported = true;
-
-                        // Track what's imported
-                        if let Some(specifiers) = &import.specifiers {
-                            for spec in specifiers {
-                                match spec {
-                                    ImportDeclarationSpecifier::ImportDefaultSpecifier(default) => {
-                                        self.imported_names.insert(default.local.name.to_string());
-                                    }
-                                    ImportDeclarationSpecifier::ImportSpecifier(named) => {
-                                        self.imported_names.insert(named.local.name.to_string());
-                                    }
-                                    _ => {}
-                                }
+    fn on_program_start(&mut self, _linter: &mut Linter, program: &'a Program<'a>) {
+        for item in &program.body {
+            if let Statement::ImportDeclaration(import) = item {
+                if import.source.value == "node:process" || import.source.value == "process" {
+                    for spec in import.specifiers.iter().flatten() {
+                        match spec {
+                            ImportDeclarationSpecifier::ImportDefaultSpecifier(default) => {
+                                self.imported_names.insert(default.local.name.to_string());
+                            }
+                            ImportDeclarationSpecifier::ImportSpecifier(named) => {
+                                self.imported_names.insert(named.local.name.to_string());
                             }
+                            _ => {}
                         }
                     }
                 }
@@ -52,25 +38,22 @@ pub fn check_no_global_process(linter: &mut Linter, program: &Program) {
         }
     }
 
-    impl<'a, 'b> Visit<'b> for NoGlobalProcessVisitor<'a, 'b> {
-        fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'b>) {
-            // Check for global process usage
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        if let AstKind::IdentifierReference(ident) = kind {
             if ident.name == "process" && !self.imported_names.contains("process") {
-                self.linter.add_error(
+                linter.add_error(
                     "no-global-process".to_string(),
                     "Global 'process' is not allowed. Import it from 'node:process' instead"
                         .to_string(),
                     ident.span,
                 );
             }
-
-            oxc::ast_visit::walk::walk_identifier_reference(self, ident);
         }
     }
+}
 
-    let mut visitor = NoGlobalProcessVisitor::new(linter);
-    visitor.check_imports(program);
-    visitor.visit_program(program);
+pub fn check_no_global_process(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoGlobalProcess::default());
 }
 
 #[cfg(test)]
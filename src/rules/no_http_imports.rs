@@ -1,25 +1,48 @@
 use oxc::ast::ast::*;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_http_imports(linter: &mut Linter, program: &Program) {
-    for item in &program.body {
-        if let Statement::ImportDeclaration(import) = item {
-            let source = &import.source.value;
-            if source.starts_with("http://") || source.starts_with("https://") {
-                linter.add_error(
-                    "no-http-imports".to_string(),
-                    format!(
-                        "HTTP(S) imports are not allowed. Import from '{}' is forbidden",
-                        source
-                    ),
-                    import.span,
-                );
-            }
+/// Prohibits imports from `http://` and `https://` URLs
+#[derive(Default)]
+pub struct NoHttpImports;
+
+impl<'a> Rule<'a> for NoHttpImports {
+    fn id(&self) -> &str {
+        "no-http-imports"
+    }
+
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::ImportDeclaration(import) = kind else {
+            return;
+        };
+
+        let source = import.source.value.as_str();
+        let resolved = linter.resolve_specifier(source);
+        if resolved.starts_with("http://") || resolved.starts_with("https://") {
+            // Name the URL when the import map hides it behind a bare specifier
+            let mapped = if resolved != source {
+                format!(" (mapped to '{}')", resolved)
+            } else {
+                String::new()
+            };
+            linter.add_error(
+                "no-http-imports".to_string(),
+                format!(
+                    "HTTP(S) imports are not allowed. Import from '{}'{} is forbidden",
+                    source, mapped
+                ),
+                import.span,
+            );
         }
     }
 }
 
+pub fn check_no_http_imports(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoHttpImports);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,4 +118,35 @@ mod tests {
         let errors = parse_and_check(source);
         assert_eq!(errors.len(), 3); // 3 HTTP(S) imports
     }
+
+    #[test]
+    fn test_import_map_to_url() {
+        use crate::deno_config::ImportMap;
+        use std::collections::BTreeMap;
+        use std::sync::Arc;
+
+        let source = r#"
+            import { join } from "std/path/mod.ts";
+            import { assert } from "@std/assert";
+        "#;
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::default()).parse();
+
+        let import_map = ImportMap::new(BTreeMap::from([
+            ("std/".to_string(), "https://deno.land/std/".to_string()),
+            ("@std/assert".to_string(), "jsr:@std/assert@1".to_string()),
+        ]));
+        let mut linter = Linter::new(Path::new("main.ts"), source, false)
+            .with_import_map(Some(Arc::new(import_map)));
+        check_no_http_imports(&mut linter, &ret.program);
+
+        let errors: Vec<String> = linter.errors.into_iter().map(|e| e.message).collect();
+        assert_eq!(
+            errors,
+            vec![
+                "HTTP(S) imports are not allowed. Import from 'std/path/mod.ts' (mapped to \
+                  'https://deno.land/std/path/mod.ts') is forbidden"
+            ]
+        );
+    }
 }
@@ -0,0 +1,275 @@
+use oxc::ast::ast::*;
+use oxc::ast::AstKind;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::directories::in_directory;
+use crate::rule::{run_rule, Rule};
+use crate::Linter;
+
+/// Options for the no-imperative-loops rule, from `imperativeLoops` in
+/// `purets.json`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct ImperativeLoopsOptions {
+    /// Directory names where loops are restricted (matched as `/<dir>/`)
+    pub directories: Vec<String>,
+    /// Whether `for...of` over a readonly input is allowed
+    pub allow_readonly_for_of: bool,
+}
+
+impl Default for ImperativeLoopsOptions {
+    fn default() -> Self {
+        Self {
+            directories: vec!["pure".to_string()],
+            allow_readonly_for_of: true,
+        }
+    }
+}
+
+impl ImperativeLoopsOptions {
+    /// Check if loops are restricted for the given file path
+    pub fn applies_to(&self, file_path: &Path) -> bool {
+        self.directories
+            .iter()
+            .any(|dir| in_directory(file_path, dir))
+    }
+}
+
+/// Whether a type annotation is a readonly collection type, e.g.
+/// `readonly T[]`, `ReadonlyArray<T>`, `ReadonlyMap<K, V>` or `Readonly<T>`
+fn is_readonly_type(ts_type: &TSType) -> bool {
+    match ts_type {
+        TSType::TSTypeOperatorType(op) => op.operator == TSTypeOperatorOperator::Readonly,
+        TSType::TSTypeReference(type_ref) => match &type_ref.type_name {
+            TSTypeName::IdentifierReference(id) => matches!(
+                id.name.as_str(),
+                "ReadonlyArray" | "ReadonlyMap" | "ReadonlySet" | "Readonly"
+            ),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Name of an identifier binding and whether it is declared with a readonly
+/// type annotation
+fn binding<'a>(pattern: &BindingPattern<'a>) -> Option<(&'a str, bool)> {
+    let BindingPatternKind::BindingIdentifier(id) = &pattern.kind else {
+        return None;
+    };
+    let readonly = pattern
+        .type_annotation
+        .as_ref()
+        .is_some_and(|type_ann| is_readonly_type(&type_ann.type_annotation));
+    Some((id.name.as_str(), readonly))
+}
+
+fn loop_message(kind: &str) -> String {
+    format!(
+        "'{}' loops are not allowed in pure code. Use map/filter/reduce/flatMap instead",
+        kind
+    )
+}
+
+fn for_of_message() -> String {
+    "'for...of' in pure code is only allowed over a readonly input (readonly T[] or ReadonlyArray<T>). Otherwise use map/filter/reduce/flatMap".to_string()
+}
+
+/// Whether `for...of` iterates directly over a binding known to be readonly,
+/// looking the name up from the innermost function outwards
+fn is_readonly_iteration(right: &Expression, scopes: &[HashMap<&str, bool>]) -> bool {
+    match right.without_parentheses() {
+        Expression::Identifier(id) => scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(id.name.as_str()))
+            .is_some_and(|readonly| *readonly),
+        _ => false,
+    }
+}
+
+/// Check for imperative loops in pure code
+///
+/// - `for`, `for...in` and `while` are not allowed
+/// - `for...of` is allowed only over a readonly input
+#[derive(Default)]
+pub struct NoImperativeLoops<'a> {
+    options: ImperativeLoopsOptions,
+    restricted: bool,
+    /// Bindings declared in each enclosing function (the program first),
+    /// with whether they are readonly
+    scopes: Vec<HashMap<&'a str, bool>>,
+}
+
+impl<'a> NoImperativeLoops<'a> {
+    pub fn new(options: ImperativeLoopsOptions) -> Self {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+
+    fn declare(&mut self, pattern: &BindingPattern<'a>) {
+        if let (Some((name, readonly)), Some(scope)) = (binding(pattern), self.scopes.last_mut()) {
+            scope.insert(name, readonly);
+        }
+    }
+}
+
+impl<'a> Rule<'a> for NoImperativeLoops<'a> {
+    fn id(&self) -> &str {
+        "no-imperative-loops"
+    }
+
+    fn on_program_start(&mut self, linter: &mut Linter, _program: &'a Program<'a>) {
+        self.restricted = self.options.applies_to(&linter.path);
+        self.scopes = vec![HashMap::new()];
+    }
+
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        if !self.restricted {
+            return;
+        }
+        let (message, span) = match kind {
+            AstKind::Function(_) | AstKind::ArrowFunctionExpression(_) => {
+                self.scopes.push(HashMap::new());
+                return;
+            }
+            AstKind::FormalParameter(param) => {
+                self.declare(&param.pattern);
+                return;
+            }
+            AstKind::VariableDeclarator(decl) => {
+                self.declare(&decl.id);
+                return;
+            }
+            AstKind::ForStatement(stmt) => (loop_message("for"), stmt.span),
+            AstKind::ForInStatement(stmt) => (loop_message("for...in"), stmt.span),
+            AstKind::WhileStatement(stmt) => (loop_message("while"), stmt.span),
+            AstKind::ForOfStatement(stmt) => {
+                if self.options.allow_readonly_for_of
+                    && is_readonly_iteration(&stmt.right, &self.scopes)
+                {
+                    return;
+                }
+                (for_of_message(), stmt.span)
+            }
+            _ => return,
+        };
+        linter.add_error("no-imperative-loops".to_string(), message, span);
+    }
+
+    fn leave_node(&mut self, _linter: &mut Linter, kind: AstKind<'a>) {
+        if self.restricted
+            && matches!(
+                kind,
+                AstKind::Function(_) | AstKind::ArrowFunctionExpression(_)
+            )
+        {
+            self.scopes.pop();
+        }
+    }
+}
+
+pub fn check_no_imperative_loops(
+    linter: &mut Linter,
+    program: &Program,
+    options: &ImperativeLoopsOptions,
+) {
+    run_rule(linter, program, NoImperativeLoops::new(options.clone()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Linter;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+    use std::path::Path;
+
+    fn parse_and_check(
+        source: &str,
+        file_path: &str,
+        options: &ImperativeLoopsOptions,
+    ) -> Vec<String> {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path(file_path).unwrap();
+        let ret = Parser::new(&allocator, source, source_type).parse();
+
+        let mut linter = Linter::new(Path::new(file_path), source, false);
+        check_no_imperative_loops(&mut linter, &ret.program, options);
+
+        linter.errors.into_iter().map(|e| e.message).collect()
+    }
+
+    const LOOPS: &str = r#"
+export function sum(values: readonly number[], extra: number[]): number {
+  const total: number = values.reduce((a, b) => a + b, 0);
+  for (let i = 0; i < 1; i++) {}
+  for (const key in extra) {}
+  while (total < 0) {}
+  for (const v of values) {}
+  for (const v of extra) {}
+  return total;
+}
+"#;
+
+    #[test]
+    fn test_loops_in_pure() {
+        let errors = parse_and_check(LOOPS, "src/pure/sum.ts", &ImperativeLoopsOptions::default());
+        assert_eq!(errors.len(), 4);
+        assert!(errors[0].contains("'for' loops are not allowed"));
+        assert!(errors[1].contains("'for...in' loops are not allowed"));
+        assert!(errors[2].contains("'while' loops are not allowed"));
+        assert!(errors[3].contains("only allowed over a readonly input"));
+    }
+
+    #[test]
+    fn test_loops_outside_pure() {
+        let errors = parse_and_check(LOOPS, "src/io/sum.ts", &ImperativeLoopsOptions::default());
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_readonly_bindings_are_scoped_per_function() {
+        let source = r#"
+export function sum(values: readonly number[]): number {
+  const inner = (values: number[]): void => {
+    for (const v of values) {}
+  };
+  const other = (): void => {
+    const items: readonly number[] = values;
+    for (const v of items) {}
+  };
+  const last = (): void => {
+    for (const v of items) {}
+  };
+  for (const v of values) {}
+  return 0;
+}
+"#;
+        let errors = parse_and_check(
+            source,
+            "src/pure/sum.ts",
+            &ImperativeLoopsOptions::default(),
+        );
+        // The shadowing mutable parameter and `items` outside its function
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+    }
+
+    #[test]
+    fn test_configured_options() {
+        let options = ImperativeLoopsOptions {
+            directories: vec!["domain".to_string()],
+            allow_readonly_for_of: false,
+        };
+        let errors = parse_and_check(LOOPS, "src/domain/sum.ts", &options);
+        assert_eq!(errors.len(), 5);
+
+        let errors = parse_and_check(LOOPS, "src/pure/sum.ts", &options);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+}
@@ -0,0 +1,122 @@
+use oxc::ast::ast::*;
+
+use crate::directories::in_directory;
+use crate::rule::{run_rule, Rule};
+use crate::scope_analysis::ScopeAnalysis;
+use crate::Linter;
+
+/// Prefer `const` over `let`
+///
+/// Reports `let` bindings that are never reassigned. In `pure/` files every
+/// `let` is reported, since reassignment is a form of local mutation.
+#[derive(Default)]
+pub struct NoLet;
+
+impl<'a> Rule<'a> for NoLet {
+    fn id(&self) -> &str {
+        "no-let"
+    }
+
+    fn on_program_end(&mut self, linter: &mut Linter, program: &'a Program<'a>) {
+        let is_pure_file = in_directory(&linter.path, "pure");
+
+        let analysis = ScopeAnalysis::analyze(program);
+        for binding in analysis.let_bindings {
+            if !binding.reassigned {
+                linter.add_error(
+                    "no-let".to_string(),
+                    format!(
+                        "'{}' is never reassigned. Use 'const' instead",
+                        binding.name
+                    ),
+                    binding.span,
+                );
+            } else if is_pure_file {
+                linter.add_error(
+                    "no-let".to_string(),
+                    format!(
+                        "'let' is not allowed in pure/ code. Compute '{}' with an expression (map/reduce/ternary) instead of reassigning it",
+                        binding.name
+                    ),
+                    binding.span,
+                );
+            }
+        }
+    }
+}
+
+pub fn check_no_let(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoLet);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Linter;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+    use std::path::Path;
+
+    fn parse_and_check(source: &str, file_path: &str) -> Vec<String> {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path(file_path).unwrap();
+        let ret = Parser::new(&allocator, source, source_type).parse();
+
+        let mut linter = Linter::new(Path::new(file_path), source, false);
+        check_no_let(&mut linter, &ret.program);
+
+        linter.errors.into_iter().map(|e| e.message).collect()
+    }
+
+    #[test]
+    fn test_let_never_reassigned() {
+        let source = r#"
+export function total(values: readonly number[]): number {
+  let sum: number = 0;
+  let label: string = "total";
+  for (const v of values) {
+    sum += v;
+  }
+  return sum + label.length;
+}
+"#;
+        let errors = parse_and_check(source, "src/io/total.ts");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'label' is never reassigned"));
+    }
+
+    #[test]
+    fn test_reassigned_let_in_pure() {
+        let source = r#"
+export function total(values: readonly number[]): number {
+  let sum: number = 0;
+  for (const v of values) {
+    sum += v;
+  }
+  return sum;
+}
+"#;
+        let errors = parse_and_check(source, "src/pure/total.ts");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'let' is not allowed in pure/ code"));
+    }
+
+    #[test]
+    fn test_shadowed_binding_is_not_a_reassignment() {
+        let source = r#"
+export function outer(): number {
+  let value: number = 1;
+  const inner = (): number => {
+    let value: number = 2;
+    value = 3;
+    return value;
+  };
+  return value + inner();
+}
+"#;
+        let errors = parse_and_check(source, "src/io/outer.ts");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'value' is never reassigned"));
+    }
+}
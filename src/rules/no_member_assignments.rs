@@ -1,34 +1,51 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_member_assignments(linter: &mut Linter, program: &Program) {
-    struct MemberAssignmentChecker<'a> {
-        linter: &'a mut Linter,
+/// Prohibits assignments to object members
+///
+/// Files under `errors/` are skipped so error classes can set `this.name`.
+#[derive(Default)]
+pub struct NoMemberAssignments {
+    is_error_file: bool,
+}
+
+impl<'a> Rule<'a> for NoMemberAssignments {
+    fn id(&self) -> &str {
+        "no-member-assignments"
     }
 
-    impl<'a> Visit<'a> for MemberAssignmentChecker<'a> {
-        fn visit_assignment_expression(&mut self, expr: &AssignmentExpression<'a>) {
-            match &expr.left {
-                AssignmentTarget::StaticMemberExpression(_)
-                | AssignmentTarget::ComputedMemberExpression(_)
-                | AssignmentTarget::PrivateFieldExpression(_) => {
-                    self.linter.add_error(
-                        "no-member-assignments".to_string(),
-                        "Member assignments like 'foo.bar = value' are not allowed in pure TypeScript subset".to_string(),
-                        expr.span,
-                    );
-                }
-                _ => {}
+    fn on_program_start(&mut self, linter: &mut Linter, _program: &'a Program<'a>) {
+        let path_str = linter.path.to_str().unwrap_or("").replace('\\', "/");
+        self.is_error_file = path_str.contains("/errors/");
+    }
+
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::AssignmentExpression(expr) = kind else {
+            return;
+        };
+        if self.is_error_file {
+            return;
+        }
+        match &expr.left {
+            AssignmentTarget::StaticMemberExpression(_)
+            | AssignmentTarget::ComputedMemberExpression(_)
+            | AssignmentTarget::PrivateFieldExpression(_) => {
+                linter.add_error(
+                    "no-member-assignments".to_string(),
+                    "Member assignments like 'foo.bar = value' are not allowed in pure TypeScript subset".to_string(),
+                    expr.span,
+                );
             }
-            walk::walk_assignment_expression(self, expr);
+            _ => {}
         }
     }
+}
 
-    let mut checker = MemberAssignmentChecker { linter };
-    checker.visit_program(program);
+pub fn check_no_member_assignments(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoMemberAssignments::default());
 }
 
 #[cfg(test)]
@@ -1,38 +1,58 @@
-use crate::Linter;
 use oxc::ast::ast::*;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
-pub fn check_no_mutable_record(linter: &mut Linter, program: &Program) {
-    struct RecordChecker<'a> {
-        linter: &'a mut Linter,
+use crate::rule::{run_rule, Rule};
+use crate::Linter;
+
+/// Prohibits mutable `Record<K, V>` types
+///
+/// `Readonly<Record<K, V>>` is allowed.
+#[derive(Default)]
+pub struct NoMutableRecord {
+    // Depth of enclosing Readonly<...> type references
+    readonly_depth: usize,
+}
+
+fn type_name<'a>(type_ref: &TSTypeReference<'a>) -> Option<&'a str> {
+    match &type_ref.type_name {
+        TSTypeName::IdentifierReference(id) => Some(id.name.as_str()),
+        _ => None,
+    }
+}
+
+impl<'a> Rule<'a> for NoMutableRecord {
+    fn id(&self) -> &str {
+        "no-mutable-record"
+    }
+
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::TSTypeReference(type_ref) = kind else {
+            return;
+        };
+        match type_name(type_ref) {
+            Some("Readonly") => self.readonly_depth += 1,
+            Some("Record") if self.readonly_depth == 0 => {
+                linter.add_error(
+                    "no-mutable-record".to_string(),
+                    "Mutable Record<K, V> is not allowed. Use Readonly<Record<K, V>>, ReadonlyMap or a Map for mutable key-value collections".to_string(),
+                    type_ref.span,
+                );
+            }
+            _ => {}
+        }
     }
 
-    impl<'a> Visit<'a> for RecordChecker<'a> {
-        fn visit_variable_declarator(&mut self, decl: &VariableDeclarator<'a>) {
-            // Check if type annotation is Record<K, V>
-            if let Some(type_ann) = &decl.id.type_annotation {
-                if let TSType::TSTypeReference(type_ref) = &type_ann.type_annotation {
-                    if let TSTypeName::IdentifierReference(id) = &type_ref.type_name {
-                        if id.name == "Record" {
-                            // Check if initialized with empty object
-                            if let Some(Expression::ObjectExpression(obj)) = &decl.init {
-                                if obj.properties.is_empty() {
-                                    self.linter.add_error(
-                                        "no-mutable-record".to_string(),
-                                        "Mutable Record<K, V> = {} is not allowed. Use Map instead for mutable key-value collections".to_string(),
-                                        decl.span,
-                                    );
-                                }
-                            }
-                        }
-                    }
-                }
+    fn leave_node(&mut self, _linter: &mut Linter, kind: AstKind<'a>) {
+        if let AstKind::TSTypeReference(type_ref) = kind {
+            if type_name(type_ref) == Some("Readonly") {
+                self.readonly_depth -= 1;
             }
         }
     }
+}
 
-    let mut checker = RecordChecker { linter };
-    checker.visit_program(program);
+pub fn check_no_mutable_record(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoMutableRecord::default());
 }
 
 #[cfg(test)]
@@ -72,6 +92,16 @@ mod tests {
             const obj: Record<string, number> = { a: 1, b: 2 };
         "#;
 
+        let errors = parse_and_check(source);
+        assert_eq!(errors, vec!["no-mutable-record".to_string()]);
+    }
+
+    #[test]
+    fn test_readonly_record_allowed() {
+        let source = r#"
+            const obj: Readonly<Record<string, number>> = { a: 1, b: 2 };
+        "#;
+
         let errors = parse_and_check(source);
         assert!(errors.is_empty());
     }
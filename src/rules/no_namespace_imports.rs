@@ -1,29 +1,52 @@
 use oxc::ast::ast::*;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_namespace_imports(linter: &mut Linter, program: &Program) {
-    for item in &program.body {
-        if let Statement::ImportDeclaration(import) = item {
-            if let Some(specifiers) = &import.specifiers {
-                for specifier in specifiers {
-                    if matches!(
-                        specifier,
-                        ImportDeclarationSpecifier::ImportNamespaceSpecifier(_)
-                    ) {
-                        linter.add_error(
-                            "no-namespace-imports".to_string(),
-                            format!("Namespace imports from '{}' are not allowed. Use named imports instead", 
-                                import.source.value),
-                            import.span,
-                        );
-                    }
+/// Prohibits namespace imports (`import * as x`)
+///
+/// Namespace imports from `node:` modules are reported by `node-import-style`.
+#[derive(Default)]
+pub struct NoNamespaceImports;
+
+impl<'a> Rule<'a> for NoNamespaceImports {
+    fn id(&self) -> &str {
+        "no-namespace-imports"
+    }
+
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::ImportDeclaration(import) = kind else {
+            return;
+        };
+        if import.source.value.starts_with("node:") {
+            return;
+        }
+
+        if let Some(specifiers) = &import.specifiers {
+            for specifier in specifiers {
+                if matches!(
+                    specifier,
+                    ImportDeclarationSpecifier::ImportNamespaceSpecifier(_)
+                ) {
+                    linter.add_error(
+                        "no-namespace-imports".to_string(),
+                        format!(
+                            "Namespace imports from '{}' are not allowed. Use named imports instead",
+                            import.source.value
+                        ),
+                        import.span,
+                    );
                 }
             }
         }
     }
 }
 
+pub fn check_no_namespace_imports(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoNamespaceImports);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,36 +1,36 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_object_assign(linter: &mut Linter, program: &Program) {
-    struct ObjectAssignChecker<'a> {
-        linter: &'a mut Linter,
+/// Prohibits Object.assign, whether called or passed around
+#[derive(Default)]
+pub struct NoObjectAssign;
+
+impl<'a> Rule<'a> for NoObjectAssign {
+    fn id(&self) -> &str {
+        "no-object-assign"
     }
 
-    impl<'a> Visit<'a> for ObjectAssignChecker<'a> {
-        fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
-            // Check for Object.assign()
-            if let Expression::StaticMemberExpression(member) = &call.callee {
-                if let Expression::Identifier(obj) = &member.object {
-                    if obj.name.as_str() == "Object" && member.property.name.as_str() == "assign" {
-                        self.linter.add_error(
-                            "no-object-assign".to_string(),
-                            "Object.assign is not allowed. Use spread operator (...) instead"
-                                .to_string(),
-                            call.span,
-                        );
-                    }
-                }
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::StaticMemberExpression(member) = kind else {
+            return;
+        };
+        if let Expression::Identifier(obj) = &member.object {
+            if obj.name.as_str() == "Object" && member.property.name.as_str() == "assign" {
+                linter.add_error(
+                    "no-object-assign".to_string(),
+                    "Object.assign is not allowed. Use spread operator (...) instead".to_string(),
+                    member.span,
+                );
             }
-
-            walk::walk_call_expression(self, call);
         }
     }
+}
 
-    let mut checker = ObjectAssignChecker { linter };
-    checker.visit_program(program);
+pub fn check_no_object_assign(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoObjectAssign);
 }
 
 #[cfg(test)]
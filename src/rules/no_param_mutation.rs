@@ -0,0 +1,96 @@
+use oxc::ast::ast::*;
+
+use crate::rule::{run_rule, Rule};
+use crate::scope_analysis::ScopeAnalysis;
+use crate::Linter;
+
+/// Check that function parameters are never mutated
+///
+/// Reports assignments, `delete`, `++`/`--` and mutating array methods
+/// applied to a parameter or anything reachable through it. References are
+/// resolved through closures, and shadowing declarations are not reported.
+#[derive(Default)]
+pub struct NoParamMutation;
+
+impl<'a> Rule<'a> for NoParamMutation {
+    fn id(&self) -> &str {
+        "no-param-mutation"
+    }
+
+    fn on_program_end(&mut self, linter: &mut Linter, program: &'a Program<'a>) {
+        let analysis = ScopeAnalysis::analyze(program);
+        for mutation in analysis.param_mutations {
+            linter.add_error(
+                "no-param-mutation".to_string(),
+                format!(
+                    "Parameter '{}' must not be mutated ({}). Return a new value instead",
+                    mutation.name, mutation.kind
+                ),
+                mutation.span,
+            );
+        }
+    }
+}
+
+pub fn check_no_param_mutation(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoParamMutation);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Linter;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+    use std::path::Path;
+
+    fn parse_and_check(source: &str) -> Vec<String> {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path("test.ts").unwrap();
+        let ret = Parser::new(&allocator, source, source_type).parse();
+
+        let mut linter = Linter::new(Path::new("test.ts"), source, false);
+        check_no_param_mutation(&mut linter, &ret.program);
+
+        linter.errors.into_iter().map(|e| e.message).collect()
+    }
+
+    #[test]
+    fn test_param_mutations() {
+        let source = r#"
+export function update(user: { name: string; age: number }, tags: string[], count: number): void {
+  user.name = "x";
+  delete user.age;
+  tags.push("a");
+  count++;
+  count = 0;
+}
+"#;
+        let errors = parse_and_check(source);
+        assert_eq!(errors.len(), 5);
+        assert!(errors[0].contains("'user' must not be mutated (property assignment)"));
+        assert!(errors[1].contains("(delete)"));
+        assert!(errors[2].contains("'tags' must not be mutated ('push()' call)"));
+        assert!(errors[3].contains("(increment/decrement)"));
+        assert!(errors[4].contains("(reassignment)"));
+    }
+
+    #[test]
+    fn test_local_copies_are_allowed() {
+        let source = r#"
+export function append(tags: readonly string[]): string[] {
+  const copy = [...tags];
+  copy.push("a");
+  const f = () => {
+    const tags: string[] = [];
+    tags.push("b");
+    return tags;
+  };
+  return [...copy, ...f()];
+}
+"#;
+        let errors = parse_and_check(source);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+}
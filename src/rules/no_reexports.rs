@@ -1,77 +1,79 @@
 use oxc::ast::ast::*;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_reexports(linter: &mut Linter, program: &Program) {
-    // Allow re-exports in index.ts and entry point files
-    let filename = linter
-        .path
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("");
-
-    // Check if this is an entry point file
-    let is_entry_point = filename == "index" || linter.is_entry_point || linter.is_main_entry;
-
-    if linter.verbose {
-        eprintln!(
-            "DEBUG no_reexports: filename={}, is_entry_point={}, is_entry={}, is_main={}",
-            filename, is_entry_point, linter.is_entry_point, linter.is_main_entry
-        );
+/// Prohibits re-exports outside entry points, and namespace re-exports everywhere
+#[derive(Default)]
+pub struct NoReexports;
+
+impl<'a> Rule<'a> for NoReexports {
+    fn id(&self) -> &str {
+        "no-reexports"
     }
 
-    if is_entry_point {
-        // For entry points, validate re-export rules:
-        // 1. Must use named exports: export { name } from "..."
-        // 2. Cannot use namespace exports: export * from "..." or export * as ns from "..."
-        for item in &program.body {
-            match item {
-                Statement::ExportAllDeclaration(export) => {
+    fn on_program_start(&mut self, linter: &mut Linter, program: &'a Program<'a>) {
+        // Allow re-exports in index.ts and entry point files
+        let filename = linter
+            .path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("");
+
+        // Check if this is an entry point file
+        let is_entry_point = filename == "index" || linter.is_entry_point || linter.is_main_entry;
+
+        if linter.verbose {
+            eprintln!(
+                "DEBUG no_reexports: filename={}, is_entry_point={}, is_entry={}, is_main={}",
+                filename, is_entry_point, linter.is_entry_point, linter.is_main_entry
+            );
+        }
+
+        if is_entry_point {
+            // For entry points, validate re-export rules:
+            // 1. Must use named exports: export { name } from "..."
+            // 2. Cannot use namespace exports: export * from "..." or export * as ns from "..."
+            for item in &program.body {
+                if let Statement::ExportAllDeclaration(export) = item {
                     linter.add_error(
                         "no-reexports".to_string(),
                         format!("Namespace re-exports are not allowed in entry points. Use named exports: export {{ name }} from '{}'", export.source.value),
                         export.span,
                     );
                 }
-                Statement::ExportNamedDeclaration(_export) => {
-                    // Named re-exports are allowed in entry points
-                    // Additional validation could be added here for:
-                    // - Function name matching
-                    // - No leading underscore
-                    // - No @internal JSDoc tag
-                }
-                _ => {}
             }
-        }
-    } else {
-        // For non-entry files, no re-exports are allowed
-        for item in &program.body {
-            match item {
-                Statement::ExportAllDeclaration(export) => {
-                    linter.add_error(
-                        "no-reexports".to_string(),
-                        format!("Re-exports from '{}' are not allowed", export.source.value),
-                        export.span,
-                    );
-                }
-                Statement::ExportNamedDeclaration(export) => {
-                    if export.source.is_some() && !export.specifiers.is_empty() {
+        } else {
+            // For non-entry files, no re-exports are allowed
+            for item in &program.body {
+                match item {
+                    Statement::ExportAllDeclaration(export) => {
                         linter.add_error(
                             "no-reexports".to_string(),
-                            format!(
-                                "Re-exports from '{}' are not allowed",
-                                export.source.as_ref().unwrap().value
-                            ),
+                            format!("Re-exports from '{}' are not allowed", export.source.value),
                             export.span,
                         );
                     }
+                    Statement::ExportNamedDeclaration(export) if !export.specifiers.is_empty() => {
+                        if let Some(source) = &export.source {
+                            linter.add_error(
+                                "no-reexports".to_string(),
+                                format!("Re-exports from '{}' are not allowed", source.value),
+                                export.span,
+                            );
+                        }
+                    }
+                    _ => {}
                 }
-                _ => {}
             }
         }
     }
 }
 
+pub fn check_no_reexports(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoReexports);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
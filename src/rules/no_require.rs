@@ -1,37 +1,38 @@
 use oxc::ast::ast::*;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_require(linter: &mut Linter, program: &Program) {
-    use oxc::ast_visit::Visit;
+/// Prohibits `require()` calls
+#[derive(Default)]
+pub struct NoRequire;
 
-    struct NoRequireVisitor<'a, 'b> {
-        linter: &'a mut Linter,
-        _phantom: std::marker::PhantomData<&'b ()>,
+impl<'a> Rule<'a> for NoRequire {
+    fn id(&self) -> &str {
+        "no-require"
     }
 
-    impl<'a, 'b> Visit<'b> for NoRequireVisitor<'a, 'b> {
-        fn visit_call_expression(&mut self, call: &CallExpression<'b>) {
-            // Check for require() calls
-            if let Expression::Identifier(ident) = &call.callee {
-                if ident.name == "require" {
-                    self.linter.add_error(
-                        "no-require".to_string(),
-                        "require() is not allowed. Use ES6 import statements instead".to_string(),
-                        call.span,
-                    );
-                }
-            }
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::CallExpression(call) = kind else {
+            return;
+        };
 
-            oxc::ast_visit::walk::walk_call_expression(self, call);
+        // Check for require() calls
+        if let Expression::Identifier(ident) = &call.callee {
+            if ident.name == "require" {
+                linter.add_error(
+                    "no-require".to_string(),
+                    "require() is not allowed. Use ES6 import statements instead".to_string(),
+                    call.span,
+                );
+            }
         }
     }
+}
 
-    let mut visitor = NoRequireVisitor {
-        linter,
-        _phantom: std::marker::PhantomData,
-    };
-    visitor.visit_program(program);
+pub fn check_no_require(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoRequire);
 }
 
 #[cfg(test)]
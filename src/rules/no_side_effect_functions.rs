@@ -1,9 +1,14 @@
 use oxc::ast::ast::*;
+use oxc::ast::AstKind;
 
+use crate::capabilities::AllowScopes;
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-// Functions that have side effects and should not be called directly
-const SIDE_EFFECT_FUNCTIONS: &[(&str, &str)] = &[("Math", "random"), ("Date", "now")];
+// Functions that have side effects and should not be called directly, with
+// the capability that allows them
+const SIDE_EFFECT_FUNCTIONS: &[(&str, &str, &str)] =
+    &[("Math", "random", "random"), ("Date", "now", "time")];
 
 const SIDE_EFFECT_GLOBAL_FUNCTIONS: &[&str] = &[
     "setTimeout",
@@ -13,62 +18,45 @@ const SIDE_EFFECT_GLOBAL_FUNCTIONS: &[&str] = &[
     "requestIdleCallback",
 ];
 
-pub fn check_no_side_effect_functions(linter: &mut Linter, program: &Program) {
-    use oxc::ast_visit::Visit;
+/// Prohibits calling non-deterministic functions inside functions
+///
+/// Parameter defaults are allowed, so callers can inject the value instead.
+/// Timer functions, `Date.now()`/`new Date()` and `Math.random()` are allowed
+/// in files and functions with an `@allow timers`, `@allow time` or
+/// `@allow random` directive respectively.
+#[derive(Default)]
+pub struct NoSideEffectFunctions {
+    function_depth: usize,
+    parameter_depth: usize,
+    allow: AllowScopes,
+}
 
-    struct SideEffectVisitor<'a, 'b> {
-        linter: &'a mut Linter,
-        in_function: bool,
-        in_default_parameter: bool,
-        _phantom: std::marker::PhantomData<&'b ()>,
+impl NoSideEffectFunctions {
+    fn in_function_body(&self) -> bool {
+        self.function_depth > 0 && self.parameter_depth == 0
     }
+}
 
-    impl<'a, 'b> Visit<'b> for SideEffectVisitor<'a, 'b> {
-        fn visit_function(&mut self, func: &Function<'b>, _: oxc::syntax::scope::ScopeFlags) {
-            let was_in_function = self.in_function;
-            self.in_function = true;
-
-            // Visit parameters to check for default values
-            for param in &func.params.items {
-                if param.pattern.type_annotation.is_some() {
-                    // Check default parameter values
-                    self.in_default_parameter = true;
-                    oxc::ast_visit::walk::walk_formal_parameter(self, param);
-                    self.in_default_parameter = false;
-                }
-            }
-
-            // Visit function body
-            if let Some(body) = &func.body {
-                self.visit_function_body(body);
-            }
-
-            self.in_function = was_in_function;
-        }
+impl<'a> Rule<'a> for NoSideEffectFunctions {
+    fn id(&self) -> &str {
+        "no-side-effect-functions"
+    }
 
-        fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'b>) {
-            let was_in_function = self.in_function;
-            self.in_function = true;
+    fn on_program_start(&mut self, linter: &mut Linter, program: &'a Program<'a>) {
+        self.allow = AllowScopes::from_program(&linter.source_text, program);
+    }
 
-            // Visit parameters
-            for param in &arrow.params.items {
-                self.in_default_parameter = true;
-                oxc::ast_visit::walk::walk_formal_parameter(self, param);
-                self.in_default_parameter = false;
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::Function(_) | AstKind::ArrowFunctionExpression(_) => {
+                self.function_depth += 1;
             }
-
-            // Visit body
-            oxc::ast_visit::walk::walk_arrow_function_expression(self, arrow);
-
-            self.in_function = was_in_function;
-        }
-
-        fn visit_new_expression(&mut self, new_expr: &NewExpression<'b>) {
-            // Check for new Date()
-            if self.in_function && !self.in_default_parameter {
+            AstKind::FormalParameter(_) => self.parameter_depth += 1,
+            AstKind::NewExpression(new_expr) if self.in_function_body() => {
+                // Check for new Date()
                 if let Expression::Identifier(ident) = &new_expr.callee {
-                    if ident.name == "Date" {
-                        self.linter.add_error(
+                    if ident.name == "Date" && !self.allow.allows("time", new_expr.span.start) {
+                        linter.add_error(
                             "no-side-effect-functions".to_string(),
                             "Direct use of 'new Date()' is not allowed in functions. Pass it as a parameter or use a default parameter instead".to_string(),
                             new_expr.span,
@@ -76,12 +64,7 @@ pub fn check_no_side_effect_functions(linter: &mut Linter, program: &Program) {
                     }
                 }
             }
-
-            oxc::ast_visit::walk::walk_new_expression(self, new_expr);
-        }
-
-        fn visit_call_expression(&mut self, call: &CallExpression<'b>) {
-            if self.in_function && !self.in_default_parameter {
+            AstKind::CallExpression(call) if self.in_function_body() => {
                 // Check for Math.random(), Date.now()
                 if let Some(MemberExpression::StaticMemberExpression(static_member)) =
                     call.callee.as_member_expression()
@@ -90,16 +73,19 @@ pub fn check_no_side_effect_functions(linter: &mut Linter, program: &Program) {
                         let obj_name = obj.name.as_str();
                         let method_name = static_member.property.name.as_str();
 
-                        for (object, method) in SIDE_EFFECT_FUNCTIONS {
-                            if obj_name == *object && method_name == *method {
-                                self.linter.add_error(
-                                        "no-side-effect-functions".to_string(),
-                                        format!(
-                                            "Direct use of '{}.{}()' is not allowed in functions. Pass it as a parameter or use a default parameter instead",
-                                            object, method
-                                        ),
-                                        call.span,
-                                    );
+                        for (object, method, capability) in SIDE_EFFECT_FUNCTIONS {
+                            if obj_name == *object
+                                && method_name == *method
+                                && !self.allow.allows(capability, call.span.start)
+                            {
+                                linter.add_error(
+                                    "no-side-effect-functions".to_string(),
+                                    format!(
+                                        "Direct use of '{}.{}()' is not allowed in functions. Pass it as a parameter or use a default parameter instead",
+                                        object, method
+                                    ),
+                                    call.span,
+                                );
                             }
                         }
                     }
@@ -107,8 +93,10 @@ pub fn check_no_side_effect_functions(linter: &mut Linter, program: &Program) {
 
                 // Check for global side-effect functions
                 if let Expression::Identifier(ident) = &call.callee {
-                    if SIDE_EFFECT_GLOBAL_FUNCTIONS.contains(&ident.name.as_str()) {
-                        self.linter.add_error(
+                    if !self.allow.allows("timers", call.span.start)
+                        && SIDE_EFFECT_GLOBAL_FUNCTIONS.contains(&ident.name.as_str())
+                    {
+                        linter.add_error(
                             "no-side-effect-functions".to_string(),
                             format!(
                                 "Direct use of '{}()' is not allowed in functions. Pass it as a parameter or use a default parameter instead",
@@ -119,19 +107,23 @@ pub fn check_no_side_effect_functions(linter: &mut Linter, program: &Program) {
                     }
                 }
             }
-
-            oxc::ast_visit::walk::walk_call_expression(self, call);
+            _ => {}
         }
     }
 
-    let mut visitor = SideEffectVisitor {
-        linter,
-        in_function: false,
-        in_default_parameter: false,
-        _phantom: std::marker::PhantomData,
-    };
+    fn leave_node(&mut self, _linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::Function(_) | AstKind::ArrowFunctionExpression(_) => {
+                self.function_depth -= 1;
+            }
+            AstKind::FormalParameter(_) => self.parameter_depth -= 1,
+            _ => {}
+        }
+    }
+}
 
-    visitor.visit_program(program);
+pub fn check_no_side_effect_functions(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoSideEffectFunctions::default());
 }
 
 #[cfg(test)]
@@ -201,6 +193,21 @@ mod tests {
         assert!(errors[0].contains("new Date()"));
     }
 
+    #[test]
+    fn test_allowed_by_capability() {
+        let source = r#"
+            /**
+             * @allow time
+             */
+            function getCurrentDate() {
+                return [new Date(), Date.now(), Math.random()];
+            }
+        "#;
+        let errors = parse_and_check(source);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("Math.random()"));
+    }
+
     #[test]
     fn test_settimeout_direct() {
         let source = r#"
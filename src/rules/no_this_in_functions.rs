@@ -1,54 +1,57 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
-use oxc::syntax::scope::ScopeFlags;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_this_in_functions(linter: &mut Linter, program: &Program) {
-    struct ThisChecker<'a> {
-        linter: &'a mut Linter,
-        in_function: bool,
-        in_arrow_function: bool,
-    }
-
-    impl<'a> Visit<'a> for ThisChecker<'a> {
-        fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
-            let was_in_function = self.in_function;
-            self.in_function = true;
-
-            walk::walk_function(self, func, flags);
-
-            self.in_function = was_in_function;
-        }
-
-        fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'a>) {
-            let was_in_arrow = self.in_arrow_function;
-            self.in_arrow_function = true;
+/// Prohibits `this` inside functions and arrow functions
+///
+/// Files under `errors/` are skipped so error classes can set `this.name`.
+#[derive(Default)]
+pub struct NoThisInFunctions {
+    function_depth: usize,
+    is_error_file: bool,
+}
 
-            walk::walk_arrow_function_expression(self, arrow);
+impl<'a> Rule<'a> for NoThisInFunctions {
+    fn id(&self) -> &str {
+        "no-this-in-functions"
+    }
 
-            self.in_arrow_function = was_in_arrow;
-        }
+    fn on_program_start(&mut self, linter: &mut Linter, _program: &'a Program<'a>) {
+        let path_str = linter.path.to_str().unwrap_or("").replace('\\', "/");
+        self.is_error_file = path_str.contains("/errors/");
+    }
 
-        fn visit_this_expression(&mut self, this: &ThisExpression) {
-            if self.in_function || self.in_arrow_function {
-                self.linter.add_error(
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::Function(_) | AstKind::ArrowFunctionExpression(_) => {
+                self.function_depth += 1;
+            }
+            AstKind::ThisExpression(this) if self.function_depth > 0 && !self.is_error_file => {
+                linter.add_error(
                     "no-this-in-functions".to_string(),
                     "Using 'this' in functions is not allowed in pure TypeScript subset"
                         .to_string(),
                     this.span,
                 );
             }
+            _ => {}
+        }
+    }
+
+    fn leave_node(&mut self, _linter: &mut Linter, kind: AstKind<'a>) {
+        if matches!(
+            kind,
+            AstKind::Function(_) | AstKind::ArrowFunctionExpression(_)
+        ) {
+            self.function_depth -= 1;
         }
     }
+}
 
-    let mut checker = ThisChecker {
-        linter,
-        in_function: false,
-        in_arrow_function: false,
-    };
-    checker.visit_program(program);
+pub fn check_no_this_in_functions(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoThisInFunctions::default());
 }
 
 #[cfg(test)]
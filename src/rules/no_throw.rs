@@ -1,114 +1,100 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::capabilities::AllowScopes;
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_throw(linter: &mut Linter, program: &Program) {
-    struct ThrowChecker<'a> {
-        linter: &'a mut Linter,
-    }
-
-    impl<'a> Visit<'a> for ThrowChecker<'a> {
-        fn visit_throw_statement(&mut self, stmt: &ThrowStatement<'a>) {
-            self.linter.add_error(
-                "no-throw".to_string(),
-                "Throwing exceptions is not allowed. Use Result type from neverthrow instead"
-                    .to_string(),
-                stmt.span,
-            );
-        }
-
-        fn visit_try_statement(&mut self, stmt: &TryStatement<'a>) {
-            // First report that try-catch is not allowed
-            self.linter.add_error(
-                "no-try-catch".to_string(),
-                "Try-catch blocks are not allowed. Use Result type from neverthrow instead"
-                    .to_string(),
-                stmt.span,
-            );
-
-            // But if try-catch is used, ensure it returns ok() in try and err() in catch
-            self.check_try_block_returns(&stmt.block);
+/// Prohibits throw and try/catch, except a try/catch that converts to a
+/// Result by returning `ok(...)` from the try block and `err(...)` from the
+/// catch block
+///
+/// Throw statements and try/catch are allowed in files and functions with an
+/// `@allow throws` directive.
+#[derive(Default)]
+pub struct NoThrow {
+    allow: AllowScopes,
+}
 
-            if let Some(handler) = &stmt.handler {
-                self.check_catch_block_returns(&handler.body);
-            }
+impl<'a> Rule<'a> for NoThrow {
+    fn id(&self) -> &str {
+        "no-throw"
+    }
 
-            walk::walk_try_statement(self, stmt);
-        }
+    fn on_program_start(&mut self, linter: &mut Linter, program: &'a Program<'a>) {
+        self.allow = AllowScopes::from_program(&linter.source_text, program);
     }
 
-    impl<'a> ThrowChecker<'a> {
-        fn check_try_block_returns(&mut self, block: &BlockStatement<'a>) {
-            let has_ok_return = self.block_returns_ok(block);
-            if !has_ok_return {
-                self.linter.add_error(
-                    "try-must-return-ok".to_string(),
-                    "Try block must return ok(...) from neverthrow".to_string(),
-                    block.span,
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::ThrowStatement(stmt) if !self.allow.allows("throws", stmt.span.start) => {
+                linter.add_error(
+                    "no-throw".to_string(),
+                    "Throwing exceptions is not allowed. Use Result type from neverthrow instead"
+                        .to_string(),
+                    stmt.span,
                 );
             }
-        }
-
-        fn check_catch_block_returns(&mut self, block: &BlockStatement<'a>) {
-            let has_err_return = self.block_returns_err(block);
-            if !has_err_return {
-                self.linter.add_error(
-                    "catch-must-return-err".to_string(),
-                    "Catch block must return err(...) from neverthrow".to_string(),
-                    block.span,
+            AstKind::TryStatement(stmt)
+                if !self.allow.allows("throws", stmt.span.start) && !is_result_conversion(stmt) =>
+            {
+                // First report that try-catch is not allowed
+                linter.add_error(
+                    "no-try-catch".to_string(),
+                    "Try-catch blocks are not allowed. Use Result type from neverthrow instead"
+                        .to_string(),
+                    stmt.span,
                 );
-            }
-        }
 
-        fn block_returns_ok(&self, block: &BlockStatement<'a>) -> bool {
-            for stmt in &block.body {
-                if let Statement::ReturnStatement(ret) = stmt {
-                    if let Some(arg) = &ret.argument {
-                        if self.is_ok_call(arg) {
-                            return true;
-                        }
-                    }
+                // But if try-catch is used, ensure it returns ok() in try and err() in catch
+                if !block_returns_call(&stmt.block, "ok") {
+                    linter.add_error(
+                        "try-must-return-ok".to_string(),
+                        "Try block must return ok(...) from neverthrow".to_string(),
+                        stmt.block.span,
+                    );
                 }
-            }
-            false
-        }
 
-        fn block_returns_err(&self, block: &BlockStatement<'a>) -> bool {
-            for stmt in &block.body {
-                if let Statement::ReturnStatement(ret) = stmt {
-                    if let Some(arg) = &ret.argument {
-                        if self.is_err_call(arg) {
-                            return true;
-                        }
+                if let Some(handler) = &stmt.handler {
+                    if !block_returns_call(&handler.body, "err") {
+                        linter.add_error(
+                            "catch-must-return-err".to_string(),
+                            "Catch block must return err(...) from neverthrow".to_string(),
+                            handler.body.span,
+                        );
                     }
                 }
             }
-            false
+            _ => {}
         }
+    }
+}
 
-        fn is_ok_call(&self, expr: &Expression<'a>) -> bool {
-            if let Expression::CallExpression(call) = expr {
-                if let Expression::Identifier(ident) = &call.callee {
-                    return ident.name == "ok";
-                }
-            }
-            false
-        }
+/// Whether a try/catch converts to a Result: the try block returns `ok(...)`
+/// and the catch block returns `err(...)`
+pub(crate) fn is_result_conversion(stmt: &TryStatement) -> bool {
+    block_returns_call(&stmt.block, "ok")
+        && stmt
+            .handler
+            .as_ref()
+            .is_some_and(|handler| block_returns_call(&handler.body, "err"))
+}
 
-        fn is_err_call(&self, expr: &Expression<'a>) -> bool {
-            if let Expression::CallExpression(call) = expr {
-                if let Expression::Identifier(ident) = &call.callee {
-                    return ident.name == "err";
-                }
-            }
-            false
-        }
-    }
+/// Whether a block directly contains `return <callee>(...)`
+fn block_returns_call(block: &BlockStatement, callee: &str) -> bool {
+    block.body.iter().any(|stmt| {
+        let Statement::ReturnStatement(ret) = stmt else {
+            return false;
+        };
+        let Some(Expression::CallExpression(call)) = &ret.argument else {
+            return false;
+        };
+        matches!(&call.callee, Expression::Identifier(ident) if ident.name == callee)
+    })
+}
 
-    let mut checker = ThrowChecker { linter };
-    checker.visit_program(program);
+pub fn check_no_throw(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoThrow::default());
 }
 
 #[cfg(test)]
@@ -159,6 +145,30 @@ mod tests {
         assert!(errors.contains(&"no-try-catch".to_string()));
     }
 
+    #[test]
+    fn test_throws_allowed_in_one_function() {
+        let source = r#"
+            /**
+             * Helpers.
+             */
+            const header = 1;
+
+            /**
+             * @allow throws
+             */
+            function fail() {
+                throw new FailError();
+            }
+
+            function doSomething() {
+                throw new Error("Something went wrong");
+            }
+        "#;
+
+        let errors = parse_and_check(source);
+        assert_eq!(errors, vec!["no-throw"]);
+    }
+
     #[test]
     fn test_try_without_ok() {
         let source = r#"
@@ -205,11 +215,9 @@ mod tests {
             }
         "#;
 
+        // Converting to a Result is the allowed use of try/catch
         let errors = parse_and_check(source);
-        // Should still have no-try-catch error, but not the return errors
-        assert!(errors.contains(&"no-try-catch".to_string()));
-        assert!(!errors.contains(&"try-must-return-ok".to_string()));
-        assert!(!errors.contains(&"catch-must-return-err".to_string()));
+        assert!(errors.is_empty(), "{:?}", errors);
     }
 
     #[test]
@@ -1,89 +1,113 @@
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 use oxc::ast::ast::*;
 use oxc::span::GetSpan;
 
-pub fn check_no_top_level_side_effects(linter: &mut Linter, program: &Program) {
-    // Allow main() calls in main.ts files
-    let path_str = linter.path.to_str().unwrap_or("");
-    let is_main_file =
-        path_str.ends_with("/main.ts") || path_str.ends_with("\\main.ts") || path_str == "main.ts";
+/// Prohibits calls, assignments, loops and conditionals at module top level
+///
+/// Test files and files under `errors/` are skipped.
+#[derive(Default)]
+pub struct NoTopLevelSideEffects;
 
-    if linter.verbose && is_main_file {
-        eprintln!("DEBUG: Detected main.ts file: {}", path_str);
+impl<'a> Rule<'a> for NoTopLevelSideEffects {
+    fn id(&self) -> &str {
+        "no-top-level-side-effects"
     }
 
-    for item in &program.body {
-        match item {
-            Statement::ExpressionStatement(expr_stmt) => match &expr_stmt.expression {
-                Expression::CallExpression(call) => {
-                    // Allow main() calls in main.ts
-                    if is_main_file && is_main_function_call(call) {
-                        continue;
-                    }
+    fn on_program_start(&mut self, linter: &mut Linter, program: &'a Program<'a>) {
+        let path_str = linter.path.to_str().unwrap_or("").replace('\\', "/");
+        let is_test_file = path_str.contains("_test.ts")
+            || path_str.contains(".test.ts")
+            || path_str.contains(".spec.ts");
+        let is_error_file = path_str.contains("/errors/");
+        if is_test_file || is_error_file {
+            return;
+        }
 
-                    // Allow Deno.test() calls in test files when using deno-test runner
-                    if is_deno_test_call(call)
-                        && linter.test_runner == Some(crate::TestRunner::DenoTest)
-                    {
-                        continue;
-                    }
+        // Allow main() calls in main.ts files
+        let is_main_file = path_str.ends_with("/main.ts") || path_str == "main.ts";
 
-                    if !is_iife(call) {
+        if linter.verbose && is_main_file {
+            eprintln!("DEBUG: Detected main.ts file: {}", path_str);
+        }
+
+        for item in &program.body {
+            match item {
+                Statement::ExpressionStatement(expr_stmt) => match &expr_stmt.expression {
+                    Expression::CallExpression(call) => {
+                        // Allow main() calls in main.ts
+                        if is_main_file && is_main_function_call(call) {
+                            continue;
+                        }
+
+                        // Allow Deno.test() calls in test files when using deno-test runner
+                        if is_deno_test_call(call)
+                            && linter.test_runner == Some(crate::TestRunner::DenoTest)
+                        {
+                            continue;
+                        }
+
+                        if !is_iife(call) {
+                            linter.add_error(
+                                "no-top-level-side-effects".to_string(),
+                                "Top-level function calls are not allowed (side effects)"
+                                    .to_string(),
+                                expr_stmt.span,
+                            );
+                        }
+                    }
+                    Expression::AssignmentExpression(_) => {
                         linter.add_error(
                             "no-top-level-side-effects".to_string(),
-                            "Top-level function calls are not allowed (side effects)".to_string(),
+                            "Top-level assignments are not allowed (side effects)".to_string(),
                             expr_stmt.span,
                         );
                     }
-                }
-                Expression::AssignmentExpression(_) => {
-                    linter.add_error(
-                        "no-top-level-side-effects".to_string(),
-                        "Top-level assignments are not allowed (side effects)".to_string(),
-                        expr_stmt.span,
-                    );
-                }
-                Expression::UpdateExpression(_) => {
-                    linter.add_error(
-                        "no-top-level-side-effects".to_string(),
-                        "Top-level update expressions are not allowed (side effects)".to_string(),
-                        expr_stmt.span,
-                    );
-                }
-                Expression::NewExpression(_) => {
+                    Expression::UpdateExpression(_) => {
+                        linter.add_error(
+                            "no-top-level-side-effects".to_string(),
+                            "Top-level update expressions are not allowed (side effects)"
+                                .to_string(),
+                            expr_stmt.span,
+                        );
+                    }
+                    Expression::NewExpression(_) => {
+                        linter.add_error(
+                            "no-top-level-side-effects".to_string(),
+                            "Top-level new expressions are not allowed (side effects)".to_string(),
+                            expr_stmt.span,
+                        );
+                    }
+                    _ => {}
+                },
+                Statement::ForStatement(_)
+                | Statement::ForInStatement(_)
+                | Statement::ForOfStatement(_)
+                | Statement::WhileStatement(_)
+                | Statement::DoWhileStatement(_) => {
                     linter.add_error(
                         "no-top-level-side-effects".to_string(),
-                        "Top-level new expressions are not allowed (side effects)".to_string(),
-                        expr_stmt.span,
+                        "Top-level loops are not allowed (side effects)".to_string(),
+                        item.span(),
                     );
                 }
-                _ => {}
-            },
-            Statement::ForStatement(_)
-            | Statement::ForInStatement(_)
-            | Statement::ForOfStatement(_)
-            | Statement::WhileStatement(_)
-            | Statement::DoWhileStatement(_) => {
-                linter.add_error(
-                    "no-top-level-side-effects".to_string(),
-                    "Top-level loops are not allowed (side effects)".to_string(),
-                    item.span(),
-                );
-            }
-            Statement::IfStatement(if_stmt) => {
-                if !is_type_guard_only(if_stmt) {
+                Statement::IfStatement(if_stmt) if !is_type_guard_only(if_stmt) => {
                     linter.add_error(
                         "no-top-level-side-effects".to_string(),
                         "Top-level if statements are not allowed (side effects)".to_string(),
                         if_stmt.span,
                     );
                 }
+                _ => {}
             }
-            _ => {}
         }
     }
 }
 
+pub fn check_no_top_level_side_effects(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoTopLevelSideEffects);
+}
+
 fn is_iife(call: &CallExpression) -> bool {
     match &call.callee {
         Expression::FunctionExpression(_) | Expression::ArrowFunctionExpression(_) => true,
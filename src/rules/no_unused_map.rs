@@ -1,33 +1,42 @@
-use crate::Linter;
 use oxc::ast::ast::*;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
-pub fn check_no_unused_map(linter: &mut Linter, program: &Program) {
-    struct MapChecker<'a> {
-        linter: &'a mut Linter,
+use crate::rule::{run_rule, Rule};
+use crate::Linter;
+
+/// Prohibits discarding the result of `.map()`
+#[derive(Default)]
+pub struct NoUnusedMap;
+
+impl<'a> Rule<'a> for NoUnusedMap {
+    fn id(&self) -> &str {
+        "no-unused-map"
     }
 
-    impl<'a> Visit<'a> for MapChecker<'a> {
-        fn visit_expression_statement(&mut self, stmt: &ExpressionStatement<'a>) {
-            if let Expression::CallExpression(call) = &stmt.expression {
-                // Check if it's a .map() call
-                if let Some(MemberExpression::StaticMemberExpression(static_member)) =
-                    call.callee.as_member_expression()
-                {
-                    if static_member.property.name == "map" {
-                        self.linter.add_error(
-                                "no-unused-map".to_string(),
-                                "map() return value must be used. Use forEach() for side effects or assign the result".to_string(),
-                                stmt.span,
-                            );
-                    }
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::ExpressionStatement(stmt) = kind else {
+            return;
+        };
+
+        if let Expression::CallExpression(call) = &stmt.expression {
+            // Check if it's a .map() call
+            if let Some(MemberExpression::StaticMemberExpression(static_member)) =
+                call.callee.as_member_expression()
+            {
+                if static_member.property.name == "map" {
+                    linter.add_error(
+                        "no-unused-map".to_string(),
+                        "map() return value must be used. Use forEach() for side effects or assign the result".to_string(),
+                        stmt.span,
+                    );
                 }
             }
         }
     }
+}
 
-    let mut checker = MapChecker { linter };
-    checker.visit_program(program);
+pub fn check_no_unused_map(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoUnusedMap);
 }
 
 #[cfg(test)]
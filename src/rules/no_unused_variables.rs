@@ -1,105 +1,84 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
-use oxc::syntax::scope::ScopeFlags;
+use oxc::ast::AstKind;
+use oxc::span::Span;
 use std::collections::{HashMap, HashSet};
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_no_unused_variables(linter: &mut Linter, program: &Program) {
-    struct VariableUsageChecker<'a> {
-        declared_vars: HashMap<String, oxc::span::Span>,
-        used_vars: HashSet<String>,
-        imported_vars: HashMap<String, oxc::span::Span>,
-        used_imports: HashSet<String>,
-        linter: &'a mut Linter,
+/// Reports variables, function parameters and imports that are never referenced
+///
+/// Names starting with `_` are ignored.
+#[derive(Default)]
+pub struct NoUnusedVariables {
+    declared_vars: HashMap<String, Span>,
+    imported_vars: HashMap<String, Span>,
+    // Every referenced name, so uses before the declaration also count
+    referenced: HashSet<String>,
+}
+
+impl<'a> Rule<'a> for NoUnusedVariables {
+    fn id(&self) -> &str {
+        "no-unused-variables"
     }
 
-    impl<'a> Visit<'a> for VariableUsageChecker<'a> {
-        fn visit_import_declaration(&mut self, import: &ImportDeclaration<'a>) {
-            if let Some(specifiers) = &import.specifiers {
-                for specifier in specifiers {
-                    match specifier {
-                        ImportDeclarationSpecifier::ImportSpecifier(spec) => {
-                            let name = spec.local.name.as_str();
-                            self.imported_vars.insert(name.to_string(), import.span);
-                        }
-                        ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
-                            let name = spec.local.name.as_str();
-                            self.imported_vars.insert(name.to_string(), import.span);
-                        }
-                        ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
-                            let name = spec.local.name.as_str();
-                            self.imported_vars.insert(name.to_string(), import.span);
-                        }
-                    }
+    fn enter_node(&mut self, _linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::ImportDeclaration(import) => {
+                for specifier in import.specifiers.iter().flatten() {
+                    self.imported_vars
+                        .insert(specifier.local().name.to_string(), import.span);
                 }
             }
-        }
-
-        fn visit_variable_declaration(&mut self, var_decl: &VariableDeclaration<'a>) {
-            for decl in &var_decl.declarations {
+            AstKind::VariableDeclarator(decl) => {
                 if let BindingPatternKind::BindingIdentifier(id) = &decl.id.kind {
                     self.declared_vars.insert(id.name.to_string(), decl.span);
                 }
             }
-            walk::walk_variable_declaration(self, var_decl);
-        }
-
-        fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
-            // Add function parameters as declared
-            for param in &func.params.items {
-                if let BindingPatternKind::BindingIdentifier(id) = &param.pattern.kind {
-                    self.declared_vars.insert(id.name.to_string(), param.span);
+            AstKind::Function(func) => {
+                // Add function parameters as declared
+                for param in &func.params.items {
+                    if let BindingPatternKind::BindingIdentifier(id) = &param.pattern.kind {
+                        self.declared_vars.insert(id.name.to_string(), param.span);
+                    }
                 }
             }
-            walk::walk_function(self, func, flags);
-        }
-
-        fn visit_identifier_reference(&mut self, id: &IdentifierReference) {
-            let name = id.name.as_str();
-            if self.declared_vars.contains_key(name) {
-                self.used_vars.insert(name.to_string());
-            }
-            if self.imported_vars.contains_key(name) {
-                self.used_imports.insert(name.to_string());
+            AstKind::IdentifierReference(id) => {
+                self.referenced.insert(id.name.to_string());
             }
+            _ => {}
         }
     }
 
-    let mut checker = VariableUsageChecker {
-        declared_vars: HashMap::new(),
-        used_vars: HashSet::new(),
-        imported_vars: HashMap::new(),
-        used_imports: HashSet::new(),
-        linter,
-    };
-
-    checker.visit_program(program);
-
-    // Report unused variables
-    for (name, span) in checker.declared_vars {
-        if !checker.used_vars.contains(&name) && !name.starts_with('_') {
-            checker.linter.add_error(
-                "no-unused-variables".to_string(),
-                format!("Variable '{}' is declared but never used", name),
-                span,
-            );
+    fn on_program_end(&mut self, linter: &mut Linter, _program: &'a Program<'a>) {
+        // Report unused variables
+        for (name, span) in &self.declared_vars {
+            if !self.referenced.contains(name) && !name.starts_with('_') {
+                linter.add_error(
+                    "no-unused-variables".to_string(),
+                    format!("Variable '{}' is declared but never used", name),
+                    *span,
+                );
+            }
         }
-    }
 
-    // Report unused imports
-    for (name, span) in checker.imported_vars {
-        if !checker.used_imports.contains(&name) && !name.starts_with('_') {
-            checker.linter.add_error(
-                "no-unused-imports".to_string(),
-                format!("Import '{}' is declared but never used", name),
-                span,
-            );
+        // Report unused imports
+        for (name, span) in &self.imported_vars {
+            if !self.referenced.contains(name) && !name.starts_with('_') {
+                linter.add_error(
+                    "no-unused-imports".to_string(),
+                    format!("Import '{}' is declared but never used", name),
+                    *span,
+                );
+            }
         }
     }
 }
 
+pub fn check_no_unused_variables(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NoUnusedVariables::default());
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
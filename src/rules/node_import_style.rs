@@ -1,5 +1,7 @@
 use oxc::ast::ast::*;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
 // Common Node.js built-in modules
@@ -57,75 +59,71 @@ const PREFER_PROMISES: &[(&str, &str)] = &[
     ("readline", "readline/promises"),
 ];
 
-pub fn check_node_import_style(linter: &mut Linter, program: &Program) {
-    use oxc::ast_visit::Visit;
+/// Requires the node: prefix, promise-based APIs and named imports for Node.js built-ins
+#[derive(Default)]
+pub struct NodeImportStyle;
 
-    struct NodeImportVisitor<'a, 'b> {
-        linter: &'a mut Linter,
-        _phantom: std::marker::PhantomData<&'b ()>,
+impl<'a> Rule<'a> for NodeImportStyle {
+    fn id(&self) -> &str {
+        "node-import-style"
     }
 
-    impl<'a, 'b> Visit<'b> for NodeImportVisitor<'a, 'b> {
-        fn visit_import_declaration(&mut self, import: &ImportDeclaration<'b>) {
-            let source = import.source.value.as_str();
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::ImportDeclaration(import) = kind else {
+            return;
+        };
+        let source = import.source.value.as_str();
+
+        // Check if it's a Node.js built-in without node: prefix
+        if NODE_BUILTINS.contains(&source) {
+            linter.add_error(
+                "node-import-style".to_string(),
+                format!(
+                    "Node.js built-in '{}' must be imported with 'node:' prefix. Use 'node:{}' instead",
+                    source, source
+                ),
+                import.span,
+            );
+        }
 
-            // Check if it's a Node.js built-in without node: prefix
-            if NODE_BUILTINS.contains(&source) {
-                self.linter.add_error(
+        // Check for modules that should use promises version
+        for (old, new) in PREFER_PROMISES {
+            if source == *old || source == format!("node:{}", old).as_str() {
+                linter.add_error(
                     "node-import-style".to_string(),
                     format!(
-                        "Node.js built-in '{}' must be imported with 'node:' prefix. Use 'node:{}' instead",
-                        source, source
+                        "Prefer promise-based API. Use 'node:{}' instead of '{}'",
+                        new, source
                     ),
                     import.span,
                 );
             }
+        }
 
-            // Check for modules that should use promises version
-            for (old, new) in PREFER_PROMISES {
-                if source == *old || source == format!("node:{}", old).as_str() {
-                    self.linter.add_error(
-                        "node-import-style".to_string(),
-                        format!(
-                            "Prefer promise-based API. Use 'node:{}' instead of '{}'",
-                            new, source
-                        ),
-                        import.span,
-                    );
-                }
-            }
-
-            // Check for namespace imports from node: modules
-            if source.starts_with("node:") {
-                if let Some(specifiers) = &import.specifiers {
-                    for spec in specifiers {
-                        if matches!(
-                            spec,
-                            ImportDeclarationSpecifier::ImportNamespaceSpecifier(_)
-                        ) {
-                            self.linter.add_error(
-                                "node-import-style".to_string(),
-                                format!(
-                                    "Use named imports instead of namespace import from '{}'. Example: import {{ readFile }} from '{}'",
-                                    source, source
-                                ),
-                                import.span,
-                            );
-                            break;
-                        }
-                    }
-                }
+        // Check for namespace imports from node: modules
+        if source.starts_with("node:") {
+            let has_namespace = import.specifiers.iter().flatten().any(|spec| {
+                matches!(
+                    spec,
+                    ImportDeclarationSpecifier::ImportNamespaceSpecifier(_)
+                )
+            });
+            if has_namespace {
+                linter.add_error(
+                    "node-import-style".to_string(),
+                    format!(
+                        "Use named imports instead of namespace import from '{}'. Example: import {{ readFile }} from '{}'",
+                        source, source
+                    ),
+                    import.span,
+                );
             }
-
-            oxc::ast_visit::walk::walk_import_declaration(self, import);
         }
     }
+}
 
-    let mut visitor = NodeImportVisitor {
-        linter,
-        _phantom: std::marker::PhantomData,
-    };
-    visitor.visit_program(program);
+pub fn check_node_import_style(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, NodeImportStyle);
 }
 
 #[cfg(test)]
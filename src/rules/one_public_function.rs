@@ -1,68 +1,127 @@
+use std::collections::HashSet;
+
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 use oxc::ast::ast::*;
 
-pub fn check_one_public_function(linter: &mut Linter, program: &Program) {
-    let mut exported_functions = Vec::new();
-    let mut exported_other = Vec::new();
+/// Each file may export a single function and nothing else
+///
+/// Type-only exports, classes (see `no-classes`) and re-exports (see
+/// `no-reexports`) are not counted.
+#[derive(Default)]
+pub struct OnePublicFunction;
+
+fn is_function_init(init: &Expression) -> bool {
+    matches!(
+        init,
+        Expression::ArrowFunctionExpression(_) | Expression::FunctionExpression(_)
+    )
+}
 
+/// Names of top-level functions, used to classify `export { name }` lists
+fn local_function_names<'a>(program: &Program<'a>) -> HashSet<&'a str> {
+    let mut names = HashSet::new();
     for item in &program.body {
         match item {
-            Statement::ExportNamedDeclaration(export) => {
-                if let Some(Declaration::FunctionDeclaration(func)) = &export.declaration {
-                    if let Some(id) = &func.id {
-                        exported_functions.push((id.name.as_str(), export.span));
+            Statement::FunctionDeclaration(func) => {
+                if let Some(id) = &func.id {
+                    names.insert(id.name.as_str());
+                }
+            }
+            Statement::VariableDeclaration(var_decl) => {
+                for decl in &var_decl.declarations {
+                    if let (BindingPatternKind::BindingIdentifier(id), Some(init)) =
+                        (&decl.id.kind, &decl.init)
+                    {
+                        if is_function_init(init) {
+                            names.insert(id.name.as_str());
+                        }
                     }
-                } else if let Some(Declaration::VariableDeclaration(var_decl)) = &export.declaration
-                {
-                    for decl in &var_decl.declarations {
-                        if let BindingPatternKind::BindingIdentifier(id) = &decl.id.kind {
-                            if let Some(init) = &decl.init {
-                                if matches!(
-                                    init,
-                                    Expression::ArrowFunctionExpression(_)
-                                        | Expression::FunctionExpression(_)
-                                ) {
-                                    exported_functions.push((id.name.as_str(), export.span));
-                                } else {
-                                    exported_other.push((id.name.as_str(), export.span));
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+impl<'a> Rule<'a> for OnePublicFunction {
+    fn id(&self) -> &str {
+        "one-public-function"
+    }
+
+    fn on_program_end(&mut self, linter: &mut Linter, program: &'a Program<'a>) {
+        let local_functions = local_function_names(program);
+        let mut exported_functions = Vec::new();
+        let mut exported_other = Vec::new();
+
+        for item in &program.body {
+            match item {
+                Statement::ExportNamedDeclaration(export) => {
+                    if export.export_kind.is_type() || export.source.is_some() {
+                        continue;
+                    }
+                    match &export.declaration {
+                        Some(Declaration::FunctionDeclaration(func)) => {
+                            if let Some(id) = &func.id {
+                                exported_functions.push((id.name.as_str(), export.span));
+                            }
+                        }
+                        Some(Declaration::VariableDeclaration(var_decl)) => {
+                            for decl in &var_decl.declarations {
+                                if let BindingPatternKind::BindingIdentifier(id) = &decl.id.kind {
+                                    if let Some(init) = &decl.init {
+                                        if is_function_init(init) {
+                                            exported_functions
+                                                .push((id.name.as_str(), export.span));
+                                        } else {
+                                            exported_other.push((id.name.as_str(), export.span));
+                                        }
+                                    }
                                 }
                             }
                         }
+                        Some(declaration) if declaration.is_type() => {}
+                        Some(Declaration::ClassDeclaration(_)) => {}
+                        Some(_) => exported_other.push(("(declaration)", export.span)),
+                        None => {}
                     }
-                } else if export.declaration.is_some() {
-                    exported_other.push(("(declaration)", export.span));
-                }
 
-                for spec in &export.specifiers {
-                    exported_other.push((spec.exported.name().as_str(), export.span));
-                }
-            }
-            Statement::ExportDefaultDeclaration(export) => match &export.declaration {
-                ExportDefaultDeclarationKind::FunctionDeclaration(_) => {
-                    exported_functions.push(("default", export.span));
-                }
-                _ if export.declaration.is_expression() => {
-                    if let Some(expr) = export.declaration.as_expression() {
-                        if matches!(
-                            expr,
-                            Expression::ArrowFunctionExpression(_)
-                                | Expression::FunctionExpression(_)
-                        ) {
-                            exported_functions.push(("default", export.span));
+                    for spec in &export.specifiers {
+                        if spec.export_kind.is_type() {
+                            continue;
+                        }
+                        let name = spec.exported.name();
+                        if local_functions.contains(spec.local.name().as_str()) {
+                            exported_functions.push((name.as_str(), export.span));
                         } else {
-                            exported_other.push(("default", export.span));
+                            exported_other.push((name.as_str(), export.span));
                         }
                     }
                 }
-                _ => {
-                    exported_other.push(("default", export.span));
-                }
-            },
-            _ => {}
+                Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+                    ExportDefaultDeclarationKind::FunctionDeclaration(_) => {
+                        exported_functions.push(("default", export.span));
+                    }
+                    _ if export.declaration.is_expression() => {
+                        if let Some(expr) = export.declaration.as_expression() {
+                            if is_function_init(expr) {
+                                exported_functions.push(("default", export.span));
+                            } else {
+                                exported_other.push(("default", export.span));
+                            }
+                        }
+                    }
+                    ExportDefaultDeclarationKind::TSInterfaceDeclaration(_)
+                    | ExportDefaultDeclarationKind::ClassDeclaration(_) => {}
+                    _ => {
+                        exported_other.push(("default", export.span));
+                    }
+                },
+                _ => {}
+            }
         }
-    }
 
-    if !exported_other.is_empty() {
         for (name, span) in &exported_other {
             linter.add_error(
                 "one-public-function".to_string(),
@@ -73,22 +132,26 @@ pub fn check_one_public_function(linter: &mut Linter, program: &Program) {
                 *span,
             );
         }
-    }
 
-    if exported_functions.len() > 1 {
-        for (name, span) in &exported_functions[1..] {
-            linter.add_error(
-                "one-public-function".to_string(),
-                format!(
-                    "Only one function can be exported per file. Found additional export: {}",
-                    name
-                ),
-                *span,
-            );
+        if exported_functions.len() > 1 {
+            for (name, span) in &exported_functions[1..] {
+                linter.add_error(
+                    "one-public-function".to_string(),
+                    format!(
+                        "Only one function can be exported per file. Found additional export: {}",
+                        name
+                    ),
+                    *span,
+                );
+            }
         }
     }
 }
 
+pub fn check_one_public_function(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, OnePublicFunction);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -185,4 +248,14 @@ mod tests {
         assert_eq!(errors.len(), 1);
         assert!(errors.contains(&"one-public-function".to_string()));
     }
+
+    #[test]
+    fn test_class_export_not_counted() {
+        let source = r#"
+            export class NotFoundError extends Error {}
+        "#;
+
+        let errors = parse_and_check(source);
+        assert!(errors.is_empty());
+    }
 }
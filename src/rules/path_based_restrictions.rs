@@ -1,6 +1,9 @@
 use oxc::ast::ast::*;
 use oxc::span::Span;
+use std::path::Path;
 
+use crate::directories::{self, in_directory};
+use crate::rule::{run_rule, Rule};
 use crate::{Linter, TestRunner};
 
 /// Check path-based restrictions for TypeScript files
@@ -10,16 +13,33 @@ use crate::{Linter, TestRunner};
 /// - pure/**/*.ts: Pure functions only, export function name must match filename
 /// - types/**/*.ts: Only one type export allowed, must match filename
 /// - *.test.ts or *_test.ts: Must import function with same name (minus test suffix)
-pub fn check_path_based_restrictions(linter: &mut Linter, program: &Program, file_path: &str) {
-    let normalized_path = file_path.replace('\\', "/");
+#[derive(Default)]
+pub struct PathBasedRestrictions;
 
+impl<'a> Rule<'a> for PathBasedRestrictions {
+    fn id(&self) -> &str {
+        "path-based-restrictions"
+    }
+
+    fn on_program_start(&mut self, linter: &mut Linter, program: &'a Program<'a>) {
+        let normalized_path = directories::normalize(&linter.path);
+        check_path(linter, program, &normalized_path);
+    }
+}
+
+pub fn check_path_based_restrictions(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, PathBasedRestrictions);
+}
+
+fn check_path(linter: &mut Linter, program: &Program, normalized_path: &str) {
+    let path = Path::new(normalized_path);
     // Check test files first (they can be in any directory)
     if normalized_path.ends_with("_test.ts") || normalized_path.ends_with(".test.ts") {
         // Default to vitest if no test runner specified
         if linter.test_runner.is_none() {
             linter.test_runner = Some(crate::TestRunner::Vitest);
         }
-        check_test_file_imports(linter, program, &normalized_path);
+        check_test_file_imports(linter, program, normalized_path);
         return; // Test files don't need to follow other path restrictions
     }
 
@@ -34,22 +54,22 @@ pub fn check_path_based_restrictions(linter: &mut Linter, program: &Program, fil
     }
 
     // Check io/**/*.ts - async functions are optional (not required)
-    if normalized_path.contains("/io/") && normalized_path.ends_with(".ts") {
+    if in_directory(path, "io") && normalized_path.ends_with(".ts") {
         // Check io/errors/*.ts - custom error classes allowed
-        if normalized_path.contains("/io/errors/") {
-            check_error_class_definitions(linter, program, &normalized_path);
+        if in_directory(path, "io/errors") {
+            check_error_class_definitions(linter, program, normalized_path);
         }
         // No longer enforcing async-only, just allow both sync and async
     }
 
     // Check pure/**/*.ts - pure functions with filename match
-    if normalized_path.contains("/pure/") && normalized_path.ends_with(".ts") {
-        check_pure_functions(linter, program, &normalized_path);
+    if in_directory(path, "pure") && normalized_path.ends_with(".ts") {
+        check_pure_functions(linter, program, normalized_path);
     }
 
     // Check types/**/*.ts - single type export matching filename
-    if normalized_path.contains("/types/") && normalized_path.ends_with(".ts") {
-        check_type_definitions(linter, program, &normalized_path);
+    if in_directory(path, "types") && normalized_path.ends_with(".ts") {
+        check_type_definitions(linter, program, normalized_path);
     }
 }
 
@@ -57,9 +77,9 @@ pub fn check_path_based_restrictions(linter: &mut Linter, program: &Program, fil
 fn check_index_reexports_only(linter: &mut Linter, program: &Program) {
     for stmt in &program.body {
         match stmt {
-            Statement::ExportNamedDeclaration(export) => {
+            Statement::ExportNamedDeclaration(export)
                 // Check if it's a re-export (has source but no declaration)
-                if export.source.is_none() && export.declaration.is_some() {
+                if export.source.is_none() && export.declaration.is_some() => {
                     linter.add_error(
                         "path-based-restrictions".to_string(),
                         "index.ts files can only contain re-exports, not direct exports"
@@ -67,7 +87,6 @@ fn check_index_reexports_only(linter: &mut Linter, program: &Program) {
                         export.span,
                     );
                 }
-            }
             Statement::ExportDefaultDeclaration(export) => {
                 linter.add_error(
                     "path-based-restrictions".to_string(),
@@ -192,7 +211,8 @@ fn check_pure_functions(linter: &mut Linter, program: &Program, file_path: &str)
     // First, check that pure files don't import from io
     for stmt in &program.body {
         if let Statement::ImportDeclaration(import) = stmt {
-            let source = import.source.value.as_str();
+            // Import map aliases such as `@/io/` hide the directory
+            let source = linter.resolve_specifier(import.source.value.as_str());
             if source.contains("/io/") {
                 linter.add_error(
                     "path-based-restrictions".to_string(),
@@ -226,16 +246,15 @@ fn check_pure_functions(linter: &mut Linter, program: &Program, file_path: &str)
                     }
                 }
             }
-            Statement::FunctionDeclaration(func) => {
+            Statement::FunctionDeclaration(func)
                 // Non-exported functions in pure files should also not be async
-                if func.r#async {
+                if func.r#async => {
                     linter.add_error(
                         "path-based-restrictions".to_string(),
                         "Functions in pure/**/*.ts cannot be async".to_string(),
                         func.span,
                     );
                 }
-            }
             _ => {}
         }
     }
@@ -444,7 +463,8 @@ fn check_test_runner_imports(linter: &mut Linter, program: &Program, test_runner
     // Check all imports
     for stmt in &program.body {
         if let Statement::ImportDeclaration(import) = stmt {
-            let source = import.source.value.as_str();
+            let source = linter.resolve_specifier(import.source.value.as_str());
+            let source = source.as_ref();
 
             // Check if this import matches the specified test runner
             if test_runner.matches_import(source) {
@@ -525,7 +545,7 @@ mod tests {
         let parser_ret = Parser::new(&allocator, source, source_type).parse();
 
         let mut linter = Linter::new(Path::new(file_path), source, false);
-        check_path_based_restrictions(&mut linter, &parser_ret.program, file_path);
+        check_path_based_restrictions(&mut linter, &parser_ret.program);
 
         linter.errors.into_iter().map(|e| e.message).collect()
     }
@@ -1,20 +1,20 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 use oxc::span::Span;
 use std::collections::{HashMap, HashSet};
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-/// Track array variables and their mutating method calls
-pub struct ArrayMutabilityVisitor<'a> {
+/// Reports arrays that are never mutated and could be readonly
+#[derive(Default)]
+pub struct PreferReadonlyArray {
     // Track array variables (name -> span where declared)
     array_variables: HashMap<String, Span>,
     // Track which arrays have been mutated
     mutated_arrays: HashSet<String>,
     // Track which arrays are already readonly
     readonly_arrays: HashSet<String>,
-    // Reference to the linter
-    linter: &'a mut Linter,
 }
 
 // Mutating array methods that change the array in-place
@@ -30,152 +30,136 @@ const MUTATING_METHODS: &[&str] = &[
     "copyWithin",
 ];
 
-impl<'a> ArrayMutabilityVisitor<'a> {
-    pub fn new(linter: &'a mut Linter) -> Self {
-        Self {
-            array_variables: HashMap::new(),
-            mutated_arrays: HashSet::new(),
-            readonly_arrays: HashSet::new(),
-            linter,
-        }
-    }
-
-    pub fn check(&mut self, program: &'a Program<'a>) {
-        // First pass: collect array declarations
-        self.visit_program(program);
-
-        // Report arrays that could be readonly
-        for (name, span) in &self.array_variables {
-            if !self.mutated_arrays.contains(name) && !self.readonly_arrays.contains(name) {
-                self.linter.add_error(
-                    "prefer-readonly-array".to_string(),
-                    format!(
-                        "Array '{}' is never mutated. Consider using 'ReadonlyArray' or 'readonly' modifier",
-                        name
-                    ),
-                    *span,
-                );
+fn is_array_type(type_ann: &TSTypeAnnotation) -> bool {
+    match &type_ann.type_annotation {
+        TSType::TSArrayType(_) => true,
+        TSType::TSTypeReference(type_ref) => {
+            if let TSTypeName::IdentifierReference(id) = &type_ref.type_name {
+                id.name == "Array"
+            } else {
+                false
             }
         }
+        _ => false,
     }
+}
 
-    fn is_array_type(type_ann: &TSTypeAnnotation) -> bool {
-        match &type_ann.type_annotation {
-            TSType::TSArrayType(_) => true,
-            TSType::TSTypeReference(type_ref) => {
-                if let TSTypeName::IdentifierReference(id) = &type_ref.type_name {
-                    id.name == "Array"
-                } else {
-                    false
-                }
+fn is_readonly_array_type(type_ann: &TSTypeAnnotation) -> bool {
+    match &type_ann.type_annotation {
+        TSType::TSTypeReference(type_ref) => {
+            if let TSTypeName::IdentifierReference(id) = &type_ref.type_name {
+                id.name == "ReadonlyArray"
+            } else {
+                false
             }
-            _ => false,
         }
+        _ => false,
     }
+}
 
-    fn is_readonly_array_type(type_ann: &TSTypeAnnotation) -> bool {
-        match &type_ann.type_annotation {
-            TSType::TSTypeReference(type_ref) => {
-                if let TSTypeName::IdentifierReference(id) = &type_ref.type_name {
-                    id.name == "ReadonlyArray"
-                } else {
-                    false
-                }
+impl PreferReadonlyArray {
+    fn track_declarator(&mut self, decl: &VariableDeclarator) {
+        let BindingPatternKind::BindingIdentifier(id) = &decl.id.kind else {
+            return;
+        };
+        let var_name = id.name.to_string();
+
+        // Check if it has an array type annotation
+        if let Some(type_ann) = &decl.id.type_annotation {
+            if is_array_type(type_ann) {
+                self.array_variables.insert(var_name.clone(), decl.span);
+            } else if is_readonly_array_type(type_ann) {
+                self.readonly_arrays.insert(var_name.clone());
             }
-            _ => false,
         }
-    }
-}
-
-impl<'a> Visit<'a> for ArrayMutabilityVisitor<'a> {
-    fn visit_variable_declarator(&mut self, decl: &VariableDeclarator<'a>) {
-        // Check if it's an array declaration
-        if let BindingPatternKind::BindingIdentifier(id) = &decl.id.kind {
-            let var_name = id.name.to_string();
 
-            // Check if it has an array type annotation
-            if let Some(type_ann) = &decl.id.type_annotation {
-                if Self::is_array_type(type_ann) {
-                    self.array_variables.insert(var_name.clone(), decl.span);
-                } else if Self::is_readonly_array_type(type_ann) {
-                    self.readonly_arrays.insert(var_name.clone());
+        // Check if it's initialized with an array literal or Array constructor
+        match &decl.init {
+            Some(Expression::ArrayExpression(_))
+                // Only track if no type annotation (will infer as mutable array)
+                if decl.id.type_annotation.is_none() => {
+                    self.array_variables.insert(var_name, decl.span);
                 }
-            }
-
-            // Check if it's initialized with an array literal or Array constructor
-            if let Some(init) = &decl.init {
-                match init {
-                    Expression::ArrayExpression(_) => {
-                        // Only track if no type annotation (will infer as mutable array)
-                        if decl.id.type_annotation.is_none() {
-                            self.array_variables.insert(var_name.clone(), decl.span);
-                        }
+            Some(Expression::NewExpression(new_expr)) => {
+                if let Expression::Identifier(id) = &new_expr.callee {
+                    if id.name == "Array" {
+                        self.array_variables.insert(var_name, decl.span);
                     }
-                    Expression::NewExpression(new_expr) => {
-                        if let Expression::Identifier(id) = &new_expr.callee {
-                            if id.name == "Array" {
-                                self.array_variables.insert(var_name.clone(), decl.span);
-                            }
-                        }
-                    }
-                    Expression::CallExpression(call) => {
-                        // Check for Array.from, Array.of, etc.
-                        if let Expression::StaticMemberExpression(member) = &call.callee {
-                            if let Expression::Identifier(obj) = &member.object {
-                                if obj.name == "Array" {
-                                    self.array_variables.insert(var_name.clone(), decl.span);
-                                }
-                            }
+                }
+            }
+            Some(Expression::CallExpression(call)) => {
+                // Check for Array.from, Array.of, etc.
+                if let Expression::StaticMemberExpression(member) = &call.callee {
+                    if let Expression::Identifier(obj) = &member.object {
+                        if obj.name == "Array" {
+                            self.array_variables.insert(var_name, decl.span);
                         }
                     }
-                    _ => {}
                 }
             }
+            _ => {}
         }
-
-        oxc::ast_visit::walk::walk_variable_declarator(self, decl);
     }
+}
 
-    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
-        // Check for mutating method calls on tracked arrays
-        if let Some(MemberExpression::StaticMemberExpression(static_member)) =
-            call.callee.as_member_expression()
-        {
-            // Get the object being called on
-            if let Expression::Identifier(obj_id) = &static_member.object {
-                let obj_name = obj_id.name.to_string();
+impl<'a> Rule<'a> for PreferReadonlyArray {
+    fn id(&self) -> &str {
+        "prefer-readonly-array"
+    }
 
-                // Check if this is a tracked array and a mutating method
-                if self.array_variables.contains_key(&obj_name) {
-                    let method_name = static_member.property.name.as_str();
-                    if MUTATING_METHODS.contains(&method_name) {
-                        self.mutated_arrays.insert(obj_name);
+    fn enter_node(&mut self, _linter: &mut Linter, kind: AstKind<'a>) {
+        match kind {
+            AstKind::VariableDeclarator(decl) => self.track_declarator(decl),
+            AstKind::CallExpression(call) => {
+                // Check for mutating method calls on tracked arrays
+                if let Some(MemberExpression::StaticMemberExpression(static_member)) =
+                    call.callee.as_member_expression()
+                {
+                    if let Expression::Identifier(obj_id) = &static_member.object {
+                        let obj_name = obj_id.name.to_string();
+                        let method_name = static_member.property.name.as_str();
+                        if self.array_variables.contains_key(&obj_name)
+                            && MUTATING_METHODS.contains(&method_name)
+                        {
+                            self.mutated_arrays.insert(obj_name);
+                        }
+                    }
+                }
+            }
+            AstKind::AssignmentExpression(expr) => {
+                // Check for array element assignments like arr[0] = value
+                if let AssignmentTarget::ComputedMemberExpression(member) = &expr.left {
+                    if let Expression::Identifier(id) = &member.object {
+                        let name = id.name.to_string();
+                        if self.array_variables.contains_key(&name) {
+                            self.mutated_arrays.insert(name);
+                        }
                     }
                 }
             }
+            _ => {}
         }
-
-        oxc::ast_visit::walk::walk_call_expression(self, call);
     }
 
-    fn visit_assignment_expression(&mut self, expr: &AssignmentExpression<'a>) {
-        // Check for array element assignments like arr[0] = value
-        if let AssignmentTarget::ComputedMemberExpression(member) = &expr.left {
-            if let Expression::Identifier(id) = &member.object {
-                let name = id.name.to_string();
-                if self.array_variables.contains_key(&name) {
-                    self.mutated_arrays.insert(name);
-                }
+    fn on_program_end(&mut self, linter: &mut Linter, _program: &'a Program<'a>) {
+        // Report arrays that could be readonly
+        for (name, span) in &self.array_variables {
+            if !self.mutated_arrays.contains(name) && !self.readonly_arrays.contains(name) {
+                linter.add_error(
+                    "prefer-readonly-array".to_string(),
+                    format!(
+                        "Array '{}' is never mutated. Consider using 'ReadonlyArray' or 'readonly' modifier",
+                        name
+                    ),
+                    *span,
+                );
             }
         }
-
-        oxc::ast_visit::walk::walk_assignment_expression(self, expr);
     }
 }
 
 pub fn check_prefer_readonly_array(linter: &mut Linter, program: &Program) {
-    let mut visitor = ArrayMutabilityVisitor::new(linter);
-    visitor.check(program);
+    run_rule(linter, program, PreferReadonlyArray::default());
 }
 
 #[cfg(test)]
@@ -189,7 +173,7 @@ mod tests {
 
     fn parse_and_check(source: &str) -> Vec<String> {
         let allocator = Allocator::default();
-        let source_type = SourceType::default();
+        let source_type = SourceType::ts();
         let ret = Parser::new(&allocator, source, source_type).parse();
 
         let mut linter = Linter::new(Path::new("test.ts"), source, false);
@@ -199,7 +183,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore] // This rule is now integrated into CombinedVisitor
     fn test_array_never_mutated() {
         let source = r#"
             const arr: number[] = [1, 2, 3];
@@ -277,7 +260,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore] // This rule is now integrated into CombinedVisitor
     fn test_multiple_arrays() {
         let source = r#"
             const mutable: number[] = [1, 2];
@@ -0,0 +1,283 @@
+use oxc::ast::ast::*;
+use oxc::ast::AstKind;
+use std::collections::HashSet;
+
+use crate::directories::in_directory;
+use crate::rule::{run_rule, Rule};
+use crate::Linter;
+
+/// Mutable collection types and their readonly counterparts
+const MUTABLE_COLLECTIONS: &[(&str, &str)] = &[("Map", "ReadonlyMap"), ("Set", "ReadonlySet")];
+
+fn has_mutable_member(signatures: &[TSSignature]) -> bool {
+    signatures.iter().any(|signature| match signature {
+        TSSignature::TSPropertySignature(prop) => !prop.readonly,
+        TSSignature::TSIndexSignature(index) => !index.readonly,
+        _ => false,
+    })
+}
+
+/// Names of object types declared in this file that have mutable members
+fn collect_mutable_object_types(program: &Program) -> HashSet<String> {
+    let mut names = HashSet::new();
+
+    let mut check_declaration = |decl: &Declaration| match decl {
+        Declaration::TSTypeAliasDeclaration(alias) => {
+            if let TSType::TSTypeLiteral(literal) = &alias.type_annotation {
+                if has_mutable_member(&literal.members) {
+                    names.insert(alias.id.name.to_string());
+                }
+            }
+        }
+        Declaration::TSInterfaceDeclaration(interface)
+            if has_mutable_member(&interface.body.body) =>
+        {
+            names.insert(interface.id.name.to_string());
+        }
+        _ => {}
+    };
+
+    for stmt in &program.body {
+        if let Statement::ExportNamedDeclaration(export) = stmt {
+            if let Some(decl) = &export.declaration {
+                check_declaration(decl);
+            }
+        } else if let Some(decl) = stmt.as_declaration() {
+            check_declaration(decl);
+        }
+    }
+
+    names
+}
+
+/// Check immutability of object shapes
+///
+/// Rules:
+/// - types/**/*.ts: interface and type literal members must be `readonly`
+/// - types/**/*.ts, pure/**/*.ts: use `ReadonlyMap`/`ReadonlySet` instead of `Map`/`Set` in types
+/// - pure/**/*.ts: object-typed function parameters must be wrapped in `Readonly<...>`
+#[derive(Default)]
+pub struct ReadonlyProperties {
+    is_types_file: bool,
+    is_pure_file: bool,
+    mutable_object_types: HashSet<String>,
+    // Nesting depth of `Readonly<...>` type arguments
+    readonly_depth: usize,
+}
+
+impl ReadonlyProperties {
+    fn report_mutable_member(linter: &mut Linter, name: &str, span: oxc::span::Span) {
+        linter.add_error(
+            "readonly-properties".to_string(),
+            format!(
+                "Property '{}' must be readonly. Use 'readonly {}' or wrap the type in Readonly<...>",
+                name, name
+            ),
+            span,
+        );
+    }
+
+    fn check_parameter_type(&self, linter: &mut Linter, name: &str, type_ann: &TSTypeAnnotation) {
+        let is_mutable_object = match &type_ann.type_annotation {
+            TSType::TSTypeLiteral(literal) => has_mutable_member(&literal.members),
+            TSType::TSTypeReference(type_ref) => match &type_ref.type_name {
+                TSTypeName::IdentifierReference(id) => {
+                    self.mutable_object_types.contains(id.name.as_str())
+                }
+                _ => false,
+            },
+            _ => false,
+        };
+
+        if is_mutable_object {
+            linter.add_error(
+                "readonly-properties".to_string(),
+                format!(
+                    "Parameter '{}' has a mutable object type. Wrap it in Readonly<...>",
+                    name
+                ),
+                type_ann.span,
+            );
+        }
+    }
+}
+
+impl<'a> Rule<'a> for ReadonlyProperties {
+    fn id(&self) -> &str {
+        "readonly-properties"
+    }
+
+    fn on_program_start(&mut self, linter: &mut Linter, program: &'a Program<'a>) {
+        self.is_types_file = in_directory(&linter.path, "types");
+        self.is_pure_file = in_directory(&linter.path, "pure");
+        if self.is_types_file || self.is_pure_file {
+            self.mutable_object_types = collect_mutable_object_types(program);
+        }
+    }
+
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        if !self.is_types_file && !self.is_pure_file {
+            return;
+        }
+
+        match kind {
+            AstKind::TSPropertySignature(prop)
+                if self.is_types_file && self.readonly_depth == 0 && !prop.readonly =>
+            {
+                let name = prop
+                    .key
+                    .static_name()
+                    .map(|name| name.to_string())
+                    .unwrap_or_else(|| "[computed]".to_string());
+                Self::report_mutable_member(linter, &name, prop.span);
+            }
+            AstKind::TSIndexSignature(index)
+                if self.is_types_file && self.readonly_depth == 0 && !index.readonly =>
+            {
+                Self::report_mutable_member(linter, "[index]", index.span);
+            }
+            AstKind::TSTypeReference(type_ref) => {
+                let TSTypeName::IdentifierReference(id) = &type_ref.type_name else {
+                    return;
+                };
+
+                if let Some((_, readonly)) = MUTABLE_COLLECTIONS
+                    .iter()
+                    .find(|(mutable, _)| id.name == *mutable)
+                {
+                    linter.add_error(
+                        "readonly-properties".to_string(),
+                        format!(
+                            "Mutable '{}' is not allowed in type positions. Use '{}' instead",
+                            id.name, readonly
+                        ),
+                        type_ref.span,
+                    );
+                }
+
+                if id.name == "Readonly" {
+                    self.readonly_depth += 1;
+                }
+            }
+            AstKind::FormalParameter(param) if self.is_pure_file => {
+                if let Some(type_ann) = &param.pattern.type_annotation {
+                    let name = match &param.pattern.kind {
+                        BindingPatternKind::BindingIdentifier(id) => id.name.as_str(),
+                        _ => "{...}",
+                    };
+                    self.check_parameter_type(linter, name, type_ann);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn leave_node(&mut self, _linter: &mut Linter, kind: AstKind<'a>) {
+        if let AstKind::TSTypeReference(type_ref) = kind {
+            if matches!(&type_ref.type_name, TSTypeName::IdentifierReference(id) if id.name == "Readonly")
+                && self.readonly_depth > 0
+            {
+                self.readonly_depth -= 1;
+            }
+        }
+    }
+}
+
+pub fn check_readonly_properties(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, ReadonlyProperties::default());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Linter;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+    use std::path::Path;
+
+    fn parse_and_check(source: &str, file_path: &str) -> Vec<String> {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path(file_path).unwrap();
+        let ret = Parser::new(&allocator, source, source_type).parse();
+
+        let mut linter = Linter::new(Path::new(file_path), source, false);
+        check_readonly_properties(&mut linter, &ret.program);
+
+        linter.errors.into_iter().map(|e| e.message).collect()
+    }
+
+    #[test]
+    fn test_mutable_members_in_types() {
+        let source = r#"
+export type User = {
+  readonly id: string;
+  name: string;
+  [key: string]: string;
+};
+"#;
+        let errors = parse_and_check(source, "src/types/User.ts");
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("Property 'name' must be readonly"));
+        assert!(errors[1].contains("Property '[index]' must be readonly"));
+    }
+
+    #[test]
+    fn test_readonly_members_and_wrapper() {
+        let source = r#"
+export type User = {
+  readonly id: string;
+  readonly tags: ReadonlySet<string>;
+};
+export type Config = Readonly<{ host: string; port: number }>;
+"#;
+        let errors = parse_and_check(source, "src/types/User.ts");
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_mutable_collections() {
+        let source = r#"
+export type Index = {
+  readonly byId: Map<string, number>;
+  readonly seen: Set<string>;
+};
+"#;
+        let errors = parse_and_check(source, "src/types/Index.ts");
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("Use 'ReadonlyMap' instead"));
+        assert!(errors[1].contains("Use 'ReadonlySet' instead"));
+    }
+
+    #[test]
+    fn test_pure_parameter_object_types() {
+        let source = r#"
+type Options = { scale: number };
+
+export function scale(point: { x: number }, options: Options): number {
+  return point.x * options.scale;
+}
+"#;
+        let errors = parse_and_check(source, "src/pure/scale.ts");
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("Parameter 'point' has a mutable object type"));
+        assert!(errors[1].contains("Parameter 'options' has a mutable object type"));
+
+        let source = r#"
+export function scale(point: Readonly<{ x: number }>, factor: number): number {
+  return point.x * factor;
+}
+"#;
+        let errors = parse_and_check(source, "src/pure/scale.ts");
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_other_directories_are_ignored() {
+        let source = r#"
+export type State = { count: number; cache: Map<string, number> };
+"#;
+        let errors = parse_and_check(source, "src/io/state.ts");
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+}
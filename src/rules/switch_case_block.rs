@@ -1,46 +1,49 @@
 use oxc::ast::ast::*;
-use oxc::ast_visit::walk;
-use oxc::ast_visit::Visit;
+use oxc::ast::AstKind;
 
+use crate::rule::{run_rule, Rule};
 use crate::Linter;
 
-pub fn check_switch_case_block(linter: &mut Linter, program: &Program) {
-    struct SwitchCaseBlockChecker<'a> {
-        linter: &'a mut Linter,
+/// Requires switch cases with statements to use a block
+///
+/// Empty fallthrough cases and a lone `break` are allowed.
+#[derive(Default)]
+pub struct SwitchCaseBlock;
+
+impl<'a> Rule<'a> for SwitchCaseBlock {
+    fn id(&self) -> &str {
+        "switch-case-block"
     }
 
-    impl<'a> Visit<'a> for SwitchCaseBlockChecker<'a> {
-        fn visit_switch_case(&mut self, case: &SwitchCase<'a>) {
-            // Skip default case or cases with no consequent
-            if case.consequent.is_empty() {
-                walk::walk_switch_case(self, case);
-                return;
-            }
-
-            // Check if the case has a block statement
-            let has_block = case.consequent.len() == 1
-                && matches!(case.consequent.first(), Some(Statement::BlockStatement(_)));
-
-            if !has_block {
-                // Check if it's just a break statement (which is allowed)
-                let only_break = case.consequent.len() == 1
-                    && matches!(case.consequent.first(), Some(Statement::BreakStatement(_)));
-
-                if !only_break {
-                    self.linter.add_error(
-                        "switch-case-block".to_string(),
-                        "Switch case must use block statement: case 'value': { ... }".to_string(),
-                        case.span,
-                    );
-                }
-            }
-
-            walk::walk_switch_case(self, case);
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        let AstKind::SwitchCase(case) = kind else {
+            return;
+        };
+        // Skip cases with no consequent
+        if case.consequent.is_empty() {
+            return;
+        }
+
+        // A block may be followed by statements such as break
+        let has_block = case
+            .consequent
+            .iter()
+            .any(|stmt| matches!(stmt, Statement::BlockStatement(_)));
+        let only_break = case.consequent.len() == 1
+            && matches!(case.consequent.first(), Some(Statement::BreakStatement(_)));
+
+        if !has_block && !only_break {
+            linter.add_error(
+                "switch-case-block".to_string(),
+                "Switch case must use block statement: case 'value': { ... }".to_string(),
+                case.span,
+            );
         }
     }
+}
 
-    let mut checker = SwitchCaseBlockChecker { linter };
-    checker.visit_program(program);
+pub fn check_switch_case_block(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, SwitchCaseBlock);
 }
 
 #[cfg(test)]
@@ -0,0 +1,818 @@
+use oxc::allocator::Allocator;
+use oxc::ast::ast::*;
+use oxc::ast::AstKind;
+use oxc::parser::Parser;
+use oxc::span::SourceType;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::directories::resolve_dots;
+use crate::rule::{run_rule, Rule};
+use crate::Linter;
+
+/// Maximum depth when following type aliases, interface extends and imports
+const MAX_RESOLVE_DEPTH: usize = 16;
+
+/// A type reference or an inline object type appearing in a union
+#[derive(Debug, Clone)]
+enum UnionMember {
+    Named(String),
+    Object(HashMap<String, String>),
+    Other,
+}
+
+/// Owned summary of a type declaration, detached from the AST allocator
+#[derive(Debug, Clone)]
+enum TypeDecl {
+    /// `type X = A | B | { kind: "c" }`
+    Alias(Vec<UnionMember>),
+    /// `interface X extends Y { kind: "x" }`
+    Interface {
+        extends: Vec<String>,
+        literal_props: HashMap<String, String>,
+    },
+    /// `import type { X } from "./types/X.ts"`
+    Imported { file: PathBuf, name: String },
+}
+
+/// Type declarations visible in a single file
+#[derive(Debug, Default)]
+struct TypeEnv {
+    decls: HashMap<String, TypeDecl>,
+}
+
+impl TypeEnv {
+    fn from_program(program: &Program, file_path: &Path) -> Self {
+        let mut env = Self::default();
+        let base_dir = file_path.parent().unwrap_or(Path::new("."));
+
+        for stmt in &program.body {
+            match stmt {
+                Statement::TSTypeAliasDeclaration(alias) => env.add_alias(alias),
+                Statement::TSInterfaceDeclaration(decl) => env.add_interface(decl),
+                Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                    Some(Declaration::TSTypeAliasDeclaration(alias)) => env.add_alias(alias),
+                    Some(Declaration::TSInterfaceDeclaration(decl)) => env.add_interface(decl),
+                    _ => {}
+                },
+                Statement::ImportDeclaration(import) => {
+                    let source = import.source.value.as_str();
+                    if !is_types_import(source) {
+                        continue;
+                    }
+                    let file = resolve_dots(&base_dir.join(source));
+                    if let Some(specifiers) = &import.specifiers {
+                        for spec in specifiers {
+                            if let ImportDeclarationSpecifier::ImportSpecifier(named) = spec {
+                                env.decls.insert(
+                                    named.local.name.to_string(),
+                                    TypeDecl::Imported {
+                                        file: file.clone(),
+                                        name: named.imported.name().to_string(),
+                                    },
+                                );
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        env
+    }
+
+    fn add_alias(&mut self, alias: &TSTypeAliasDeclaration) {
+        let mut members = Vec::new();
+        collect_union_members(&alias.type_annotation, &mut members);
+        self.decls
+            .insert(alias.id.name.to_string(), TypeDecl::Alias(members));
+    }
+
+    fn add_interface(&mut self, decl: &TSInterfaceDeclaration) {
+        let extends = decl
+            .extends
+            .iter()
+            .filter_map(|heritage| match &heritage.expression {
+                Expression::Identifier(id) => Some(id.name.to_string()),
+                _ => None,
+            })
+            .collect();
+        self.decls.insert(
+            decl.id.name.to_string(),
+            TypeDecl::Interface {
+                extends,
+                literal_props: literal_props(&decl.body.body),
+            },
+        );
+    }
+}
+
+/// Only relative imports that point into a `types/` directory are followed
+fn is_types_import(source: &str) -> bool {
+    source.starts_with('.')
+        && source.contains("/types/")
+        && (source.ends_with(".ts") || source.ends_with(".tsx"))
+}
+
+fn collect_union_members(ts_type: &TSType, members: &mut Vec<UnionMember>) {
+    match ts_type {
+        TSType::TSUnionType(union) => {
+            for member in &union.types {
+                collect_union_members(member, members);
+            }
+        }
+        TSType::TSParenthesizedType(paren) => {
+            collect_union_members(&paren.type_annotation, members);
+        }
+        TSType::TSTypeReference(type_ref) => match &type_ref.type_name {
+            TSTypeName::IdentifierReference(id) => {
+                members.push(UnionMember::Named(id.name.to_string()))
+            }
+            _ => members.push(UnionMember::Other),
+        },
+        TSType::TSTypeLiteral(literal) => {
+            members.push(UnionMember::Object(literal_props(&literal.members)));
+        }
+        _ => members.push(UnionMember::Other),
+    }
+}
+
+/// Collect properties whose type is a single literal, e.g. `kind: "circle"`
+fn literal_props(signatures: &[TSSignature]) -> HashMap<String, String> {
+    let mut props = HashMap::new();
+    for signature in signatures {
+        if let TSSignature::TSPropertySignature(prop) = signature {
+            let Some(name) = prop.key.static_name() else {
+                continue;
+            };
+            let Some(type_ann) = &prop.type_annotation else {
+                continue;
+            };
+            if let TSType::TSLiteralType(literal) = &type_ann.type_annotation {
+                if let Some(value) = ts_literal_value(&literal.literal) {
+                    props.insert(name.to_string(), value);
+                }
+            }
+        }
+    }
+    props
+}
+
+fn ts_literal_value(literal: &TSLiteral) -> Option<String> {
+    match literal {
+        TSLiteral::StringLiteral(lit) => Some(format!("'{}'", lit.value)),
+        TSLiteral::NumericLiteral(lit) => Some(lit.value.to_string()),
+        TSLiteral::BooleanLiteral(lit) => Some(lit.value.to_string()),
+        _ => None,
+    }
+}
+
+fn case_literal_value(expr: &Expression) -> Option<String> {
+    match expr {
+        Expression::StringLiteral(lit) => Some(format!("'{}'", lit.value)),
+        Expression::NumericLiteral(lit) => Some(lit.value.to_string()),
+        Expression::BooleanLiteral(lit) => Some(lit.value.to_string()),
+        Expression::TemplateLiteral(tpl) if tpl.expressions.is_empty() => tpl
+            .quasis
+            .first()
+            .and_then(|q| q.value.cooked.as_ref())
+            .map(|cooked| format!("'{}'", cooked)),
+        _ => None,
+    }
+}
+
+/// Resolves type names to the set of tag values of a discriminated union,
+/// loading imported `types/` files on demand
+struct TypeResolver {
+    envs: HashMap<PathBuf, TypeEnv>,
+    /// Files read besides the linted one, including unreadable ones
+    read: BTreeSet<PathBuf>,
+}
+
+impl TypeResolver {
+    fn new(root_file: PathBuf, root_env: TypeEnv) -> Self {
+        let mut envs = HashMap::new();
+        envs.insert(root_file, root_env);
+        Self {
+            envs,
+            read: BTreeSet::new(),
+        }
+    }
+
+    fn load(&mut self, file: &Path) -> bool {
+        if self.envs.contains_key(file) {
+            return true;
+        }
+        self.read.insert(file.to_path_buf());
+        let Ok(source_text) = std::fs::read_to_string(file) else {
+            return false;
+        };
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path(file).unwrap_or_default();
+        let ret = Parser::new(&allocator, &source_text, source_type).parse();
+        let env = TypeEnv::from_program(&ret.program, file);
+        self.envs.insert(file.to_path_buf(), env);
+        true
+    }
+
+    fn lookup(&mut self, file: &Path, name: &str, depth: usize) -> Option<(PathBuf, TypeDecl)> {
+        if depth > MAX_RESOLVE_DEPTH || !self.load(file) {
+            return None;
+        }
+        match self.envs.get(file)?.decls.get(name)?.clone() {
+            TypeDecl::Imported { file: target, name } => self.lookup(&target, &name, depth + 1),
+            decl => Some((file.to_path_buf(), decl)),
+        }
+    }
+
+    /// Returns the tag values of every union member, or `None` when any
+    /// member cannot be resolved to an object type with a literal `tag`
+    fn tag_values(&mut self, file: &Path, type_name: &str, tag: &str) -> Option<BTreeSet<String>> {
+        let mut values = BTreeSet::new();
+        self.collect_tag_values(
+            file,
+            &UnionMember::Named(type_name.to_string()),
+            tag,
+            &mut values,
+            0,
+        )?;
+        // A single object type is not a union worth checking
+        if values.len() < 2 {
+            return None;
+        }
+        Some(values)
+    }
+
+    fn collect_tag_values(
+        &mut self,
+        file: &Path,
+        member: &UnionMember,
+        tag: &str,
+        values: &mut BTreeSet<String>,
+        depth: usize,
+    ) -> Option<()> {
+        if depth > MAX_RESOLVE_DEPTH {
+            return None;
+        }
+        match member {
+            UnionMember::Object(props) => {
+                values.insert(props.get(tag)?.clone());
+                Some(())
+            }
+            UnionMember::Named(name) => {
+                let (decl_file, decl) = self.lookup(file, name, depth)?;
+                match decl {
+                    TypeDecl::Alias(members) => {
+                        for member in &members {
+                            self.collect_tag_values(&decl_file, member, tag, values, depth + 1)?;
+                        }
+                        Some(())
+                    }
+                    TypeDecl::Interface { .. } => {
+                        let value = self.interface_tag(&decl_file, name, tag, depth + 1)?;
+                        values.insert(value);
+                        Some(())
+                    }
+                    TypeDecl::Imported { .. } => None,
+                }
+            }
+            UnionMember::Other => None,
+        }
+    }
+
+    fn interface_tag(
+        &mut self,
+        file: &Path,
+        name: &str,
+        tag: &str,
+        depth: usize,
+    ) -> Option<String> {
+        let (decl_file, decl) = self.lookup(file, name, depth)?;
+        match decl {
+            TypeDecl::Interface {
+                extends,
+                literal_props,
+            } => {
+                if let Some(value) = literal_props.get(tag) {
+                    return Some(value.clone());
+                }
+                extends
+                    .iter()
+                    .find_map(|parent| self.interface_tag(&decl_file, parent, tag, depth + 1))
+            }
+            TypeDecl::Alias(members) => match members.as_slice() {
+                [UnionMember::Object(props)] => props.get(tag).cloned(),
+                [UnionMember::Named(inner)] => {
+                    let inner = inner.clone();
+                    self.interface_tag(&decl_file, &inner, tag, depth + 1)
+                }
+                _ => None,
+            },
+            TypeDecl::Imported { .. } => None,
+        }
+    }
+}
+
+/// Check that `switch (x.kind)` over a discriminated union ends with
+/// `default: assertNever(x)`, so that the compiler reports missing variants,
+/// and that it handles every variant when it does not
+///
+/// The type of `x` is taken from its parameter or variable annotation and
+/// resolved through type aliases and interfaces declared in the same file
+/// or in an imported `types/` file. Switches with a case that is not a
+/// literal (e.g. `case Kind.A:`) are not checked.
+#[derive(Default)]
+pub struct SwitchExhaustive {
+    file_path: PathBuf,
+    resolver: Option<TypeResolver>,
+    // Declared type names of bindings, one map per function or block scope.
+    // `None` marks an unannotated binding that shadows an outer one.
+    scopes: Vec<Scope>,
+}
+
+#[derive(Default)]
+struct Scope {
+    /// Whether `var` declarations are hoisted to this scope
+    is_function: bool,
+    bindings: HashMap<String, Option<String>>,
+}
+
+impl Scope {
+    fn function() -> Self {
+        Self {
+            is_function: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// Nodes that open a scope for `let`, `const` and parameters
+fn scope_kind(kind: &AstKind) -> Option<Scope> {
+    match kind {
+        AstKind::Function(_) | AstKind::ArrowFunctionExpression(_) | AstKind::StaticBlock(_) => {
+            Some(Scope::function())
+        }
+        AstKind::BlockStatement(_)
+        | AstKind::ForStatement(_)
+        | AstKind::ForInStatement(_)
+        | AstKind::ForOfStatement(_)
+        | AstKind::CatchClause(_)
+        | AstKind::SwitchStatement(_) => Some(Scope::default()),
+        _ => None,
+    }
+}
+
+impl SwitchExhaustive {
+    fn declare(&mut self, pattern: &BindingPattern, hoisted: bool) {
+        let BindingPatternKind::BindingIdentifier(id) = &pattern.kind else {
+            return;
+        };
+        let type_name =
+            pattern
+                .type_annotation
+                .as_ref()
+                .and_then(|ann| match &ann.type_annotation {
+                    TSType::TSTypeReference(type_ref) => match &type_ref.type_name {
+                        TSTypeName::IdentifierReference(name) => Some(name.name.to_string()),
+                        _ => None,
+                    },
+                    _ => None,
+                });
+        let scope = if hoisted {
+            self.scopes.iter_mut().rev().find(|scope| scope.is_function)
+        } else {
+            self.scopes.last_mut()
+        };
+        if let Some(scope) = scope {
+            scope.bindings.insert(id.name.to_string(), type_name);
+        }
+    }
+
+    fn declared_type(&self, name: &str) -> Option<String> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.bindings.get(name))
+            .cloned()
+            .flatten()
+    }
+
+    fn check_switch(&mut self, linter: &mut Linter, stmt: &SwitchStatement) {
+        let Expression::StaticMemberExpression(member) = &stmt.discriminant else {
+            return;
+        };
+        let Expression::Identifier(object) = &member.object else {
+            return;
+        };
+        let Some(type_name) = self.declared_type(object.name.as_str()) else {
+            return;
+        };
+        let tag = member.property.name.as_str();
+        let Some(resolver) = &mut self.resolver else {
+            return;
+        };
+        let Some(expected) = resolver.tag_values(&self.file_path, &type_name, tag) else {
+            return;
+        };
+
+        let mut covered = HashSet::new();
+        let mut default_case = None;
+        for case in &stmt.cases {
+            match &case.test {
+                // A constant or enum member could be any value
+                Some(test) => match case_literal_value(test) {
+                    Some(value) => {
+                        covered.insert(value);
+                    }
+                    None => return,
+                },
+                None => default_case = Some(case),
+            }
+        }
+
+        let discriminant = format!("{}.{}", object.name, tag);
+        // With assertNever(x) in the default, the compiler reports missing cases
+        let has_assert_never = default_case
+            .is_some_and(|case| calls_assert_never(&case.consequent, object.name.as_str()));
+        let missing: Vec<&str> = expected
+            .iter()
+            .filter(|value| !covered.contains(*value))
+            .map(|value| value.as_str())
+            .collect();
+        if !missing.is_empty() && !has_assert_never {
+            linter.add_error(
+                "switch-exhaustive".to_string(),
+                format!(
+                    "Switch on '{}' is not exhaustive. Missing cases: {}",
+                    discriminant,
+                    missing.join(", ")
+                ),
+                stmt.span,
+            );
+        }
+
+        // Without it, adding a member later would go unnoticed
+        if !has_assert_never {
+            linter.add_error(
+                "switch-exhaustive".to_string(),
+                format!(
+                    "Switch on '{}' must end with 'default: assertNever({})'",
+                    discriminant, object.name
+                ),
+                default_case.map_or(stmt.span, |case| case.span),
+            );
+        }
+    }
+}
+
+impl<'a> Rule<'a> for SwitchExhaustive {
+    fn id(&self) -> &str {
+        "switch-exhaustive"
+    }
+
+    fn on_program_start(&mut self, linter: &mut Linter, program: &'a Program<'a>) {
+        self.file_path = linter.path.clone();
+        let env = TypeEnv::from_program(program, &self.file_path);
+        self.resolver = Some(TypeResolver::new(self.file_path.clone(), env));
+        self.scopes = vec![Scope::function()];
+    }
+
+    fn enter_node(&mut self, linter: &mut Linter, kind: AstKind<'a>) {
+        // The discriminant is evaluated outside the switch's own scope
+        if let AstKind::SwitchStatement(stmt) = kind {
+            self.check_switch(linter, stmt);
+        }
+        if let Some(scope) = scope_kind(&kind) {
+            self.scopes.push(scope);
+        }
+        match kind {
+            AstKind::FormalParameter(param) => self.declare(&param.pattern, false),
+            AstKind::VariableDeclarator(decl) => {
+                self.declare(&decl.id, decl.kind == VariableDeclarationKind::Var)
+            }
+            _ => {}
+        }
+    }
+
+    fn leave_node(&mut self, _linter: &mut Linter, kind: AstKind<'a>) {
+        if scope_kind(&kind).is_some() {
+            self.scopes.pop();
+        }
+    }
+
+    fn on_program_end(&mut self, linter: &mut Linter, _program: &'a Program<'a>) {
+        if let Some(resolver) = self.resolver.take() {
+            linter.dependencies.extend(resolver.read);
+        }
+    }
+}
+
+pub fn check_switch_exhaustive(linter: &mut Linter, program: &Program) {
+    run_rule(linter, program, SwitchExhaustive::default());
+}
+
+/// Whether the default case calls `assertNever(x)` or `assertNever(x.tag)`
+fn calls_assert_never(consequent: &[Statement], binding: &str) -> bool {
+    consequent.iter().any(|stmt| match stmt {
+        Statement::BlockStatement(block) => calls_assert_never(&block.body, binding),
+        Statement::ExpressionStatement(expr) => is_assert_never_call(&expr.expression, binding),
+        Statement::ReturnStatement(ret) => ret
+            .argument
+            .as_ref()
+            .is_some_and(|arg| is_assert_never_call(arg, binding)),
+        Statement::ThrowStatement(throw) => is_assert_never_call(&throw.argument, binding),
+        _ => false,
+    })
+}
+
+fn is_assert_never_call(expr: &Expression, binding: &str) -> bool {
+    let Expression::CallExpression(call) = expr else {
+        return false;
+    };
+    let Expression::Identifier(callee) = &call.callee else {
+        return false;
+    };
+    if callee.name != "assertNever" {
+        return false;
+    }
+    call.arguments.first().is_some_and(|arg| match arg {
+        Argument::Identifier(id) => id.name == binding,
+        Argument::StaticMemberExpression(member) => {
+            matches!(&member.object, Expression::Identifier(id) if id.name == binding)
+        }
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Linter;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+    use std::path::Path;
+
+    fn parse_and_check(source: &str, file_path: &Path) -> Vec<String> {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path(file_path).unwrap();
+        let ret = Parser::new(&allocator, source, source_type).parse();
+
+        let mut linter = Linter::new(file_path, source, false);
+        check_switch_exhaustive(&mut linter, &ret.program);
+
+        linter.errors.into_iter().map(|e| e.message).collect()
+    }
+
+    const SHAPE: &str = r#"
+type Circle = { kind: "circle"; radius: number };
+type Square = { kind: "square"; size: number };
+type Shape = Circle | Square | { kind: "triangle"; base: number };
+"#;
+
+    #[test]
+    fn test_missing_case() {
+        let source = format!(
+            "{}{}",
+            SHAPE,
+            r#"
+export function area(shape: Shape): number {
+  switch (shape.kind) {
+    case "circle": {
+      return shape.radius;
+    }
+    case "square": {
+      return shape.size;
+    }
+  }
+  return 0;
+}
+"#
+        );
+        let errors = parse_and_check(&source, Path::new("src/pure/area.ts"));
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("Missing cases: 'triangle'"));
+        assert!(errors[1].contains("must end with 'default: assertNever(shape)'"));
+    }
+
+    #[test]
+    fn test_assert_never_default_leaves_missing_cases_to_the_compiler() {
+        let source = format!(
+            "{}{}",
+            SHAPE,
+            r#"
+export function area(shape: Shape): number {
+  switch (shape.kind) {
+    case "circle": {
+      return shape.radius;
+    }
+    default: {
+      return assertNever(shape);
+    }
+  }
+}
+"#
+        );
+        let errors = parse_and_check(&source, Path::new("src/pure/area.ts"));
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_all_cases_without_assert_never() {
+        let source = format!(
+            "{}{}",
+            SHAPE,
+            r#"
+export function area(shape: Shape): number {
+  switch (shape.kind) {
+    case "circle": {
+      return shape.radius;
+    }
+    case "square": {
+      return shape.size;
+    }
+    case "triangle": {
+      return shape.base;
+    }
+  }
+}
+"#
+        );
+        let errors = parse_and_check(&source, Path::new("src/pure/area.ts"));
+        assert_eq!(
+            errors,
+            vec!["Switch on 'shape.kind' must end with 'default: assertNever(shape)'"]
+        );
+    }
+
+    #[test]
+    fn test_default_without_assert_never() {
+        let source = format!(
+            "{}{}",
+            SHAPE,
+            r#"
+export function area(shape: Shape): number {
+  switch (shape.kind) {
+    case "circle": {
+      return shape.radius;
+    }
+    case "square": {
+      return shape.size;
+    }
+    case "triangle": {
+      return shape.base;
+    }
+    default: {
+      return 0;
+    }
+  }
+}
+"#
+        );
+        let errors = parse_and_check(&source, Path::new("src/pure/area.ts"));
+        assert_eq!(
+            errors,
+            vec!["Switch on 'shape.kind' must end with 'default: assertNever(shape)'"]
+        );
+    }
+
+    #[test]
+    fn test_exhaustive_switch() {
+        let source = r#"
+interface Base { readonly id: string }
+interface Ok extends Base { kind: "ok"; value: number }
+interface Err extends Base { kind: "err"; error: string }
+type Result = Ok | Err;
+
+export function unwrap(result: Result): number {
+  switch (result.kind) {
+    case "ok": {
+      return result.value;
+    }
+    case "err": {
+      return 0;
+    }
+    default: {
+      return assertNever(result);
+    }
+  }
+}
+"#;
+        let errors = parse_and_check(source, Path::new("src/pure/unwrap.ts"));
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_ignores_non_union_and_shadowed() {
+        let source = r#"
+type Shape = { kind: "circle" } | { kind: "square" };
+
+export function check(shape: Shape, label: string): number {
+  switch (label.length) {
+    case 1: {
+      return 1;
+    }
+  }
+  const inner = (shape) => {
+    switch (shape.kind) {
+      case "circle": {
+        return 1;
+      }
+    }
+    return 0;
+  };
+  return inner(shape);
+}
+"#;
+        let errors = parse_and_check(source, Path::new("src/pure/check.ts"));
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_block_scoped_shadowing() {
+        let source = r#"
+type Shape = { kind: "circle" } | { kind: "square" };
+
+export function check(shape: Shape, other: Shape): number {
+  if (shape.kind === "circle") {
+    const shape = { kind: "x" };
+    switch (shape.kind) {
+      case "x": {
+        return 1;
+      }
+    }
+  }
+  {
+    const other: Shape = shape;
+  }
+  switch (shape.kind) {
+    case "circle": {
+      return 2;
+    }
+  }
+  return 0;
+}
+"#;
+        let errors = parse_and_check(source, Path::new("src/pure/check.ts"));
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors[0].contains("Switch on 'shape.kind'"));
+        assert!(errors[0].contains("Missing cases: 'square'"));
+    }
+
+    #[test]
+    fn test_skips_non_literal_cases() {
+        let source = r#"
+type Shape = { kind: "circle" } | { kind: "square" };
+const CIRCLE = "circle";
+
+export function check(shape: Shape): number {
+  switch (shape.kind) {
+    case CIRCLE: {
+      return 1;
+    }
+    case Kind.Square: {
+      return 2;
+    }
+  }
+  return 0;
+}
+"#;
+        let errors = parse_and_check(source, Path::new("src/pure/check.ts"));
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_resolves_imported_types_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let types_dir = dir.path().join("src/types");
+        let pure_dir = dir.path().join("src/pure");
+        std::fs::create_dir_all(&types_dir).unwrap();
+        std::fs::create_dir_all(&pure_dir).unwrap();
+        std::fs::write(
+            types_dir.join("Event.ts"),
+            r#"
+type Click = { type: "click"; x: number };
+type Key = { type: "key"; code: string };
+export type Event = Click | Key;
+"#,
+        )
+        .unwrap();
+
+        let source = r#"
+import type { Event } from "../types/Event.ts";
+
+export function describe(event: Event): string {
+  switch (event.type) {
+    case "click": {
+      return "click";
+    }
+  }
+  return "";
+}
+"#;
+        let errors = parse_and_check(source, &pure_dir.join("describe.ts"));
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("Missing cases: 'key'"));
+    }
+}
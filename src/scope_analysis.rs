@@ -0,0 +1,605 @@
+//! Lightweight lexical scope analysis
+//!
+//! Resolves writes, mutations and calls to the binding they refer to, so
+//! rules can tell a reassigned `let`, a mutated parameter or a call to an
+//! imported function apart from a shadowing declaration with the same name
+//! in a nested block or closure.
+
+use oxc::ast::ast::*;
+use oxc::ast_visit::walk;
+use oxc::ast_visit::Visit;
+use oxc::span::Span;
+use oxc::syntax::scope::ScopeFlags;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Array methods that mutate the receiver in place
+pub const MUTATING_ARRAY_METHODS: &[&str] = &[
+    "push",
+    "pop",
+    "shift",
+    "unshift",
+    "splice",
+    "sort",
+    "reverse",
+    "fill",
+    "copyWithin",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BindingKind {
+    Let,
+    Parameter,
+    Other,
+}
+
+#[derive(Debug)]
+struct Binding {
+    kind: BindingKind,
+    span: Span,
+    reassigned: bool,
+}
+
+/// How a parameter was mutated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MutationKind {
+    Reassignment,
+    PropertyAssignment,
+    Delete,
+    Update,
+    MutatingMethod(String),
+}
+
+impl fmt::Display for MutationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Reassignment => write!(f, "reassignment"),
+            Self::PropertyAssignment => write!(f, "property assignment"),
+            Self::Delete => write!(f, "delete"),
+            Self::Update => write!(f, "increment/decrement"),
+            Self::MutatingMethod(method) => write!(f, "'{}()' call", method),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Access {
+    /// The binding itself is written (`x = 1`, `x++`, `[x] = arr`)
+    Write(MutationKind),
+    /// Something reachable from the binding is mutated (`x.a = 1`, `x.push(1)`)
+    Mutation(MutationKind),
+    /// The binding is called (`x()`)
+    Call,
+}
+
+#[derive(Debug)]
+struct PendingAccess {
+    name: String,
+    span: Span,
+    access: Access,
+}
+
+#[derive(Debug, Default)]
+struct Scope {
+    is_function: bool,
+    bindings: HashMap<String, Binding>,
+    pending: Vec<PendingAccess>,
+}
+
+/// A `let` binding and whether it is ever reassigned
+#[derive(Debug)]
+pub struct LetBinding {
+    pub name: String,
+    pub span: Span,
+    pub reassigned: bool,
+}
+
+/// A write or mutation that resolves to a function parameter
+#[derive(Debug)]
+pub struct ParamMutation {
+    pub name: String,
+    pub span: Span,
+    pub kind: MutationKind,
+}
+
+/// Result of analyzing a program
+#[derive(Debug, Default)]
+pub struct ScopeAnalysis {
+    pub let_bindings: Vec<LetBinding>,
+    pub param_mutations: Vec<ParamMutation>,
+    /// Span of the binding identifier each called identifier resolves to,
+    /// keyed by the span of the callee. Calls to globals are left out
+    pub callees: HashMap<Span, Span>,
+}
+
+impl ScopeAnalysis {
+    pub fn analyze(program: &Program) -> Self {
+        let mut analyzer = ScopeAnalyzer {
+            scopes: vec![Scope {
+                is_function: true,
+                ..Scope::default()
+            }],
+            result: Self::default(),
+        };
+        analyzer.visit_program(program);
+        analyzer.pop_scope();
+        analyzer.result.let_bindings.sort_by_key(|b| b.span.start);
+        analyzer
+            .result
+            .param_mutations
+            .sort_by_key(|m| m.span.start);
+        analyzer.result
+    }
+}
+
+struct ScopeAnalyzer {
+    scopes: Vec<Scope>,
+    result: ScopeAnalysis,
+}
+
+impl ScopeAnalyzer {
+    fn push_scope(&mut self, is_function: bool) {
+        self.scopes.push(Scope {
+            is_function,
+            ..Scope::default()
+        });
+    }
+
+    /// Resolve pending accesses against this scope's bindings, forward the
+    /// rest to the parent scope and record results for finished bindings.
+    /// Resolution is deferred to scope exit so closures that are declared
+    /// before a binding still see it.
+    fn pop_scope(&mut self) {
+        let Some(mut scope) = self.scopes.pop() else {
+            return;
+        };
+
+        let mut unresolved = Vec::new();
+        for pending in scope.pending.drain(..) {
+            let Some(binding) = scope.bindings.get_mut(&pending.name) else {
+                unresolved.push(pending);
+                continue;
+            };
+            match pending.access {
+                Access::Write(kind) => {
+                    binding.reassigned = true;
+                    if binding.kind == BindingKind::Parameter {
+                        self.result.param_mutations.push(ParamMutation {
+                            name: pending.name,
+                            span: pending.span,
+                            kind,
+                        });
+                    }
+                }
+                Access::Mutation(kind) => {
+                    if binding.kind == BindingKind::Parameter {
+                        self.result.param_mutations.push(ParamMutation {
+                            name: pending.name,
+                            span: pending.span,
+                            kind,
+                        });
+                    }
+                }
+                Access::Call => {
+                    self.result.callees.insert(pending.span, binding.span);
+                }
+            }
+        }
+
+        for (name, binding) in scope.bindings {
+            if binding.kind == BindingKind::Let {
+                self.result.let_bindings.push(LetBinding {
+                    name,
+                    span: binding.span,
+                    reassigned: binding.reassigned,
+                });
+            }
+        }
+
+        if let Some(parent) = self.scopes.last_mut() {
+            parent.pending.extend(unresolved);
+        }
+    }
+
+    fn declare(&mut self, name: &str, span: Span, kind: BindingKind) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.bindings.insert(
+                name.to_string(),
+                Binding {
+                    kind,
+                    span,
+                    reassigned: false,
+                },
+            );
+        }
+    }
+
+    fn declare_pattern(&mut self, pattern: &BindingPattern, kind: BindingKind) {
+        for id in pattern.get_binding_identifiers() {
+            self.declare(id.name.as_str(), id.span, kind);
+        }
+    }
+
+    /// `var` bindings are hoisted to the nearest function scope
+    fn declare_var(&mut self, pattern: &BindingPattern) {
+        let Some(scope) = self.scopes.iter_mut().rev().find(|s| s.is_function) else {
+            return;
+        };
+        for id in pattern.get_binding_identifiers() {
+            scope.bindings.insert(
+                id.name.to_string(),
+                Binding {
+                    kind: BindingKind::Other,
+                    span: id.span,
+                    reassigned: false,
+                },
+            );
+        }
+    }
+
+    fn record(&mut self, name: &str, span: Span, access: Access) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.pending.push(PendingAccess {
+                name: name.to_string(),
+                span,
+                access,
+            });
+        }
+    }
+
+    fn record_member_mutation(&mut self, object: &Expression, span: Span, kind: MutationKind) {
+        if let Some(root) = root_identifier(object) {
+            self.record(root.name.as_str(), span, Access::Mutation(kind));
+        }
+    }
+
+    fn record_simple_target(
+        &mut self,
+        target: &SimpleAssignmentTarget,
+        span: Span,
+        kind: MutationKind,
+    ) {
+        match target {
+            SimpleAssignmentTarget::AssignmentTargetIdentifier(id) => {
+                self.record(id.name.as_str(), span, Access::Write(kind));
+            }
+            SimpleAssignmentTarget::StaticMemberExpression(member) => {
+                self.record_member_mutation(&member.object, span, member_kind(kind));
+            }
+            SimpleAssignmentTarget::ComputedMemberExpression(member) => {
+                self.record_member_mutation(&member.object, span, member_kind(kind));
+            }
+            SimpleAssignmentTarget::PrivateFieldExpression(member) => {
+                self.record_member_mutation(&member.object, span, member_kind(kind));
+            }
+            _ => {
+                if let Some(expr) = target.get_expression() {
+                    if let Some(root) = root_identifier(expr) {
+                        self.record(root.name.as_str(), span, Access::Write(kind));
+                    }
+                }
+            }
+        }
+    }
+
+    fn record_assignment_target(&mut self, target: &AssignmentTarget, span: Span) {
+        if let Some(simple) = target.as_simple_assignment_target() {
+            self.record_simple_target(simple, span, MutationKind::Reassignment);
+            return;
+        }
+        match target {
+            AssignmentTarget::ArrayAssignmentTarget(array) => {
+                for element in array.elements.iter().flatten() {
+                    self.record_maybe_default(element, span);
+                }
+                if let Some(rest) = &array.rest {
+                    self.record_assignment_target(&rest.target, span);
+                }
+            }
+            AssignmentTarget::ObjectAssignmentTarget(object) => {
+                for property in &object.properties {
+                    match property {
+                        AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(prop) => {
+                            self.record(
+                                prop.binding.name.as_str(),
+                                span,
+                                Access::Write(MutationKind::Reassignment),
+                            );
+                        }
+                        AssignmentTargetProperty::AssignmentTargetPropertyProperty(prop) => {
+                            self.record_maybe_default(&prop.binding, span);
+                        }
+                    }
+                }
+                if let Some(rest) = &object.rest {
+                    self.record_assignment_target(&rest.target, span);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn record_maybe_default(&mut self, target: &AssignmentTargetMaybeDefault, span: Span) {
+        match target {
+            AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(with_default) => {
+                self.record_assignment_target(&with_default.binding, span);
+            }
+            _ => {
+                if let Some(target) = target.as_assignment_target() {
+                    self.record_assignment_target(target, span);
+                }
+            }
+        }
+    }
+}
+
+/// Writes through a member of a binding mutate it rather than reassign it
+fn member_kind(kind: MutationKind) -> MutationKind {
+    match kind {
+        MutationKind::Reassignment => MutationKind::PropertyAssignment,
+        other => other,
+    }
+}
+
+/// The identifier at the root of a member chain, e.g. `a` in `a.b[0].c`
+fn root_identifier<'a, 'b>(expr: &'b Expression<'a>) -> Option<&'b IdentifierReference<'a>> {
+    match expr {
+        Expression::Identifier(id) => Some(id),
+        Expression::StaticMemberExpression(member) => root_identifier(&member.object),
+        Expression::ComputedMemberExpression(member) => root_identifier(&member.object),
+        Expression::PrivateFieldExpression(member) => root_identifier(&member.object),
+        Expression::ParenthesizedExpression(paren) => root_identifier(&paren.expression),
+        Expression::TSNonNullExpression(non_null) => root_identifier(&non_null.expression),
+        Expression::TSAsExpression(as_expr) => root_identifier(&as_expr.expression),
+        _ => None,
+    }
+}
+
+impl<'a> Visit<'a> for ScopeAnalyzer {
+    fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
+        // Declarations bind their name in the enclosing scope
+        if func.is_declaration() {
+            if let Some(id) = &func.id {
+                self.declare(id.name.as_str(), id.span, BindingKind::Other);
+            }
+        }
+        self.push_scope(true);
+        if func.is_expression() {
+            if let Some(id) = &func.id {
+                self.declare(id.name.as_str(), id.span, BindingKind::Other);
+            }
+        }
+        walk::walk_function(self, func, flags);
+        self.pop_scope();
+    }
+
+    fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'a>) {
+        self.push_scope(true);
+        walk::walk_arrow_function_expression(self, arrow);
+        self.pop_scope();
+    }
+
+    fn visit_formal_parameter(&mut self, param: &FormalParameter<'a>) {
+        self.declare_pattern(&param.pattern, BindingKind::Parameter);
+        walk::walk_formal_parameter(self, param);
+    }
+
+    fn visit_formal_parameters(&mut self, params: &FormalParameters<'a>) {
+        if let Some(rest) = &params.rest {
+            self.declare_pattern(&rest.argument, BindingKind::Parameter);
+        }
+        walk::walk_formal_parameters(self, params);
+    }
+
+    fn visit_block_statement(&mut self, block: &BlockStatement<'a>) {
+        self.push_scope(false);
+        walk::walk_block_statement(self, block);
+        self.pop_scope();
+    }
+
+    fn visit_for_statement(&mut self, stmt: &ForStatement<'a>) {
+        self.push_scope(false);
+        walk::walk_for_statement(self, stmt);
+        self.pop_scope();
+    }
+
+    fn visit_for_in_statement(&mut self, stmt: &ForInStatement<'a>) {
+        self.push_scope(false);
+        if let Some(target) = stmt.left.as_assignment_target() {
+            self.record_assignment_target(target, stmt.span);
+        }
+        walk::walk_for_in_statement(self, stmt);
+        self.pop_scope();
+    }
+
+    fn visit_for_of_statement(&mut self, stmt: &ForOfStatement<'a>) {
+        self.push_scope(false);
+        if let Some(target) = stmt.left.as_assignment_target() {
+            self.record_assignment_target(target, stmt.span);
+        }
+        walk::walk_for_of_statement(self, stmt);
+        self.pop_scope();
+    }
+
+    fn visit_switch_statement(&mut self, stmt: &SwitchStatement<'a>) {
+        self.push_scope(false);
+        walk::walk_switch_statement(self, stmt);
+        self.pop_scope();
+    }
+
+    fn visit_catch_clause(&mut self, clause: &CatchClause<'a>) {
+        self.push_scope(false);
+        if let Some(param) = &clause.param {
+            self.declare_pattern(&param.pattern, BindingKind::Other);
+        }
+        walk::walk_catch_clause(self, clause);
+        self.pop_scope();
+    }
+
+    fn visit_variable_declaration(&mut self, decl: &VariableDeclaration<'a>) {
+        for declarator in &decl.declarations {
+            match decl.kind {
+                VariableDeclarationKind::Let => {
+                    self.declare_pattern(&declarator.id, BindingKind::Let);
+                }
+                VariableDeclarationKind::Var => self.declare_var(&declarator.id),
+                _ => self.declare_pattern(&declarator.id, BindingKind::Other),
+            }
+        }
+        walk::walk_variable_declaration(self, decl);
+    }
+
+    fn visit_class(&mut self, class: &Class<'a>) {
+        if class.is_declaration() {
+            if let Some(id) = &class.id {
+                self.declare(id.name.as_str(), id.span, BindingKind::Other);
+            }
+        }
+        walk::walk_class(self, class);
+    }
+
+    fn visit_import_declaration(&mut self, import: &ImportDeclaration<'a>) {
+        if let Some(specifiers) = &import.specifiers {
+            for spec in specifiers {
+                let local = spec.local();
+                self.declare(local.name.as_str(), local.span, BindingKind::Other);
+            }
+        }
+        walk::walk_import_declaration(self, import);
+    }
+
+    fn visit_assignment_expression(&mut self, expr: &AssignmentExpression<'a>) {
+        self.record_assignment_target(&expr.left, expr.span);
+        walk::walk_assignment_expression(self, expr);
+    }
+
+    fn visit_update_expression(&mut self, expr: &UpdateExpression<'a>) {
+        self.record_simple_target(&expr.argument, expr.span, MutationKind::Update);
+        walk::walk_update_expression(self, expr);
+    }
+
+    fn visit_unary_expression(&mut self, expr: &UnaryExpression<'a>) {
+        if expr.operator == UnaryOperator::Delete {
+            if let Some(member) = expr.argument.as_member_expression() {
+                self.record_member_mutation(member.object(), expr.span, MutationKind::Delete);
+            }
+        }
+        walk::walk_unary_expression(self, expr);
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        if let Expression::Identifier(id) = call.callee.without_parentheses() {
+            self.record(id.name.as_str(), id.span, Access::Call);
+        }
+        if let Some(member) = call.callee.as_member_expression() {
+            if let Some(method) = member.static_property_name() {
+                if MUTATING_ARRAY_METHODS.contains(&method) {
+                    self.record_member_mutation(
+                        member.object(),
+                        call.span,
+                        MutationKind::MutatingMethod(method.to_string()),
+                    );
+                }
+            }
+        }
+        walk::walk_call_expression(self, call);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc::allocator::Allocator;
+    use oxc::parser::Parser;
+    use oxc::span::SourceType;
+
+    fn analyze(source: &str) -> ScopeAnalysis {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path("test.ts").unwrap();
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        ScopeAnalysis::analyze(&ret.program)
+    }
+
+    #[test]
+    fn test_shadowed_let_is_resolved_to_inner_binding() {
+        let analysis = analyze(
+            r#"
+let count = 0;
+function inc() {
+  let count = 1;
+  count += 1;
+  return count;
+}
+"#,
+        );
+        assert_eq!(analysis.let_bindings.len(), 2);
+        assert!(!analysis.let_bindings[0].reassigned);
+        assert!(analysis.let_bindings[1].reassigned);
+    }
+
+    #[test]
+    fn test_closure_declared_before_binding() {
+        let analysis = analyze(
+            r#"
+function f() {
+  const reset = () => {
+    value = 0;
+  };
+  let value = 1;
+  return reset;
+}
+"#,
+        );
+        assert_eq!(analysis.let_bindings.len(), 1);
+        assert!(analysis.let_bindings[0].reassigned);
+    }
+
+    #[test]
+    fn test_param_mutation_through_closure_and_shadowing() {
+        let analysis = analyze(
+            r#"
+function f(items: number[], opts: { n: number }) {
+  const add = () => items.push(1);
+  const g = (opts: { n: number }) => {
+    opts.n = 2;
+  };
+  return [add, g];
+}
+"#,
+        );
+        assert_eq!(analysis.param_mutations.len(), 2);
+        assert_eq!(analysis.param_mutations[0].name, "items");
+        assert_eq!(
+            analysis.param_mutations[0].kind,
+            MutationKind::MutatingMethod("push".to_string())
+        );
+        // The inner `opts` is a parameter of the arrow function
+        assert_eq!(analysis.param_mutations[1].name, "opts");
+        assert_eq!(
+            analysis.param_mutations[1].kind,
+            MutationKind::PropertyAssignment
+        );
+    }
+
+    #[test]
+    fn test_calls_resolve_to_the_nearest_binding() {
+        let source = r#"
+import { parse as p } from "./parse.ts";
+function run(parse: (s: string) => number) {
+  parse("1");
+  p("2");
+  console.log("3");
+}
+"#;
+        let analysis = analyze(source);
+        let offset = |needle: &str| source.find(needle).unwrap() as u32;
+        let binding = |callee: &str, len: u32| {
+            let span = Span::sized(offset(callee), len);
+            analysis.callees.get(&span).map(|binding| binding.start)
+        };
+        assert_eq!(binding("parse(\"1", 5), Some(offset("parse: ")));
+        assert_eq!(binding("p(\"2", 1), Some(offset("p }")));
+        // `console` is a global and `log` is a member call
+        assert_eq!(analysis.callees.len(), 2);
+    }
+}
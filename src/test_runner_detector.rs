@@ -19,6 +19,16 @@ impl TestRunner {
             TestRunner::None => "none",
         }
     }
+
+    /// The runner the linter enforces, if one was detected
+    pub fn to_runner(&self) -> Option<crate::TestRunner> {
+        match self {
+            TestRunner::Vitest => Some(crate::TestRunner::Vitest),
+            TestRunner::NodeTest => Some(crate::TestRunner::NodeTest),
+            TestRunner::DenoTest => Some(crate::TestRunner::DenoTest),
+            TestRunner::None => None,
+        }
+    }
 }
 
 pub struct TestRunnerDetector {
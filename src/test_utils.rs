@@ -16,6 +16,7 @@ pub mod test {
         let source_type = SourceType::from_path(Path::new("test.ts")).unwrap();
         let ret = Parser::new(&allocator, source, source_type).parse();
         let mut linter = Linter::new(Path::new("test.ts"), source, false);
+        linter.load_directives(&ret.program);
         check_fn(&mut linter, &ret.program);
         linter.errors.into_iter().map(|e| e.message).collect()
     }
@@ -29,6 +30,7 @@ pub mod test {
         let source_type = SourceType::from_path(Path::new(path)).unwrap();
         let ret = Parser::new(&allocator, source, source_type).parse();
         let mut linter = Linter::new(Path::new(path), source, false);
+        linter.load_directives(&ret.program);
         check_fn(&mut linter, &ret.program);
         linter.errors.into_iter().map(|e| e.message).collect()
     }
@@ -1,18 +1,42 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use colored::*;
+use oxc::span::Span;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::jsonc;
+use crate::Linter;
+
+/// A single tsconfig file as written
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TsConfig {
     #[serde(rename = "compilerOptions")]
-    pub compiler_options: Option<CompilerOptions>,
+    pub compiler_options: Option<Map<String, Value>>,
     pub include: Option<Vec<String>>,
     pub exclude: Option<Vec<String>>,
-    pub extends: Option<String>,
+    pub files: Option<Vec<String>>,
+    pub extends: Option<Extends>,
+}
+
+/// `extends` names one base config or, since TypeScript 5.0, several
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Extends {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Extends {
+    fn specifiers(&self) -> &[String] {
+        match self {
+            Extends::One(specifier) => std::slice::from_ref(specifier),
+            Extends::Many(specifiers) => specifiers,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -35,217 +59,611 @@ pub struct CompilerOptions {
     pub no_property_access_from_index_signature: Option<bool>,
     pub allow_unreachable_code: Option<bool>,
     pub allow_unused_labels: Option<bool>,
-    pub allow_import_ts_extension: Option<bool>,
+    /// Also read from the misspelled `allowImportTsExtension` earlier
+    /// versions asked for
+    #[serde(alias = "allowImportTsExtension")]
+    pub allow_importing_ts_extensions: Option<bool>,
     pub verbatim_module_syntax: Option<bool>,
     pub module: Option<String>,
     pub target: Option<String>,
+    pub out_dir: Option<String>,
+    pub root_dir: Option<String>,
 }
 
-pub struct TsConfigValidator {
-    path: String,
-    errors: Vec<String>,
-    warnings: Vec<String>,
+/// A tsconfig with its `extends` chain applied
+#[derive(Debug, Default)]
+pub struct EffectiveTsConfig {
+    /// `None` when no file in the chain has `compilerOptions`
+    pub compiler_options: Option<CompilerOptions>,
+    /// The file that set each compiler option, by option name
+    pub origins: BTreeMap<String, PathBuf>,
+    pub include: Option<Vec<String>>,
+    pub exclude: Option<Vec<String>>,
+    pub files: Option<Vec<String>>,
+    /// `extends` specifiers that could not be resolved, with the file naming them
+    pub unresolved: Vec<(String, PathBuf)>,
 }
 
-impl TsConfigValidator {
-    pub fn new(path: String) -> Self {
-        Self {
-            path,
-            errors: Vec::new(),
-            warnings: Vec::new(),
+/// Merged compiler options and file lists of a config and its bases
+#[derive(Debug, Default)]
+struct Layer {
+    options: Option<BTreeMap<String, (Value, PathBuf)>>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    files: Option<Vec<String>>,
+    unresolved: Vec<(String, PathBuf)>,
+}
+
+impl Layer {
+    /// Apply `other` on top of `self`, as a later base or the extending file
+    fn merge(&mut self, other: Layer) {
+        if let Some(options) = other.options {
+            self.options
+                .get_or_insert_with(BTreeMap::new)
+                .extend(options);
         }
+        self.include = other.include.or(self.include.take());
+        self.exclude = other.exclude.or(self.exclude.take());
+        self.files = other.files.or(self.files.take());
+        self.unresolved.extend(other.unresolved);
     }
+}
 
-    pub fn validate(&mut self) -> Result<()> {
-        let tsconfig_path = if self.path.ends_with("tsconfig.json") {
-            self.path.clone()
-        } else {
-            format!("{}/tsconfig.json", self.path)
-        };
+impl EffectiveTsConfig {
+    /// Read a tsconfig and follow its `extends` chain. Later bases override
+    /// earlier ones and the file itself overrides all of them, option by
+    /// option; `include`, `exclude` and `files` are replaced as a whole
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_source(path, &content)
+    }
 
-        let path = Path::new(&tsconfig_path);
-        if !path.exists() {
-            self.errors
-                .push(format!("tsconfig.json not found at {}", tsconfig_path));
-            return Ok(());
+    /// Like `load`, with the text of the tsconfig itself given (e.g. an
+    /// unsaved editor buffer); bases are still read from disk
+    pub fn from_source(path: &Path, content: &str) -> Result<Self> {
+        let layer = load_layer(path, content, &mut Vec::new())?;
+        let origins = layer
+            .options
+            .iter()
+            .flatten()
+            .map(|(name, (_, origin))| (name.clone(), origin.clone()))
+            .collect();
+        let compiler_options = layer
+            .options
+            .map(|options| {
+                let values = options
+                    .into_iter()
+                    .map(|(name, (value, _))| (name, value))
+                    .collect::<Map<_, _>>();
+                serde_json::from_value(Value::Object(values))
+            })
+            .transpose()
+            .with_context(|| format!("Invalid compilerOptions in {}", path.display()))?;
+
+        Ok(Self {
+            compiler_options,
+            origins,
+            include: layer.include,
+            exclude: layer.exclude,
+            files: layer.files,
+            unresolved: layer.unresolved,
+        })
+    }
+}
+
+fn load_layer(path: &Path, content: &str, stack: &mut Vec<PathBuf>) -> Result<Layer> {
+    let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if stack.contains(&canonical) {
+        bail!("Circular extends in {}", path.display());
+    }
+
+    let tsconfig: TsConfig =
+        jsonc::from_str(content).with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    stack.push(canonical);
+    let mut layer = Layer::default();
+    let dir = path.parent().unwrap_or(Path::new("."));
+    for specifier in tsconfig.extends.iter().flat_map(Extends::specifiers) {
+        match resolve_extends(dir, specifier) {
+            Some(base) => {
+                let content = fs::read_to_string(&base)
+                    .with_context(|| format!("Failed to read {}", base.display()))?;
+                layer.merge(load_layer(&base, &content, stack)?)
+            }
+            None => layer
+                .unresolved
+                .push((specifier.clone(), path.to_path_buf())),
         }
+    }
+    stack.pop();
+
+    layer.merge(Layer {
+        options: tsconfig.compiler_options.map(|options| {
+            options
+                .into_iter()
+                .map(|(name, value)| (name, (value, path.to_path_buf())))
+                .collect()
+        }),
+        include: tsconfig.include,
+        exclude: tsconfig.exclude,
+        files: tsconfig.files,
+        unresolved: Vec::new(),
+    });
+    Ok(layer)
+}
 
-        let content = fs::read_to_string(path).context("Failed to read tsconfig.json")?;
+/// Resolve an `extends` specifier like TypeScript: relative and absolute
+/// paths from the extending file, anything else as a package in the nearest
+/// `node_modules`
+fn resolve_extends(dir: &Path, specifier: &str) -> Option<PathBuf> {
+    if specifier.starts_with("./")
+        || specifier.starts_with("../")
+        || Path::new(specifier).is_absolute()
+    {
+        return with_json_extension(&dir.join(specifier));
+    }
 
-        // Parse as raw JSON first to check for unknown properties
-        let _raw_json: Value =
-            serde_json::from_str(&content).context("Invalid JSON in tsconfig.json")?;
+    dir.ancestors().find_map(|ancestor| {
+        let target = ancestor.join("node_modules").join(specifier);
+        if target.is_dir() {
+            // A bare package name points at its "tsconfig" field or tsconfig.json
+            let entry = fs::read_to_string(target.join("package.json"))
+                .ok()
+                .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+                .and_then(|package| package["tsconfig"].as_str().map(str::to_string))
+                .unwrap_or_else(|| "tsconfig.json".to_string());
+            return Some(target.join(entry)).filter(|path| path.is_file());
+        }
+        with_json_extension(&target)
+    })
+}
 
-        // Parse into struct
-        let tsconfig: TsConfig =
-            serde_json::from_str(&content).context("Failed to parse tsconfig.json structure")?;
+fn with_json_extension(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+    let mut with_extension = path.as_os_str().to_owned();
+    with_extension.push(".json");
+    Some(PathBuf::from(with_extension)).filter(|path| path.is_file())
+}
 
-        self.validate_compiler_options(&tsconfig.compiler_options);
-        self.validate_required_settings(&tsconfig);
+/// Check the tsconfig being linted, following its `extends` chain. Bad
+/// options are reported at their key, or at `extends` when a base set them
+pub fn check_tsconfig(linter: &mut Linter) -> Result<()> {
+    let tsconfig = EffectiveTsConfig::from_source(&linter.path, &linter.source_text)?;
+    let mut checker = Checker {
+        linter,
+        origins: tsconfig.origins,
+    };
+
+    for (specifier, file) in &tsconfig.unresolved {
+        let span = if *file == checker.linter.path {
+            checker.key_span(&["extends"])
+        } else {
+            checker.extends_span()
+        };
+        checker.linter.add_error(
+            "tsconfig-extends".to_string(),
+            format!(
+                "Cannot resolve extends '{}' in {}",
+                specifier,
+                file.display()
+            ),
+            span,
+        );
+    }
+    checker.check_compiler_options(&tsconfig.compiler_options);
 
-        Ok(())
+    Ok(())
+}
+
+struct Checker<'a> {
+    linter: &'a mut Linter,
+    origins: BTreeMap<String, PathBuf>,
+}
+
+impl Checker<'_> {
+    fn key_span(&self, path: &[&str]) -> Span {
+        jsonc::key_span(&self.linter.source_text, path).unwrap_or_default()
     }
 
-    fn validate_compiler_options(&mut self, options: &Option<CompilerOptions>) {
-        match options {
-            None => {
-                self.errors
-                    .push("compilerOptions is missing in tsconfig.json".to_string());
-            }
-            Some(opts) => {
-                // Check strict mode
-                if opts.strict != Some(true) {
-                    self.errors.push("strict must be set to true".to_string());
-                }
+    fn extends_span(&self) -> Span {
+        self.key_span(&["extends"])
+    }
 
-                // If strict is not true, check individual strict options
-                if opts.strict != Some(true) {
-                    let strict_options = vec![
-                        (opts.no_implicit_any, "noImplicitAny"),
-                        (opts.no_implicit_this, "noImplicitThis"),
-                        (opts.always_strict, "alwaysStrict"),
-                        (opts.strict_null_checks, "strictNullChecks"),
-                        (opts.strict_function_types, "strictFunctionTypes"),
-                        (opts.strict_bind_call_apply, "strictBindCallApply"),
-                        (
-                            opts.strict_property_initialization,
-                            "strictPropertyInitialization",
-                        ),
-                    ];
-
-                    for (option, name) in strict_options {
-                        if option != Some(true) {
-                            self.warnings.push(format!(
-                                "{} should be true when strict is not enabled",
-                                name
-                            ));
-                        }
-                    }
-                }
+    /// Where to report an option: its key, `extends` when a base set it, or
+    /// `compilerOptions` when it is not set at all
+    fn option_span(&self, option: &str) -> Span {
+        match self.origins.get(option) {
+            Some(origin) if *origin == self.linter.path => {
+                self.key_span(&["compilerOptions", option])
+            }
+            Some(_) => self.extends_span(),
+            None => self.key_span(&["compilerOptions"]),
+        }
+    }
 
-                // Recommend additional strict options
-                if opts.no_implicit_returns != Some(true) {
-                    self.warnings
-                        .push("Consider enabling noImplicitReturns for safer code".to_string());
-                }
+    /// Suffix naming the base config that set an option, if it is not the
+    /// checked file itself
+    fn origin(&self, option: &str) -> String {
+        match self.origins.get(option) {
+            Some(origin) if *origin != self.linter.path => {
+                format!(" (set in {})", origin.display())
+            }
+            _ => String::new(),
+        }
+    }
 
-                if opts.no_fallthrough_cases_in_switch != Some(true) {
-                    self.warnings
-                        .push("Consider enabling noFallthroughCasesInSwitch".to_string());
-                }
+    fn report(&mut self, rule: &str, option: &str, message: String) {
+        let span = self.option_span(option);
+        let message = format!("{}{}", message, self.origin(option));
+        self.linter.add_error(rule.to_string(), message, span);
+    }
 
-                if opts.no_unused_locals != Some(true) {
-                    self.warnings
-                        .push("Consider enabling noUnusedLocals".to_string());
-                }
+    fn check_compiler_options(&mut self, options: &Option<CompilerOptions>) {
+        let Some(opts) = options else {
+            self.linter.add_error(
+                "tsconfig-strict".to_string(),
+                "compilerOptions is missing in tsconfig.json".to_string(),
+                Span::default(),
+            );
+            return;
+        };
 
-                if opts.no_unused_parameters != Some(true) {
-                    self.errors
-                        .push("noUnusedParameters must be set to true".to_string());
-                }
+        // Check strict mode
+        if opts.strict != Some(true) {
+            self.report(
+                "tsconfig-strict",
+                "strict",
+                "strict must be set to true".to_string(),
+            );
 
-                if opts.exact_optional_property_types != Some(true) {
-                    self.warnings.push(
-                        "Consider enabling exactOptionalPropertyTypes for stricter typing"
-                            .to_string(),
+            // If strict is not true, check individual strict options
+            let strict_options = [
+                (opts.no_implicit_any, "noImplicitAny"),
+                (opts.no_implicit_this, "noImplicitThis"),
+                (opts.always_strict, "alwaysStrict"),
+                (opts.strict_null_checks, "strictNullChecks"),
+                (opts.strict_function_types, "strictFunctionTypes"),
+                (opts.strict_bind_call_apply, "strictBindCallApply"),
+                (
+                    opts.strict_property_initialization,
+                    "strictPropertyInitialization",
+                ),
+            ];
+
+            for (option, name) in strict_options {
+                if option != Some(true) {
+                    self.report(
+                        "tsconfig-recommended",
+                        name,
+                        format!("{} should be true when strict is not enabled", name),
                     );
                 }
+            }
+        }
 
-                if opts.no_unchecked_indexed_access != Some(true) {
-                    self.warnings.push(
-                        "Consider enabling noUncheckedIndexedAccess for safer array/object access"
-                            .to_string(),
-                    );
-                }
+        // Recommend additional strict options
+        let recommended = [
+            (
+                opts.no_implicit_returns,
+                "noImplicitReturns",
+                "Consider enabling noImplicitReturns for safer code",
+            ),
+            (
+                opts.no_fallthrough_cases_in_switch,
+                "noFallthroughCasesInSwitch",
+                "Consider enabling noFallthroughCasesInSwitch",
+            ),
+            (
+                opts.no_unused_locals,
+                "noUnusedLocals",
+                "Consider enabling noUnusedLocals",
+            ),
+            (
+                opts.exact_optional_property_types,
+                "exactOptionalPropertyTypes",
+                "Consider enabling exactOptionalPropertyTypes for stricter typing",
+            ),
+            (
+                opts.no_unchecked_indexed_access,
+                "noUncheckedIndexedAccess",
+                "Consider enabling noUncheckedIndexedAccess for safer array/object access",
+            ),
+        ];
+        for (option, name, message) in recommended {
+            if option != Some(true) {
+                self.report("tsconfig-recommended", name, message.to_string());
+            }
+        }
 
-                // Check for problematic settings
-                if opts.allow_unreachable_code == Some(true) {
-                    self.errors
-                        .push("allowUnreachableCode should not be true".to_string());
-                }
+        if opts.no_unused_parameters != Some(true) {
+            self.report(
+                "tsconfig-strict",
+                "noUnusedParameters",
+                "noUnusedParameters must be set to true".to_string(),
+            );
+        }
 
-                if opts.allow_unused_labels == Some(true) {
-                    self.errors
-                        .push("allowUnusedLabels should not be true".to_string());
-                }
+        // Check for problematic settings
+        if opts.allow_unreachable_code == Some(true) {
+            self.report(
+                "tsconfig-strict",
+                "allowUnreachableCode",
+                "allowUnreachableCode should not be true".to_string(),
+            );
+        }
 
-                // Check required settings for .ts extension imports
-                if opts.allow_import_ts_extension != Some(true) {
-                    self.errors
-                        .push("allowImportTsExtension must be set to true".to_string());
-                }
+        if opts.allow_unused_labels == Some(true) {
+            self.report(
+                "tsconfig-strict",
+                "allowUnusedLabels",
+                "allowUnusedLabels should not be true".to_string(),
+            );
+        }
 
-                if opts.verbatim_module_syntax != Some(true) {
-                    self.errors
-                        .push("verbatimModuleSyntax must be set to true".to_string());
-                }
+        // Check required settings for .ts extension imports
+        if opts.allow_importing_ts_extensions != Some(true) {
+            self.report(
+                "tsconfig-module-syntax",
+                "allowImportingTsExtensions",
+                "allowImportingTsExtensions must be set to true".to_string(),
+            );
+        }
 
-                // Check module and target
-                if let Some(module) = &opts.module {
-                    if module != "ESNext" && module != "ES2022" && module != "ES2020" {
-                        self.warnings.push(format!(
-                            "Consider using ESNext or ES2022 for module, currently: {}",
-                            module
-                        ));
-                    }
-                }
+        if opts.verbatim_module_syntax != Some(true) {
+            self.report(
+                "tsconfig-module-syntax",
+                "verbatimModuleSyntax",
+                "verbatimModuleSyntax must be set to true".to_string(),
+            );
+        }
 
-                if let Some(target) = &opts.target {
-                    if target != "ESNext" && target != "ES2022" && target != "ES2020" {
-                        self.warnings.push(format!(
-                            "Consider using ESNext or ES2022 for target, currently: {}",
-                            target
-                        ));
-                    }
+        // Check module and target
+        for (value, name) in [(&opts.module, "module"), (&opts.target, "target")] {
+            if let Some(value) = value {
+                if value != "ESNext" && value != "ES2022" && value != "ES2020" {
+                    self.report(
+                        "tsconfig-recommended",
+                        name,
+                        format!(
+                            "Consider using ESNext or ES2022 for {}, currently: {}",
+                            name, value
+                        ),
+                    );
                 }
             }
         }
     }
+}
+
+pub struct TsConfigValidator {
+    path: String,
+    disabled_rules: HashSet<String>,
+    linter: Option<Linter>,
+}
 
-    fn validate_required_settings(&mut self, tsconfig: &TsConfig) {
-        // Check if extends is used (which might override settings)
-        if let Some(extends) = &tsconfig.extends {
-            self.warnings.push(format!(
-                "Using extends '{}' - make sure it doesn't override strict settings",
-                extends
-            ));
+impl TsConfigValidator {
+    pub fn new(path: String) -> Self {
+        Self {
+            path,
+            disabled_rules: HashSet::new(),
+            linter: None,
         }
     }
 
-    pub fn report(&self) {
-        if !self.errors.is_empty() {
-            eprintln!(
-                "\n{} {} in tsconfig.json:",
-                "✗".red().bold(),
-                "Errors".red().bold()
-            );
-            for error in &self.errors {
-                eprintln!("  {} {}", "•".red(), error);
-            }
-        }
+    /// Skip diagnostics of the given rule ids
+    pub fn with_disabled_rules(mut self, rules: HashSet<String>) -> Self {
+        self.disabled_rules = rules;
+        self
+    }
 
-        if !self.warnings.is_empty() {
-            eprintln!(
-                "\n{} {} in tsconfig.json:",
-                "⚠".yellow().bold(),
-                "Warnings".yellow().bold()
+    pub fn validate(&mut self) -> Result<()> {
+        let tsconfig_path = if self.path.ends_with("tsconfig.json") {
+            self.path.clone()
+        } else {
+            format!("{}/tsconfig.json", self.path)
+        };
+
+        let path = Path::new(&tsconfig_path);
+        let mut linter = if path.exists() {
+            let content = fs::read_to_string(path).context("Failed to read tsconfig.json")?;
+            let mut linter =
+                Linter::new(path, &content, false).with_disabled_rules(self.disabled_rules.clone());
+            check_tsconfig(&mut linter)?;
+            linter
+        } else {
+            let mut linter =
+                Linter::new(path, "", false).with_disabled_rules(self.disabled_rules.clone());
+            linter.add_error(
+                "tsconfig-strict".to_string(),
+                format!("tsconfig.json not found at {}", tsconfig_path),
+                Span::default(),
             );
-            for warning in &self.warnings {
-                eprintln!("  {} {}", "•".yellow(), warning);
-            }
-        }
+            linter
+        };
+        linter.errors.sort_by_key(|error| error.span.start);
+        self.linter = Some(linter);
+
+        Ok(())
+    }
 
-        if self.errors.is_empty() && self.warnings.is_empty() {
-            println!("{} tsconfig.json validation passed", "✓".green().bold());
+    pub fn report(&self) {
+        match &self.linter {
+            Some(linter) if !linter.errors.is_empty() => linter.report_errors(),
+            _ => println!("{} tsconfig.json validation passed", "✓".green().bold()),
         }
     }
 
     pub fn has_errors(&self) -> bool {
-        !self.errors.is_empty()
+        self.linter.as_ref().is_some_and(Linter::has_errors)
     }
 
     #[allow(dead_code)]
     pub fn has_warnings(&self) -> bool {
-        !self.warnings.is_empty()
+        self.linter
+            .as_ref()
+            .is_some_and(|linter| linter.warning_count() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule_registry::Severity;
+    use tempfile::TempDir;
+
+    const STRICT_OPTIONS: &str = r#"{
+  // Shared settings
+  "compilerOptions": {
+    "strict": true,
+    "noUnusedParameters": true,
+    "allowImportingTsExtensions": true,
+    "verbatimModuleSyntax": true,
+    "noImplicitReturns": true,
+    "noFallthroughCasesInSwitch": true,
+    "noUnusedLocals": true,
+    "exactOptionalPropertyTypes": true,
+    "noUncheckedIndexedAccess": true,
+  },
+}"#;
+
+    /// Messages of the diagnostics with the given severity
+    fn validate(dir: &Path, severity: Severity) -> Vec<String> {
+        let mut validator = TsConfigValidator::new(dir.to_string_lossy().to_string());
+        validator.validate().unwrap();
+        validator
+            .linter
+            .unwrap()
+            .errors
+            .into_iter()
+            .filter(|error| error.severity == severity)
+            .map(|error| error.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_jsonc_and_relative_extends() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("configs")).unwrap();
+        fs::write(dir.path().join("configs/base.json"), STRICT_OPTIONS).unwrap();
+        fs::write(
+            dir.path().join("tsconfig.json"),
+            r#"{
+  "extends": "./configs/base", // resolved with .json
+  "compilerOptions": { "target": "ES2022", },
+  "include": ["src"],
+}"#,
+        )
+        .unwrap();
+
+        assert_eq!(validate(dir.path(), Severity::Error), Vec::<String>::new());
+        assert_eq!(
+            validate(dir.path(), Severity::Warning),
+            Vec::<String>::new()
+        );
+
+        let tsconfig = EffectiveTsConfig::load(&dir.path().join("tsconfig.json")).unwrap();
+        assert_eq!(tsconfig.include, Some(vec!["src".to_string()]));
+        assert_eq!(
+            tsconfig.origins["strict"],
+            dir.path().join("./configs/base.json")
+        );
+    }
+
+    #[test]
+    fn test_extends_array_and_packages() {
+        let dir = TempDir::new().unwrap();
+        let package = dir.path().join("node_modules/@scope/tsconfig");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(
+            package.join("package.json"),
+            r#"{ "tsconfig": "strict.json" }"#,
+        )
+        .unwrap();
+        fs::write(package.join("strict.json"), STRICT_OPTIONS).unwrap();
+        fs::write(
+            dir.path().join("loose.json"),
+            r#"{ "compilerOptions": { "strict": false, "allowUnusedLabels": true } }"#,
+        )
+        .unwrap();
+        fs::create_dir(dir.path().join("app")).unwrap();
+        fs::write(
+            dir.path().join("app/tsconfig.json"),
+            r#"{ "extends": ["@scope/tsconfig", "../loose.json", "missing-config"] }"#,
+        )
+        .unwrap();
+
+        // The later base wins over the earlier one
+        let loose = dir.path().join("app/../loose.json");
+        assert_eq!(
+            validate(&dir.path().join("app"), Severity::Error),
+            vec![
+                format!(
+                    "Cannot resolve extends 'missing-config' in {}/app/tsconfig.json",
+                    dir.path().display()
+                ),
+                format!("strict must be set to true (set in {})", loose.display()),
+                format!(
+                    "allowUnusedLabels should not be true (set in {})",
+                    loose.display()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_circular_extends() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("tsconfig.json"),
+            r#"{ "extends": "./a.json" }"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("a.json"),
+            r#"{ "extends": "./tsconfig.json" }"#,
+        )
+        .unwrap();
+
+        let mut validator = TsConfigValidator::new(dir.path().to_string_lossy().to_string());
+        assert!(validator.validate().is_err());
+    }
+
+    #[test]
+    fn test_diagnostics_point_at_options() {
+        let source = r#"{
+  "extends": "./missing.json",
+  "compilerOptions": {
+    "strict": false,
+    "allowUnusedLabels": true
+  }
+}"#;
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("tsconfig.json");
+        let mut linter = Linter::new(&path, source, false)
+            .with_disabled_rules(["tsconfig-recommended".to_string()].into());
+        check_tsconfig(&mut linter).unwrap();
+
+        let located: Vec<(&str, &str)> = linter
+            .errors
+            .iter()
+            .map(|error| {
+                let key = &source[error.span.start as usize..error.span.end as usize];
+                (error.rule.as_str(), key)
+            })
+            .collect();
+        assert_eq!(
+            located,
+            vec![
+                ("tsconfig-extends", "\"extends\""),
+                ("tsconfig-strict", "\"strict\""),
+                ("tsconfig-strict", "\"compilerOptions\""),
+                ("tsconfig-strict", "\"allowUnusedLabels\""),
+                ("tsconfig-module-syntax", "\"compilerOptions\""),
+                ("tsconfig-module-syntax", "\"compilerOptions\""),
+            ]
+        );
     }
 }
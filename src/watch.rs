@@ -0,0 +1,184 @@
+// File system watching for `purets --watch`
+
+use anyhow::{Context, Result};
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher as _};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+
+use crate::gitignore_filter::GitignoreFilter;
+
+/// Events arriving within this window are handled as one batch
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Files whose changes affect the file set or the rules applied to every file
+const CONFIG_FILES: &[&str] = &[
+    ".gitignore",
+    "package.json",
+    "tsconfig.json",
+    "purets.json",
+    "pnpm-workspace.yaml",
+    "deno.json",
+    "deno.jsonc",
+];
+
+/// A batch of relevant file system changes
+#[derive(Debug, Default, PartialEq)]
+pub struct ChangeSet {
+    /// TypeScript files that were created, modified or removed
+    pub sources: BTreeSet<PathBuf>,
+    /// Whether a configuration file changed
+    pub config_changed: bool,
+}
+
+impl ChangeSet {
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty() && !self.config_changed
+    }
+
+    /// Sort changed paths (absolute, under `root`) into sources and config files
+    pub fn classify<'a>(root: &Path, paths: impl IntoIterator<Item = &'a Path>) -> Self {
+        let filter = GitignoreFilter::new();
+        let mut changes = ChangeSet::default();
+
+        for path in paths {
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            let is_config = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| CONFIG_FILES.contains(&name));
+
+            if is_config && !filter.contains_excluded_dir(relative) {
+                changes.config_changed = true;
+            } else if is_source_file(path) && !filter.contains_excluded_dir(relative) {
+                changes.sources.insert(path.to_path_buf());
+            }
+        }
+
+        changes
+    }
+}
+
+fn is_source_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("ts" | "tsx")
+    )
+}
+
+fn is_relevant(event: &Event) -> bool {
+    match event.kind {
+        EventKind::Create(_) | EventKind::Remove(_) => true,
+        EventKind::Modify(ModifyKind::Metadata(_)) => false,
+        EventKind::Modify(_) => true,
+        _ => false,
+    }
+}
+
+/// Recursive watcher over a project directory
+pub struct Watcher {
+    root: PathBuf,
+    events: Receiver<notify::Result<Event>>,
+    // Dropping the watcher stops the events
+    _watcher: RecommendedWatcher,
+}
+
+impl Watcher {
+    pub fn new(root: &Path) -> Result<Self> {
+        let root = root
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", root.display()))?;
+        let (sender, events) = channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {}", root.display()))?;
+
+        Ok(Self {
+            root,
+            events,
+            _watcher: watcher,
+        })
+    }
+
+    /// Block until relevant files change, batching events within `DEBOUNCE`
+    pub fn next_changes(&self) -> Result<ChangeSet> {
+        loop {
+            let mut paths = Vec::new();
+            let first = self.events.recv().context("File watcher stopped")?;
+            let mut pending = Some(first);
+
+            while let Some(event) = pending {
+                let event = event?;
+                if is_relevant(&event) {
+                    paths.extend(event.paths);
+                }
+                pending = self.events.recv_timeout(DEBOUNCE).ok();
+            }
+
+            let changes = ChangeSet::classify(&self.root, paths.iter().map(PathBuf::as_path));
+            if !changes.is_empty() {
+                return Ok(changes);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_classify() {
+        let root = Path::new("/project");
+        let paths = [
+            "/project/src/pure/add.ts",
+            "/project/src/App.tsx",
+            "/project/src/notes.md",
+            "/project/node_modules/lib/index.ts",
+            "/project/dist/add.ts",
+        ]
+        .map(PathBuf::from);
+        let changes = ChangeSet::classify(root, paths.iter().map(PathBuf::as_path));
+
+        assert!(!changes.config_changed);
+        assert_eq!(
+            changes.sources.into_iter().collect::<Vec<_>>(),
+            vec![
+                PathBuf::from("/project/src/App.tsx"),
+                PathBuf::from("/project/src/pure/add.ts"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classify_config_files() {
+        let root = Path::new("/project");
+        for name in ["package.json", ".gitignore", "tsconfig.json", "purets.json"] {
+            let path = root.join(name);
+            assert!(ChangeSet::classify(root, [path.as_path()]).config_changed);
+        }
+        let nested = root.join("node_modules/lib/package.json");
+        assert!(ChangeSet::classify(root, [nested.as_path()]).is_empty());
+    }
+
+    #[test]
+    fn test_watcher_reports_source_change() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("add.ts");
+        fs::write(&file, "export const a = 1;\n").unwrap();
+
+        let watcher = Watcher::new(dir.path()).unwrap();
+        fs::write(&file, "export const a = 2;\n").unwrap();
+
+        let changes = watcher.next_changes().unwrap();
+        assert_eq!(
+            changes.sources.into_iter().collect::<Vec<_>>(),
+            vec![file.canonicalize().unwrap()]
+        );
+    }
+}
@@ -3,6 +3,8 @@ use serde_json::Value;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::deno_config::{expand_members, DenoConfig};
+
 /// Workspace configuration
 #[derive(Debug, Clone)]
 pub struct WorkspaceConfig {
@@ -17,6 +19,7 @@ pub enum WorkspaceType {
     NpmWorkspaces,
     PnpmWorkspaces,
     YarnWorkspaces,
+    DenoWorkspaces,
 }
 
 impl WorkspaceConfig {
@@ -32,6 +35,11 @@ impl WorkspaceConfig {
             return config;
         }
 
+        // Check for a deno.json workspace member list
+        if let Ok(config) = detect_deno_workspace(root) {
+            return config;
+        }
+
         // Default to single package
         Self {
             workspace_type: WorkspaceType::Single,
@@ -99,8 +107,25 @@ impl WorkspaceConfig {
             return None;
         }
 
-        // Try to extract package name from path
-        let relative = path.strip_prefix(&self.root).ok()?;
+        // Try to extract package name from path. Globs under `.` yield paths
+        // without the `./` prefix
+        let relative = match path.strip_prefix(&self.root) {
+            Ok(relative) => relative,
+            Err(_) if self.root == Path::new(".") && path.is_relative() => path,
+            Err(_) => return None,
+        };
+
+        // Deno members can sit at any depth, so the package is the member
+        // directory holding the file
+        if self.workspace_type == WorkspaceType::DenoWorkspaces {
+            return expand_members(&self.root, &self.packages)
+                .iter()
+                .map(|dir| dir.strip_prefix(&self.root).unwrap_or(dir))
+                .map(|dir| dir.strip_prefix(".").unwrap_or(dir))
+                .filter(|dir| relative.starts_with(dir))
+                .max_by_key(|dir| dir.components().count())
+                .map(|dir| dir.to_string_lossy().replace('\\', "/"));
+        }
         let components: Vec<_> = relative.components().collect();
 
         if components.len() >= 2 {
@@ -208,6 +233,21 @@ fn detect_npm_workspace(root: &Path) -> Result<WorkspaceConfig, Box<dyn std::err
     Err("No workspaces configuration found".into())
 }
 
+/// Detect a Deno workspace from the `workspace` members of deno.json(c)
+fn detect_deno_workspace(root: &Path) -> Result<WorkspaceConfig, Box<dyn std::error::Error>> {
+    let config_path = DenoConfig::find(root).ok_or("No deno.json found")?;
+    let packages = DenoConfig::load(&config_path)?.members().to_vec();
+    if packages.is_empty() {
+        return Err("No workspace members found".into());
+    }
+
+    Ok(WorkspaceConfig {
+        workspace_type: WorkspaceType::DenoWorkspaces,
+        packages,
+        root: root.to_path_buf(),
+    })
+}
+
 /// Check if directory contains TypeScript files
 fn has_typescript_files(dir: &Path) -> bool {
     if let Ok(entries) = fs::read_dir(dir) {
@@ -248,6 +288,25 @@ mod tests {
         assert_eq!(config.packages, vec!["src"]);
     }
 
+    #[test]
+    fn test_get_package_name() {
+        let config = WorkspaceConfig {
+            workspace_type: WorkspaceType::NpmWorkspaces,
+            packages: vec!["packages/*".to_string()],
+            root: PathBuf::from("."),
+        };
+        let name = |path: &str| config.get_package_name(Path::new(path));
+        assert_eq!(
+            name("./packages/api/src/get.ts").as_deref(),
+            Some("packages/api")
+        );
+        assert_eq!(
+            name("packages/api/src/get.ts").as_deref(),
+            Some("packages/api")
+        );
+        assert_eq!(name("index.ts"), None);
+    }
+
     #[test]
     fn test_pnpm_workspace_detection() {
         let temp_dir = TempDir::new().unwrap();
@@ -317,6 +376,34 @@ packages:
         assert_eq!(config.workspace_type, WorkspaceType::YarnWorkspaces);
     }
 
+    #[test]
+    fn test_deno_workspace_detection() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("deno.jsonc"),
+            r#"{
+  // Members may be listed directly or under "members"
+  "workspace": ["./api", "./libs/*"],
+}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(temp_dir.path().join("api/src")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("libs/math")).unwrap();
+        fs::write(temp_dir.path().join("libs/math/mod.ts"), "").unwrap();
+
+        let config = WorkspaceConfig::detect(temp_dir.path());
+        assert_eq!(config.workspace_type, WorkspaceType::DenoWorkspaces);
+
+        let targets = config.get_target_dirs();
+        assert!(targets.iter().any(|p| p.ends_with("api/src")));
+        assert!(targets.iter().any(|p| p.ends_with("libs/math")));
+
+        let name = |path: &str| config.get_package_name(&temp_dir.path().join(path));
+        assert_eq!(name("api/src/get.ts").as_deref(), Some("api"));
+        assert_eq!(name("libs/math/mod.ts").as_deref(), Some("libs/math"));
+        assert_eq!(name("main.ts"), None);
+    }
+
     #[test]
     fn test_get_target_dirs() {
         let temp_dir = TempDir::new().unwrap();
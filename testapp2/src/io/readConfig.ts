@@ -1,3 +1,6 @@
+/**
+ * @allow fs
+ */
 import fs from "node:fs/promises";
 import { Result, ok, err } from "neverthrow";
 
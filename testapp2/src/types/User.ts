@@ -1,3 +1,6 @@
+/**
+ * A registered user
+ */
 export type User = {
   readonly id: string;
   readonly name: string;
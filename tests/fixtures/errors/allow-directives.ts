@@ -66,24 +66,25 @@ async function fullAccess() {
 }
 
 /**
- * OK: Function with @allow mutations (allows Date.now, Math.random, etc)
- * @allow mutations
+ * OK: Function with @allow time and @allow random
+ * @allow time
+ * @allow random
  */
-function withMutations() {
-    const now = Date.now(); // OK with @allow mutations
-    const random = Math.random(); // OK with @allow mutations
-    const date = new Date(); // OK with @allow mutations
+function withClockAndRandom() {
+    const now = Date.now(); // OK with @allow time
+    const random = Math.random(); // OK with @allow random
+    const date = new Date(); // OK with @allow time
     return { now, random, date };
 }
 
-// Error: Process without @allow process or import
+// Error: Environment without @allow env
 function useProcess() {
-    console.log(process.env.NODE_ENV); // Error (both console and process)
+    console.log(process.env.NODE_ENV); // Error (both console and env)
 }
 
 /**
- * OK: With @allow process
- * @allow process
+ * OK: With @allow env
+ * @allow env
  * @allow console
  */
 function withProcess() {
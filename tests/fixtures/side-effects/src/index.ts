@@ -0,0 +1,3 @@
+import "./register.ts";
+
+export { version } from "./version.ts";
@@ -0,0 +1,5 @@
+const registry: Map<string, string> = new Map();
+
+registry.set("default", "1.0.0");
+
+export default registry;